@@ -18,7 +18,7 @@ $path = __DIR__ . '/../output/php-invoice.pdf';
 
 /** Format a monetary value with thousands separator: 9600.00 → "$9,600.00" */
 function fmtMoney(float $amount): string {
-    return '$' . number_format($amount, 2, '.', ',');
+    return Format::currency($amount, '$', 2);
 }
 
 // ── constants ────────────────────────────────────────────────────────────────
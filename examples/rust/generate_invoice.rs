@@ -7,6 +7,7 @@
 ///   cargo run --example generate_invoice -p pdf-examples
 ///
 /// Opens output at: examples/output/rust-invoice.pdf
+use pdf_core::format::format_currency;
 use pdf_core::{
     BuiltinFont, Cell, CellStyle, Color, FitResult, FontRef, PdfDocument, Rect, Row, Table,
     TableCursor, TextAlign, TextStyle,
@@ -23,6 +24,7 @@ fn bold(sz: f64) -> TextStyle {
     TextStyle {
         font: FontRef::Builtin(BuiltinFont::HelveticaBold),
         font_size: sz,
+        ..TextStyle::default()
     }
 }
 
@@ -30,6 +32,7 @@ fn regular(sz: f64) -> TextStyle {
     TextStyle {
         font: FontRef::Builtin(BuiltinFont::Helvetica),
         font_size: sz,
+        ..TextStyle::default()
     }
 }
 
@@ -37,6 +40,7 @@ fn oblique(sz: f64) -> TextStyle {
     TextStyle {
         font: FontRef::Builtin(BuiltinFont::HelveticaOblique),
         font_size: sz,
+        ..TextStyle::default()
     }
 }
 
@@ -59,20 +63,7 @@ fn stripe_bg() -> Color {
 
 /// Format a monetary value with thousands separator: 9600.00 → "$9,600.00"
 fn fmt_money(amount: f64) -> String {
-    let cents = (amount * 100.0).round() as u64;
-    let dollars = cents / 100;
-    let cents_part = cents % 100;
-
-    let s = dollars.to_string();
-    let with_commas = s
-        .as_bytes()
-        .rchunks(3)
-        .rev()
-        .map(|c| std::str::from_utf8(c).unwrap())
-        .collect::<Vec<_>>()
-        .join(",");
-
-    format!("${}.{:02}", with_commas, cents_part)
+    format_currency(amount, "$", 2)
 }
 
 // ── invoice data ──────────────────────────────────────────────────────────────
@@ -296,9 +287,9 @@ fn draw_line_items<W: std::io::Write>(doc: &mut PdfDocument<W>) -> f64 {
         };
         let row = Row::new(vec![
             Cell::styled(item.description, ds),
-            Cell::styled(&item.qty.to_string(), ds_right.clone()),
-            Cell::styled(&fmt_money(item.unit_price), ds_right.clone()),
-            Cell::styled(&fmt_money(item.total()), ds_right),
+            Cell::styled(item.qty.to_string(), ds_right.clone()),
+            Cell::styled(fmt_money(item.unit_price), ds_right.clone()),
+            Cell::styled(fmt_money(item.total()), ds_right),
         ]);
         match doc
             .fit_row(&table, &row, &mut cursor)
@@ -377,7 +368,7 @@ fn draw_totals<W: std::io::Write>(doc: &mut PdfDocument<W>, table_bottom: f64) {
         &totals_table,
         &Row::new(vec![
             Cell::styled("Subtotal:", gray_label.clone()),
-            Cell::styled(&fmt_money(subtotal), base.clone()),
+            Cell::styled(fmt_money(subtotal), base.clone()),
         ]),
         &mut cursor,
     )
@@ -386,8 +377,8 @@ fn draw_totals<W: std::io::Write>(doc: &mut PdfDocument<W>, table_bottom: f64) {
     doc.fit_row(
         &totals_table,
         &Row::new(vec![
-            Cell::styled(&format!("Tax ({:.0}%):", tax_rate * 100.0), gray_label),
-            Cell::styled(&fmt_money(tax), base),
+            Cell::styled(format!("Tax ({:.0}%):", tax_rate * 100.0), gray_label),
+            Cell::styled(fmt_money(tax), base),
         ]),
         &mut cursor,
     )
@@ -407,7 +398,7 @@ fn draw_totals<W: std::io::Write>(doc: &mut PdfDocument<W>, table_bottom: f64) {
         &totals_table,
         &Row::new(vec![
             Cell::styled("TOTAL:", total_label),
-            Cell::styled(&fmt_money(total), total_amt),
+            Cell::styled(fmt_money(total), total_amt),
         ]),
         &mut cursor,
     )
@@ -39,10 +39,12 @@ fn main() {
     let body_style = TextStyle {
         font: FontRef::Builtin(BuiltinFont::TimesRoman),
         font_size: 12.0,
+        ..TextStyle::default()
     };
     let footer_style = TextStyle {
         font: FontRef::Builtin(BuiltinFont::Helvetica),
         font_size: 9.0,
+        ..TextStyle::default()
     };
 
     // Build a multi-page textflow with sample content
@@ -60,6 +62,7 @@ fn main() {
             &TextStyle {
                 font: FontRef::Builtin(BuiltinFont::HelveticaBold),
                 font_size: 12.0,
+                ..TextStyle::default()
             },
         );
         for _ in 0..4 {
@@ -97,7 +97,7 @@ const LAST_NAME_COL: usize = 8;
 const EMAIL_COL: usize = 9;
 
 fn data_row(values: &[String], row_index: usize) -> Row {
-    let bg = if row_index % 2 == 0 {
+    let bg = if row_index.is_multiple_of(2) {
         Color::rgb(0.95, 0.97, 1.0)
     } else {
         Color::rgb(1.0, 1.0, 1.0)
@@ -110,13 +110,13 @@ fn data_row(values: &[String], row_index: usize) -> Row {
     };
     // Last names are single words that can't wrap; shrink the font to fit.
     let last_name_style = CellStyle {
-        overflow: CellOverflow::Shrink,
+        overflow: Some(CellOverflow::Shrink),
         ..cell_style.clone()
     };
     // Email addresses have no word-break characters so they can't wrap.
     // Clip prevents them from visually overflowing into adjacent columns.
     let email_style = CellStyle {
-        overflow: CellOverflow::Clip,
+        overflow: Some(CellOverflow::Clip),
         ..cell_style.clone()
     };
 
@@ -170,6 +170,7 @@ fn main() {
     let footer_style = TextStyle {
         font: FontRef::Builtin(BuiltinFont::Helvetica),
         font_size: 8.0,
+        ..TextStyle::default()
     };
 
     let mut stmt = conn.prepare(SQL).expect("prepare SQL");
@@ -206,17 +207,14 @@ fn main() {
             Some(v) => v,
         };
 
-        if cursor.is_first_row() {
-            match doc
+        if cursor.is_first_row()
+            && doc
                 .fit_row(&table, &header_row(), &mut cursor)
                 .expect("fit_row header")
-            {
-                FitResult::BoxEmpty => {
-                    eprintln!("Warning: bounding box too small to fit header");
-                    break;
-                }
-                _ => {}
-            }
+                == FitResult::BoxEmpty
+        {
+            eprintln!("Warning: bounding box too small to fit header");
+            break;
         }
 
         let row = data_row(&values, row_index);
@@ -114,17 +114,14 @@ fn main() {
 
     while rows_iter.peek().is_some() {
         // Repeat header at the top of every page.
-        if cursor.is_first_row() {
-            match doc
+        if cursor.is_first_row()
+            && doc
                 .fit_row(&table, &header_row(), &mut cursor)
                 .expect("fit_row header")
-            {
-                FitResult::BoxEmpty => {
-                    eprintln!("Warning: bounding box too small to fit header row");
-                    break;
-                }
-                _ => {}
-            }
+                == FitResult::BoxEmpty
+        {
+            eprintln!("Warning: bounding box too small to fit header row");
+            break;
         }
 
         let row = rows_iter.peek().unwrap();
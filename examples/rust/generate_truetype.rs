@@ -22,10 +22,12 @@ fn main() {
     let tt_style = TextStyle {
         font: tt_font,
         font_size: 14.0,
+        ..TextStyle::default()
     };
     let tt_small = TextStyle {
         font: tt_font,
         font_size: 11.0,
+        ..TextStyle::default()
     };
     let builtin = TextStyle::default();
     let bold = TextStyle::builtin(BuiltinFont::HelveticaBold, 14.0);
@@ -57,6 +59,7 @@ fn main() {
         &TextStyle {
             font: tt_font,
             font_size: 16.0,
+            ..TextStyle::default()
         },
     );
     tf.add_text(
@@ -1,5 +1,7 @@
+use std::borrow::Borrow;
 use std::collections::BTreeMap;
 use std::collections::BTreeSet;
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{self, BufWriter, Write};
 use std::path::Path;
@@ -7,19 +9,46 @@ use std::path::Path;
 use flate2::write::ZlibEncoder;
 use flate2::Compression;
 
-use crate::fonts::{BuiltinFont, FontRef, TrueTypeFontId};
-use crate::graphics::Color;
-use crate::images::{self, ImageData, ImageFit, ImageFormat, ImageId};
+use crate::fonts::{BuiltinFont, FontMetrics, FontRef, TrueTypeFontId};
+use crate::forms::{FormBuilder, FormId, FormResources, FormXObject};
+use crate::graphics::{self, Color, GraphicsDefaults, OpsWriter};
+use crate::images::{self, ColorSpace, ImageAnchor, ImageData, ImageFit, ImageFormat, ImageId};
 use crate::objects::{ObjId, PdfObject};
-use crate::tables::{Row, Table, TableCursor};
-use crate::textflow::{FitResult, Rect, TextFlow, TextStyle};
-use crate::truetype::TrueTypeFont;
+use crate::reader::{self, PdfReadError, PdfReader};
+use crate::tables::{self, Row, Table, TableCursor, TextAlign, VerticalAlign};
+use crate::textflow::{FitResult, Rect, TextFlow, TextStyle, WordBreak};
+use crate::truetype::{self, TrueTypeFont};
+use crate::validate::{self, ValidationWarning};
 use crate::writer::PdfWriter;
 
 const CATALOG_OBJ: ObjId = ObjId(1, 0);
 const PAGES_OBJ: ObjId = ObjId(2, 0);
 const FIRST_PAGE_OBJ_NUM: u32 = 3;
 
+/// Size in bytes of the zeroed `/Contents` placeholder written by
+/// `add_signature_field` (16384 hex digits in the file).
+pub const SIGNATURE_CONTENTS_LEN: usize = 8192;
+/// Wide dummy value for `/ByteRange` entries, so the signer can overwrite
+/// them with real offsets without changing the file length.
+const BYTE_RANGE_PLACEHOLDER: i64 = 9_999_999_999;
+/// Largest width or height, in pixels, accepted for a page thumbnail.
+pub const MAX_THUMBNAIL_SIZE: u32 = 256;
+/// Most lines `draw_grid` strokes in one direction; finer spacings are clamped.
+pub const MAX_GRID_LINES: usize = 10_000;
+
+/// Stream categories that `make_stream` can compress independently.
+#[derive(Clone, Copy)]
+enum StreamKind {
+    /// Page content, overlays and form XObjects.
+    Content,
+    /// FontFile2 data and ToUnicode CMaps.
+    Font,
+    /// PNG pixel data and soft masks.
+    Image,
+    /// Page content that is never compressed (`set_page_uncompressed`).
+    Plain,
+}
+
 /// Pre-allocated object IDs for an image XObject.
 struct ImageObjIds {
     xobject: ObjId,
@@ -49,6 +78,99 @@ struct PageRecord {
     used_fonts: BTreeSet<BuiltinFont>,
     used_truetype_fonts: BTreeSet<usize>,
     used_images: BTreeSet<usize>,
+    used_forms: BTreeSet<usize>,
+    /// Annotation objects (e.g. signature widgets) placed on the page.
+    annot_ids: Vec<ObjId>,
+    /// Main content held back for overlay merging; written to
+    /// `content_ids[0]` in `end_document()`.
+    pending_content: Option<Vec<u8>>,
+    /// Clockwise display rotation in degrees (0, 90, 180 or 270).
+    rotate: i32,
+    /// Started with `begin_page_rotated`, so `rotate` overrides any
+    /// document rotation (even when 0).
+    rotated: bool,
+    /// Content is turned 180° by `begin_page_flipped`.
+    flipped: bool,
+    /// Image XObject used as the page's `/Thumb`.
+    thumb_id: Option<ObjId>,
+    /// Lower-left corner of the MediaBox.
+    origin: (f64, f64),
+    /// Presentation auto-advance time in seconds (`/Dur`).
+    duration: Option<f64>,
+    /// Presentation effect shown when the page appears (`/Trans`).
+    transition: Option<Transition>,
+    /// Output offset of the first object `end_page` wrote for the page;
+    /// `None` if it wrote nothing.
+    byte_offset: Option<usize>,
+}
+
+/// Output size statistics, as reported by [`PdfDocument::stats`].
+///
+/// Byte counts are the serialized size of the objects written so far,
+/// including their dictionaries and `obj`/`endobj` wrappers. TrueType
+/// fonts are written in `end_document()`, so `font_bytes` only covers
+/// builtin font dictionaries until then.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DocStats {
+    /// Completed pages.
+    pub page_count: usize,
+    /// Page content streams, including overlays and form XObjects.
+    pub content_bytes: usize,
+    /// Font dictionaries, descriptors, embedded font files and CMaps.
+    pub font_bytes: usize,
+    /// Image XObjects and their soft masks.
+    pub image_bytes: usize,
+    /// Indirect objects written to the output.
+    pub object_count: usize,
+}
+
+/// Progress notification passed to the callback registered with
+/// `PdfDocument::set_progress_callback`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressEvent {
+    /// `end_page()` finished page `page` (1-based). Overlays added with
+    /// `open_page` report the page they were drawn on.
+    PageEnded { page: usize },
+    /// An image XObject was written; `bytes` is its serialized size,
+    /// including any soft mask.
+    ImageWritten { image: ImageId, bytes: usize },
+}
+
+/// Page transition effect for presentations, written as the page's
+/// `/Trans` dictionary. Viewers in full-screen mode play it when
+/// advancing to the page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Transition {
+    Split,
+    Blinds,
+    Box,
+    Wipe,
+    Dissolve,
+    Glitter,
+    Fly,
+    Push,
+    Cover,
+    Uncover,
+    Fade,
+}
+
+impl Transition {
+    /// The `/S` name for this transition style.
+    pub fn pdf_name(self) -> &'static str {
+        match self {
+            Transition::Split => "Split",
+            Transition::Blinds => "Blinds",
+            Transition::Box => "Box",
+            Transition::Wipe => "Wipe",
+            Transition::Dissolve => "Dissolve",
+            Transition::Glitter => "Glitter",
+            Transition::Fly => "Fly",
+            Transition::Push => "Push",
+            Transition::Cover => "Cover",
+            Transition::Uncover => "Uncover",
+            Transition::Fade => "Fade",
+        }
+    }
 }
 
 /// High-level API for building PDF documents.
@@ -59,12 +181,23 @@ struct PageRecord {
 /// Pages are written incrementally: `end_page()` flushes page data
 /// to the writer and frees page content from memory. This keeps
 /// memory usage low even for documents with hundreds of pages.
+///
+/// Output is strictly forward-only. The writer is never asked to seek or
+/// to rewrite earlier bytes: xref offsets are counted as bytes go out, so
+/// a pipe or socket works as well as a file.
 pub struct PdfDocument<W: Write> {
     writer: PdfWriter<W>,
     info: Vec<(String, String)>,
     page_records: Vec<PageRecord>,
     current_page: Option<PageBuilder>,
+    /// Error from a page that `begin_page` failed to auto-close, returned
+    /// by the next `end_page` or `end_document`.
+    auto_close_error: Option<io::Error>,
     next_obj_num: u32,
+    /// Callback from `set_progress_callback`.
+    progress: Option<Box<dyn FnMut(ProgressEvent) + Send>>,
+    /// `/Rotate` on the Pages node, inherited by unrotated pages.
+    document_rotate: i32,
     /// Maps each used builtin font to its written ObjId.
     font_obj_ids: BTreeMap<BuiltinFont, ObjId>,
     /// Loaded TrueType fonts.
@@ -73,30 +206,140 @@ pub struct PdfDocument<W: Write> {
     truetype_font_obj_ids: BTreeMap<usize, TrueTypeFontObjIds>,
     /// Next font number for PDF resource names (F15, F16, ...).
     next_font_num: u32,
+    /// Whether builtin fonts with a replacement are embedded instead of referenced.
+    embed_standard_fonts: bool,
+    /// Metric-compatible TrueType fonts embedded in place of builtin fonts.
+    standard_font_replacements: BTreeMap<BuiltinFont, TrueTypeFont>,
+    /// Fonts tried in order for characters their primary font lacks.
+    font_fallbacks: BTreeMap<FontRef, Vec<FontRef>>,
+    /// Whether embedded replacements are subset to the glyphs shown.
+    subset_standard_fonts: bool,
+    /// Byte codes shown with each replaced builtin font, for subsetting.
+    standard_font_codes: BTreeMap<BuiltinFont, BTreeSet<u8>>,
+    /// Replaced builtin fonts whose objects wait for `end_document` so the
+    /// subset covers every page.
+    pending_standard_fonts: Vec<(BuiltinFont, ObjId)>,
     /// Whether to compress stream objects with FlateDecode.
     compress: bool,
+    /// Per-kind switches consulted when `compress` is on.
+    compress_content: bool,
+    compress_fonts: bool,
+    compress_images: bool,
+    /// Whether content streams separate operators with spaces, not newlines.
+    compact_content: bool,
+    /// 1-based page numbers whose content streams skip compression.
+    uncompressed_pages: BTreeSet<usize>,
+    /// Whether `open_page` overlays are merged into the page's main
+    /// content stream instead of being written as separate streams.
+    merge_overlays: bool,
     /// Loaded images.
     images: Vec<ImageData>,
     /// Pre-allocated ObjIds for images (by index).
     image_obj_ids: BTreeMap<usize, ImageObjIds>,
     /// Images whose XObjects have already been written.
     written_images: BTreeSet<usize>,
+    /// Images whose XObjects request viewer smoothing (`/Interpolate true`).
+    interpolated_images: BTreeSet<usize>,
+    /// Images whose samples are inverted via a `/Decode` array.
+    inverted_images: BTreeSet<usize>,
+    /// Color images whose `/SMask` is another loaded (grayscale) image,
+    /// keyed by color image index.
+    image_masks: BTreeMap<usize, usize>,
+    /// Recorded form XObjects, indexed by `FormId`.
+    forms: Vec<FormXObject>,
     /// Next image number for PDF resource names (Im1, Im2, ...).
     next_image_num: u32,
+    /// Graphics state emitted at the start of every new page.
+    graphics_defaults: GraphicsDefaults,
+    /// Page size used by `begin_page_default` (US Letter unless set).
+    default_page_size: (f64, f64),
+    /// Whether `end_document` fills in missing `/CreationDate` and `/ModDate`.
+    auto_dates: bool,
+    /// Decimal places kept by `format_coord` in content streams.
+    coord_precision: usize,
+    /// `/Producer` written at `end_document` unless set with `set_info`.
+    producer: Option<String>,
+    /// Interactive form fields, listed in the catalog's `/AcroForm`.
+    form_field_ids: Vec<ObjId>,
+    /// Whether validation warnings are turned into errors.
+    strict: bool,
+    /// Whether the catalog gets a named `/Print` open action.
+    print_on_open: bool,
+    /// Whether output must be byte-identical across runs.
+    deterministic: bool,
+    /// Per-category byte counts accumulated as objects are written.
+    stats: DocStats,
 }
 
 struct PageBuilder {
+    /// Page size as seen by the user (after any `/Rotate`).
     width: f64,
     height: f64,
+    /// Clockwise display rotation in degrees (0, 90, 180 or 270).
+    rotate: i32,
+    /// Started with `begin_page_rotated` (see `PageRecord::rotated`).
+    rotated: bool,
+    /// Content is turned 180° without `/Rotate` (`begin_page_flipped`).
+    flipped: bool,
+    /// Lower-left corner of the MediaBox (non-zero via `begin_page_box`).
+    origin: (f64, f64),
     content_ops: Vec<u8>,
+    /// Length of the page preamble (rotation matrix, graphics defaults) at
+    /// the start of `content_ops`; background ops are spliced in here.
+    body_start: usize,
+    /// Operators drawn behind all other page content
+    /// (`place_image_background`), merged in at `end_page()`.
+    background_ops: Vec<u8>,
     used_fonts: BTreeSet<BuiltinFont>,
     used_truetype_fonts: BTreeSet<usize>,
     used_images: BTreeSet<usize>,
+    used_forms: BTreeSet<usize>,
+    /// Offsets into `content_ops` where each open transparency group began.
+    group_starts: Vec<(usize, bool, bool)>,
+    annot_ids: Vec<ObjId>,
+    /// Image index set by `set_page_thumbnail`.
+    thumbnail: Option<usize>,
+    /// Set by `set_page_duration`.
+    duration: Option<f64>,
+    /// Set by `set_page_transition`.
+    transition: Option<Transition>,
     /// When `Some(idx)`, this builder is adding an overlay to `page_records[idx]`
     /// rather than creating a new page.
     overlay_for: Option<usize>,
 }
 
+impl PageBuilder {
+    fn new(
+        width: f64,
+        height: f64,
+        rotate: i32,
+        content_ops: Vec<u8>,
+        overlay_for: Option<usize>,
+    ) -> Self {
+        PageBuilder {
+            width,
+            height,
+            rotate,
+            rotated: false,
+            flipped: false,
+            origin: (0.0, 0.0),
+            body_start: content_ops.len(),
+            content_ops,
+            background_ops: Vec::new(),
+            used_fonts: BTreeSet::new(),
+            used_truetype_fonts: BTreeSet::new(),
+            used_images: BTreeSet::new(),
+            used_forms: BTreeSet::new(),
+            group_starts: Vec::new(),
+            annot_ids: Vec::new(),
+            thumbnail: None,
+            duration: None,
+            transition: None,
+            overlay_for,
+        }
+    }
+}
+
 impl PdfDocument<BufWriter<File>> {
     /// Create a new PDF document that writes to a file.
     pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
@@ -105,28 +348,127 @@ impl PdfDocument<BufWriter<File>> {
     }
 }
 
+impl PdfDocument<Vec<u8>> {
+    /// Concatenate PDFs produced by this crate into one document, keeping
+    /// the pages of each input in order.
+    ///
+    /// Every object of each input is copied byte for byte with its object
+    /// number remapped; only the catalogs and page tree roots are replaced
+    /// by a new catalog and a `/Pages` node listing all pages. The first
+    /// input's Info dictionary is kept. Scoped to files this crate writes:
+    /// a traditional xref table, a flat page tree and self-contained page
+    /// resources. Catalog entries such as outlines, named destinations or a
+    /// signature field's `/AcroForm`, and a `/Rotate` inherited from the
+    /// `/Pages` node (`set_document_rotation`), are not carried over.
+    pub fn concat(docs: Vec<Vec<u8>>) -> Result<Vec<u8>, PdfReadError> {
+        let mut writer = PdfWriter::new(Vec::new());
+        let mut next_obj_num = FIRST_PAGE_OBJ_NUM;
+        let mut kids = Vec::new();
+        let mut info_id = None;
+
+        for (i, data) in docs.into_iter().enumerate() {
+            let reader = PdfReader::from_bytes(data)?;
+            let (pages_obj, pages) = reader.page_objects()?;
+            let bodies = reader.object_bodies()?;
+
+            let mut numbers = HashMap::new();
+            numbers.insert(pages_obj, PAGES_OBJ.0);
+            for &(num, _) in &bodies {
+                let dropped = num == reader.root()
+                    || num == pages_obj
+                    || (i > 0 && Some(num) == reader.info());
+                if !dropped {
+                    numbers.insert(num, next_obj_num);
+                    next_obj_num += 1;
+                }
+            }
+
+            for (num, body) in bodies {
+                if num == pages_obj {
+                    continue;
+                }
+                if let Some(&new) = numbers.get(&num) {
+                    let body = reader::renumber_refs(body, |n| numbers.get(&n).copied());
+                    writer.write_raw_object(ObjId(new, 0), &body)?;
+                }
+            }
+            kids.extend(
+                pages
+                    .iter()
+                    .map(|num| PdfObject::Reference(ObjId(numbers[num], 0))),
+            );
+            if i == 0 {
+                info_id = reader.info().map(|num| ObjId(numbers[&num], 0));
+            }
+        }
+
+        let count = kids.len() as i64;
+        let pages = PdfObject::dict(vec![
+            ("Type", PdfObject::name("Pages")),
+            ("Kids", PdfObject::Array(kids)),
+            ("Count", PdfObject::Integer(count)),
+        ]);
+        writer.write_object(PAGES_OBJ, &pages)?;
+        let catalog = PdfObject::dict(vec![
+            ("Type", PdfObject::name("Catalog")),
+            ("Pages", PdfObject::Reference(PAGES_OBJ)),
+        ]);
+        writer.write_object(CATALOG_OBJ, &catalog)?;
+        writer.write_xref_and_trailer(CATALOG_OBJ, info_id)?;
+        Ok(writer.into_inner())
+    }
+}
+
 impl<W: Write> PdfDocument<W> {
     /// Create a new PDF document that writes to the given writer.
-    /// Writes the PDF header immediately.
+    /// The PDF header is written with the first object.
     pub fn new(writer: W) -> io::Result<Self> {
-        let mut pdf_writer = PdfWriter::new(writer);
-        pdf_writer.write_header()?;
+        let pdf_writer = PdfWriter::new(writer);
 
         Ok(PdfDocument {
             writer: pdf_writer,
             info: Vec::new(),
             page_records: Vec::new(),
             current_page: None,
+            auto_close_error: None,
+            progress: None,
+            document_rotate: 0,
             next_obj_num: FIRST_PAGE_OBJ_NUM,
             font_obj_ids: BTreeMap::new(),
             truetype_fonts: Vec::new(),
             truetype_font_obj_ids: BTreeMap::new(),
+            embed_standard_fonts: false,
+            standard_font_replacements: BTreeMap::new(),
+            font_fallbacks: BTreeMap::new(),
+            subset_standard_fonts: false,
+            standard_font_codes: BTreeMap::new(),
+            pending_standard_fonts: Vec::new(),
             next_font_num: 15,
             compress: false,
+            compress_content: true,
+            compact_content: false,
+            compress_fonts: true,
+            compress_images: true,
+            uncompressed_pages: BTreeSet::new(),
+            merge_overlays: false,
             images: Vec::new(),
             image_obj_ids: BTreeMap::new(),
             written_images: BTreeSet::new(),
+            interpolated_images: BTreeSet::new(),
+            inverted_images: BTreeSet::new(),
+            image_masks: BTreeMap::new(),
+            forms: Vec::new(),
             next_image_num: 1,
+            graphics_defaults: GraphicsDefaults::default(),
+            default_page_size: (612.0, 792.0),
+            auto_dates: true,
+            coord_precision: DEFAULT_COORD_PRECISION,
+            producer: Some(format!("pivot-pdf {}", env!("CARGO_PKG_VERSION"))),
+            form_field_ids: Vec::new(),
+            strict: false,
+            print_on_open: false,
+            deterministic: false,
+            stats: DocStats::default(),
         })
     }
 
@@ -136,6 +478,87 @@ impl<W: Write> PdfDocument<W> {
         self
     }
 
+    /// Set the `/Title` info entry, replacing any earlier title.
+    pub fn set_title(&mut self, title: &str) -> &mut Self {
+        self.replace_info("Title", title)
+    }
+
+    /// Set the `/Author` info entry, replacing any earlier author.
+    pub fn set_author(&mut self, author: &str) -> &mut Self {
+        self.replace_info("Author", author)
+    }
+
+    /// Set the `/Subject` info entry, replacing any earlier subject.
+    pub fn set_subject(&mut self, subject: &str) -> &mut Self {
+        self.replace_info("Subject", subject)
+    }
+
+    /// Set the `/Keywords` info entry, replacing any earlier keywords.
+    ///
+    /// The spec stores keywords as one text string; separate them with
+    /// commas, e.g. `"invoice, 2026, paid"`.
+    pub fn set_keywords(&mut self, keywords: &str) -> &mut Self {
+        self.replace_info("Keywords", keywords)
+    }
+
+    /// Set the `/Creator` info entry (the application that made the
+    /// content), replacing any earlier creator.
+    pub fn set_creator(&mut self, creator: &str) -> &mut Self {
+        self.replace_info("Creator", creator)
+    }
+
+    /// Set `key` to `value`, dropping entries for `key` added before.
+    fn replace_info(&mut self, key: &str, value: &str) -> &mut Self {
+        self.info.retain(|(k, _)| k != key);
+        self.set_info(key, value)
+    }
+
+    /// Set how many decimal places coordinates, sizes and colors keep in
+    /// content streams (default 4).
+    ///
+    /// Fewer digits give smaller files at the cost of positioning accuracy;
+    /// more suit high-precision CAD output. Whole numbers are always written
+    /// without a fraction. Affects content generated after the call.
+    pub fn set_coordinate_precision(&mut self, digits: u8) -> &mut Self {
+        self.coord_precision = digits as usize;
+        self
+    }
+
+    /// Set the `/Producer` written when none was set with `set_info`.
+    ///
+    /// Defaults to `"pivot-pdf <version>"`. Pass `None` to omit the entry.
+    pub fn set_producer(&mut self, producer: Option<&str>) -> &mut Self {
+        self.producer = producer.map(str::to_string);
+        self
+    }
+
+    /// Fill in `/CreationDate` and `/ModDate` with the current time (UTC)
+    /// at `end_document` when they were not set with `set_info`.
+    /// Enabled by default.
+    pub fn set_auto_dates(&mut self, enabled: bool) -> &mut Self {
+        self.auto_dates = enabled;
+        self
+    }
+
+    /// Make output reproducible: building the same document twice yields
+    /// byte-identical files.
+    ///
+    /// Automatic `/CreationDate` and `/ModDate` are skipped (explicit
+    /// `set_info` dates are kept), and the trailer gets an `/ID` derived
+    /// only from a hash of the bytes written before it. Disabled by default.
+    pub fn set_deterministic(&mut self, enabled: bool) -> &mut Self {
+        self.deterministic = enabled;
+        self
+    }
+
+    /// Ask the viewer to open its print dialog when the document is opened,
+    /// via an `/OpenAction << /S /Named /N /Print >>` in the catalog.
+    /// Disabled by default.
+    pub fn set_print_on_open(&mut self, enabled: bool) -> &mut Self {
+        self.print_on_open = enabled;
+        self
+    }
+
     /// Enable or disable FlateDecode compression for stream objects.
     /// When enabled, page content, embedded fonts, and ToUnicode CMaps
     /// are compressed, typically reducing file size by 50-80%.
@@ -145,6 +568,263 @@ impl<W: Write> PdfDocument<W> {
         self
     }
 
+    /// Compress page content and form XObject streams when compression is
+    /// on. Enabled by default; turn off to keep content readable while
+    /// fonts and images stay compressed.
+    pub fn set_compress_content(&mut self, enabled: bool) -> &mut Self {
+        self.compress_content = enabled;
+        self
+    }
+
+    /// Compress embedded font files and ToUnicode CMaps when compression
+    /// is on. Enabled by default.
+    pub fn set_compress_fonts(&mut self, enabled: bool) -> &mut Self {
+        self.compress_fonts = enabled;
+        self
+    }
+
+    /// Compress PNG image data and soft masks when compression is on.
+    /// JPEG data is always written as-is. Enabled by default.
+    pub fn set_compress_images(&mut self, enabled: bool) -> &mut Self {
+        self.compress_images = enabled;
+        self
+    }
+
+    /// Separate content-stream operators with single spaces instead of
+    /// newlines.
+    ///
+    /// Applies to page content, overlays and form XObjects as they are
+    /// written, shrinking uncompressed streams by roughly one byte per
+    /// operator line. Strings, inline image data and comments are left
+    /// intact. Off by default, since one operator per line is easier to read.
+    pub fn set_compact_content(&mut self, enabled: bool) -> &mut Self {
+        self.compact_content = enabled;
+        self
+    }
+
+    /// Follow the `%PDF-1.7` line with the binary comment line
+    /// (`%\xe2\xe3\xcf\xd3`, default true).
+    ///
+    /// The comment tells transfer tools the file holds binary data. Turning
+    /// it off gives a pure-ASCII header for text-only pipelines and test
+    /// fixtures, but the PDF spec recommends the comment whenever the file
+    /// contains binary data (e.g. compressed streams or images), so only
+    /// disable it when the output is known to be handled as binary anyway.
+    ///
+    /// Panics if called after the first page or object has been written.
+    pub fn set_binary_marker(&mut self, enabled: bool) -> &mut Self {
+        assert!(
+            !self.writer.header_written(),
+            "set_binary_marker called after output started"
+        );
+        self.writer.set_binary_marker(enabled);
+        self
+    }
+
+    /// Write the content streams of page `page_num` (1-based) without
+    /// compression, even when compression is on.
+    ///
+    /// A debugging aid: the page's operators stay readable in the output
+    /// while every other stream is compressed as usual. Applies to the
+    /// page's main stream and any `open_page` overlays written for it.
+    pub fn set_page_uncompressed(&mut self, page_num: usize) -> &mut Self {
+        self.uncompressed_pages.insert(page_num);
+        self
+    }
+
+    /// Stream kind for a content stream of page `page_num` (1-based).
+    fn page_content_kind(&self, page_num: usize) -> StreamKind {
+        if self.uncompressed_pages.contains(&page_num) {
+            StreamKind::Plain
+        } else {
+            StreamKind::Content
+        }
+    }
+
+    /// Merge `open_page` overlays into the page's main content stream.
+    ///
+    /// By default each overlay becomes its own stream object in the page's
+    /// `/Contents` array. When enabled, the content of every page ended
+    /// afterwards is buffered until `end_document()` so overlays can be
+    /// appended to it, and each page is written as a single stream. This
+    /// trades memory for fewer (and better-compressed) objects. Disabled by
+    /// default.
+    pub fn set_merge_overlays(&mut self, enabled: bool) -> &mut Self {
+        self.merge_overlays = enabled;
+        self
+    }
+
+    /// Turn validation warnings into errors.
+    ///
+    /// When enabled, `end_page` fails if the page's content references a
+    /// font or XObject missing from its resources, and `end_document` fails
+    /// if `validate()` reports any warning. Disabled by default.
+    pub fn set_strict(&mut self, enabled: bool) -> &mut Self {
+        self.strict = enabled;
+        self
+    }
+
+    /// Fonts referenced so far by the open page (or overlay), builtin
+    /// fonts first. Empty when no page is open.
+    pub fn current_page_fonts(&self) -> Vec<FontRef> {
+        let Some(page) = &self.current_page else {
+            return Vec::new();
+        };
+        page.used_fonts
+            .iter()
+            .map(|&font| FontRef::Builtin(font))
+            .chain(
+                page.used_truetype_fonts
+                    .iter()
+                    .map(|&idx| FontRef::TrueType(TrueTypeFontId(idx))),
+            )
+            .collect()
+    }
+
+    /// Images placed so far on the open page (or overlay), in load order.
+    /// Empty when no page is open.
+    pub fn current_page_images(&self) -> Vec<ImageId> {
+        self.current_page
+            .as_ref()
+            .map(|page| page.used_images.iter().map(|&idx| ImageId(idx)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Cross-check pages against the objects written so far.
+    ///
+    /// Reports resources used by completed pages that have no PDF objects,
+    /// overlays targeting missing pages, and names referenced by the open
+    /// page's content that are not in its resources. Completed pages'
+    /// content has already been written, so only the open page is scanned.
+    pub fn validate(&self) -> Vec<ValidationWarning> {
+        let mut warnings = Vec::new();
+
+        for (i, record) in self.page_records.iter().enumerate() {
+            let page = i + 1;
+            for &font in &record.used_truetype_fonts {
+                if !self.truetype_font_obj_ids.contains_key(&font) {
+                    warnings.push(ValidationWarning::UnallocatedFont { page, font });
+                }
+            }
+            for &image in &record.used_images {
+                if !self.written_images.contains(&image) {
+                    warnings.push(ValidationWarning::UnwrittenImage { page, image });
+                }
+            }
+        }
+
+        if let Some(builder) = &self.current_page {
+            let page = match builder.overlay_for {
+                Some(idx) if idx >= self.page_records.len() => {
+                    warnings.push(ValidationWarning::MissingPage { page: idx + 1 });
+                    idx + 1
+                }
+                Some(idx) => idx + 1,
+                None => self.page_records.len() + 1,
+            };
+            warnings.extend(self.content_warnings(page, builder));
+        }
+
+        warnings
+    }
+
+    /// Names referenced by a page's content that its resources lack.
+    fn content_warnings(&self, page: usize, builder: &PageBuilder) -> Vec<ValidationWarning> {
+        let (mut font_names, mut xobject_names): (BTreeSet<String>, BTreeSet<String>) =
+            (BTreeSet::new(), BTreeSet::new());
+        let mut add_page_resources =
+            |fonts: &BTreeSet<BuiltinFont>,
+             tt: &BTreeSet<usize>,
+             imgs: &BTreeSet<usize>,
+             forms: &BTreeSet<usize>| {
+                font_names.extend(fonts.iter().map(|f| f.pdf_name().to_string()));
+                font_names.extend(tt.iter().map(|&i| self.truetype_fonts[i].pdf_name.clone()));
+                xobject_names.extend(
+                    imgs.iter()
+                        .filter_map(|i| self.image_obj_ids.get(i))
+                        .map(|ids| ids.pdf_name.clone()),
+                );
+                xobject_names.extend(forms.iter().map(|&i| self.forms[i].pdf_name.clone()));
+            };
+        add_page_resources(
+            &builder.used_fonts,
+            &builder.used_truetype_fonts,
+            &builder.used_images,
+            &builder.used_forms,
+        );
+        // Overlays share the resource dictionary of the page they extend.
+        if let Some(record) = builder.overlay_for.and_then(|i| self.page_records.get(i)) {
+            add_page_resources(
+                &record.used_fonts,
+                &record.used_truetype_fonts,
+                &record.used_images,
+                &record.used_forms,
+            );
+        }
+
+        let (mut fonts, mut xobjects) = validate::referenced_resources(&builder.content_ops);
+        // Backgrounds are only spliced into the content when the page ends.
+        let (background_fonts, background_xobjects) =
+            validate::referenced_resources(&builder.background_ops);
+        fonts.extend(background_fonts);
+        xobjects.extend(background_xobjects);
+        let mut warnings: Vec<ValidationWarning> = fonts
+            .into_iter()
+            .filter(|name| !font_names.contains(name))
+            .map(|name| ValidationWarning::UndefinedFont { page, name })
+            .collect();
+        warnings.extend(
+            xobjects
+                .into_iter()
+                .filter(|name| !xobject_names.contains(name))
+                .map(|name| ValidationWarning::UndefinedXObject { page, name }),
+        );
+        warnings
+    }
+
+    /// Set the graphics state applied at the start of every subsequent
+    /// page, replacing any earlier defaults.
+    ///
+    /// The state is written in the order line width, stroke color, fill
+    /// color, line cap, line join, dash; `None` fields are skipped.
+    pub fn set_graphics_defaults(&mut self, defaults: GraphicsDefaults) -> &mut Self {
+        self.graphics_defaults = defaults;
+        self
+    }
+
+    /// The graphics state applied at the start of every new page.
+    pub fn graphics_defaults(&self) -> &GraphicsDefaults {
+        &self.graphics_defaults
+    }
+
+    /// Set a line width applied at the start of every subsequent page.
+    ///
+    /// PDF graphics state does not carry across pages, so without a default
+    /// each page starts at the viewer's 1pt line width.
+    pub fn set_default_line_width(&mut self, width: f64) -> &mut Self {
+        self.graphics_defaults.line_width = Some(width);
+        self
+    }
+
+    /// Set a stroke color applied at the start of every subsequent page.
+    pub fn set_default_stroke_color(&mut self, color: Color) -> &mut Self {
+        self.graphics_defaults.stroke_color = Some(color);
+        self
+    }
+
+    /// Set a fill color applied at the start of every subsequent page.
+    pub fn set_default_fill_color(&mut self, color: Color) -> &mut Self {
+        self.graphics_defaults.fill_color = Some(color);
+        self
+    }
+
+    /// Set the page size, in points, used by `begin_page_default`.
+    /// Defaults to US Letter (612 x 792).
+    pub fn set_default_page_size(&mut self, width: f64, height: f64) -> &mut Self {
+        self.default_page_size = (width, height);
+        self
+    }
+
     /// Load a TrueType font from a file path.
     /// Returns a FontRef that can be used in TextStyle.
     pub fn load_font_file<P: AsRef<Path>>(&mut self, path: P) -> Result<FontRef, String> {
@@ -164,34 +844,277 @@ impl<W: Write> PdfDocument<W> {
         Ok(FontRef::TrueType(TrueTypeFontId(idx)))
     }
 
+    /// Embed builtin fonts instead of referencing them by name (off by default).
+    ///
+    /// For PDF/A or viewers without the standard 14 fonts. Each builtin font
+    /// with a replacement from `load_standard_font_replacement` is written as
+    /// an embedded simple TrueType font; layout and content streams still use
+    /// the builtin metrics, so the replacement should be metric-compatible
+    /// (e.g. Liberation Sans for Helvetica). Builtin fonts without a
+    /// replacement stay as plain references.
+    pub fn set_embed_standard_fonts(&mut self, enabled: bool) -> &mut Self {
+        self.embed_standard_fonts = enabled;
+        self
+    }
+
+    /// Embed only the glyphs actually shown from each standard-font
+    /// replacement (off by default).
+    ///
+    /// Applies when `set_embed_standard_fonts` is on. Text shown with a
+    /// replaced font is collected from every page, and at `end_document()`
+    /// the replacement is written with all other glyph outlines removed
+    /// and its name tagged as a subset (`ABCDEF+Name`). Glyph IDs are kept,
+    /// so the font's cmap still maps WinAnsi codes to the right glyphs.
+    /// Fonts without TrueType (`glyf`) outlines are embedded in full.
+    pub fn set_subset_standard_fonts(&mut self, enabled: bool) -> &mut Self {
+        self.subset_standard_fonts = enabled;
+        self
+    }
+
+    /// Register the TrueType font embedded for `font` when
+    /// `set_embed_standard_fonts` is on. No replacement fonts ship with the
+    /// crate, so supply a metric-compatible one here.
+    pub fn load_standard_font_replacement(
+        &mut self,
+        font: BuiltinFont,
+        data: Vec<u8>,
+    ) -> Result<(), String> {
+        let replacement = TrueTypeFont::from_bytes(data, 0)?;
+        self.standard_font_replacements.insert(font, replacement);
+        Ok(())
+    }
+
+    /// Turn pair kerning on or off for a TrueType font (off by default).
+    ///
+    /// Kerning comes from the font's legacy `kern` table and affects both
+    /// measurement and the emitted `TJ` adjustments, so enable it before
+    /// laying out text with the font. Builtin fonts are never kerned, so
+    /// this is a no-op for them.
+    pub fn set_font_kerning(&mut self, font: FontRef, enabled: bool) -> &mut Self {
+        if let FontRef::TrueType(id) = font {
+            self.truetype_fonts[id.0].kerning = enabled;
+        }
+        self
+    }
+
+    /// Map `glyph` of a TrueType font to `unicode` in its ToUnicode CMap.
+    ///
+    /// Copy-paste and search read text back through the CMap, which is
+    /// built from the font's own cmap table. Use this to correct glyphs the
+    /// font maps wrongly. Only glyphs that appear in the document are
+    /// written to the CMap. A no-op for builtin fonts.
+    pub fn set_glyph_unicode(&mut self, font: &FontRef, glyph: u16, unicode: char) -> &mut Self {
+        if let FontRef::TrueType(id) = font {
+            self.truetype_fonts[id.0]
+                .unicode_overrides
+                .insert(glyph, unicode);
+        }
+        self
+    }
+
     /// Returns the number of completed pages (pages for which `end_page` has been called).
     pub fn page_count(&self) -> usize {
         self.page_records.len()
     }
 
+    /// Byte offset in the output where page `page_num` (1-based) begins:
+    /// the first object `end_page` wrote for it, such as a font or image
+    /// first used on the page, otherwise its content stream.
+    ///
+    /// Page dictionaries are written by `end_document`, so this is where
+    /// the page's data starts rather than its `/Page` object. Returns
+    /// `None` for pages not yet ended, and for pages whose `end_page` wrote
+    /// nothing because the content is held back by `set_merge_overlays`
+    /// and every resource was already written by an earlier page.
+    pub fn page_byte_offset(&self, page_num: usize) -> Option<usize> {
+        page_num
+            .checked_sub(1)
+            .and_then(|idx| self.page_records.get(idx))
+            .and_then(|record| record.byte_offset)
+    }
+
+    /// Output size statistics for everything written so far.
+    ///
+    /// Can be called at any point before `end_document()` to see where
+    /// the bytes of a large document are going.
+    pub fn stats(&self) -> DocStats {
+        DocStats {
+            page_count: self.page_records.len(),
+            object_count: self.writer.object_count(),
+            ..self.stats
+        }
+    }
+
+    /// Call `callback` as the document is written, e.g. to drive a progress
+    /// bar for a long report.
+    ///
+    /// It receives a `ProgressEvent::PageEnded` at the end of every
+    /// `end_page()` and a `ProgressEvent::ImageWritten` whenever an image
+    /// XObject is written (images are written with the first page that
+    /// uses them). Without a callback, nothing is tracked. The callback
+    /// must be `Send` so the document can still be built on another thread.
+    pub fn set_progress_callback(
+        &mut self,
+        callback: impl FnMut(ProgressEvent) + Send + 'static,
+    ) -> &mut Self {
+        self.progress = Some(Box::new(callback));
+        self
+    }
+
+    /// Pass `event` to the progress callback, if one is set.
+    fn report_progress(&mut self, event: ProgressEvent) {
+        if let Some(callback) = self.progress.as_mut() {
+            callback(event);
+        }
+    }
+
     /// Begin a new page with the given dimensions in points.
-    /// If a page is currently open, it is automatically closed.
+    /// If a page is currently open, it is automatically closed. When that
+    /// fails the old page is discarded and its error is returned by the
+    /// next `end_page` or `end_document`.
+    /// Any defaults from `set_default_*` are emitted at the top of the page.
     pub fn begin_page(&mut self, width: f64, height: f64) -> &mut Self {
-        if self.current_page.is_some() {
-            let _ = self.end_page();
+        self.auto_close_page();
+        let content_ops = self.default_state_ops();
+        self.current_page = Some(PageBuilder::new(width, height, 0, content_ops, None));
+        self
+    }
+
+    /// Close the open page before another is begun, keeping the first
+    /// failure for `end_page` since the page builders cannot return it.
+    fn auto_close_page(&mut self) {
+        let earlier = self.auto_close_error.take();
+        let result = self.end_page();
+        // A page that failed to close cannot stay open once the next begins.
+        self.current_page = None;
+        self.auto_close_error = earlier.or(result.err());
+    }
+
+    /// Begin a new page using the size from `set_default_page_size`.
+    pub fn begin_page_default(&mut self) -> &mut Self {
+        let (width, height) = self.default_page_size;
+        self.begin_page(width, height)
+    }
+
+    /// Begin a new page whose MediaBox is `[llx lly urx ury]`.
+    ///
+    /// Unlike `begin_page`, the lower-left corner need not be the origin,
+    /// which imposition workflows rely on. Content coordinates are not
+    /// translated: user space stays the PDF default, so content for a box
+    /// starting at (10, 10) should itself be placed from (10, 10).
+    pub fn begin_page_box(&mut self, llx: f64, lly: f64, urx: f64, ury: f64) -> &mut Self {
+        self.begin_page(urx - llx, ury - lly);
+        if let Some(page) = self.current_page.as_mut() {
+            page.origin = (llx, lly);
         }
-        self.current_page = Some(PageBuilder {
-            width,
-            height,
-            content_ops: Vec::new(),
-            used_fonts: BTreeSet::new(),
-            used_truetype_fonts: BTreeSet::new(),
-            used_images: BTreeSet::new(),
-            overlay_for: None,
-        });
         self
     }
 
+    /// Rotate every page clockwise by `degrees` when displayed, unless the
+    /// page was started with `begin_page_rotated`.
+    ///
+    /// Written once as `/Rotate` on the `/Pages` node, which pages inherit,
+    /// instead of on each page dictionary. Unlike `begin_page_rotated`, no
+    /// compensating matrix is added: content is laid out on the unrotated
+    /// page and the viewer turns the whole page. Pages from
+    /// `begin_page_rotated` keep their own rotation and write `/Rotate`
+    /// whenever it differs. `degrees` must be a multiple of 90 (negative
+    /// values and values of 360 or more are normalized).
+    pub fn set_document_rotation(&mut self, degrees: i32) -> &mut Self {
+        assert!(
+            degrees % 90 == 0,
+            "set_document_rotation: degrees must be a multiple of 90, got {}",
+            degrees
+        );
+        self.document_rotate = degrees.rem_euclid(360);
+        self
+    }
+
+    /// Begin a new page displayed rotated clockwise by `degrees`.
+    ///
+    /// `width` and `height` are the page size as the reader sees it. The page
+    /// gets `/Rotate degrees` and a compensating `cm` at the top of its
+    /// content stream, so coordinates passed to `place_text`, `rect`,
+    /// `fit_textflow` etc. keep their usual bottom-left-origin meaning on the
+    /// rotated page. `degrees` must be a multiple of 90 (negative values and
+    /// values of 360 or more are normalized).
+    pub fn begin_page_rotated(&mut self, width: f64, height: f64, degrees: i32) -> &mut Self {
+        assert!(
+            degrees % 90 == 0,
+            "begin_page_rotated: degrees must be a multiple of 90, got {}",
+            degrees
+        );
+        self.auto_close_page();
+        let rotate = degrees.rem_euclid(360);
+        let mut content_ops = rotation_ops(rotate, width, height, self.coord_precision);
+        content_ops.extend(self.default_state_ops());
+        let mut page = PageBuilder::new(width, height, rotate, content_ops, None);
+        page.rotated = true;
+        self.current_page = Some(page);
+        self
+    }
+
+    /// Begin a new page whose content is turned 180°.
+    ///
+    /// The content stream starts with a `-1 0 0 -1 width height cm` matrix,
+    /// so everything drawn with the usual bottom-left-origin coordinates
+    /// lands upside down on the sheet. Unlike `begin_page_rotated(w, h, 180)`
+    /// the page gets no `/Rotate`, so viewers show it flipped: this is what
+    /// duplex booklet imposition wants for back pages.
+    pub fn begin_page_flipped(&mut self, width: f64, height: f64) -> &mut Self {
+        self.auto_close_page();
+        let mut content_ops = rotation_ops(180, width, height, self.coord_precision);
+        content_ops.extend(self.default_state_ops());
+        let mut page = PageBuilder::new(width, height, 0, content_ops, None);
+        page.flipped = true;
+        self.current_page = Some(page);
+        self
+    }
+
+    /// Content operators that establish the document-scoped graphics defaults.
+    fn default_state_ops(&self) -> Vec<u8> {
+        let defaults = &self.graphics_defaults;
+        let mut ops = String::new();
+        if let Some(width) = defaults.line_width {
+            ops.push_str(&format!(
+                "{} w\n",
+                format_coord(width, self.coord_precision)
+            ));
+        }
+        if let Some(c) = defaults.stroke_color {
+            ops.push_str(&format!(
+                "{} {} {} RG\n",
+                format_coord(c.r, self.coord_precision),
+                format_coord(c.g, self.coord_precision),
+                format_coord(c.b, self.coord_precision),
+            ));
+        }
+        if let Some(c) = defaults.fill_color {
+            ops.push_str(&format!(
+                "{} {} {} rg\n",
+                format_coord(c.r, self.coord_precision),
+                format_coord(c.g, self.coord_precision),
+                format_coord(c.b, self.coord_precision),
+            ));
+        }
+        if let Some(cap) = defaults.line_cap {
+            ops.push_str(&format!("{} J\n", cap.pdf_value()));
+        }
+        if let Some(join) = defaults.line_join {
+            ops.push_str(&format!("{} j\n", join.pdf_value()));
+        }
+        if let Some((pattern, phase)) = &defaults.dash {
+            ops.push_str(&graphics::dash_op(pattern, *phase, self.coord_precision));
+        }
+        ops.into_bytes()
+    }
+
     /// Open a completed page for editing (1-indexed).
     ///
     /// Used for adding overlay content such as page numbers ("Page X of Y")
     /// after all pages have been written. The overlay content is written as
-    /// an additional content stream appended to the page's `/Contents` array.
+    /// an additional content stream appended to the page's `/Contents` array,
+    /// or appended to the page's own stream when `set_merge_overlays` is on.
     ///
     /// If a page is currently open, it is automatically closed first.
     ///
@@ -215,171 +1138,938 @@ impl<W: Write> PdfDocument<W> {
         let idx = page_num - 1;
         let width = self.page_records[idx].width;
         let height = self.page_records[idx].height;
+        let rotate = self.page_records[idx].rotate;
+        let flipped = self.page_records[idx].flipped;
+        let origin = self.page_records[idx].origin;
 
-        self.current_page = Some(PageBuilder {
+        // A flipped page's content needs the same matrix as a 180° rotation.
+        let preamble_rotate = if flipped { 180 } else { rotate };
+        let mut page = PageBuilder::new(
             width,
             height,
-            content_ops: Vec::new(),
-            used_fonts: BTreeSet::new(),
-            used_truetype_fonts: BTreeSet::new(),
-            used_images: BTreeSet::new(),
-            overlay_for: Some(idx),
-        });
+            rotate,
+            rotation_ops(preamble_rotate, width, height, self.coord_precision),
+            Some(idx),
+        );
+        page.flipped = flipped;
+        page.origin = origin;
+        self.current_page = Some(page);
 
         Ok(())
     }
 
+    /// Move completed page `from` so it becomes page `to` (both 1-indexed).
+    ///
+    /// The pages in between shift by one place. Page objects are written as
+    /// pages complete, so this only reorders the `/Kids` array written by
+    /// `end_document`; later `open_page` calls use the new numbering.
+    ///
+    /// If a page is currently open, it is automatically closed first.
+    ///
+    /// Returns an error if either page number is out of range.
+    pub fn move_page(&mut self, from: usize, to: usize) -> io::Result<()> {
+        if self.current_page.is_some() {
+            self.end_page()?;
+        }
+
+        let count = self.page_records.len();
+        for page_num in [from, to] {
+            if page_num == 0 || page_num > count {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "move_page: page_num {} out of range (1..={})",
+                        page_num, count
+                    ),
+                ));
+            }
+        }
+
+        let record = self.page_records.remove(from - 1);
+        self.page_records.insert(to - 1, record);
+        // Pages marked with `set_page_uncompressed` keep the mark.
+        self.uncompressed_pages = std::mem::take(&mut self.uncompressed_pages)
+            .into_iter()
+            .map(|page_num| moved_page_num(page_num, from, to))
+            .collect();
+        Ok(())
+    }
+
     /// Place text at position (x, y) using default 12pt Helvetica.
     /// Coordinates use PDF's default bottom-left origin.
     pub fn place_text(&mut self, text: &str, x: f64, y: f64) -> &mut Self {
         let page = self
             .current_page
             .as_mut()
-            .expect("place_text called with no open page");
-        page.used_fonts.insert(BuiltinFont::Helvetica);
-        let escaped = crate::writer::escape_pdf_string(text);
-        let ops = format!(
-            "BT\n/F1 12 Tf\n{} {} Td\n({}) Tj\nET\n",
-            format_coord(x),
-            format_coord(y),
-            escaped,
-        );
-        page.content_ops.extend_from_slice(ops.as_bytes());
+            .expect("place_text called with no open page");
+        page.used_fonts.insert(BuiltinFont::Helvetica);
+        let escaped = crate::writer::escape_pdf_string(text);
+        let ops = format!(
+            "BT\n/F1 12 Tf\n{} {} Td\n({}) Tj\nET\n",
+            format_coord(x, self.coord_precision),
+            format_coord(y, self.coord_precision),
+            escaped,
+        );
+        page.content_ops.extend_from_slice(ops.as_bytes());
+        self
+    }
+
+    /// Place text at position (x, y) with the given style.
+    /// Coordinates use PDF's default bottom-left origin.
+    ///
+    /// Characters the style's font lacks are set in the first font of its
+    /// fallback chain (`set_font_fallback`) that has them, with a `Tf`
+    /// switch at each change of font.
+    pub fn place_text_styled(
+        &mut self,
+        text: &str,
+        x: f64,
+        y: f64,
+        style: &TextStyle,
+    ) -> &mut Self {
+        assert!(
+            self.current_page.is_some(),
+            "place_text_styled called with no open page"
+        );
+        let size = format_coord(style.font_size, self.coord_precision);
+        let mut ops = String::from("BT\n");
+        for (i, (font, run)) in self.font_runs(text, style.font).into_iter().enumerate() {
+            let (font_name, text_op) = self.encode_run(font, &run);
+            ops.push_str(&format!("/{} {} Tf\n", font_name, size));
+            if i == 0 {
+                ops.push_str(&format!(
+                    "{} {} Td\n",
+                    format_coord(x, self.coord_precision),
+                    format_coord(y, self.coord_precision),
+                ));
+            }
+            ops.push_str(&text_op);
+            ops.push('\n');
+        }
+        ops.push_str("ET\n");
+        let page = self.current_page.as_mut().unwrap();
+        page.content_ops.extend_from_slice(ops.as_bytes());
+        self
+    }
+
+    /// Set the fonts tried, in order, for characters `primary` lacks.
+    ///
+    /// `place_text_styled` (and the helpers built on it) and `measure_text`
+    /// split text into runs, each set in `primary` or the first fallback
+    /// that has the character; characters no font has stay in `primary`.
+    /// A TrueType font has a character when its cmap maps it to a glyph;
+    /// builtin fonts cover printable ASCII. An empty list removes the chain.
+    pub fn set_font_fallback(&mut self, primary: FontRef, fallbacks: Vec<FontRef>) -> &mut Self {
+        if fallbacks.is_empty() {
+            self.font_fallbacks.remove(&primary);
+        } else {
+            self.font_fallbacks.insert(primary, fallbacks);
+        }
+        self
+    }
+
+    /// Whether `font` can show `ch` without falling back to `.notdef`.
+    fn font_has_glyph(&self, font: FontRef, ch: char) -> bool {
+        match font {
+            FontRef::Builtin(_) => (' '..='~').contains(&ch),
+            FontRef::TrueType(id) => self.truetype_fonts[id.0].has_glyph(ch),
+        }
+    }
+
+    /// Split `text` into consecutive runs by the font that shows each
+    /// character, following `primary`'s fallback chain.
+    fn font_runs(&self, text: &str, primary: FontRef) -> Vec<(FontRef, String)> {
+        let Some(fallbacks) = self.font_fallbacks.get(&primary) else {
+            return vec![(primary, text.to_string())];
+        };
+        let mut runs: Vec<(FontRef, String)> = Vec::new();
+        for ch in text.chars() {
+            let font = if self.font_has_glyph(primary, ch) {
+                primary
+            } else {
+                fallbacks
+                    .iter()
+                    .copied()
+                    .find(|&f| self.font_has_glyph(f, ch))
+                    .unwrap_or(primary)
+            };
+            match runs.last_mut() {
+                Some((last, run)) if *last == font => run.push(ch),
+                _ => runs.push((font, ch.to_string())),
+            }
+        }
+        if runs.is_empty() {
+            runs.push((primary, String::new()));
+        }
+        runs
+    }
+
+    /// Encode one run for a text-showing operator and mark its font used
+    /// on the current page. Returns the font's resource name and the
+    /// operator.
+    fn encode_run(&mut self, font: FontRef, text: &str) -> (String, String) {
+        let encoded = match font {
+            FontRef::Builtin(b) => {
+                let escaped = crate::writer::escape_pdf_string(text);
+                (b.pdf_name().to_string(), format!("({}) Tj", escaped))
+            }
+            FontRef::TrueType(id) => {
+                let tt = &mut self.truetype_fonts[id.0];
+                let op = tt.encode_text_op(text);
+                (tt.pdf_name.clone(), op)
+            }
+        };
+        let page = self.current_page.as_mut().unwrap();
+        match font {
+            FontRef::Builtin(b) => {
+                page.used_fonts.insert(b);
+            }
+            FontRef::TrueType(id) => {
+                page.used_truetype_fonts.insert(id.0);
+            }
+        }
+        encoded
+    }
+
+    /// Fill the gap between `from_x` and `to_x` on baseline `y` with a dot
+    /// leader, as between a table-of-contents entry and its page number.
+    ///
+    /// Dots are periods set in `style`, one per width of `". "`, as many as
+    /// fit the gap. The run ends flush at `to_x`, so leaders on successive
+    /// lines with the same `to_x` line up. Draws nothing when not even one
+    /// dot fits.
+    pub fn draw_leader(&mut self, from_x: f64, to_x: f64, y: f64, style: &TextStyle) -> &mut Self {
+        let pitch = self.measure_text(". ", style);
+        let count = if pitch > 0.0 {
+            ((to_x - from_x) / pitch + 1e-9).floor() as usize
+        } else {
+            0
+        };
+        if count == 0 {
+            return self;
+        }
+        let dots = vec!["."; count].join(" ");
+        let x = to_x - self.measure_text(&dots, style);
+        self.place_text_styled(&dots, x, y, style)
+    }
+
+    /// Set `text` along a circle centred on `(cx, cy)`, glyph by glyph.
+    ///
+    /// The first glyph's baseline starts at `start_angle` degrees
+    /// (counter-clockwise from the positive x-axis) on a circle of
+    /// `radius`, and each following glyph advances clockwise by its
+    /// measured width, so text started at 150° reads left to right over
+    /// the top. Glyphs stand upright away from the centre; each gets its
+    /// own `Tm`. Kerning between glyphs is not applied.
+    pub fn place_text_on_arc(
+        &mut self,
+        text: &str,
+        cx: f64,
+        cy: f64,
+        radius: f64,
+        start_angle: f64,
+        style: &TextStyle,
+    ) -> &mut Self {
+        let precision = self.coord_precision;
+        // Snap tiny trig residue so e.g. cos(90°) is written as 0, not -0.
+        let coord = |v: f64| format_coord(if v.abs() < 1e-9 { 0.0 } else { v }, precision);
+        let mut angle = start_angle.to_radians();
+        let mut ops = String::new();
+        for ch in text.chars() {
+            let glyph = ch.encode_utf8(&mut [0; 4]).to_string();
+            let (sin, cos) = angle.sin_cos();
+            let text_op = match style.font {
+                FontRef::Builtin(_) => {
+                    format!("({}) Tj", crate::writer::escape_pdf_string(&glyph))
+                }
+                FontRef::TrueType(id) => self.truetype_fonts[id.0].encode_text_op(&glyph),
+            };
+            ops.push_str(&format!(
+                "{} {} {} {} {} {} Tm\n{}\n",
+                coord(sin),
+                coord(-cos),
+                coord(cos),
+                coord(sin),
+                coord(cx + radius * cos),
+                coord(cy + radius * sin),
+                text_op,
+            ));
+            if radius > 0.0 {
+                angle -= self.measure_text(&glyph, style) / radius;
+            }
+        }
+
+        let font_name = match style.font {
+            FontRef::Builtin(b) => b.pdf_name().to_string(),
+            FontRef::TrueType(id) => self.truetype_fonts[id.0].pdf_name.clone(),
+        };
+        let page = self
+            .current_page
+            .as_mut()
+            .expect("place_text_on_arc called with no open page");
+        match style.font {
+            FontRef::Builtin(b) => {
+                page.used_fonts.insert(b);
+            }
+            FontRef::TrueType(id) => {
+                page.used_truetype_fonts.insert(id.0);
+            }
+        }
+        let ops = format!(
+            "BT\n/{} {} Tf\n{}ET\n",
+            font_name,
+            format_coord(style.font_size, precision),
+            ops,
+        );
+        page.content_ops.extend_from_slice(ops.as_bytes());
+        self
+    }
+
+    /// Place a single line of text aligned relative to `x`.
+    ///
+    /// `Left` starts the text at `x`, `Right` ends it at `x`, and `Center`
+    /// centers it on `x`, using the measured width of `text` in `style`.
+    pub fn place_text_aligned(
+        &mut self,
+        text: &str,
+        x: f64,
+        y: f64,
+        style: &TextStyle,
+        align: TextAlign,
+    ) -> &mut Self {
+        let width = self.measure_text(text, style);
+        let start_x = match align {
+            TextAlign::Left => x,
+            TextAlign::Center => x - width / 2.0,
+            TextAlign::Right => x - width,
+        };
+        self.place_text_styled(text, start_x, y, style)
+    }
+
+    /// Place `text` wrapped to `rect.width`, aligned within `rect`.
+    ///
+    /// Lines break at spaces and `\n` like table cells (over-long words are
+    /// broken). The block is `lines * line height` tall and is positioned
+    /// by `valign`, with the first baseline one font size below the block
+    /// top; each line is aligned by `halign`. A block taller than the rect
+    /// overflows it: below the bottom for `Top`, equally on both sides for
+    /// `Middle`. Nothing is clipped. Returns the number of lines placed.
+    pub fn place_text_block(
+        &mut self,
+        text: &str,
+        rect: &Rect,
+        style: &TextStyle,
+        halign: TextAlign,
+        valign: VerticalAlign,
+    ) -> usize {
+        let lines = tables::wrap_text(
+            text,
+            rect.width,
+            style,
+            WordBreak::BreakAll,
+            '-',
+            &self.truetype_fonts,
+        );
+        let line_height = self.line_height_for(style);
+        let block_height = lines.len() as f64 * line_height;
+        let offset = match valign {
+            VerticalAlign::Top => 0.0,
+            VerticalAlign::Middle => (rect.height - block_height) / 2.0,
+            VerticalAlign::Bottom => rect.height - block_height,
+        };
+        let mut baseline = rect.y - offset - style.font_size;
+        for line in &lines {
+            let x = match halign {
+                TextAlign::Left => rect.x,
+                TextAlign::Center => rect.x + rect.width / 2.0,
+                TextAlign::Right => rect.x + rect.width,
+            };
+            self.place_text_aligned(line, x, baseline, style, halign);
+            baseline -= line_height;
+        }
+        lines.len()
+    }
+
+    /// Width of `text` in points when set in `style`.
+    ///
+    /// Follows the font's fallback chain like `place_text_styled`.
+    pub fn measure_text(&self, text: &str, style: &TextStyle) -> f64 {
+        self.font_runs(text, style.font)
+            .iter()
+            .map(|(font, run)| {
+                let run_style = TextStyle {
+                    font: *font,
+                    ..style.clone()
+                };
+                crate::textflow::measure_word(run, &run_style, &self.truetype_fonts)
+            })
+            .sum()
+    }
+
+    /// Shorten `text` to fit within `max_width` points when set in `style`.
+    ///
+    /// Returns `text` unchanged when it already fits. Otherwise returns the
+    /// longest prefix (trailing whitespace trimmed) followed by an ellipsis
+    /// that measures within `max_width`, or an empty string when not even
+    /// the ellipsis fits. TrueType fonts get `…` (U+2026); builtin fonts,
+    /// whose strings are written as single bytes, get `...`.
+    pub fn fit_text_ellipsized(&self, text: &str, style: &TextStyle, max_width: f64) -> String {
+        if self.measure_text(text, style) <= max_width {
+            return text.to_string();
+        }
+        let ellipsis = match style.font {
+            FontRef::Builtin(_) => "...",
+            FontRef::TrueType(_) => "\u{2026}",
+        };
+        let mut best = String::new();
+        for (end, _) in text.char_indices().skip(1) {
+            let candidate = format!("{}{}", text[..end].trim_end(), ellipsis);
+            if self.measure_text(&candidate, style) > max_width {
+                break;
+            }
+            best = candidate;
+        }
+        if best.is_empty() && self.measure_text(ellipsis, style) <= max_width {
+            best = ellipsis.to_string();
+        }
+        best
+    }
+
+    /// Total height and line count the rest of `flow` would take when
+    /// wrapped to `width`, without moving its cursor or emitting content.
+    ///
+    /// The height matches what `fit_textflow` consumes (`last_fit_height`)
+    /// given a rect of this width tall enough for the whole flow, so it can
+    /// be used to decide whether a block fits before placing it.
+    pub fn measure_flow(&self, flow: &TextFlow, width: f64) -> (f64, usize) {
+        flow.measure_lines(width, &self.truetype_fonts)
+    }
+
+    /// Fit a TextFlow into a bounding rectangle on the current
+    /// page. The flow's cursor advances so subsequent calls
+    /// continue where it left off (for multi-page flow).
+    pub fn fit_textflow(&mut self, flow: &mut TextFlow, rect: &Rect) -> io::Result<FitResult> {
+        assert!(
+            self.current_page.is_some(),
+            "fit_textflow called with no open page"
+        );
+        let mut image_names = BTreeMap::new();
+        for image in flow.inline_images() {
+            self.ensure_image_obj_ids(image.0);
+            image_names.insert(image.0, self.image_obj_ids[&image.0].pdf_name.clone());
+        }
+        let (ops, result, used_fonts) = flow.generate_content_ops(
+            rect,
+            &mut self.truetype_fonts,
+            &image_names,
+            self.coord_precision,
+        );
+
+        let page = self.current_page.as_mut().unwrap();
+        page.content_ops.extend_from_slice(&ops);
+        page.used_fonts.extend(used_fonts.builtin);
+        page.used_truetype_fonts.extend(used_fonts.truetype);
+        page.used_images.extend(used_fonts.images);
+        Ok(result)
+    }
+
+    /// Place a single table row on the current page.
+    ///
+    /// `cursor` tracks the current Y position within the page. Pass the same
+    /// cursor to successive calls; call `cursor.reset()` when starting a new page.
+    ///
+    /// Returns:
+    /// - `Stop`     — row placed; advance to the next row.
+    /// - `BoxFull`  — page full; end the page, begin a new one, reset the cursor, retry.
+    /// - `BoxEmpty` — rect too small for this row even from the top; skip or abort.
+    pub fn fit_row(
+        &mut self,
+        table: &Table,
+        row: &Row,
+        cursor: &mut TableCursor,
+    ) -> io::Result<FitResult> {
+        let (ops, result, used_fonts) =
+            table.generate_row_ops(row, cursor, &mut self.truetype_fonts, self.coord_precision);
+
+        let page = self
+            .current_page
+            .as_mut()
+            .expect("fit_row called with no open page");
+        page.content_ops.extend_from_slice(&ops);
+        page.used_fonts.extend(used_fonts.builtin);
+        page.used_truetype_fonts.extend(used_fonts.truetype);
+        Ok(result)
+    }
+
+    /// Place all `rows` in `rect` on the current page, shrinking the table
+    /// uniformly if it would not fit.
+    ///
+    /// When the rows are taller than `rect`, font sizes, paddings and fixed
+    /// row heights are scaled by the same factor (text also wraps less at
+    /// the smaller size) until they fit. Border widths are unchanged.
+    /// Returns the applied scale: `1.0` when the table fits as is. If even
+    /// a heavily reduced table cannot fit (e.g. a rect only a few points
+    /// tall), rows are placed until the rect is full.
+    pub fn fit_table_to_rect(
+        &mut self,
+        table: &Table,
+        rows: &[Row],
+        rect: &Rect,
+    ) -> io::Result<f64> {
+        const MAX_ATTEMPTS: usize = 20;
+
+        let mut scale = 1.0;
+        let mut scaled_table = table.clone();
+        let mut scaled_rows = rows.to_vec();
+        for _ in 0..MAX_ATTEMPTS {
+            let height = scaled_table.measure_rows(
+                &scaled_rows,
+                rect.width,
+                &mut self.truetype_fonts,
+                self.coord_precision,
+            );
+            if height <= rect.height {
+                break;
+            }
+            // Heights shrink at least in proportion to the scale; the 0.99
+            // cap guarantees progress when rounding leaves a sliver over.
+            scale *= (rect.height / height).min(0.99);
+            scaled_table = table.scaled(scale);
+            scaled_rows = rows.iter().map(|row| row.scaled(scale)).collect();
+        }
+
+        let mut cursor = TableCursor::new(rect);
+        for row in &scaled_rows {
+            if self.fit_row(&scaled_table, row, &mut cursor)? != FitResult::Stop {
+                break;
+            }
+        }
+        Ok(scale)
+    }
+
+    /// Column widths that fit the text of `header` and the first
+    /// `sample_rows` of `rows` without wrapping, for `Table::new`.
+    ///
+    /// Each width is the longest line of text in the column plus the
+    /// cell's padding on both sides. Only the sampled rows are pulled from
+    /// `rows`, so a large or streamed dataset is never buffered; pass
+    /// `iter.by_ref()` to keep the rest of an iterator for `fit_row`. Rows
+    /// past the sample that are wider than every sampled one wrap (or
+    /// overflow, per their `CellOverflow`).
+    pub fn auto_columns<I>(&self, header: &Row, rows: I, sample_rows: usize) -> Vec<f64>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Row>,
+    {
+        tables::natural_column_widths(header, rows, sample_rows, &self.truetype_fonts)
+    }
+
+    /// Vertical space left below `cursor` on the current page, in points.
+    ///
+    /// Same as `cursor.remaining_height()`. Compare it with the height a
+    /// section needs to start that section on a fresh page instead of
+    /// splitting it.
+    pub fn space_remaining(&self, cursor: &TableCursor) -> f64 {
+        cursor.remaining_height()
+    }
+
+    /// Line height for `style`, resolving TrueType metrics when needed.
+    pub(crate) fn line_height_for(&self, style: &TextStyle) -> f64 {
+        crate::textflow::line_height_for(style, &self.truetype_fonts)
+    }
+
+    // -------------------------------------------------------
+    // Image operations
+    // -------------------------------------------------------
+
+    /// Load an image from a file path.
+    /// Returns an ImageId that can be used with `place_image`.
+    pub fn load_image_file<P: AsRef<Path>>(&mut self, path: P) -> Result<ImageId, String> {
+        let data = std::fs::read(path.as_ref())
+            .map_err(|e| format!("Failed to read image file: {}", e))?;
+        self.load_image_bytes(data)
+    }
+
+    /// Load an image from raw bytes (JPEG or PNG).
+    /// Returns an ImageId that can be used with `place_image`.
+    pub fn load_image_bytes(&mut self, data: Vec<u8>) -> Result<ImageId, String> {
+        let image_data = images::load_image(data)?;
+        let idx = self.images.len();
+        self.images.push(image_data);
+        Ok(ImageId(idx))
+    }
+
+    /// Load an image from raw bytes and store it as JPEG (DCTDecode).
+    ///
+    /// PNG input is decoded and re-encoded at `quality` (1-100, clamped),
+    /// which is usually far smaller than Flate for photographic content.
+    /// JPEG input is stored as-is. Alpha is preserved as a soft mask.
+    pub fn load_image_bytes_as_jpeg(
+        &mut self,
+        data: Vec<u8>,
+        quality: u8,
+    ) -> Result<ImageId, String> {
+        let image_data = images::convert_to_jpeg(images::load_image(data)?, quality)?;
+        let idx = self.images.len();
+        self.images.push(image_data);
+        Ok(ImageId(idx))
+    }
+
+    /// Pixel dimensions `(width, height)` of a loaded image.
+    pub fn image_dimensions(&self, image: &ImageId) -> (u32, u32) {
+        let img = &self.images[image.0];
+        (img.width, img.height)
+    }
+
+    /// Ask viewers to smooth the image when it is scaled up.
+    ///
+    /// Sets `/Interpolate true` on the image XObject. The XObject is shared
+    /// by every placement and written when the first page using it ends, so
+    /// call this before then. Off by default.
+    pub fn set_image_interpolation(&mut self, image: &ImageId, interpolate: bool) -> &mut Self {
+        if interpolate {
+            self.interpolated_images.insert(image.0);
+        } else {
+            self.interpolated_images.remove(&image.0);
+        }
+        self
+    }
+
+    /// Invert the image's samples when rendered.
+    ///
+    /// Emits `/Decode [1 0]` (grayscale) or `/Decode [1 0 1 0 1 0]` (RGB)
+    /// on the image XObject, so scans stored inverted display correctly
+    /// without re-encoding pixels. Like interpolation this is a per-image
+    /// setting; call it before the first page using the image ends.
+    pub fn set_image_invert(&mut self, image: &ImageId, invert: bool) -> &mut Self {
+        if invert {
+            self.inverted_images.insert(image.0);
+        } else {
+            self.inverted_images.remove(&image.0);
+        }
+        self
+    }
+
+    /// Advance from the current page automatically after `seconds` when
+    /// the document is shown as a full-screen presentation (`/Dur`).
+    ///
+    /// Panics if `seconds` is negative or not finite.
+    pub fn set_page_duration(&mut self, seconds: f64) -> &mut Self {
+        assert!(
+            seconds.is_finite() && seconds >= 0.0,
+            "set_page_duration: seconds must be a non-negative number, got {}",
+            seconds
+        );
+        let page = self
+            .current_page
+            .as_mut()
+            .expect("set_page_duration called with no open page");
+        page.duration = Some(seconds);
+        self
+    }
+
+    /// Play `transition` when a presentation viewer moves to the current
+    /// page (`/Trans`). The viewer's default speed and direction apply.
+    pub fn set_page_transition(&mut self, transition: Transition) -> &mut Self {
+        let page = self
+            .current_page
+            .as_mut()
+            .expect("set_page_transition called with no open page");
+        page.transition = Some(transition);
+        self
+    }
+
+    /// Use a loaded image as the current page's thumbnail (`/Thumb`).
+    ///
+    /// The image XObject is written when the page ends and referenced from
+    /// the page dictionary. Returns an `InvalidInput` error if either
+    /// dimension exceeds `MAX_THUMBNAIL_SIZE` pixels.
+    pub fn set_page_thumbnail(&mut self, image: &ImageId) -> io::Result<&mut Self> {
+        let (w, h) = self.image_dimensions(image);
+        if w > MAX_THUMBNAIL_SIZE || h > MAX_THUMBNAIL_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "set_page_thumbnail: {}x{} image exceeds {}px limit",
+                    w, h, MAX_THUMBNAIL_SIZE
+                ),
+            ));
+        }
+        let page = self
+            .current_page
+            .as_mut()
+            .expect("set_page_thumbnail called with no open page");
+        page.thumbnail = Some(image.0);
+        Ok(self)
+    }
+
+    /// Place an image on the current page within the given bounding rect.
+    pub fn place_image(&mut self, image: &ImageId, rect: &Rect, fit: ImageFit) -> &mut Self {
+        let ops = self.image_ops(image, rect, fit, fit.default_anchor(), "place_image");
+        let page = self.current_page.as_mut().unwrap();
+        page.content_ops.extend_from_slice(&ops);
         self
     }
 
-    /// Place text at position (x, y) with the given style.
-    /// Coordinates use PDF's default bottom-left origin.
-    pub fn place_text_styled(
+    /// Place an image like `place_image`, positioned within `rect` by
+    /// `anchor`.
+    ///
+    /// `place_image` centers `Fit` and `Fill` images and puts `None` images
+    /// at the top-left; use this to align a logo to a corner or edge of its
+    /// box instead, or to choose which part of a `Fill` image is kept.
+    pub fn place_image_anchored(
         &mut self,
-        text: &str,
+        image: &ImageId,
+        rect: &Rect,
+        fit: ImageFit,
+        anchor: ImageAnchor,
+    ) -> &mut Self {
+        let ops = self.image_ops(image, rect, fit, anchor, "place_image_anchored");
+        let page = self.current_page.as_mut().unwrap();
+        page.content_ops.extend_from_slice(&ops);
+        self
+    }
+
+    /// Place an image with an exact `width 0 0 height x y cm` matrix.
+    ///
+    /// `(x, y)` is the image's lower-left corner in PDF coordinates. The
+    /// aspect ratio is not preserved; use this when the display size is
+    /// already known, e.g. for pre-scaled thumbnails.
+    pub fn place_image_exact(
+        &mut self,
+        image: &ImageId,
         x: f64,
         y: f64,
-        style: &TextStyle,
+        width: f64,
+        height: f64,
     ) -> &mut Self {
-        // Encode text before borrowing page mutably
-        let (font_name, text_op) = match style.font {
-            FontRef::Builtin(b) => {
-                let escaped = crate::writer::escape_pdf_string(text);
-                (b.pdf_name().to_string(), format!("({}) Tj", escaped))
-            }
-            FontRef::TrueType(id) => {
-                let font = &mut self.truetype_fonts[id.0];
-                let hex = font.encode_text_hex(text);
-                (font.pdf_name.clone(), format!("{} Tj", hex))
-            }
-        };
-
         let page = self
             .current_page
-            .as_mut()
-            .expect("place_text_styled called with no open page");
-
-        match style.font {
-            FontRef::Builtin(b) => {
-                page.used_fonts.insert(b);
-            }
-            FontRef::TrueType(id) => {
-                page.used_truetype_fonts.insert(id.0);
-            }
-        }
-
-        let ops = format!(
-            "BT\n/{} {} Tf\n{} {} Td\n{}\nET\n",
-            font_name,
-            format_coord(style.font_size),
-            format_coord(x),
-            format_coord(y),
-            text_op,
+            .as_ref()
+            .expect("place_image_exact called with no open page");
+        let rect = Rect {
+            x,
+            y: page.origin.1 + page.height - (y + height),
+            width,
+            height,
+        };
+        let ops = self.image_ops(
+            image,
+            &rect,
+            ImageFit::Stretch,
+            ImageFit::Stretch.default_anchor(),
+            "place_image_exact",
         );
-        page.content_ops.extend_from_slice(ops.as_bytes());
+        let page = self.current_page.as_mut().unwrap();
+        page.content_ops.extend_from_slice(&ops);
         self
     }
 
-    /// Fit a TextFlow into a bounding rectangle on the current
-    /// page. The flow's cursor advances so subsequent calls
-    /// continue where it left off (for multi-page flow).
-    pub fn fit_textflow(&mut self, flow: &mut TextFlow, rect: &Rect) -> io::Result<FitResult> {
-        let (ops, result, used_fonts) = flow.generate_content_ops(rect, &mut self.truetype_fonts);
-
+    /// Place an image `width` points wide with its top-left corner at
+    /// `(x, top_y)`, taking whatever height its aspect ratio implies.
+    ///
+    /// Unlike `place_image`, `top_y` is in PDF coordinates (like
+    /// `place_text`), so the return value — the y just below the image,
+    /// `top_y` minus the height — can be passed straight to the next
+    /// text or image.
+    pub fn place_image_full_width(
+        &mut self,
+        image: &ImageId,
+        x: f64,
+        top_y: f64,
+        width: f64,
+    ) -> f64 {
+        let (img_w, img_h) = self.image_dimensions(image);
+        let height = width * img_h as f64 / img_w as f64;
         let page = self
             .current_page
-            .as_mut()
-            .expect("fit_textflow called with no open page");
+            .as_ref()
+            .expect("place_image_full_width called with no open page");
+        // Image rects measure y down from the top of the MediaBox.
+        let rect = Rect {
+            x,
+            y: page.origin.1 + page.height - top_y,
+            width,
+            height,
+        };
+        let ops = self.image_ops(
+            image,
+            &rect,
+            ImageFit::Stretch,
+            ImageFit::Stretch.default_anchor(),
+            "place_image_full_width",
+        );
+        let page = self.current_page.as_mut().unwrap();
         page.content_ops.extend_from_slice(&ops);
-        page.used_fonts.extend(used_fonts.builtin);
-        page.used_truetype_fonts.extend(used_fonts.truetype);
-        Ok(result)
+        top_y - height
     }
 
-    /// Place a single table row on the current page.
-    ///
-    /// `cursor` tracks the current Y position within the page. Pass the same
-    /// cursor to successive calls; call `cursor.reset()` when starting a new page.
+    /// Repeat an image across `rect` in a grid of `tile_w` x `tile_h` cells.
     ///
-    /// Returns:
-    /// - `Stop`     — row placed; advance to the next row.
-    /// - `BoxFull`  — page full; end the page, begin a new one, reset the cursor, retry.
-    /// - `BoxEmpty` — rect too small for this row even from the top; skip or abort.
-    pub fn fit_row(
+    /// Tiles start at the rect's top-left corner; each is placed in its cell
+    /// as `place_image` would with `fit`. Partial tiles at the right and
+    /// bottom edges are clipped to `rect`, and the whole grid is wrapped in
+    /// `q`/`Q`. Every tile is a separate `Do` of the same XObject, so the
+    /// image data is still written once. A non-positive tile size places
+    /// nothing.
+    pub fn tile_image(
         &mut self,
-        table: &Table,
-        row: &Row,
-        cursor: &mut TableCursor,
-    ) -> io::Result<FitResult> {
-        let (ops, result, used_fonts) =
-            table.generate_row_ops(row, cursor, &mut self.truetype_fonts);
-
+        image: &ImageId,
+        rect: &Rect,
+        tile_w: f64,
+        tile_h: f64,
+        fit: ImageFit,
+    ) -> &mut Self {
         let page = self
             .current_page
-            .as_mut()
-            .expect("fit_row called with no open page");
+            .as_ref()
+            .expect("tile_image called with no open page");
+        if tile_w <= 0.0 || tile_h <= 0.0 {
+            return self;
+        }
+        let page_height = page.origin.1 + page.height;
+        // Tolerate rounding so a rect that is an exact multiple gets no sliver.
+        let cols = (rect.width / tile_w - 1e-9).ceil().max(0.0) as usize;
+        let rows = (rect.height / tile_h - 1e-9).ceil().max(0.0) as usize;
+
+        let mut ops = format!(
+            "q\n{} {} {} {} re W n\n",
+            format_coord(rect.x, self.coord_precision),
+            format_coord(page_height - (rect.y + rect.height), self.coord_precision),
+            format_coord(rect.width, self.coord_precision),
+            format_coord(rect.height, self.coord_precision),
+        )
+        .into_bytes();
+        for row in 0..rows {
+            for col in 0..cols {
+                let cell = Rect {
+                    x: rect.x + col as f64 * tile_w,
+                    y: rect.y + row as f64 * tile_h,
+                    width: tile_w,
+                    height: tile_h,
+                };
+                ops.extend(self.image_ops(image, &cell, fit, fit.default_anchor(), "tile_image"));
+            }
+        }
+        ops.extend_from_slice(b"Q\n");
+        let page = self.current_page.as_mut().unwrap();
         page.content_ops.extend_from_slice(&ops);
-        page.used_fonts.extend(used_fonts.builtin);
-        page.used_truetype_fonts.extend(used_fonts.truetype);
-        Ok(result)
+        self
     }
 
-    // -------------------------------------------------------
-    // Image operations
-    // -------------------------------------------------------
+    /// Place `color` using a separately loaded grayscale image as its alpha.
+    ///
+    /// The color image's XObject gets an `/SMask` referencing the mask
+    /// image's XObject. Because the XObject is shared by every placement,
+    /// the pairing is fixed once the color image has been written; pairing
+    /// it with a different mask afterwards is an `InvalidInput` error. The
+    /// mask must be DeviceGray, and the color image must not carry its own
+    /// alpha channel.
+    pub fn place_image_with_mask(
+        &mut self,
+        color: &ImageId,
+        mask: &ImageId,
+        rect: &Rect,
+        fit: ImageFit,
+    ) -> io::Result<&mut Self> {
+        let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidInput, msg.to_string());
+        if color.0 == mask.0 {
+            return Err(invalid("an image cannot be its own mask"));
+        }
+        if self.images[mask.0].color_space != ColorSpace::DeviceGray {
+            return Err(invalid("mask image must be grayscale"));
+        }
+        if self.images[color.0].smask_data.is_some() {
+            return Err(invalid("color image already has an alpha channel"));
+        }
+        if self.written_images.contains(&color.0) && self.image_masks.get(&color.0) != Some(&mask.0)
+        {
+            return Err(invalid(
+                "color image was already written with a different mask",
+            ));
+        }
+        self.image_masks.insert(color.0, mask.0);
+        self.ensure_image_obj_ids(mask.0);
+        Ok(self.place_image(color, rect, fit))
+    }
 
-    /// Load an image from a file path.
-    /// Returns an ImageId that can be used with `place_image`.
-    pub fn load_image_file<P: AsRef<Path>>(&mut self, path: P) -> Result<ImageId, String> {
-        let data = std::fs::read(path.as_ref())
-            .map_err(|e| format!("Failed to read image file: {}", e))?;
-        self.load_image_bytes(data)
+    /// Place an image behind everything else on the current page.
+    ///
+    /// Content is otherwise painted in call order, so text drawn before an
+    /// image would be hidden by it. Background images are buffered
+    /// separately and merged in at `end_page()`, just after the page
+    /// preamble (rotation matrix and graphics defaults), so they sit beneath
+    /// all content regardless of when this is called. On a page reopened
+    /// with `open_page`, "behind" is relative to the overlay only.
+    pub fn place_image_background(
+        &mut self,
+        image: &ImageId,
+        rect: &Rect,
+        fit: ImageFit,
+    ) -> &mut Self {
+        let ops = self.image_ops(
+            image,
+            rect,
+            fit,
+            fit.default_anchor(),
+            "place_image_background",
+        );
+        let page = self.current_page.as_mut().unwrap();
+        page.background_ops.extend_from_slice(&ops);
+        self
     }
 
-    /// Load an image from raw bytes (JPEG or PNG).
-    /// Returns an ImageId that can be used with `place_image`.
-    pub fn load_image_bytes(&mut self, data: Vec<u8>) -> Result<ImageId, String> {
-        let image_data = images::load_image(data)?;
-        let idx = self.images.len();
-        self.images.push(image_data);
-        Ok(ImageId(idx))
+    /// Cover the whole current page with `image`, behind all other content.
+    ///
+    /// A shortcut for `place_image_background` with a rect spanning the
+    /// page's MediaBox (the displayed size on rotated pages), for full-bleed
+    /// covers. `ImageFit::Fill` covers the page and crops the overflow,
+    /// `Fit` letterboxes, and `Stretch` ignores the aspect ratio.
+    pub fn set_page_background_image(&mut self, image: &ImageId, fit: ImageFit) -> &mut Self {
+        let page = self
+            .current_page
+            .as_ref()
+            .expect("set_page_background_image called with no open page");
+        let rect = Rect {
+            x: page.origin.0,
+            y: 0.0,
+            width: page.width,
+            height: page.height,
+        };
+        self.place_image_background(image, &rect, fit)
     }
 
-    /// Place an image on the current page within the given bounding rect.
-    pub fn place_image(&mut self, image: &ImageId, rect: &Rect, fit: ImageFit) -> &mut Self {
+    /// Register an image on the current page and build its `q cm Do Q` operators.
+    fn image_ops(
+        &mut self,
+        image: &ImageId,
+        rect: &Rect,
+        fit: ImageFit,
+        anchor: ImageAnchor,
+        caller: &str,
+    ) -> Vec<u8> {
         let idx = image.0;
         let img = &self.images[idx];
-        let page_height = self
+        let page = self
             .current_page
             .as_ref()
-            .expect("place_image called with no open page")
-            .height;
-
-        let placement = images::calculate_placement(img.width, img.height, rect, fit, page_height);
+            .unwrap_or_else(|| panic!("{} called with no open page", caller));
+        // Top edge of the MediaBox in user space.
+        let page_height = page.origin.1 + page.height;
+
+        let placement = images::calculate_anchored_placement(
+            img.width,
+            img.height,
+            rect,
+            fit,
+            anchor,
+            page_height,
+        );
 
         self.ensure_image_obj_ids(idx);
         let pdf_name = self.image_obj_ids[&idx].pdf_name.clone();
 
-        let page = self
-            .current_page
-            .as_mut()
-            .expect("place_image called with no open page");
+        let page = self.current_page.as_mut().unwrap();
         page.used_images.insert(idx);
 
         // Build content stream operators
@@ -390,10 +2080,10 @@ impl<W: Write> PdfDocument<W> {
         if let Some(clip) = &placement.clip {
             ops.push_str(&format!(
                 "{} {} {} {} re W n\n",
-                format_coord(clip.x),
-                format_coord(clip.y),
-                format_coord(clip.width),
-                format_coord(clip.height),
+                format_coord(clip.x, self.coord_precision),
+                format_coord(clip.y, self.coord_precision),
+                format_coord(clip.width, self.coord_precision),
+                format_coord(clip.height, self.coord_precision),
             ));
         }
 
@@ -401,18 +2091,17 @@ impl<W: Write> PdfDocument<W> {
         // cm matrix: [width 0 0 height x y]
         ops.push_str(&format!(
             "{} 0 0 {} {} {} cm\n",
-            format_coord(placement.width),
-            format_coord(placement.height),
-            format_coord(placement.x),
-            format_coord(placement.y),
+            format_coord(placement.width, self.coord_precision),
+            format_coord(placement.height, self.coord_precision),
+            format_coord(placement.x, self.coord_precision),
+            format_coord(placement.y, self.coord_precision),
         ));
 
         // Paint the image
         ops.push_str(&format!("/{} Do\n", pdf_name));
         ops.push_str("Q\n");
 
-        page.content_ops.extend_from_slice(ops.as_bytes());
-        self
+        ops.into_bytes()
     }
 
     /// Pre-allocate ObjIds for an image if not yet done.
@@ -450,6 +2139,16 @@ impl<W: Write> PdfDocument<W> {
             return Ok(());
         }
 
+        // An external mask is an ordinary image XObject written alongside.
+        let external_mask = match self.image_masks.get(&idx) {
+            Some(&mask) => {
+                self.write_image_xobject(mask)?;
+                Some(self.image_obj_ids[&mask].xobject)
+            }
+            None => None,
+        };
+
+        let start_bytes = self.stats.image_bytes;
         let img = &self.images[idx];
         let obj_ids = &self.image_obj_ids[&idx];
         let xobject_id = obj_ids.xobject;
@@ -458,6 +2157,7 @@ impl<W: Write> PdfDocument<W> {
         // Write SMask XObject first if alpha data exists
         if let (Some(smask_obj_id), Some(smask_data)) = (smask_id, img.smask_data.as_ref()) {
             let smask_stream = self.make_stream(
+                StreamKind::Image,
                 vec![
                     ("Type", PdfObject::name("XObject")),
                     ("Subtype", PdfObject::name("Image")),
@@ -468,7 +2168,8 @@ impl<W: Write> PdfDocument<W> {
                 ],
                 smask_data.clone(),
             );
-            self.writer.write_object(smask_obj_id, &smask_stream)?;
+            self.stats.image_bytes +=
+                write_measured(&mut self.writer, smask_obj_id, &smask_stream)?;
         }
 
         // Build image XObject dict entries
@@ -484,10 +2185,25 @@ impl<W: Write> PdfDocument<W> {
             ),
         ];
 
-        if let Some(smask_obj_id) = smask_id {
+        if let Some(smask_obj_id) = smask_id.or(external_mask) {
             entries.push(("SMask", PdfObject::Reference(smask_obj_id)));
         }
 
+        if self.interpolated_images.contains(&idx) {
+            entries.push(("Interpolate", PdfObject::Boolean(true)));
+        }
+
+        if self.inverted_images.contains(&idx) {
+            let components = match img.color_space {
+                ColorSpace::DeviceGray => 1,
+                ColorSpace::DeviceRGB => 3,
+            };
+            let decode = (0..components)
+                .flat_map(|_| [PdfObject::Integer(1), PdfObject::Integer(0)])
+                .collect();
+            entries.push(("Decode", PdfObject::Array(decode)));
+        }
+
         // For JPEG: embed raw data with DCTDecode, never double-compress
         // For PNG (decoded pixels): use make_stream for optional FlateDecode
         let image_obj = match img.format {
@@ -495,163 +2211,769 @@ impl<W: Write> PdfDocument<W> {
                 entries.push(("Filter", PdfObject::name("DCTDecode")));
                 PdfObject::stream(entries, img.data.clone())
             }
-            ImageFormat::Png => self.make_stream(entries, img.data.clone()),
+            ImageFormat::Png => self.make_stream(StreamKind::Image, entries, img.data.clone()),
         };
 
-        self.writer.write_object(xobject_id, &image_obj)?;
+        self.stats.image_bytes += write_measured(&mut self.writer, xobject_id, &image_obj)?;
         self.written_images.insert(idx);
+        self.report_progress(ProgressEvent::ImageWritten {
+            image: ImageId(idx),
+            bytes: self.stats.image_bytes - start_bytes,
+        });
         Ok(())
     }
 
     // -------------------------------------------------------
-    // Graphics operations
+    // Form XObjects
     // -------------------------------------------------------
 
-    /// Set the stroke color (PDF `RG` operator).
-    pub fn set_stroke_color(&mut self, color: Color) -> &mut Self {
+    /// Record reusable vector art as a form XObject.
+    ///
+    /// `draw` receives a `FormBuilder` whose coordinates run from `(0, 0)`
+    /// to `(width, height)`. The form is written once, the first time a
+    /// page using it ends; every `place_form` afterwards is a single `Do`.
+    pub fn create_form_xobject(
+        &mut self,
+        width: f64,
+        height: f64,
+        draw: impl FnOnce(&mut FormBuilder),
+    ) -> FormId {
+        let mut builder = FormBuilder::new(self.coord_precision);
+        draw(&mut builder);
+        let idx = self.forms.len();
+        self.forms.push(FormXObject {
+            width,
+            height,
+            content: builder.ops,
+            pdf_name: format!("Fm{}", idx + 1),
+            obj_id: None,
+            origin: (0.0, 0.0),
+            group: None,
+            resources: FormResources::default(),
+        });
+        FormId(idx)
+    }
+
+    /// Start collecting page content into a transparency group.
+    ///
+    /// Everything drawn until the matching `end_transparency_group` is
+    /// moved into a form XObject with a `/Group << /S /Transparency >>`
+    /// dictionary, which is then painted in place with `Do`. `isolated`
+    /// and `knockout` become the group's `/I` and `/K` entries. Groups
+    /// nest.
+    pub fn begin_transparency_group(&mut self, isolated: bool, knockout: bool) -> &mut Self {
         let page = self
             .current_page
             .as_mut()
-            .expect("set_stroke_color called with no open page");
-        let ops = format!(
-            "{} {} {} RG\n",
-            format_coord(color.r),
-            format_coord(color.g),
-            format_coord(color.b),
-        );
-        page.content_ops.extend_from_slice(ops.as_bytes());
+            .expect("begin_transparency_group called with no open page");
+        let start = page.content_ops.len();
+        page.group_starts.push((start, isolated, knockout));
         self
     }
 
-    /// Set the fill color (PDF `rg` operator).
-    pub fn set_fill_color(&mut self, color: Color) -> &mut Self {
+    /// Close the innermost transparency group and paint it on the page.
+    ///
+    /// The group's bounding box is the page's MediaBox. Returns an
+    /// `InvalidInput` error if no group is open.
+    pub fn end_transparency_group(&mut self) -> io::Result<&mut Self> {
+        let idx = self.forms.len();
+        let page = self
+            .current_page
+            .as_mut()
+            .expect("end_transparency_group called with no open page");
+        let (start, isolated, knockout) = page.group_starts.pop().ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "end_transparency_group: no open group",
+            )
+        })?;
+        let content = page.content_ops.split_off(start);
+        let pdf_name = format!("Fm{}", idx + 1);
+        let resources = FormResources {
+            fonts: page.used_fonts.iter().copied().collect(),
+            truetype_fonts: page.used_truetype_fonts.iter().copied().collect(),
+            images: page.used_images.iter().copied().collect(),
+            forms: page.used_forms.iter().copied().collect(),
+        };
+        page.used_forms.insert(idx);
+        page.content_ops
+            .extend_from_slice(format!("/{} Do\n", pdf_name).as_bytes());
+        self.forms.push(FormXObject {
+            width: page.width,
+            height: page.height,
+            content,
+            pdf_name,
+            obj_id: None,
+            origin: page.origin,
+            group: Some((isolated, knockout)),
+            resources,
+        });
+        Ok(self)
+    }
+
+    /// Draw a form XObject on the current page with its lower-left corner
+    /// at `(x, y)`, scaled uniformly by `scale`.
+    pub fn place_form(&mut self, form: &FormId, x: f64, y: f64, scale: f64) -> &mut Self {
+        let pdf_name = self.forms[form.0].pdf_name.clone();
         let page = self
             .current_page
             .as_mut()
-            .expect("set_fill_color called with no open page");
+            .expect("place_form called with no open page");
+        page.used_forms.insert(form.0);
         let ops = format!(
-            "{} {} {} rg\n",
-            format_coord(color.r),
-            format_coord(color.g),
-            format_coord(color.b),
+            "q\n{} 0 0 {} {} {} cm\n/{} Do\nQ\n",
+            format_coord(scale, self.coord_precision),
+            format_coord(scale, self.coord_precision),
+            format_coord(x, self.coord_precision),
+            format_coord(y, self.coord_precision),
+            pdf_name,
         );
         page.content_ops.extend_from_slice(ops.as_bytes());
         self
     }
 
-    /// Set the line width (PDF `w` operator).
-    pub fn set_line_width(&mut self, width: f64) -> &mut Self {
+    /// Write a form XObject the first time a page uses it.
+    fn write_form_xobject(&mut self, idx: usize) -> io::Result<()> {
+        if self.forms[idx].obj_id.is_some() {
+            return Ok(());
+        }
+        let id = ObjId(self.next_obj_num, 0);
+        self.next_obj_num += 1;
+        let form = &mut self.forms[idx];
+        let (width, height) = (form.width, form.height);
+        let (llx, lly) = form.origin;
+        let group = form.group;
+        let content = std::mem::take(&mut form.content);
+        form.obj_id = Some(id);
+        self.record_standard_font_codes(&content);
+        let resources = match group {
+            Some(_) => {
+                let res = &self.forms[idx].resources;
+                self.build_resource_dict(&res.fonts, &res.truetype_fonts, &res.images, &res.forms)
+            }
+            None => PdfObject::Dictionary(vec![]),
+        };
+        let mut entries = vec![
+            ("Type", PdfObject::name("XObject")),
+            ("Subtype", PdfObject::name("Form")),
+            (
+                "BBox",
+                PdfObject::array(vec![
+                    box_number(llx),
+                    box_number(lly),
+                    PdfObject::Real(llx + width),
+                    PdfObject::Real(lly + height),
+                ]),
+            ),
+            ("Resources", resources),
+        ];
+        if let Some((isolated, knockout)) = group {
+            entries.push((
+                "Group",
+                PdfObject::dict(vec![
+                    ("S", PdfObject::name("Transparency")),
+                    ("I", PdfObject::Boolean(isolated)),
+                    ("K", PdfObject::Boolean(knockout)),
+                ]),
+            ));
+        }
+        let stream = self.make_stream(StreamKind::Content, entries, content);
+        self.stats.content_bytes += write_measured(&mut self.writer, id, &stream)?;
+        Ok(())
+    }
+
+    // -------------------------------------------------------
+    // Graphics operations
+    // -------------------------------------------------------
+
+    /// Operator writer for the open page's content stream.
+    fn page_ops(&mut self, caller: &str) -> OpsWriter<'_> {
         let page = self
             .current_page
             .as_mut()
-            .expect("set_line_width called with no open page");
-        let ops = format!("{} w\n", format_coord(width));
-        page.content_ops.extend_from_slice(ops.as_bytes());
+            .unwrap_or_else(|| panic!("{} called with no open page", caller));
+        OpsWriter {
+            ops: &mut page.content_ops,
+            precision: self.coord_precision,
+        }
+    }
+
+    /// Set the stroke color (PDF `RG` operator).
+    pub fn set_stroke_color(&mut self, color: Color) -> &mut Self {
+        self.page_ops("set_stroke_color").stroke_color(color);
+        self
+    }
+
+    /// Set the fill color (PDF `rg` operator).
+    pub fn set_fill_color(&mut self, color: Color) -> &mut Self {
+        self.page_ops("set_fill_color").fill_color(color);
+        self
+    }
+
+    /// Set the line width (PDF `w` operator).
+    pub fn set_line_width(&mut self, width: f64) -> &mut Self {
+        self.page_ops("set_line_width").line_width(width);
+        self
+    }
+
+    /// Set the line dash pattern (PDF `d` operator).
+    ///
+    /// `pattern` alternates dash and gap lengths in points; `phase` is the
+    /// distance into the pattern at which to start. Pass an empty pattern to
+    /// return to solid lines.
+    pub fn set_dash(&mut self, pattern: &[f64], phase: f64) -> &mut Self {
+        self.page_ops("set_dash").dash(pattern, phase);
         self
     }
 
     /// Move to a point without drawing (PDF `m` operator).
     pub fn move_to(&mut self, x: f64, y: f64) -> &mut Self {
-        let page = self
-            .current_page
-            .as_mut()
-            .expect("move_to called with no open page");
-        let ops = format!("{} {} m\n", format_coord(x), format_coord(y));
-        page.content_ops.extend_from_slice(ops.as_bytes());
+        self.page_ops("move_to").move_to(x, y);
         self
     }
 
     /// Draw a line from the current point (PDF `l` operator).
     pub fn line_to(&mut self, x: f64, y: f64) -> &mut Self {
-        let page = self
-            .current_page
-            .as_mut()
-            .expect("line_to called with no open page");
-        let ops = format!("{} {} l\n", format_coord(x), format_coord(y));
-        page.content_ops.extend_from_slice(ops.as_bytes());
+        self.page_ops("line_to").line_to(x, y);
+        self
+    }
+
+    /// Append a cubic Bézier curve from the current point to `(x3, y3)`
+    /// with control points `(x1, y1)` and `(x2, y2)` (PDF `c` operator).
+    pub fn curve_to(&mut self, x1: f64, y1: f64, x2: f64, y2: f64, x3: f64, y3: f64) -> &mut Self {
+        self.page_ops("curve_to").curve_to([x1, y1, x2, y2, x3, y3]);
         self
     }
 
     /// Append a rectangle to the path (PDF `re` operator).
     pub fn rect(&mut self, x: f64, y: f64, width: f64, height: f64) -> &mut Self {
-        let page = self
-            .current_page
-            .as_mut()
-            .expect("rect called with no open page");
-        let ops = format!(
-            "{} {} {} {} re\n",
-            format_coord(x),
-            format_coord(y),
-            format_coord(width),
-            format_coord(height),
-        );
-        page.content_ops.extend_from_slice(ops.as_bytes());
+        self.page_ops("rect").rect(x, y, width, height);
+        self
+    }
+
+    /// Append a closed rectangle with rounded corners to the path.
+    ///
+    /// `(x, y)` is the lower-left corner, as for `rect`. Each corner is a
+    /// quarter circle of `radius`, clamped to half the shorter side; a
+    /// radius of zero or less gives square corners. The path is not
+    /// painted.
+    pub fn rounded_rect(
+        &mut self,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        radius: f64,
+    ) -> &mut Self {
+        let r = radius.min(width / 2.0).min(height / 2.0).max(0.0);
+        let (left, right, bottom, top) = (x, x + width, y, y + height);
+        let half_pi = std::f64::consts::FRAC_PI_2;
+        // Counter-clockwise from the bottom edge: (corner centre, arc start).
+        let corners = [
+            (right - r, bottom + r, -half_pi),
+            (right - r, top - r, 0.0),
+            (left + r, top - r, half_pi),
+            (left + r, bottom + r, 2.0 * half_pi),
+        ];
+        self.move_to(left + r, bottom);
+        for (cx, cy, start) in corners {
+            self.line_to(cx + r * start.cos(), cy + r * start.sin());
+            if r > 0.0 {
+                for [(x1, y1), (x2, y2), (x3, y3)] in
+                    graphics::arc_curves(cx, cy, r, start, start + half_pi)
+                {
+                    self.curve_to(x1, y1, x2, y2, x3, y3);
+                }
+            }
+        }
+        self.close_path()
+    }
+
+    /// Append connected line segments through `points` to the path: a
+    /// `move_to` the first point, then a `line_to` each later one. With
+    /// `close`, the subpath is closed back to the first point (a polygon).
+    ///
+    /// The path is not painted; follow with `stroke`, `fill` or
+    /// `fill_stroke`. Does nothing when `points` is empty.
+    pub fn polyline(&mut self, points: &[(f64, f64)], close: bool) -> &mut Self {
+        let Some((&(x, y), rest)) = points.split_first() else {
+            return self;
+        };
+        self.move_to(x, y);
+        for &(x, y) in rest {
+            self.line_to(x, y);
+        }
+        if close {
+            self.close_path();
+        }
         self
     }
 
     /// Close the current subpath (PDF `h` operator).
     pub fn close_path(&mut self) -> &mut Self {
-        let page = self
-            .current_page
-            .as_mut()
-            .expect("close_path called with no open page");
-        page.content_ops.extend_from_slice(b"h\n");
+        self.page_ops("close_path").op("h");
         self
     }
 
     /// Stroke the current path (PDF `S` operator).
     pub fn stroke(&mut self) -> &mut Self {
-        let page = self
-            .current_page
-            .as_mut()
-            .expect("stroke called with no open page");
-        page.content_ops.extend_from_slice(b"S\n");
+        self.page_ops("stroke").op("S");
         self
     }
 
     /// Fill the current path (PDF `f` operator).
     pub fn fill(&mut self) -> &mut Self {
-        let page = self
-            .current_page
-            .as_mut()
-            .expect("fill called with no open page");
-        page.content_ops.extend_from_slice(b"f\n");
+        self.page_ops("fill").op("f");
         self
     }
 
     /// Fill and stroke the current path (PDF `B` operator).
     pub fn fill_stroke(&mut self) -> &mut Self {
-        let page = self
-            .current_page
-            .as_mut()
-            .expect("fill_stroke called with no open page");
-        page.content_ops.extend_from_slice(b"B\n");
+        self.page_ops("fill_stroke").op("B");
         self
     }
 
     /// Save the graphics state (PDF `q` operator).
     pub fn save_state(&mut self) -> &mut Self {
-        let page = self
-            .current_page
-            .as_mut()
-            .expect("save_state called with no open page");
-        page.content_ops.extend_from_slice(b"q\n");
+        self.page_ops("save_state").op("q");
         self
     }
 
     /// Restore the graphics state (PDF `Q` operator).
     pub fn restore_state(&mut self) -> &mut Self {
+        self.page_ops("restore_state").op("Q");
+        self
+    }
+
+    /// Run `draw` with everything it paints clipped to `rect`.
+    ///
+    /// Emits `q`, the clip path (`re W n`), whatever `draw` adds, then `Q`,
+    /// so the clip and any state `draw` changes end with the block. `draw`
+    /// must leave the page open. `rect` follows the `fit_textflow`
+    /// convention: `(x, y)` is the top-left corner in PDF coordinates.
+    pub fn with_clip(&mut self, rect: &Rect, draw: impl FnOnce(&mut Self)) -> &mut Self {
+        self.save_state()
+            .rect(rect.x, rect.y - rect.height, rect.width, rect.height)
+            .append_raw_content(b"W\nn\n");
+        draw(self);
+        self.restore_state()
+    }
+
+    /// Append raw bytes to the current page's content stream, unchanged.
+    ///
+    /// An escape hatch for operators the API does not cover yet. The caller
+    /// is responsible for valid syntax, balanced `q`/`Q` and `BT`/`ET`, and
+    /// for only naming resources the page already uses.
+    pub fn append_raw_content(&mut self, ops: &[u8]) -> &mut Self {
         let page = self
             .current_page
             .as_mut()
-            .expect("restore_state called with no open page");
-        page.content_ops.extend_from_slice(b"Q\n");
+            .expect("append_raw_content called with no open page");
+        page.content_ops.extend_from_slice(ops);
+        self
+    }
+
+    /// Stroke a frame inset by `margin` on all sides of the current page.
+    ///
+    /// Wrapped in `q`/`Q` so the frame's color and line width do not leak
+    /// into subsequent drawing.
+    pub fn draw_page_frame(&mut self, margin: f64, width: f64, color: Color) -> &mut Self {
+        let page = self
+            .current_page
+            .as_ref()
+            .expect("draw_page_frame called with no open page");
+        let (page_w, page_h) = (page.width, page.height);
+        let (x0, y0) = page.origin;
+        self.save_state()
+            .set_stroke_color(color)
+            .set_line_width(width)
+            .rect(
+                x0 + margin,
+                y0 + margin,
+                page_w - 2.0 * margin,
+                page_h - 2.0 * margin,
+            )
+            .stroke()
+            .restore_state()
+    }
+
+    /// Draw a rectangle filled with `fill` and/or outlined with `stroke`
+    /// (color and line width) in one call.
+    ///
+    /// Paints with `f`, `S` or `B` depending on which are given, inside
+    /// `q`/`Q` so the colors and width do not leak. Draws nothing when both
+    /// are `None`. `rect` follows the `fit_textflow` convention: `(x, y)` is
+    /// the top-left corner in PDF coordinates.
+    pub fn draw_box(
+        &mut self,
+        rect: &Rect,
+        fill: Option<Color>,
+        stroke: Option<(Color, f64)>,
+    ) -> &mut Self {
+        assert!(
+            self.current_page.is_some(),
+            "draw_box called with no open page"
+        );
+        if fill.is_none() && stroke.is_none() {
+            return self;
+        }
+        self.save_state();
+        if let Some(color) = fill {
+            self.set_fill_color(color);
+        }
+        if let Some((color, width)) = stroke {
+            self.set_stroke_color(color).set_line_width(width);
+        }
+        self.rect(rect.x, rect.y - rect.height, rect.width, rect.height);
+        match (fill, stroke) {
+            (Some(_), Some(_)) => self.fill_stroke(),
+            (Some(_), None) => self.fill(),
+            _ => self.stroke(),
+        };
+        self.restore_state()
+    }
+
+    /// Draw a status chip: a rounded rectangle filled with `fill` and
+    /// `text` centred in it.
+    ///
+    /// The text is centred horizontally by its measured width and
+    /// vertically by the font's cap height (builtin fonts) or ascent
+    /// (TrueType). Text wider than the chip is not shrunk or clipped. The
+    /// background is painted inside `q`/`Q`, so the text is drawn in the
+    /// page's current fill color. `rect` follows the `fit_textflow`
+    /// convention: `(x, y)` is the top-left corner in PDF coordinates.
+    pub fn draw_chip(
+        &mut self,
+        rect: &Rect,
+        radius: f64,
+        fill: Color,
+        text: &str,
+        style: &TextStyle,
+    ) -> &mut Self {
+        assert!(
+            self.current_page.is_some(),
+            "draw_chip called with no open page"
+        );
+        self.save_state()
+            .set_fill_color(fill)
+            .rounded_rect(
+                rect.x,
+                rect.y - rect.height,
+                rect.width,
+                rect.height,
+                radius,
+            )
+            .fill()
+            .restore_state();
+
+        let text_width = self.measure_text(text, style);
+        let ascent = crate::textflow::ascent_for(style, &self.truetype_fonts);
+        let x = rect.x + (rect.width - text_width) / 2.0;
+        let y = rect.y - (rect.height + ascent) / 2.0;
+        self.place_text_styled(text, x, y, style)
+    }
+
+    /// Stroke the eight standard crop marks around a trim box.
+    ///
+    /// Each corner gets a horizontal and a vertical hairline (0.25 pt,
+    /// black) of `length` points, starting `offset` points outside the
+    /// trim edge so the marks never print inside the trimmed page. Wrapped
+    /// in `q`/`Q`. `trim` follows the `fit_textflow` convention: `(x, y)` is
+    /// the top-left corner in PDF coordinates.
+    pub fn draw_crop_marks(&mut self, trim: &Rect, offset: f64, length: f64) -> &mut Self {
+        assert!(
+            self.current_page.is_some(),
+            "draw_crop_marks called with no open page"
+        );
+        let (left, right) = (trim.x, trim.x + trim.width);
+        let (top, bottom) = (trim.y, trim.y - trim.height);
+        let near = offset;
+        let far = offset + length;
+        // (corner x, corner y, outward x direction, outward y direction)
+        let corners = [
+            (left, top, -1.0, 1.0),
+            (right, top, 1.0, 1.0),
+            (left, bottom, -1.0, -1.0),
+            (right, bottom, 1.0, -1.0),
+        ];
+
+        self.save_state()
+            .set_stroke_color(Color::gray(0.0))
+            .set_line_width(0.25);
+        for (cx, cy, dx, dy) in corners {
+            self.move_to(cx + dx * near, cy)
+                .line_to(cx + dx * far, cy)
+                .stroke()
+                .move_to(cx, cy + dy * near)
+                .line_to(cx, cy + dy * far)
+                .stroke();
+        }
+        self.restore_state()
+    }
+
+    /// Stroke evenly spaced grid lines filling `rect`.
+    ///
+    /// Vertical lines run every `spacing_x` points from the left edge and
+    /// horizontal lines every `spacing_y` points down from the top edge;
+    /// both edges get a line when the spacing divides the rect evenly. A
+    /// spacing that is not positive and finite draws no lines in that
+    /// direction; one so fine that a direction would need more than
+    /// `MAX_GRID_LINES` lines is clamped to that many lines spread evenly
+    /// across the rect. All lines are stroked with one `S` inside `q`/`Q`,
+    /// and nothing is emitted when neither direction has lines. `rect`
+    /// follows the `fit_textflow` convention: `(x, y)` is the top-left corner.
+    pub fn draw_grid(
+        &mut self,
+        rect: &Rect,
+        spacing_x: f64,
+        spacing_y: f64,
+        color: Color,
+        width: f64,
+    ) -> &mut Self {
+        assert!(
+            self.current_page.is_some(),
+            "draw_grid called with no open page"
+        );
+        let (left, right) = (rect.x, rect.x + rect.width);
+        let (top, bottom) = (rect.y, rect.y - rect.height);
+        // Number of lines and the step between them for one direction.
+        let lines = |extent: f64, spacing: f64| {
+            if !(spacing > 0.0 && spacing.is_finite()) {
+                return (0, spacing);
+            }
+            // Tolerate rounding so a spacing that divides the rect reaches the far edge.
+            let count = ((extent + 1e-9) / spacing).floor() + 1.0;
+            if count > MAX_GRID_LINES as f64 {
+                (MAX_GRID_LINES, extent / (MAX_GRID_LINES - 1) as f64)
+            } else {
+                (count.max(0.0) as usize, spacing)
+            }
+        };
+        let (columns, step_x) = lines(rect.width, spacing_x);
+        let (rows, step_y) = lines(rect.height, spacing_y);
+        if columns == 0 && rows == 0 {
+            return self;
+        }
+
+        self.save_state()
+            .set_stroke_color(color)
+            .set_line_width(width);
+        for k in 0..columns {
+            let x = left + k as f64 * step_x;
+            self.move_to(x, bottom).line_to(x, top);
+        }
+        for k in 0..rows {
+            let y = top - k as f64 * step_y;
+            self.move_to(left, y).line_to(right, y);
+        }
+        self.stroke().restore_state()
+    }
+
+    /// Fill a pie chart centred on `(cx, cy)`.
+    ///
+    /// Each `(value, color)` slice gets a wedge whose angle is proportional
+    /// to its share of the total, starting at 12 o'clock and running
+    /// clockwise. Arcs are approximated with Bézier curves of at most 90°,
+    /// and every wedge is filled with its own `f`, all inside `q`/`Q`.
+    /// Slices with a non-positive value are skipped; nothing is drawn when
+    /// no slice is positive.
+    pub fn draw_pie(
+        &mut self,
+        cx: f64,
+        cy: f64,
+        radius: f64,
+        slices: &[(f64, Color)],
+    ) -> &mut Self {
+        assert!(
+            self.current_page.is_some(),
+            "draw_pie called with no open page"
+        );
+        let total: f64 = slices.iter().map(|&(v, _)| v).filter(|&v| v > 0.0).sum();
+        if total <= 0.0 {
+            return self;
+        }
+
+        self.save_state();
+        let mut angle = std::f64::consts::FRAC_PI_2;
+        for &(value, color) in slices.iter().filter(|&&(v, _)| v > 0.0) {
+            let end = angle - value / total * std::f64::consts::TAU;
+            self.set_fill_color(color)
+                .move_to(cx, cy)
+                .line_to(cx + radius * angle.cos(), cy + radius * angle.sin());
+            for [(x1, y1), (x2, y2), (x3, y3)] in graphics::arc_curves(cx, cy, radius, angle, end) {
+                self.curve_to(x1, y1, x2, y2, x3, y3);
+            }
+            self.close_path().fill();
+            angle = end;
+        }
+        self.restore_state()
+    }
+
+    /// Draw a horizontal bar chart filling `rect`.
+    ///
+    /// Each `(label, value, color)` entry gets an equal share of the height,
+    /// top to bottom: its label right-aligned in a column as wide as the
+    /// longest label, a bar filled in `color`, and the value just past the
+    /// bar's end. Bar lengths are scaled so `max` (default: the largest
+    /// value) spans the width left between the label column and the widest
+    /// value label; longer bars are capped there and negative values draw no
+    /// bar. Text is Helvetica sized to the row height (at most 10 pt) in the
+    /// page's current fill color; the bars are filled inside `q`/`Q`.
+    /// `rect` follows the `fit_textflow` convention: `(x, y)` is the top-left
+    /// corner in PDF coordinates.
+    pub fn draw_bar_chart(
+        &mut self,
+        rect: &Rect,
+        values: &[(String, f64, Color)],
+        max: Option<f64>,
+    ) -> &mut Self {
+        assert!(
+            self.current_page.is_some(),
+            "draw_bar_chart called with no open page"
+        );
+        if values.is_empty() {
+            return self;
+        }
+        // Space between a label, its bar and the value after it.
+        const GAP: f64 = 4.0;
+        let row_height = rect.height / values.len() as f64;
+        let bar_height = row_height * 0.7;
+        let style = TextStyle::builtin(BuiltinFont::Helvetica, (row_height * 0.6).min(10.0));
+        let value_labels: Vec<String> = values
+            .iter()
+            .map(|&(_, value, _)| format_coord(value, 2))
+            .collect();
+        let label_width = values
+            .iter()
+            .map(|(label, _, _)| self.measure_text(label, &style))
+            .fold(0.0, f64::max);
+        let value_width = value_labels
+            .iter()
+            .map(|text| self.measure_text(text, &style))
+            .fold(0.0, f64::max);
+        let bar_x = rect.x + label_width + GAP;
+        let bar_space = (rect.width - label_width - value_width - 2.0 * GAP).max(0.0);
+        let scale_max =
+            max.unwrap_or_else(|| values.iter().map(|&(_, v, _)| v).fold(0.0, f64::max));
+        let scale = if scale_max > 0.0 {
+            bar_space / scale_max
+        } else {
+            0.0
+        };
+        let bar_widths: Vec<f64> = values
+            .iter()
+            .map(|&(_, value, _)| (value.max(0.0) * scale).min(bar_space))
+            .collect();
+
+        self.save_state();
+        for (i, (&(_, _, color), &width)) in values.iter().zip(&bar_widths).enumerate() {
+            if width > 0.0 {
+                let row_top = rect.y - i as f64 * row_height;
+                self.set_fill_color(color)
+                    .rect(
+                        bar_x,
+                        row_top - (row_height + bar_height) / 2.0,
+                        width,
+                        bar_height,
+                    )
+                    .fill();
+            }
+        }
+        self.restore_state();
+
+        let ascent = crate::textflow::ascent_for(&style, &self.truetype_fonts);
+        for (i, ((label, _, _), value_label)) in values.iter().zip(&value_labels).enumerate() {
+            let baseline = rect.y - i as f64 * row_height - (row_height + ascent) / 2.0;
+            self.place_text_aligned(label, bar_x - GAP, baseline, &style, TextAlign::Right);
+            self.place_text_styled(value_label, bar_x + bar_widths[i] + GAP, baseline, &style);
+        }
         self
     }
 
+    /// Add an unsigned signature field to the current page.
+    ///
+    /// Writes a `/FT /Sig` widget annotation whose `/V` is a signature
+    /// dictionary placeholder: `/ByteRange` holds wide dummy values and
+    /// `/Contents` is a zeroed hex string of `SIGNATURE_CONTENTS_LEN` bytes.
+    /// An external signer can later patch both in place without shifting
+    /// byte offsets. The field is registered in the catalog's `/AcroForm`
+    /// with `/SigFlags 3`.
+    ///
+    /// `rect` follows the `fit_textflow` convention: `(x, y)` is the top-left
+    /// corner in PDF coordinates (measured from the page bottom).
+    pub fn add_signature_field(&mut self, name: &str, rect: &Rect) -> io::Result<&mut Self> {
+        assert!(
+            self.current_page.is_some(),
+            "add_signature_field called with no open page"
+        );
+
+        let sig_id = ObjId(self.next_obj_num, 0);
+        let field_id = ObjId(self.next_obj_num + 1, 0);
+        self.next_obj_num += 2;
+
+        let sig_dict = PdfObject::dict(vec![
+            ("Type", PdfObject::name("Sig")),
+            ("Filter", PdfObject::name("Adobe.PPKLite")),
+            ("SubFilter", PdfObject::name("adbe.pkcs7.detached")),
+            (
+                "ByteRange",
+                PdfObject::array(vec![
+                    PdfObject::Integer(0),
+                    PdfObject::Integer(BYTE_RANGE_PLACEHOLDER),
+                    PdfObject::Integer(BYTE_RANGE_PLACEHOLDER),
+                    PdfObject::Integer(BYTE_RANGE_PLACEHOLDER),
+                ]),
+            ),
+            (
+                "Contents",
+                PdfObject::HexString(vec![0; SIGNATURE_CONTENTS_LEN]),
+            ),
+        ]);
+        self.writer.write_object(sig_id, &sig_dict)?;
+
+        let field = PdfObject::dict(vec![
+            ("Type", PdfObject::name("Annot")),
+            ("Subtype", PdfObject::name("Widget")),
+            ("FT", PdfObject::name("Sig")),
+            ("T", PdfObject::text_string(name)),
+            (
+                "Rect",
+                PdfObject::array(vec![
+                    PdfObject::Real(rect.x),
+                    PdfObject::Real(rect.y - rect.height),
+                    PdfObject::Real(rect.x + rect.width),
+                    PdfObject::Real(rect.y),
+                ]),
+            ),
+            // Print | Locked
+            ("F", PdfObject::Integer(132)),
+            ("V", PdfObject::Reference(sig_id)),
+        ]);
+        self.writer.write_object(field_id, &field)?;
+
+        self.current_page.as_mut().unwrap().annot_ids.push(field_id);
+        self.form_field_ids.push(field_id);
+        Ok(self)
+    }
+
     /// Build a stream object, optionally compressing the data with FlateDecode.
-    fn make_stream(&self, mut dict_entries: Vec<(&str, PdfObject)>, data: Vec<u8>) -> PdfObject {
-        if self.compress {
+    fn make_stream(
+        &self,
+        kind: StreamKind,
+        mut dict_entries: Vec<(&str, PdfObject)>,
+        mut data: Vec<u8>,
+    ) -> PdfObject {
+        if self.compact_content && matches!(kind, StreamKind::Content | StreamKind::Plain) {
+            data = crate::writer::compact_content(&data);
+        }
+        let compress = self.compress
+            && match kind {
+                StreamKind::Content => self.compress_content,
+                StreamKind::Font => self.compress_fonts,
+                StreamKind::Image => self.compress_images,
+                StreamKind::Plain => false,
+            };
+        if compress {
             let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
             encoder.write_all(&data).expect("flate2 in-memory write");
             let compressed = encoder.finish().expect("flate2 finish");
@@ -667,18 +2989,135 @@ impl<W: Write> PdfDocument<W> {
         if let Some(&id) = self.font_obj_ids.get(&font) {
             return Ok(id);
         }
+        if self.embed_standard_fonts && self.standard_font_replacements.contains_key(&font) {
+            return self.write_embedded_standard_font(font);
+        }
         let id = ObjId(self.next_obj_num, 0);
         self.next_obj_num += 1;
-        let obj = PdfObject::dict(vec![
+        let mut entries = vec![
             ("Type", PdfObject::name("Font")),
             ("Subtype", PdfObject::name("Type1")),
             ("BaseFont", PdfObject::name(font.pdf_base_name())),
-        ]);
-        self.writer.write_object(id, &obj)?;
+        ];
+        // Symbol and ZapfDingbats have no width tables; leave them to the viewer.
+        if !matches!(font, BuiltinFont::Symbol | BuiltinFont::ZapfDingbats) {
+            entries.extend(builtin_width_entries(font));
+        }
+        let obj = PdfObject::dict(entries);
+        self.stats.font_bytes += write_measured(&mut self.writer, id, &obj)?;
+        self.font_obj_ids.insert(font, id);
+        Ok(id)
+    }
+
+    /// Write a builtin font as its embedded TrueType replacement: a simple
+    /// `/TrueType` font with WinAnsi encoding, so the page's literal-string
+    /// text operators work unchanged. `/Widths` come from the builtin metrics
+    /// the layout used.
+    ///
+    /// When subsetting, only the font's object ID is allocated here; its
+    /// objects are written by `write_subset_standard_fonts` once the text of
+    /// every page is known.
+    fn write_embedded_standard_font(&mut self, font: BuiltinFont) -> io::Result<ObjId> {
+        let id = ObjId(self.next_obj_num, 0);
+        self.next_obj_num += 1;
         self.font_obj_ids.insert(font, id);
+        if self.subset_standard_fonts {
+            self.pending_standard_fonts.push((font, id));
+            return Ok(id);
+        }
+        let replacement = &self.standard_font_replacements[&font];
+        let data = replacement.font_data.clone();
+        let name = replacement.postscript_name.clone();
+        self.write_standard_font_objects(font, id, data, &name)?;
         Ok(id)
     }
 
+    /// Write the replacements deferred by `write_embedded_standard_font`,
+    /// each subset to the glyphs its shown byte codes map to.
+    fn write_subset_standard_fonts(&mut self) -> io::Result<()> {
+        for (font, id) in std::mem::take(&mut self.pending_standard_fonts) {
+            let replacement = &self.standard_font_replacements[&font];
+            let glyphs: BTreeSet<u16> = self
+                .standard_font_codes
+                .get(&font)
+                .into_iter()
+                .flatten()
+                .filter_map(|&code| replacement.cmap.get(&(code as u32)).copied())
+                .collect();
+            let (data, name) = match truetype::subset_font_data(&replacement.font_data, &glyphs) {
+                Some(data) => (
+                    data,
+                    format!("{}+{}", subset_tag(&glyphs), replacement.postscript_name),
+                ),
+                None => (
+                    replacement.font_data.clone(),
+                    replacement.postscript_name.clone(),
+                ),
+            };
+            self.write_standard_font_objects(font, id, data, &name)?;
+        }
+        Ok(())
+    }
+
+    /// Write the font file, descriptor and font dictionary (`id`) of an
+    /// embedded standard-font replacement.
+    fn write_standard_font_objects(
+        &mut self,
+        font: BuiltinFont,
+        id: ObjId,
+        data: Vec<u8>,
+        name: &str,
+    ) -> io::Result<()> {
+        let descriptor_id = ObjId(self.next_obj_num, 0);
+        let file_id = ObjId(self.next_obj_num + 1, 0);
+        self.next_obj_num += 2;
+
+        let font_file = self.make_stream(
+            StreamKind::Font,
+            vec![("Length1", PdfObject::Integer(data.len() as i64))],
+            data,
+        );
+        let replacement = &self.standard_font_replacements[&font];
+        let descriptor = truetype_descriptor(replacement, name, file_id);
+        let mut entries = vec![
+            ("Type", PdfObject::name("Font")),
+            ("Subtype", PdfObject::name("TrueType")),
+            ("BaseFont", PdfObject::name(name)),
+        ];
+        entries.extend(builtin_width_entries(font));
+        entries.extend([
+            ("Encoding", PdfObject::name("WinAnsiEncoding")),
+            ("FontDescriptor", PdfObject::Reference(descriptor_id)),
+        ]);
+        let obj = PdfObject::dict(entries);
+
+        self.stats.font_bytes += write_measured(&mut self.writer, file_id, &font_file)?;
+        self.stats.font_bytes += write_measured(&mut self.writer, descriptor_id, &descriptor)?;
+        self.stats.font_bytes += write_measured(&mut self.writer, id, &obj)?;
+        Ok(())
+    }
+
+    /// Collect the byte codes shown with replaced builtin fonts in `ops`,
+    /// when subset embedding is on.
+    fn record_standard_font_codes(&mut self, ops: &[u8]) {
+        if !(self.embed_standard_fonts && self.subset_standard_fonts) {
+            return;
+        }
+        for (name, codes) in validate::shown_string_bytes(ops) {
+            let replaced = self
+                .standard_font_replacements
+                .keys()
+                .find(|font| font.pdf_name() == name)
+                .copied();
+            if let Some(font) = replaced {
+                self.standard_font_codes
+                    .entry(font)
+                    .or_default()
+                    .extend(codes);
+            }
+        }
+    }
+
     /// Pre-allocate ObjIds for a TrueType font if not yet done.
     fn ensure_tt_font_obj_ids(&mut self, idx: usize) -> &TrueTypeFontObjIds {
         if !self.truetype_font_obj_ids.contains_key(&idx) {
@@ -706,14 +3145,68 @@ impl<W: Write> PdfDocument<W> {
         &self.truetype_font_obj_ids[&idx]
     }
 
+    /// Problems that stop `page` from being written, checked while it is
+    /// still open so a failed `end_page` loses nothing.
+    fn check_page(&self, page: &PageBuilder) -> io::Result<()> {
+        // Background ops are plain image draws spliced in before the body,
+        // so they cannot unbalance the text objects checked here.
+        validate::check_text_objects(&page.content_ops)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("end_page: {}", e)))?;
+        if !page.group_starts.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "end_page: transparency group not closed",
+            ));
+        }
+        if self.strict {
+            let page_num = page.overlay_for.unwrap_or(self.page_records.len()) + 1;
+            let warnings = self.content_warnings(page_num, page);
+            if !warnings.is_empty() {
+                return Err(validation_error("end_page", &warnings));
+            }
+        }
+        Ok(())
+    }
+
     /// End the current page. Writes the content stream to the writer
     /// and frees page content from memory. The page dictionary is
     /// deferred until `end_document()` so overlay streams can be added.
+    ///
+    /// Returns an `InvalidData` error if the page's `BT`/`ET` text objects
+    /// are unbalanced or nested (or, in strict mode, if validation finds a
+    /// problem) and `InvalidInput` if a transparency group is still open.
+    /// Nothing is written in that case and the page stays open, so the
+    /// caller can repair it and call `end_page` again.
+    ///
+    /// If `begin_page` discarded an earlier page because auto-closing it
+    /// failed, that error is returned first and the open page is left as is.
+    ///
+    /// Calling this with no open page (e.g. a second `end_page` in a row)
+    /// is a no-op, which keeps error-recovery paths simple.
     pub fn end_page(&mut self) -> io::Result<()> {
-        let page = self
-            .current_page
-            .take()
-            .expect("end_page called with no open page");
+        if let Some(e) = self.auto_close_error.take() {
+            return Err(e);
+        }
+        let Some(page) = self.current_page.as_ref() else {
+            return Ok(());
+        };
+        self.check_page(page)?;
+        let mut page = self.current_page.take().expect("checked above");
+        if page.rotate != 0 || page.flipped {
+            // Close the `q` opened by rotation_ops.
+            page.content_ops.extend_from_slice(b"Q\n");
+        }
+        if !page.background_ops.is_empty() {
+            let background = std::mem::take(&mut page.background_ops);
+            page.content_ops
+                .splice(page.body_start..page.body_start, background);
+        }
+        self.record_standard_font_codes(&page.content_ops);
+        let page_num = page.overlay_for.unwrap_or(self.page_records.len()) + 1;
+
+        // Where this page's objects begin, past the header.
+        self.writer.write_header()?;
+        let start_offset = self.writer.current_offset();
 
         // Write builtin font objects for any not yet written
         for &font in &page.used_fonts {
@@ -731,15 +3224,36 @@ impl<W: Write> PdfDocument<W> {
             self.write_image_xobject(*idx)?;
         }
 
-        let content_id = ObjId(self.next_obj_num, 0);
-        self.next_obj_num += 1;
+        for &idx in &page.used_forms {
+            self.write_form_xobject(idx)?;
+        }
 
-        // Write content stream immediately (keeps memory usage low)
-        let content_stream = self.make_stream(vec![], page.content_ops);
-        self.writer.write_object(content_id, &content_stream)?;
+        let thumb_id = match page.thumbnail {
+            Some(idx) => {
+                self.ensure_image_obj_ids(idx);
+                self.write_image_xobject(idx)?;
+                Some(self.image_obj_ids[&idx].xobject)
+            }
+            None => None,
+        };
 
         match page.overlay_for {
             None => {
+                let content_id = ObjId(self.next_obj_num, 0);
+                self.next_obj_num += 1;
+
+                // Write content stream immediately (keeps memory usage low),
+                // unless it must stay open for merged overlays.
+                let pending_content = if self.merge_overlays {
+                    Some(page.content_ops)
+                } else {
+                    let kind = self.page_content_kind(self.page_records.len() + 1);
+                    let content_stream = self.make_stream(kind, vec![], page.content_ops);
+                    self.stats.content_bytes +=
+                        write_measured(&mut self.writer, content_id, &content_stream)?;
+                    None
+                };
+
                 // New page: pre-allocate the page dict ObjId and store the record.
                 // The page dictionary itself is written in write_page_dicts().
                 let page_id = ObjId(self.next_obj_num, 0);
@@ -753,21 +3267,67 @@ impl<W: Write> PdfDocument<W> {
                     used_fonts: page.used_fonts,
                     used_truetype_fonts: page.used_truetype_fonts,
                     used_images: page.used_images,
+                    used_forms: page.used_forms,
+                    annot_ids: page.annot_ids,
+                    pending_content,
+                    rotate: page.rotate,
+                    rotated: page.rotated,
+                    flipped: page.flipped,
+                    thumb_id,
+                    origin: page.origin,
+                    duration: page.duration,
+                    transition: page.transition,
+                    byte_offset: (self.writer.current_offset() > start_offset)
+                        .then_some(start_offset),
                 });
             }
             Some(idx) => {
-                // Overlay: append content stream to existing page record.
+                if let Some(buffered) = self.page_records[idx].pending_content.as_mut() {
+                    // Merged overlay: extend the buffered main stream.
+                    buffered.extend_from_slice(&page.content_ops);
+                } else {
+                    // Overlay: append a separate content stream.
+                    let content_id = ObjId(self.next_obj_num, 0);
+                    self.next_obj_num += 1;
+                    let kind = self.page_content_kind(idx + 1);
+                    let content_stream = self.make_stream(kind, vec![], page.content_ops);
+                    self.stats.content_bytes +=
+                        write_measured(&mut self.writer, content_id, &content_stream)?;
+                    self.page_records[idx].content_ids.push(content_id);
+                }
                 let record = &mut self.page_records[idx];
-                record.content_ids.push(content_id);
                 record.used_fonts.extend(page.used_fonts);
                 record.used_truetype_fonts.extend(page.used_truetype_fonts);
                 record.used_images.extend(page.used_images);
+                record.used_forms.extend(page.used_forms);
+                record.annot_ids.extend(page.annot_ids);
+                if thumb_id.is_some() {
+                    record.thumb_id = thumb_id;
+                }
+                if page.duration.is_some() {
+                    record.duration = page.duration;
+                }
+                if page.transition.is_some() {
+                    record.transition = page.transition;
+                }
             }
         }
 
+        self.report_progress(ProgressEvent::PageEnded { page: page_num });
         Ok(())
     }
 
+    /// Flush buffered output to the underlying writer.
+    ///
+    /// Completed pages are already written by `end_page()`; calling this
+    /// afterwards pushes them through any buffering (e.g. a `BufWriter`
+    /// around a socket) so a client can start receiving the document before
+    /// `end_document()`. Page dictionaries, fonts and the xref table still
+    /// follow at the end.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
     /// Build the font resource dictionary for a page.
     fn build_font_dict(&self, used_fonts: &[BuiltinFont], used_truetype: &[usize]) -> PdfObject {
         let mut entries: Vec<(String, PdfObject)> = used_fonts
@@ -795,10 +3355,11 @@ impl<W: Write> PdfDocument<W> {
         used_fonts: &[BuiltinFont],
         used_truetype: &[usize],
         used_images: &[usize],
+        used_forms: &[usize],
     ) -> PdfObject {
         let font_dict = self.build_font_dict(used_fonts, used_truetype);
 
-        let xobject_entries: Vec<(String, PdfObject)> = used_images
+        let mut xobject_entries: Vec<(String, PdfObject)> = used_images
             .iter()
             .filter_map(|idx| {
                 self.image_obj_ids
@@ -806,6 +3367,11 @@ impl<W: Write> PdfDocument<W> {
                     .map(|ids| (ids.pdf_name.clone(), PdfObject::Reference(ids.xobject)))
             })
             .collect();
+        xobject_entries.extend(used_forms.iter().filter_map(|&idx| {
+            let form = &self.forms[idx];
+            form.obj_id
+                .map(|id| (form.pdf_name.clone(), PdfObject::Reference(id)))
+        }));
 
         let mut resource_entries: Vec<(String, PdfObject)> = vec![("Font".to_string(), font_dict)];
         if !xobject_entries.is_empty() {
@@ -838,10 +3404,19 @@ impl<W: Write> PdfDocument<W> {
         for i in 0..self.page_records.len() {
             // Copy out page data to release the borrow before writing
             let obj_id = self.page_records[i].obj_id;
-            let content_ids: Vec<ObjId> =
-                self.page_records[i].content_ids.iter().copied().collect();
-            let width = self.page_records[i].width;
-            let height = self.page_records[i].height;
+            let content_ids: Vec<ObjId> = self.page_records[i].content_ids.to_vec();
+            let rotate = self.page_records[i].rotate;
+            let rotated = self.page_records[i].rotated;
+            let thumb_id = self.page_records[i].thumb_id;
+            let duration = self.page_records[i].duration;
+            let transition = self.page_records[i].transition;
+            let (llx, lly) = self.page_records[i].origin;
+            // Records hold the displayed size; the MediaBox is unrotated.
+            let (width, height) = if rotate % 180 == 0 {
+                (self.page_records[i].width, self.page_records[i].height)
+            } else {
+                (self.page_records[i].height, self.page_records[i].width)
+            };
             let used_fonts: Vec<BuiltinFont> =
                 self.page_records[i].used_fonts.iter().copied().collect();
             let used_truetype: Vec<usize> = self.page_records[i]
@@ -851,25 +3426,56 @@ impl<W: Write> PdfDocument<W> {
                 .collect();
             let used_images: Vec<usize> =
                 self.page_records[i].used_images.iter().copied().collect();
+            let used_forms: Vec<usize> = self.page_records[i].used_forms.iter().copied().collect();
+            let annot_ids = self.page_records[i].annot_ids.clone();
+
+            if let Some(data) = self.page_records[i].pending_content.take() {
+                let kind = self.page_content_kind(i + 1);
+                let content_stream = self.make_stream(kind, vec![], data);
+                self.stats.content_bytes +=
+                    write_measured(&mut self.writer, content_ids[0], &content_stream)?;
+            }
 
-            let resources = self.build_resource_dict(&used_fonts, &used_truetype, &used_images);
+            let resources =
+                self.build_resource_dict(&used_fonts, &used_truetype, &used_images, &used_forms);
             let contents = Self::build_contents(&content_ids);
 
-            let page_dict = PdfObject::dict(vec![
+            let mut entries = vec![
                 ("Type", PdfObject::name("Page")),
                 ("Parent", PdfObject::Reference(PAGES_OBJ)),
                 (
                     "MediaBox",
                     PdfObject::array(vec![
-                        PdfObject::Integer(0),
-                        PdfObject::Integer(0),
-                        PdfObject::Real(width),
-                        PdfObject::Real(height),
+                        box_number(llx),
+                        box_number(lly),
+                        PdfObject::Real(llx + width),
+                        PdfObject::Real(lly + height),
                     ]),
                 ),
                 ("Contents", contents),
                 ("Resources", resources),
-            ]);
+            ];
+            // Other pages inherit the document rotation from the Pages node.
+            if rotated && rotate != self.document_rotate {
+                entries.push(("Rotate", PdfObject::Integer(rotate as i64)));
+            }
+            if let Some(thumb) = thumb_id {
+                entries.push(("Thumb", PdfObject::Reference(thumb)));
+            }
+            if let Some(seconds) = duration {
+                entries.push(("Dur", box_number(seconds)));
+            }
+            if let Some(transition) = transition {
+                entries.push((
+                    "Trans",
+                    PdfObject::dict(vec![("S", PdfObject::name(transition.pdf_name()))]),
+                ));
+            }
+            if !annot_ids.is_empty() {
+                let annots = annot_ids.into_iter().map(PdfObject::Reference).collect();
+                entries.push(("Annots", PdfObject::Array(annots)));
+            }
+            let page_dict = PdfObject::dict(entries);
             self.writer.write_object(obj_id, &page_dict)?;
         }
         Ok(())
@@ -892,43 +3498,20 @@ impl<W: Write> PdfDocument<W> {
             // 1. FontFile2 stream (raw .ttf data)
             let original_len = font.font_data.len() as i64;
             let font_file_stream = self.make_stream(
+                StreamKind::Font,
                 vec![("Length1", PdfObject::Integer(original_len))],
                 font.font_data.clone(),
             );
-            self.writer.write_object(obj_ids_file, &font_file_stream)?;
+            self.stats.font_bytes +=
+                write_measured(&mut self.writer, obj_ids_file, &font_file_stream)?;
 
             // 2. FontDescriptor (values scaled to PDF units: 1/1000)
-            let descriptor = PdfObject::dict(vec![
-                ("Type", PdfObject::name("FontDescriptor")),
-                ("FontName", PdfObject::name(&font.postscript_name)),
-                ("Flags", PdfObject::Integer(font.flags as i64)),
-                (
-                    "FontBBox",
-                    PdfObject::array(vec![
-                        PdfObject::Integer(font.scale_to_pdf(font.bbox[0])),
-                        PdfObject::Integer(font.scale_to_pdf(font.bbox[1])),
-                        PdfObject::Integer(font.scale_to_pdf(font.bbox[2])),
-                        PdfObject::Integer(font.scale_to_pdf(font.bbox[3])),
-                    ]),
-                ),
-                ("ItalicAngle", PdfObject::Real(font.italic_angle)),
-                ("Ascent", PdfObject::Integer(font.scale_to_pdf(font.ascent))),
-                (
-                    "Descent",
-                    PdfObject::Integer(font.scale_to_pdf(font.descent)),
-                ),
-                (
-                    "CapHeight",
-                    PdfObject::Integer(font.scale_to_pdf(font.cap_height)),
-                ),
-                ("StemV", PdfObject::Integer(font.scale_to_pdf(font.stem_v))),
-                ("FontFile2", PdfObject::Reference(obj_ids_file)),
-            ]);
-            self.writer.write_object(obj_ids_desc, &descriptor)?;
+            let descriptor = truetype_descriptor(font, &font.postscript_name, obj_ids_file);
+            self.stats.font_bytes += write_measured(&mut self.writer, obj_ids_desc, &descriptor)?;
 
             // 3. CIDFontType2
             let w_array = font.build_w_array();
-            let cid_font = PdfObject::dict(vec![
+            let mut cid_entries = vec![
                 ("Type", PdfObject::name("Font")),
                 ("Subtype", PdfObject::name("CIDFontType2")),
                 ("BaseFont", PdfObject::name(&font.postscript_name)),
@@ -941,15 +3524,20 @@ impl<W: Write> PdfDocument<W> {
                     ]),
                 ),
                 ("FontDescriptor", PdfObject::Reference(obj_ids_desc)),
-                ("DW", PdfObject::Integer(font.default_width_pdf())),
-                ("W", PdfObject::Array(w_array)),
-            ]);
-            self.writer.write_object(obj_ids_cid, &cid_font)?;
+                ("DW", PdfObject::Integer(font.dw_pdf())),
+            ];
+            // A monospace font needs no per-glyph widths beyond /DW.
+            if !w_array.is_empty() {
+                cid_entries.push(("W", PdfObject::Array(w_array)));
+            }
+            let cid_font = PdfObject::dict(cid_entries);
+            self.stats.font_bytes += write_measured(&mut self.writer, obj_ids_cid, &cid_font)?;
 
             // 4. ToUnicode CMap stream
             let tounicode_data = font.build_tounicode_cmap();
-            let tounicode = self.make_stream(vec![], tounicode_data);
-            self.writer.write_object(obj_ids_tounicode, &tounicode)?;
+            let tounicode = self.make_stream(StreamKind::Font, vec![], tounicode_data);
+            self.stats.font_bytes +=
+                write_measured(&mut self.writer, obj_ids_tounicode, &tounicode)?;
 
             // 5. Type0 font (top-level)
             let type0 = PdfObject::dict(vec![
@@ -963,7 +3551,7 @@ impl<W: Write> PdfDocument<W> {
                 ),
                 ("ToUnicode", PdfObject::Reference(obj_ids_tounicode)),
             ]);
-            self.writer.write_object(obj_ids_type0, &type0)?;
+            self.stats.font_bytes += write_measured(&mut self.writer, obj_ids_type0, &type0)?;
         }
 
         Ok(())
@@ -973,6 +3561,16 @@ impl<W: Write> PdfDocument<W> {
     /// info dictionary, xref table, and trailer.
     /// Consumes self -- no further operations are possible.
     pub fn end_document(mut self) -> io::Result<W> {
+        if let Some(e) = self.auto_close_error.take() {
+            return Err(e);
+        }
+        if self.strict {
+            let warnings = self.validate();
+            if !warnings.is_empty() {
+                return Err(validation_error("end_document", &warnings));
+            }
+        }
+
         // Auto-close any open page
         if self.current_page.is_some() {
             self.end_page()?;
@@ -982,8 +3580,24 @@ impl<W: Write> PdfDocument<W> {
         self.write_page_dicts()?;
 
         // Write TrueType font objects (deferred until now)
+        self.write_subset_standard_fonts()?;
         self.write_truetype_fonts()?;
 
+        if self.auto_dates && !self.deterministic {
+            let now = pdf_date_now();
+            for key in ["CreationDate", "ModDate"] {
+                if !self.info.iter().any(|(k, _)| k == key) {
+                    self.info.push((key.to_string(), now.clone()));
+                }
+            }
+        }
+
+        if let Some(producer) = self.producer.take() {
+            if !self.info.iter().any(|(k, _)| k == "Producer") {
+                self.info.push(("Producer".to_string(), producer));
+            }
+        }
+
         // Write info dictionary if any entries exist
         let info_id = if !self.info.is_empty() {
             let id = ObjId(self.next_obj_num, 0);
@@ -991,7 +3605,7 @@ impl<W: Write> PdfDocument<W> {
             let entries: Vec<(&str, PdfObject)> = self
                 .info
                 .iter()
-                .map(|(k, v)| (k.as_str(), PdfObject::literal_string(v)))
+                .map(|(k, v)| (k.as_str(), PdfObject::text_string(v)))
                 .collect();
             let info_obj = PdfObject::dict(entries);
             self.writer.write_object(id, &info_obj)?;
@@ -1007,35 +3621,230 @@ impl<W: Write> PdfDocument<W> {
             .map(|r| PdfObject::Reference(r.obj_id))
             .collect();
         let page_count = self.page_records.len() as i64;
-        let pages = PdfObject::dict(vec![
+        let mut pages_entries = vec![
             ("Type", PdfObject::name("Pages")),
             ("Kids", PdfObject::Array(kids)),
             ("Count", PdfObject::Integer(page_count)),
-        ]);
+        ];
+        if self.document_rotate != 0 {
+            pages_entries.push(("Rotate", PdfObject::Integer(self.document_rotate as i64)));
+        }
+        let pages = PdfObject::dict(pages_entries);
         self.writer.write_object(PAGES_OBJ, &pages)?;
 
         // Write catalog (obj 1)
-        let catalog = PdfObject::dict(vec![
+        let mut catalog_entries = vec![
             ("Type", PdfObject::name("Catalog")),
             ("Pages", PdfObject::Reference(PAGES_OBJ)),
-        ]);
+        ];
+        if !self.form_field_ids.is_empty() {
+            let fields = self
+                .form_field_ids
+                .iter()
+                .map(|id| PdfObject::Reference(*id))
+                .collect();
+            catalog_entries.push((
+                "AcroForm",
+                PdfObject::dict(vec![
+                    ("Fields", PdfObject::Array(fields)),
+                    // SignaturesExist | AppendOnly
+                    ("SigFlags", PdfObject::Integer(3)),
+                ]),
+            ));
+        }
+        if self.print_on_open {
+            catalog_entries.push((
+                "OpenAction",
+                PdfObject::dict(vec![
+                    ("S", PdfObject::name("Named")),
+                    ("N", PdfObject::name("Print")),
+                ]),
+            ));
+        }
+        let catalog = PdfObject::dict(catalog_entries);
         self.writer.write_object(CATALOG_OBJ, &catalog)?;
 
         // Write xref and trailer
+        if self.deterministic {
+            let id = self.writer.digest().to_vec();
+            self.writer.set_file_id(id);
+        }
         self.writer.write_xref_and_trailer(CATALOG_OBJ, info_id)?;
 
         Ok(self.writer.into_inner())
     }
 }
 
-/// Format a coordinate value for PDF content streams.
-pub(crate) fn format_coord(v: f64) -> String {
+/// New number of page `page_num` after `move_page(from, to)`.
+fn moved_page_num(page_num: usize, from: usize, to: usize) -> usize {
+    if page_num == from {
+        to
+    } else if from < page_num && page_num <= to {
+        page_num - 1
+    } else if to <= page_num && page_num < from {
+        page_num + 1
+    } else {
+        page_num
+    }
+}
+
+/// Decimal places `format_coord` keeps unless `set_coordinate_precision`
+/// changes them.
+pub(crate) const DEFAULT_COORD_PRECISION: usize = 4;
+
+/// Format a coordinate value for PDF content streams, keeping at most
+/// `precision` decimal places.
+pub(crate) fn format_coord(v: f64, precision: usize) -> String {
     if v == v.floor() && v.abs() < 1e15 {
         format!("{}", v as i64)
+    } else if precision == 0 {
+        format!("{:.0}", v)
     } else {
-        let s = format!("{:.4}", v);
+        let s = format!("{:.*}", precision, v);
         let s = s.trim_end_matches('0');
         let s = s.trim_end_matches('.');
         s.to_string()
     }
 }
+
+/// `/FirstChar`, `/LastChar` and `/Widths` for the printable ASCII range
+/// covered by a builtin font's metrics table.
+fn builtin_width_entries(font: BuiltinFont) -> [(&'static str, PdfObject); 3] {
+    let widths = (32..=126u8)
+        .map(|code| PdfObject::Integer(FontMetrics::char_width(font, code as char) as i64))
+        .collect();
+    [
+        ("FirstChar", PdfObject::Integer(32)),
+        ("LastChar", PdfObject::Integer(126)),
+        ("Widths", PdfObject::Array(widths)),
+    ]
+}
+
+/// FontDescriptor for an embedded TrueType font, with metrics scaled to
+/// PDF units (1/1000 em).
+fn truetype_descriptor(font: &TrueTypeFont, font_name: &str, font_file: ObjId) -> PdfObject {
+    PdfObject::dict(vec![
+        ("Type", PdfObject::name("FontDescriptor")),
+        ("FontName", PdfObject::name(font_name)),
+        ("Flags", PdfObject::Integer(font.flags as i64)),
+        (
+            "FontBBox",
+            PdfObject::array(vec![
+                PdfObject::Integer(font.scale_to_pdf(font.bbox[0])),
+                PdfObject::Integer(font.scale_to_pdf(font.bbox[1])),
+                PdfObject::Integer(font.scale_to_pdf(font.bbox[2])),
+                PdfObject::Integer(font.scale_to_pdf(font.bbox[3])),
+            ]),
+        ),
+        ("ItalicAngle", PdfObject::Real(font.italic_angle)),
+        ("Ascent", PdfObject::Integer(font.scale_to_pdf(font.ascent))),
+        (
+            "Descent",
+            PdfObject::Integer(font.scale_to_pdf(font.descent)),
+        ),
+        (
+            "CapHeight",
+            PdfObject::Integer(font.scale_to_pdf(font.cap_height)),
+        ),
+        ("StemV", PdfObject::Integer(font.scale_to_pdf(font.stem_v))),
+        ("FontFile2", PdfObject::Reference(font_file)),
+    ])
+}
+
+/// Six-letter subset tag (ISO 32000-1, 9.6.4) derived from the glyph set,
+/// so identical subsets get identical names.
+fn subset_tag(glyphs: &BTreeSet<u16>) -> String {
+    let mut hash: u32 = 0x811c_9dc5;
+    for byte in glyphs.iter().flat_map(|gid| gid.to_be_bytes()) {
+        hash = (hash ^ byte as u32).wrapping_mul(0x0100_0193);
+    }
+    (0..6)
+        .map(|_| {
+            let letter = (b'A' + (hash % 26) as u8) as char;
+            hash /= 26;
+            letter
+        })
+        .collect()
+}
+
+/// Combine validation warnings into a single `InvalidData` error.
+fn validation_error(context: &str, warnings: &[ValidationWarning]) -> io::Error {
+    let messages: Vec<String> = warnings.iter().map(|w| w.to_string()).collect();
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("{}: {}", context, messages.join("; ")),
+    )
+}
+
+/// A MediaBox corner or other dictionary number: an integer when whole
+/// (the common `0`), real otherwise.
+fn box_number(v: f64) -> PdfObject {
+    if v.fract() == 0.0 && v.abs() < 1e15 {
+        PdfObject::Integer(v as i64)
+    } else {
+        PdfObject::Real(v)
+    }
+}
+
+/// Content prefix mapping the displayed (rotated) coordinate system of a
+/// `width` x `height` page onto its unrotated MediaBox. Opens a `q` that
+/// `end_page` closes. Empty for unrotated pages.
+fn rotation_ops(rotate: i32, width: f64, height: f64, precision: usize) -> Vec<u8> {
+    let matrix = match rotate {
+        90 => format!("0 1 -1 0 {} 0", format_coord(height, precision)),
+        180 => format!(
+            "-1 0 0 -1 {} {}",
+            format_coord(width, precision),
+            format_coord(height, precision)
+        ),
+        270 => format!("0 -1 1 0 0 {}", format_coord(width, precision)),
+        _ => return Vec::new(),
+    };
+    format!("q\n{} cm\n", matrix).into_bytes()
+}
+
+/// The current UTC time as a PDF date string.
+fn pdf_date_now() -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    pdf_date(secs)
+}
+
+/// Format seconds since the Unix epoch as `D:YYYYMMDDHHmmSS+00'00'`.
+fn pdf_date(unix_secs: u64) -> String {
+    let days = (unix_secs / 86_400) as i64;
+    let secs_of_day = unix_secs % 86_400;
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm).
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "D:{:04}{:02}{:02}{:02}{:02}{:02}+00'00'",
+        year,
+        month,
+        day,
+        secs_of_day / 3_600,
+        secs_of_day / 60 % 60,
+        secs_of_day % 60,
+    )
+}
+
+/// Write an object and return the number of bytes it took.
+fn write_measured<W: Write>(
+    writer: &mut PdfWriter<W>,
+    id: ObjId,
+    obj: &PdfObject,
+) -> io::Result<usize> {
+    writer.write_header()?;
+    let start = writer.current_offset();
+    writer.write_object(id, obj)?;
+    Ok(writer.current_offset() - start)
+}
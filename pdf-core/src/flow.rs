@@ -0,0 +1,226 @@
+use std::io::{self, Write};
+
+use crate::document::PdfDocument;
+use crate::images::{ImageFit, ImageId};
+use crate::tables::{Row, Table, TableCursor};
+use crate::textflow::{FitResult, Rect, TextFlow, TextStyle};
+
+/// Flows mixed content — headings, paragraphs, tables, images — down the
+/// page, turning pages automatically.
+///
+/// `FlowContext` borrows the document, owns the content rect, and keeps a
+/// single y-cursor shared by every block. Each method places its content at
+/// the cursor, advances it, and calls `end_page`/`begin_page` whenever the
+/// remaining space runs out. Pages are opened lazily, so a trailing page
+/// break never produces an empty page. Call `finish()` to close the last page.
+///
+/// `rect` follows the `fit_textflow` convention: `(x, y)` is the top-left
+/// corner in PDF coordinates (measured from the page bottom).
+///
+/// # Example
+/// ```no_run
+/// # use pdf_core::{BuiltinFont, FlowContext, PdfDocument, Rect, TextFlow, TextStyle};
+/// let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+/// let rect = Rect { x: 72.0, y: 720.0, width: 468.0, height: 648.0 };
+/// let mut flow = FlowContext::new(&mut doc, 612.0, 792.0, &rect);
+///
+/// flow.heading("Summary", &TextStyle::builtin(BuiltinFont::HelveticaBold, 16.0)).unwrap();
+/// flow.space(6.0).unwrap();
+/// let mut body = TextFlow::new();
+/// body.add_text("Quarterly figures follow.", &TextStyle::default());
+/// flow.paragraph(&mut body).unwrap();
+/// flow.finish().unwrap();
+/// ```
+pub struct FlowContext<'a, W: Write> {
+    doc: &'a mut PdfDocument<W>,
+    page_width: f64,
+    page_height: f64,
+    rect: Rect,
+    /// Top of the next block (PDF absolute coordinates, from page bottom).
+    current_y: f64,
+    page_open: bool,
+}
+
+impl<'a, W: Write> FlowContext<'a, W> {
+    /// Create a flow that lays content into `rect` on pages of the given size.
+    ///
+    /// No page is started until the first block is placed.
+    pub fn new(
+        doc: &'a mut PdfDocument<W>,
+        page_width: f64,
+        page_height: f64,
+        rect: &Rect,
+    ) -> Self {
+        FlowContext {
+            doc,
+            page_width,
+            page_height,
+            rect: *rect,
+            current_y: rect.y,
+            page_open: false,
+        }
+    }
+
+    /// Y coordinate where the next block will start.
+    pub fn current_y(&self) -> f64 {
+        self.current_y
+    }
+
+    /// Vertical space left on the current page, in points.
+    pub fn remaining_height(&self) -> f64 {
+        (self.current_y - self.bottom()).max(0.0)
+    }
+
+    /// Place a single line of text, moving to a new page if it does not fit.
+    pub fn heading(&mut self, text: &str, style: &TextStyle) -> io::Result<()> {
+        let line_height = self.doc.line_height_for(style);
+        self.ensure_space(line_height)?;
+        // Baseline sits one font size below the top, matching fit_textflow.
+        let baseline = self.current_y - style.font_size;
+        self.doc
+            .place_text_styled(text, self.rect.x, baseline, style);
+        self.current_y -= line_height;
+        Ok(())
+    }
+
+    /// Flow a paragraph, continuing it across as many pages as needed.
+    ///
+    /// Returns an `InvalidInput` error if the text cannot be placed even at
+    /// the top of an empty page (e.g. a line taller than the content rect).
+    pub fn paragraph(&mut self, flow: &mut TextFlow) -> io::Result<()> {
+        self.ensure_page()?;
+        loop {
+            let rect = self.remaining_rect();
+            match self.doc.fit_textflow(flow, &rect)? {
+                FitResult::Stop => {
+                    self.current_y -= flow.last_fit_height();
+                    return Ok(());
+                }
+                FitResult::BoxFull => self.turn_page()?,
+                FitResult::BoxEmpty if self.at_page_top() => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "paragraph: text does not fit in an empty content rect",
+                    ));
+                }
+                FitResult::BoxEmpty => self.turn_page()?,
+            }
+        }
+    }
+
+    /// Place table rows, turning pages as they fill.
+    ///
+    /// A row taller than the whole content rect is skipped, matching the
+    /// `BoxEmpty` handling recommended for `fit_row`.
+    pub fn table_rows(&mut self, table: &Table, rows: &[Row]) -> io::Result<()> {
+        self.ensure_page()?;
+        let mut cursor = TableCursor::new(&self.remaining_rect());
+        for row in rows {
+            loop {
+                match self.doc.fit_row(table, row, &mut cursor)? {
+                    FitResult::Stop => {
+                        self.current_y = cursor.current_y();
+                        break;
+                    }
+                    FitResult::BoxEmpty if self.at_page_top() => break,
+                    FitResult::BoxFull | FitResult::BoxEmpty => {
+                        self.turn_page()?;
+                        cursor.reset(&self.remaining_rect());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Place an image spanning the content width, its height following the
+    /// image's aspect ratio (capped at the content rect height).
+    pub fn image(&mut self, image: &ImageId, fit: ImageFit) -> io::Result<()> {
+        let (img_w, img_h) = self.doc.image_dimensions(image);
+        let width = self.rect.width;
+        let height = if img_w == 0 {
+            0.0
+        } else {
+            (width * img_h as f64 / img_w as f64).min(self.rect.height)
+        };
+        self.ensure_space(height)?;
+        // place_image expects a top-left-origin rect.
+        let target = Rect {
+            x: self.rect.x,
+            y: self.page_height - self.current_y,
+            width,
+            height,
+        };
+        self.doc.place_image(image, &target, fit);
+        self.current_y -= height;
+        Ok(())
+    }
+
+    /// Advance the cursor by `pts`. Space that would cross the bottom of the
+    /// page ends the page instead; it is not carried over to the next one.
+    pub fn space(&mut self, pts: f64) -> io::Result<()> {
+        if self.current_y - pts < self.bottom() {
+            return self.page_break();
+        }
+        self.current_y -= pts;
+        Ok(())
+    }
+
+    /// Force the next block onto a new page.
+    pub fn page_break(&mut self) -> io::Result<()> {
+        if self.page_open {
+            self.doc.end_page()?;
+            self.page_open = false;
+        }
+        self.current_y = self.rect.y;
+        Ok(())
+    }
+
+    /// Close the last page, if one is open.
+    pub fn finish(self) -> io::Result<()> {
+        if self.page_open {
+            self.doc.end_page()?;
+        }
+        Ok(())
+    }
+
+    fn bottom(&self) -> f64 {
+        self.rect.y - self.rect.height
+    }
+
+    fn at_page_top(&self) -> bool {
+        self.current_y >= self.rect.y
+    }
+
+    /// The part of the content rect below the cursor.
+    fn remaining_rect(&self) -> Rect {
+        Rect {
+            x: self.rect.x,
+            y: self.current_y,
+            width: self.rect.width,
+            height: self.remaining_height(),
+        }
+    }
+
+    fn ensure_page(&mut self) -> io::Result<()> {
+        if !self.page_open {
+            self.doc.begin_page(self.page_width, self.page_height);
+            self.page_open = true;
+        }
+        Ok(())
+    }
+
+    fn turn_page(&mut self) -> io::Result<()> {
+        self.page_break()?;
+        self.ensure_page()
+    }
+
+    /// Make sure `height` points are available, turning the page if not.
+    /// A block taller than the whole rect is placed at the top of a page.
+    fn ensure_space(&mut self, height: f64) -> io::Result<()> {
+        if !self.at_page_top() && self.current_y - height < self.bottom() {
+            self.page_break()?;
+        }
+        self.ensure_page()
+    }
+}
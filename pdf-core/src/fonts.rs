@@ -730,8 +730,9 @@ impl FontMetrics {
             _ => {}
         }
 
-        let code = ch as u32;
-        if code < 32 || code > 126 {
+        // A no-break space has the width of a regular space.
+        let code = if ch == '\u{a0}' { ' ' } else { ch } as u32;
+        if !(32..=126).contains(&code) {
             return DEFAULT_WIDTH;
         }
         let index = (code - 32) as usize;
@@ -758,6 +759,28 @@ impl FontMetrics {
         total as f64 * font_size / 1000.0
     }
 
+    /// Height of capital letters above the baseline in points, from the
+    /// AFM `CapHeight`. Symbol and ZapfDingbats have none and return
+    /// `font_size`.
+    pub fn cap_height(font: BuiltinFont, font_size: f64) -> f64 {
+        let units = match font {
+            BuiltinFont::Helvetica
+            | BuiltinFont::HelveticaBold
+            | BuiltinFont::HelveticaOblique
+            | BuiltinFont::HelveticaBoldOblique => 718,
+            BuiltinFont::TimesRoman => 662,
+            BuiltinFont::TimesBold => 676,
+            BuiltinFont::TimesItalic => 653,
+            BuiltinFont::TimesBoldItalic => 669,
+            BuiltinFont::Courier
+            | BuiltinFont::CourierBold
+            | BuiltinFont::CourierOblique
+            | BuiltinFont::CourierBoldOblique => 562,
+            BuiltinFont::Symbol | BuiltinFont::ZapfDingbats => 1000,
+        };
+        units as f64 * font_size / 1000.0
+    }
+
     /// Returns the line height for a given font size
     /// (1.2x multiplier).
     pub fn line_height(_font: BuiltinFont, font_size: f64) -> f64 {
@@ -0,0 +1,53 @@
+//! Number and currency formatting for table cells and labels.
+
+/// Format `value` rounded to `decimals` places, grouping the integer part in
+/// threes with `thousands_sep` and separating the fraction with
+/// `decimal_sep`.
+///
+/// `format_number(1234.56, 2, ",", ".")` gives `"1,234.56"` and
+/// `format_number(1234.56, 2, ".", ",")` gives `"1.234,56"`. Pass an empty
+/// `thousands_sep` to disable grouping. Negative values get a leading `-`,
+/// except when they round to zero.
+pub fn format_number(
+    value: f64,
+    decimals: usize,
+    thousands_sep: &str,
+    decimal_sep: &str,
+) -> String {
+    let rounded = format!("{:.*}", decimals, value.abs());
+    let (int_part, frac_part) = match rounded.split_once('.') {
+        Some((int_part, frac_part)) => (int_part, Some(frac_part)),
+        None => (rounded.as_str(), None),
+    };
+
+    let mut out =
+        String::with_capacity(rounded.len() + int_part.len() / 3 * thousands_sep.len() + 1);
+    let is_zero = rounded.bytes().all(|b| b == b'0' || b == b'.');
+    if value.is_sign_negative() && !is_zero {
+        out.push('-');
+    }
+    for (i, digit) in int_part.chars().enumerate() {
+        if i > 0 && (int_part.len() - i) % 3 == 0 {
+            out.push_str(thousands_sep);
+        }
+        out.push(digit);
+    }
+    if let Some(frac_part) = frac_part {
+        out.push_str(decimal_sep);
+        out.push_str(frac_part);
+    }
+    out
+}
+
+/// Format `value` as an amount prefixed with `symbol`, using `,` for
+/// thousands and `.` for decimals: `format_currency(9600.0, "$", 2)` gives
+/// `"$9,600.00"`. The minus sign of a negative amount precedes the symbol
+/// (`"-$5.00"`). For other separators, combine `format_number` with the
+/// symbol yourself.
+pub fn format_currency(value: f64, symbol: &str, decimals: usize) -> String {
+    let number = format_number(value, decimals, ",", ".");
+    match number.strip_prefix('-') {
+        Some(digits) => format!("-{}{}", symbol, digits),
+        None => format!("{}{}", symbol, number),
+    }
+}
@@ -0,0 +1,141 @@
+use crate::fonts::BuiltinFont;
+use crate::graphics::{Color, OpsWriter};
+use crate::objects::ObjId;
+
+/// Opaque handle to a reusable form XObject within a PdfDocument.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FormId(pub usize);
+
+/// Records vector drawing operators for a form XObject.
+///
+/// Passed to the closure given to `PdfDocument::create_form_xobject`.
+/// Coordinates are in the form's own space: `(0, 0)` is its lower-left
+/// corner and its bounding box is `width` x `height` points. The methods
+/// mirror `PdfDocument`'s graphics operations and chain the same way.
+pub struct FormBuilder {
+    pub(crate) ops: Vec<u8>,
+    precision: usize,
+}
+
+impl FormBuilder {
+    pub(crate) fn new(precision: usize) -> Self {
+        FormBuilder {
+            ops: Vec::new(),
+            precision,
+        }
+    }
+
+    fn writer(&mut self) -> OpsWriter<'_> {
+        OpsWriter {
+            ops: &mut self.ops,
+            precision: self.precision,
+        }
+    }
+
+    /// Set the stroke color (PDF `RG` operator).
+    pub fn set_stroke_color(&mut self, color: Color) -> &mut Self {
+        self.writer().stroke_color(color);
+        self
+    }
+
+    /// Set the fill color (PDF `rg` operator).
+    pub fn set_fill_color(&mut self, color: Color) -> &mut Self {
+        self.writer().fill_color(color);
+        self
+    }
+
+    /// Set the line width (PDF `w` operator).
+    pub fn set_line_width(&mut self, width: f64) -> &mut Self {
+        self.writer().line_width(width);
+        self
+    }
+
+    /// Set the line dash pattern (PDF `d` operator).
+    pub fn set_dash(&mut self, pattern: &[f64], phase: f64) -> &mut Self {
+        self.writer().dash(pattern, phase);
+        self
+    }
+
+    /// Move to a point (PDF `m` operator).
+    pub fn move_to(&mut self, x: f64, y: f64) -> &mut Self {
+        self.writer().move_to(x, y);
+        self
+    }
+
+    /// Line to a point (PDF `l` operator).
+    pub fn line_to(&mut self, x: f64, y: f64) -> &mut Self {
+        self.writer().line_to(x, y);
+        self
+    }
+
+    /// Append a rectangle (PDF `re` operator).
+    pub fn rect(&mut self, x: f64, y: f64, width: f64, height: f64) -> &mut Self {
+        self.writer().rect(x, y, width, height);
+        self
+    }
+
+    /// Close the current subpath (PDF `h` operator).
+    pub fn close_path(&mut self) -> &mut Self {
+        self.writer().op("h");
+        self
+    }
+
+    /// Stroke the current path (PDF `S` operator).
+    pub fn stroke(&mut self) -> &mut Self {
+        self.writer().op("S");
+        self
+    }
+
+    /// Fill the current path (PDF `f` operator).
+    pub fn fill(&mut self) -> &mut Self {
+        self.writer().op("f");
+        self
+    }
+
+    /// Fill and stroke the current path (PDF `B` operator).
+    pub fn fill_stroke(&mut self) -> &mut Self {
+        self.writer().op("B");
+        self
+    }
+
+    /// Save the graphics state (PDF `q` operator).
+    pub fn save_state(&mut self) -> &mut Self {
+        self.writer().op("q");
+        self
+    }
+
+    /// Restore the graphics state (PDF `Q` operator).
+    pub fn restore_state(&mut self) -> &mut Self {
+        self.writer().op("Q");
+        self
+    }
+}
+
+/// A recorded form XObject, written the first time a page uses it.
+pub(crate) struct FormXObject {
+    pub(crate) width: f64,
+    pub(crate) height: f64,
+    /// Content stream; emptied once the XObject has been written.
+    pub(crate) content: Vec<u8>,
+    /// Resource name, e.g. `Fm1`.
+    pub(crate) pdf_name: String,
+    /// Object ID, allocated when the form is first written.
+    pub(crate) obj_id: Option<ObjId>,
+    /// Lower-left corner of the `/BBox`; non-zero only for transparency
+    /// groups on pages with an offset MediaBox.
+    pub(crate) origin: (f64, f64),
+    /// `(isolated, knockout)` when the form is a transparency group.
+    pub(crate) group: Option<(bool, bool)>,
+    /// Resources the content may reference. Empty for `FormBuilder` forms.
+    pub(crate) resources: FormResources,
+}
+
+/// Resources referenced by a transparency group's content, captured from
+/// the page when the group ends.
+#[derive(Default)]
+pub(crate) struct FormResources {
+    pub(crate) fonts: Vec<BuiltinFont>,
+    pub(crate) truetype_fonts: Vec<usize>,
+    pub(crate) images: Vec<usize>,
+    pub(crate) forms: Vec<usize>,
+}
@@ -1,3 +1,5 @@
+use crate::document::format_coord;
+
 /// RGB color for PDF graphics operations.
 ///
 /// Each component is in the range 0.0 (none) to 1.0 (full intensity).
@@ -23,3 +25,156 @@ impl Color {
         }
     }
 }
+
+/// Shape at the ends of open stroked paths (PDF `J` operator).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineCap {
+    Butt,
+    Round,
+    Square,
+}
+
+impl LineCap {
+    /// The operand of the `J` operator.
+    pub fn pdf_value(self) -> u8 {
+        match self {
+            LineCap::Butt => 0,
+            LineCap::Round => 1,
+            LineCap::Square => 2,
+        }
+    }
+}
+
+/// Shape at the corners of stroked paths (PDF `j` operator).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineJoin {
+    Miter,
+    Round,
+    Bevel,
+}
+
+impl LineJoin {
+    /// The operand of the `j` operator.
+    pub fn pdf_value(self) -> u8 {
+        match self {
+            LineJoin::Miter => 0,
+            LineJoin::Round => 1,
+            LineJoin::Bevel => 2,
+        }
+    }
+}
+
+/// Graphics state written at the top of every new page's content stream.
+///
+/// PDF graphics state does not carry across pages; each `None` field is
+/// left at the viewer's default (1pt black solid lines, butt caps, miter
+/// joins). `dash` is a `(pattern, phase)` pair as for `set_dash`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GraphicsDefaults {
+    pub fill_color: Option<Color>,
+    pub stroke_color: Option<Color>,
+    pub line_width: Option<f64>,
+    pub line_cap: Option<LineCap>,
+    pub line_join: Option<LineJoin>,
+    pub dash: Option<(Vec<f64>, f64)>,
+}
+
+/// Format a dash pattern as a PDF `d` operator line.
+/// An empty `pattern` produces the solid-line pattern `[] 0 d`.
+pub(crate) fn dash_op(pattern: &[f64], phase: f64, precision: usize) -> String {
+    let parts: Vec<String> = pattern
+        .iter()
+        .map(|&v| format_coord(v, precision))
+        .collect();
+    format!(
+        "[{}] {} d\n",
+        parts.join(" "),
+        format_coord(phase, precision)
+    )
+}
+
+/// Writes graphics operators into a content buffer. Page drawing and
+/// `FormBuilder` both go through it, so the two emit identical operators.
+pub(crate) struct OpsWriter<'a> {
+    pub(crate) ops: &'a mut Vec<u8>,
+    pub(crate) precision: usize,
+}
+
+impl OpsWriter<'_> {
+    fn coords(&mut self, values: &[f64], op: &str) {
+        for &v in values {
+            self.ops
+                .extend_from_slice(format_coord(v, self.precision).as_bytes());
+            self.ops.push(b' ');
+        }
+        self.ops.extend_from_slice(op.as_bytes());
+        self.ops.push(b'\n');
+    }
+
+    /// Append an operator that takes no operands, e.g. `S` or `q`.
+    pub(crate) fn op(&mut self, op: &str) {
+        self.coords(&[], op);
+    }
+
+    pub(crate) fn stroke_color(&mut self, color: Color) {
+        self.coords(&[color.r, color.g, color.b], "RG");
+    }
+
+    pub(crate) fn fill_color(&mut self, color: Color) {
+        self.coords(&[color.r, color.g, color.b], "rg");
+    }
+
+    pub(crate) fn line_width(&mut self, width: f64) {
+        self.coords(&[width], "w");
+    }
+
+    pub(crate) fn dash(&mut self, pattern: &[f64], phase: f64) {
+        self.ops
+            .extend_from_slice(dash_op(pattern, phase, self.precision).as_bytes());
+    }
+
+    pub(crate) fn move_to(&mut self, x: f64, y: f64) {
+        self.coords(&[x, y], "m");
+    }
+
+    pub(crate) fn line_to(&mut self, x: f64, y: f64) {
+        self.coords(&[x, y], "l");
+    }
+
+    pub(crate) fn curve_to(&mut self, points: [f64; 6]) {
+        self.coords(&points, "c");
+    }
+
+    pub(crate) fn rect(&mut self, x: f64, y: f64, width: f64, height: f64) {
+        self.coords(&[x, y, width, height], "re");
+    }
+}
+
+/// Approximate the circular arc from angle `start` to `end` (radians,
+/// counter-clockwise positive) with cubic Bézier segments of at most 90°.
+///
+/// Returns the `(control1, control2, end)` points of each segment; the arc
+/// begins at `(cx + r·cos start, cy + r·sin start)`. A negative sweep runs
+/// clockwise.
+pub(crate) fn arc_curves(cx: f64, cy: f64, r: f64, start: f64, end: f64) -> Vec<[(f64, f64); 3]> {
+    let sweep = end - start;
+    let count = (sweep.abs() / std::f64::consts::FRAC_PI_2 - 1e-9)
+        .ceil()
+        .max(1.0) as usize;
+    let step = sweep / count as f64;
+    // Control-point distance along the tangent for a `step`-radian arc.
+    let k = 4.0 / 3.0 * (step / 4.0).tan();
+    (0..count)
+        .map(|i| {
+            let a0 = start + step * i as f64;
+            let a1 = a0 + step;
+            let (s0, c0) = a0.sin_cos();
+            let (s1, c1) = a1.sin_cos();
+            [
+                (cx + r * (c0 - k * s0), cy + r * (s0 + k * c0)),
+                (cx + r * (c1 + k * s1), cy + r * (s1 - k * c1)),
+                (cx + r * c1, cy + r * s1),
+            ]
+        })
+        .collect()
+}
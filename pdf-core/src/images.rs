@@ -24,6 +24,54 @@ pub enum ImageFit {
     None,
 }
 
+impl ImageFit {
+    /// Anchor used by `place_image`: `None` keeps the image at the rect's
+    /// top-left corner, every other mode centers it.
+    pub(crate) fn default_anchor(self) -> ImageAnchor {
+        match self {
+            ImageFit::None => ImageAnchor::TopLeft,
+            _ => ImageAnchor::Center,
+        }
+    }
+}
+
+/// Where an image that does not exactly match its rect sits within it.
+///
+/// For `Fit` and `None` this positions the image in the leftover space;
+/// for `Fill` it picks which part of the image survives the crop.
+/// `Stretch` always covers the rect, so the anchor has no effect.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageAnchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    #[default]
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl ImageAnchor {
+    /// Fractions of the leftover width and height placed before the image:
+    /// `(0, 0)` is flush left and top, `(1, 1)` flush right and bottom.
+    fn factors(self) -> (f64, f64) {
+        match self {
+            ImageAnchor::TopLeft => (0.0, 0.0),
+            ImageAnchor::Top => (0.5, 0.0),
+            ImageAnchor::TopRight => (1.0, 0.0),
+            ImageAnchor::Left => (0.0, 0.5),
+            ImageAnchor::Center => (0.5, 0.5),
+            ImageAnchor::Right => (1.0, 0.5),
+            ImageAnchor::BottomLeft => (0.0, 1.0),
+            ImageAnchor::Bottom => (0.5, 1.0),
+            ImageAnchor::BottomRight => (1.0, 1.0),
+        }
+    }
+}
+
 /// PDF color space for image data.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ColorSpace {
@@ -100,6 +148,29 @@ pub fn load_image(data: Vec<u8>) -> Result<ImageData, String> {
     }
 }
 
+/// Re-encode decoded pixel data as JPEG at the given quality (1-100).
+///
+/// JPEG input is returned unchanged: it is already DCT-compressed and
+/// decoding it again would only lose quality. Any alpha channel is kept as
+/// a separate (Flate-compressed) soft mask.
+pub fn convert_to_jpeg(image: ImageData, quality: u8) -> Result<ImageData, String> {
+    if image.format == ImageFormat::Jpeg {
+        return Ok(image);
+    }
+    let components = match image.color_space {
+        ColorSpace::DeviceRGB => 3,
+        ColorSpace::DeviceGray => 1,
+    };
+    let data =
+        crate::jpeg_encoder::encode(&image.data, image.width, image.height, components, quality)?;
+    Ok(ImageData {
+        format: ImageFormat::Jpeg,
+        bits_per_component: 8,
+        data,
+        ..image
+    })
+}
+
 /// Parse JPEG SOF marker to extract dimensions and color space.
 /// JPEG data is embedded as-is (DCTDecode); no pixel decoding needed.
 fn parse_jpeg(data: Vec<u8>) -> Result<ImageData, String> {
@@ -254,6 +325,19 @@ pub fn calculate_placement(
     rect: &Rect,
     fit: ImageFit,
     page_height: f64,
+) -> ImagePlacement {
+    calculate_anchored_placement(img_w, img_h, rect, fit, fit.default_anchor(), page_height)
+}
+
+/// Like `calculate_placement`, positioning the image within `rect`
+/// according to `anchor` instead of the fit mode's default.
+pub fn calculate_anchored_placement(
+    img_w: u32,
+    img_h: u32,
+    rect: &Rect,
+    fit: ImageFit,
+    anchor: ImageAnchor,
+    page_height: f64,
 ) -> ImagePlacement {
     let iw = img_w as f64;
     let ih = img_h as f64;
@@ -263,6 +347,17 @@ pub fn calculate_placement(
     // In PDF coords, the bottom edge is: page_height - (rect.y + rect.height)
     let pdf_bottom = page_height - (rect.y + rect.height);
 
+    // Leftover space (negative when the image overflows, as with Fill) is
+    // split by the anchor; the space below the image is what remains after
+    // the share above it.
+    let (fx, fy) = anchor.factors();
+    let anchored = |w: f64, h: f64| {
+        (
+            rect.x + (rect.width - w) * fx,
+            pdf_bottom + (rect.height - h) * (1.0 - fy),
+        )
+    };
+
     match fit {
         ImageFit::Fit => {
             let scale_x = rect.width / iw;
@@ -270,9 +365,7 @@ pub fn calculate_placement(
             let scale = scale_x.min(scale_y);
             let w = iw * scale;
             let h = ih * scale;
-            // Center within the rect
-            let x = rect.x + (rect.width - w) / 2.0;
-            let y = pdf_bottom + (rect.height - h) / 2.0;
+            let (x, y) = anchored(w, h);
             ImagePlacement {
                 x,
                 y,
@@ -287,9 +380,8 @@ pub fn calculate_placement(
             let scale = scale_x.max(scale_y);
             let w = iw * scale;
             let h = ih * scale;
-            // Center the image (some parts will be clipped)
-            let x = rect.x + (rect.width - w) / 2.0;
-            let y = pdf_bottom + (rect.height - h) / 2.0;
+            // Some parts will be clipped; the anchor picks which.
+            let (x, y) = anchored(w, h);
             ImagePlacement {
                 x,
                 y,
@@ -311,10 +403,10 @@ pub fn calculate_placement(
             clip: None,
         },
         ImageFit::None => {
-            // 1 pixel = 1 point, positioned at top-left of rect
-            let y = pdf_bottom + (rect.height - ih);
+            // 1 pixel = 1 point
+            let (x, y) = anchored(iw, ih);
             ImagePlacement {
-                x: rect.x,
+                x,
                 y,
                 width: iw,
                 height: ih,
@@ -0,0 +1,402 @@
+//! Minimal baseline JPEG encoder.
+//!
+//! Used to re-encode decoded pixel data (e.g. a large photographic PNG) as
+//! DCT-compressed JPEG so it can be embedded with `/DCTDecode`. Produces
+//! sequential baseline JPEG with the standard Annex K Huffman tables and
+//! IJG-style quality scaling. Color images are converted to YCbCr without
+//! chroma subsampling.
+
+/// Standard luminance quantization table (ITU T.81 Annex K.1), natural order.
+const LUMA_QUANT: [u16; 64] = [
+    16, 11, 10, 16, 24, 40, 51, 61, 12, 12, 14, 19, 26, 58, 60, 55, 14, 13, 16, 24, 40, 57, 69, 56,
+    14, 17, 22, 29, 51, 87, 80, 62, 18, 22, 37, 56, 68, 109, 103, 77, 24, 35, 55, 64, 81, 104, 113,
+    92, 49, 64, 78, 87, 103, 121, 120, 101, 72, 92, 95, 98, 112, 100, 103, 99,
+];
+
+/// Standard chrominance quantization table (ITU T.81 Annex K.1), natural order.
+const CHROMA_QUANT: [u16; 64] = [
+    17, 18, 24, 47, 99, 99, 99, 99, 18, 21, 26, 66, 99, 99, 99, 99, 24, 26, 56, 99, 99, 99, 99, 99,
+    47, 66, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99,
+    99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99, 99,
+];
+
+/// Natural-order index for each position in the zigzag scan.
+const ZIGZAG: [usize; 64] = [
+    0, 1, 8, 16, 9, 2, 3, 10, 17, 24, 32, 25, 18, 11, 4, 5, 12, 19, 26, 33, 40, 48, 41, 34, 27, 20,
+    13, 6, 7, 14, 21, 28, 35, 42, 49, 56, 57, 50, 43, 36, 29, 22, 15, 23, 30, 37, 44, 51, 58, 59,
+    52, 45, 38, 31, 39, 46, 53, 60, 61, 54, 47, 55, 62, 63,
+];
+
+const DC_LUMA_BITS: [u8; 16] = [0, 1, 5, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0, 0, 0];
+const DC_CHROMA_BITS: [u8; 16] = [0, 3, 1, 1, 1, 1, 1, 1, 1, 1, 1, 0, 0, 0, 0, 0];
+const DC_VALUES: [u8; 12] = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11];
+
+const AC_LUMA_BITS: [u8; 16] = [0, 2, 1, 3, 3, 2, 4, 3, 5, 5, 4, 4, 0, 0, 1, 0x7d];
+const AC_LUMA_VALUES: [u8; 162] = [
+    0x01, 0x02, 0x03, 0x00, 0x04, 0x11, 0x05, 0x12, 0x21, 0x31, 0x41, 0x06, 0x13, 0x51, 0x61, 0x07,
+    0x22, 0x71, 0x14, 0x32, 0x81, 0x91, 0xa1, 0x08, 0x23, 0x42, 0xb1, 0xc1, 0x15, 0x52, 0xd1, 0xf0,
+    0x24, 0x33, 0x62, 0x72, 0x82, 0x09, 0x0a, 0x16, 0x17, 0x18, 0x19, 0x1a, 0x25, 0x26, 0x27, 0x28,
+    0x29, 0x2a, 0x34, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3a, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48, 0x49,
+    0x4a, 0x53, 0x54, 0x55, 0x56, 0x57, 0x58, 0x59, 0x5a, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68, 0x69,
+    0x6a, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7a, 0x83, 0x84, 0x85, 0x86, 0x87, 0x88, 0x89,
+    0x8a, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97, 0x98, 0x99, 0x9a, 0xa2, 0xa3, 0xa4, 0xa5, 0xa6, 0xa7,
+    0xa8, 0xa9, 0xaa, 0xb2, 0xb3, 0xb4, 0xb5, 0xb6, 0xb7, 0xb8, 0xb9, 0xba, 0xc2, 0xc3, 0xc4, 0xc5,
+    0xc6, 0xc7, 0xc8, 0xc9, 0xca, 0xd2, 0xd3, 0xd4, 0xd5, 0xd6, 0xd7, 0xd8, 0xd9, 0xda, 0xe1, 0xe2,
+    0xe3, 0xe4, 0xe5, 0xe6, 0xe7, 0xe8, 0xe9, 0xea, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8,
+    0xf9, 0xfa,
+];
+
+const AC_CHROMA_BITS: [u8; 16] = [0, 2, 1, 2, 4, 4, 3, 4, 7, 5, 4, 4, 0, 1, 2, 0x77];
+const AC_CHROMA_VALUES: [u8; 162] = [
+    0x00, 0x01, 0x02, 0x03, 0x11, 0x04, 0x05, 0x21, 0x31, 0x06, 0x12, 0x41, 0x51, 0x07, 0x61, 0x71,
+    0x13, 0x22, 0x32, 0x81, 0x08, 0x14, 0x42, 0x91, 0xa1, 0xb1, 0xc1, 0x09, 0x23, 0x33, 0x52, 0xf0,
+    0x15, 0x62, 0x72, 0xd1, 0x0a, 0x16, 0x24, 0x34, 0xe1, 0x25, 0xf1, 0x17, 0x18, 0x19, 0x1a, 0x26,
+    0x27, 0x28, 0x29, 0x2a, 0x35, 0x36, 0x37, 0x38, 0x39, 0x3a, 0x43, 0x44, 0x45, 0x46, 0x47, 0x48,
+    0x49, 0x4a, 0x53, 0x54, 0x55, 0x56, 0x57, 0x58, 0x59, 0x5a, 0x63, 0x64, 0x65, 0x66, 0x67, 0x68,
+    0x69, 0x6a, 0x73, 0x74, 0x75, 0x76, 0x77, 0x78, 0x79, 0x7a, 0x82, 0x83, 0x84, 0x85, 0x86, 0x87,
+    0x88, 0x89, 0x8a, 0x92, 0x93, 0x94, 0x95, 0x96, 0x97, 0x98, 0x99, 0x9a, 0xa2, 0xa3, 0xa4, 0xa5,
+    0xa6, 0xa7, 0xa8, 0xa9, 0xaa, 0xb2, 0xb3, 0xb4, 0xb5, 0xb6, 0xb7, 0xb8, 0xb9, 0xba, 0xc2, 0xc3,
+    0xc4, 0xc5, 0xc6, 0xc7, 0xc8, 0xc9, 0xca, 0xd2, 0xd3, 0xd4, 0xd5, 0xd6, 0xd7, 0xd8, 0xd9, 0xda,
+    0xe2, 0xe3, 0xe4, 0xe5, 0xe6, 0xe7, 0xe8, 0xe9, 0xea, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8,
+    0xf9, 0xfa,
+];
+
+/// Canonical Huffman code table: (code, length) indexed by symbol.
+struct HuffmanTable {
+    codes: [(u16, u8); 256],
+}
+
+impl HuffmanTable {
+    fn new(bits: &[u8; 16], values: &[u8]) -> Self {
+        let mut codes = [(0u16, 0u8); 256];
+        let mut code: u16 = 0;
+        let mut k = 0;
+        for (i, &count) in bits.iter().enumerate() {
+            for _ in 0..count {
+                codes[values[k] as usize] = (code, (i + 1) as u8);
+                code += 1;
+                k += 1;
+            }
+            code <<= 1;
+        }
+        HuffmanTable { codes }
+    }
+}
+
+/// Accumulates entropy-coded bits, applying 0xFF byte stuffing.
+struct BitWriter {
+    out: Vec<u8>,
+    acc: u32,
+    count: u8,
+}
+
+impl BitWriter {
+    fn new() -> Self {
+        BitWriter {
+            out: Vec::new(),
+            acc: 0,
+            count: 0,
+        }
+    }
+
+    fn write(&mut self, value: u16, len: u8) {
+        for i in (0..len).rev() {
+            self.acc = (self.acc << 1) | ((value >> i) & 1) as u32;
+            self.count += 1;
+            if self.count == 8 {
+                self.push_byte(self.acc as u8);
+                self.acc = 0;
+                self.count = 0;
+            }
+        }
+    }
+
+    fn push_byte(&mut self, byte: u8) {
+        self.out.push(byte);
+        if byte == 0xFF {
+            self.out.push(0x00);
+        }
+    }
+
+    /// Pad the final partial byte with 1-bits, as required by T.81.
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            let pad = 8 - self.count;
+            self.write((1 << pad) - 1, pad);
+        }
+        self.out
+    }
+}
+
+/// Scale a base quantization table by an IJG quality factor (1-100).
+fn scaled_table(base: &[u16; 64], quality: u8) -> [u8; 64] {
+    let q = quality.clamp(1, 100) as u32;
+    let scale = if q < 50 { 5000 / q } else { 200 - q * 2 };
+    let mut out = [0u8; 64];
+    for (o, &b) in out.iter_mut().zip(base.iter()) {
+        *o = ((b as u32 * scale + 50) / 100).clamp(1, 255) as u8;
+    }
+    out
+}
+
+/// Number of bits needed to represent |v| (the JPEG "category").
+fn category(v: i32) -> u8 {
+    (32 - v.unsigned_abs().leading_zeros()) as u8
+}
+
+/// Low-order bits encoding `v` within its category.
+fn magnitude_bits(v: i32, size: u8) -> u16 {
+    if v >= 0 {
+        v as u16
+    } else {
+        (v + (1 << size) - 1) as u16
+    }
+}
+
+/// Forward 8x8 DCT-II on level-shifted samples.
+fn fdct(block: &[f32; 64], cos: &[[f32; 8]; 8]) -> [f32; 64] {
+    let mut tmp = [0f32; 64];
+    // Rows
+    for y in 0..8 {
+        for u in 0..8 {
+            let mut s = 0.0;
+            for x in 0..8 {
+                s += block[y * 8 + x] * cos[u][x];
+            }
+            tmp[y * 8 + u] = s;
+        }
+    }
+    // Columns
+    let mut out = [0f32; 64];
+    for u in 0..8 {
+        for v in 0..8 {
+            let mut s = 0.0;
+            for y in 0..8 {
+                s += tmp[y * 8 + u] * cos[v][y];
+            }
+            out[v * 8 + u] = s;
+        }
+    }
+    out
+}
+
+fn cos_table() -> [[f32; 8]; 8] {
+    let mut t = [[0f32; 8]; 8];
+    for (u, row) in t.iter_mut().enumerate() {
+        let c = if u == 0 {
+            std::f32::consts::FRAC_1_SQRT_2
+        } else {
+            1.0
+        };
+        for (x, v) in row.iter_mut().enumerate() {
+            let angle = ((2 * x + 1) * u) as f32 * std::f32::consts::PI / 16.0;
+            *v = 0.5 * c * angle.cos();
+        }
+    }
+    t
+}
+
+/// Per-component encoding state.
+struct Component<'a> {
+    plane: Vec<f32>,
+    quant: [u8; 64],
+    dc: &'a HuffmanTable,
+    ac: &'a HuffmanTable,
+    prev_dc: i32,
+}
+
+fn encode_block(
+    writer: &mut BitWriter,
+    comp: &mut Component,
+    cos: &[[f32; 8]; 8],
+    padded_w: usize,
+    bx: usize,
+    by: usize,
+) {
+    let mut block = [0f32; 64];
+    for y in 0..8 {
+        for x in 0..8 {
+            block[y * 8 + x] = comp.plane[(by + y) * padded_w + bx + x] - 128.0;
+        }
+    }
+    let coeffs = fdct(&block, cos);
+
+    let mut zz = [0i32; 64];
+    for (i, &n) in ZIGZAG.iter().enumerate() {
+        zz[i] = (coeffs[n] / comp.quant[n] as f32).round() as i32;
+    }
+
+    let diff = zz[0] - comp.prev_dc;
+    comp.prev_dc = zz[0];
+    let size = category(diff);
+    let (code, len) = comp.dc.codes[size as usize];
+    writer.write(code, len);
+    writer.write(magnitude_bits(diff, size), size);
+
+    let mut run = 0;
+    for &v in &zz[1..] {
+        if v == 0 {
+            run += 1;
+            continue;
+        }
+        while run > 15 {
+            let (code, len) = comp.ac.codes[0xF0];
+            writer.write(code, len);
+            run -= 16;
+        }
+        let size = category(v);
+        let (code, len) = comp.ac.codes[(run << 4 | size as usize) & 0xFF];
+        writer.write(code, len);
+        writer.write(magnitude_bits(v, size), size);
+        run = 0;
+    }
+    if run > 0 {
+        let (code, len) = comp.ac.codes[0x00];
+        writer.write(code, len);
+    }
+}
+
+fn write_segment(out: &mut Vec<u8>, marker: u8, body: &[u8]) {
+    out.extend_from_slice(&[0xFF, marker]);
+    out.extend_from_slice(&((body.len() + 2) as u16).to_be_bytes());
+    out.extend_from_slice(body);
+}
+
+fn dht_body(class_id: u8, bits: &[u8; 16], values: &[u8]) -> Vec<u8> {
+    let mut body = vec![class_id];
+    body.extend_from_slice(bits);
+    body.extend_from_slice(values);
+    body
+}
+
+/// Encode 8-bit pixel data as baseline JPEG.
+///
+/// `components` must be 1 (grayscale) or 3 (RGB, interleaved). `quality`
+/// follows the familiar 1-100 scale; values outside it are clamped.
+pub(crate) fn encode(
+    pixels: &[u8],
+    width: u32,
+    height: u32,
+    components: u8,
+    quality: u8,
+) -> Result<Vec<u8>, String> {
+    if components != 1 && components != 3 {
+        return Err(format!(
+            "Unsupported component count for JPEG encoding: {}",
+            components
+        ));
+    }
+    if width == 0 || height == 0 || width > 0xFFFF || height > 0xFFFF {
+        return Err(format!(
+            "Image dimensions {}x{} cannot be encoded as JPEG",
+            width, height
+        ));
+    }
+    let (w, h, nc) = (width as usize, height as usize, components as usize);
+    if pixels.len() != w * h * nc {
+        return Err("Pixel data length does not match image dimensions".to_string());
+    }
+
+    // Pad each plane to a multiple of 8 by replicating edge pixels.
+    let pw = w.div_ceil(8) * 8;
+    let ph = h.div_ceil(8) * 8;
+    let mut planes = vec![vec![0f32; pw * ph]; nc];
+    for y in 0..ph {
+        let sy = y.min(h - 1);
+        for x in 0..pw {
+            let sx = x.min(w - 1);
+            let p = &pixels[(sy * w + sx) * nc..(sy * w + sx + 1) * nc];
+            let i = y * pw + x;
+            if nc == 1 {
+                planes[0][i] = p[0] as f32;
+            } else {
+                let (r, g, b) = (p[0] as f32, p[1] as f32, p[2] as f32);
+                planes[0][i] = 0.299 * r + 0.587 * g + 0.114 * b;
+                planes[1][i] = -0.168_736 * r - 0.331_264 * g + 0.5 * b + 128.0;
+                planes[2][i] = 0.5 * r - 0.418_688 * g - 0.081_312 * b + 128.0;
+            }
+        }
+    }
+
+    let luma_q = scaled_table(&LUMA_QUANT, quality);
+    let chroma_q = scaled_table(&CHROMA_QUANT, quality);
+    let dc_luma = HuffmanTable::new(&DC_LUMA_BITS, &DC_VALUES);
+    let ac_luma = HuffmanTable::new(&AC_LUMA_BITS, &AC_LUMA_VALUES);
+    let dc_chroma = HuffmanTable::new(&DC_CHROMA_BITS, &DC_VALUES);
+    let ac_chroma = HuffmanTable::new(&AC_CHROMA_BITS, &AC_CHROMA_VALUES);
+
+    let mut out = vec![0xFF, 0xD8];
+    write_segment(
+        &mut out,
+        0xE0,
+        &[b'J', b'F', b'I', b'F', 0, 1, 1, 0, 0, 1, 0, 1, 0, 0],
+    );
+
+    for (id, table) in [(0u8, &luma_q), (1u8, &chroma_q)]
+        .iter()
+        .take(if nc == 1 { 1 } else { 2 })
+    {
+        let mut body = vec![*id];
+        body.extend(ZIGZAG.iter().map(|&n| table[n]));
+        write_segment(&mut out, 0xDB, &body);
+    }
+
+    let mut sof = vec![8];
+    sof.extend_from_slice(&(height as u16).to_be_bytes());
+    sof.extend_from_slice(&(width as u16).to_be_bytes());
+    sof.push(components);
+    for c in 0..components {
+        sof.extend_from_slice(&[c + 1, 0x11, if c == 0 { 0 } else { 1 }]);
+    }
+    write_segment(&mut out, 0xC0, &sof);
+
+    write_segment(&mut out, 0xC4, &dht_body(0x00, &DC_LUMA_BITS, &DC_VALUES));
+    write_segment(
+        &mut out,
+        0xC4,
+        &dht_body(0x10, &AC_LUMA_BITS, &AC_LUMA_VALUES),
+    );
+    if nc == 3 {
+        write_segment(&mut out, 0xC4, &dht_body(0x01, &DC_CHROMA_BITS, &DC_VALUES));
+        write_segment(
+            &mut out,
+            0xC4,
+            &dht_body(0x11, &AC_CHROMA_BITS, &AC_CHROMA_VALUES),
+        );
+    }
+
+    let mut sos = vec![components];
+    for c in 0..components {
+        sos.extend_from_slice(&[c + 1, if c == 0 { 0x00 } else { 0x11 }]);
+    }
+    sos.extend_from_slice(&[0, 63, 0]);
+    write_segment(&mut out, 0xDA, &sos);
+
+    let mut comps: Vec<Component> = planes
+        .into_iter()
+        .enumerate()
+        .map(|(i, plane)| {
+            let luma = i == 0;
+            Component {
+                plane,
+                quant: if luma { luma_q } else { chroma_q },
+                dc: if luma { &dc_luma } else { &dc_chroma },
+                ac: if luma { &ac_luma } else { &ac_chroma },
+                prev_dc: 0,
+            }
+        })
+        .collect();
+
+    let cos = cos_table();
+    let mut writer = BitWriter::new();
+    for by in (0..ph).step_by(8) {
+        for bx in (0..pw).step_by(8) {
+            for comp in comps.iter_mut() {
+                encode_block(&mut writer, comp, &cos, pw, bx, by);
+            }
+        }
+    }
+    out.extend(writer.finish());
+    out.extend_from_slice(&[0xFF, 0xD9]);
+    Ok(out)
+}
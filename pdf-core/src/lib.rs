@@ -1,18 +1,30 @@
 pub mod document;
+pub mod flow;
 pub mod fonts;
+pub mod format;
+pub mod forms;
 pub mod graphics;
 pub mod images;
+mod jpeg_encoder;
 pub mod objects;
 pub mod reader;
 pub mod tables;
 pub mod textflow;
 pub mod truetype;
+pub mod units;
+pub mod validate;
 pub mod writer;
 
-pub use document::PdfDocument;
+pub use document::{DocStats, PdfDocument, ProgressEvent, Transition};
+pub use flow::FlowContext;
 pub use fonts::{BuiltinFont, FontRef, TrueTypeFontId};
-pub use graphics::Color;
-pub use images::{ImageFit, ImageId};
+pub use forms::{FormBuilder, FormId};
+pub use graphics::{Color, GraphicsDefaults, LineCap, LineJoin};
+pub use images::{ImageAnchor, ImageFit, ImageId};
 pub use reader::{PdfReadError, PdfReader};
-pub use tables::{Cell, CellOverflow, CellStyle, Row, Table, TableCursor, TextAlign};
+pub use tables::{
+    Cell, CellOverflow, CellStyle, Row, Table, TableCursor, TextAlign, UnderlineStyle,
+    VerticalAlign,
+};
 pub use textflow::{FitResult, Rect, TextFlow, TextStyle, WordBreak};
+pub use validate::ValidationWarning;
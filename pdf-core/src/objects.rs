@@ -14,6 +14,8 @@ pub enum PdfObject {
     Name(String),
     /// PDF literal string (stored without the enclosing parens).
     LiteralString(String),
+    /// PDF hexadecimal string (raw bytes, written as `<...>`).
+    HexString(Vec<u8>),
     Array(Vec<PdfObject>),
     /// Key-value pairs. Uses Vec for deterministic output order.
     Dictionary(Vec<(String, PdfObject)>),
@@ -33,6 +35,26 @@ impl PdfObject {
         PdfObject::LiteralString(s.to_string())
     }
 
+    /// A PDF text string (Info values, annotation text).
+    ///
+    /// Printable ASCII stays a literal string. Anything else is written in
+    /// hex form, which needs no escaping: control characters as their raw
+    /// bytes (a literal CR would be read back as LF), and non-ASCII text as
+    /// UTF-16BE with a `FEFF` byte order mark, as the spec requires.
+    pub fn text_string(s: &str) -> Self {
+        if s.bytes().all(|b| (0x20..0x7F).contains(&b)) {
+            PdfObject::LiteralString(s.to_string())
+        } else if s.is_ascii() {
+            PdfObject::HexString(s.as_bytes().to_vec())
+        } else {
+            let mut bytes = vec![0xFE, 0xFF];
+            for unit in s.encode_utf16() {
+                bytes.extend_from_slice(&unit.to_be_bytes());
+            }
+            PdfObject::HexString(bytes)
+        }
+    }
+
     pub fn reference(obj_num: u32, gen: u16) -> Self {
         PdfObject::Reference(ObjId(obj_num, gen))
     }
@@ -59,22 +59,25 @@ impl From<io::Error> for PdfReadError {
 ///
 /// `PdfReader` parses the PDF's cross-reference table and trailer to locate
 /// and resolve objects. The raw bytes and xref offset map are retained so that
-/// future enhancements (editing, field extraction, merging) can resolve
-/// arbitrary objects without re-reading the file.
+/// objects can be resolved later (e.g. by `PdfDocument::concat`) without
+/// re-reading the file.
 ///
 /// # Limitations
 /// PDF 1.5+ cross-reference streams are not supported. Files that use them
 /// return `PdfReadError::XrefStreamNotSupported`.
 pub struct PdfReader {
-    /// Retained for future object resolution (editing, field extraction, merging).
-    #[allow(dead_code)]
     data: Vec<u8>,
     /// Maps each object number to its byte offset in `data`.
-    /// Retained for future object resolution.
-    #[allow(dead_code)]
     xref: HashMap<u32, usize>,
+    /// Byte offset of the xref table, where the last object ends.
+    xref_offset: usize,
+    /// Object number of the document catalog.
+    root: u32,
+    /// Object number of the Info dictionary, if the trailer names one.
+    info: Option<u32>,
     version: String,
     page_count: usize,
+    encrypted: bool,
 }
 
 impl PdfReader {
@@ -88,14 +91,25 @@ impl PdfReader {
     pub fn from_bytes(data: Vec<u8>) -> Result<Self, PdfReadError> {
         let version = parse_version(&data)?;
         let xref_offset = find_startxref(&data)?;
-        let (xref, root_ref) = parse_xref_and_trailer(&data, xref_offset)?;
+        let (xref, trailer) = parse_xref_and_trailer(&data, xref_offset)?;
+        let root_ref: u32 = trailer
+            .get("Root")
+            .ok_or(PdfReadError::MalformedTrailer)?
+            .parse()
+            .map_err(|_| PdfReadError::MalformedTrailer)?;
         let page_count = resolve_page_count(&data, &xref, root_ref)?;
+        let encrypted = trailer.contains_key("Encrypt");
+        let info = trailer.get("Info").and_then(|n| n.parse().ok());
 
         Ok(PdfReader {
             data,
             xref,
+            xref_offset,
+            root: root_ref,
+            info,
             version,
             page_count,
+            encrypted,
         })
     }
 
@@ -108,10 +122,201 @@ impl PdfReader {
     pub fn pdf_version(&self) -> &str {
         &self.version
     }
+
+    /// Whether the trailer has an `/Encrypt` entry.
+    ///
+    /// Strings and streams of an encrypted file cannot be read without
+    /// the password, so check this before extracting content. The page
+    /// count is still available, since dictionaries are not encrypted.
+    pub fn is_encrypted(&self) -> bool {
+        self.encrypted
+    }
+
+    /// Object number of the document catalog.
+    pub(crate) fn root(&self) -> u32 {
+        self.root
+    }
+
+    /// Object number of the Info dictionary, if any.
+    pub(crate) fn info(&self) -> Option<u32> {
+        self.info
+    }
+
+    /// The root Pages object number and its `/Kids`, in page order.
+    ///
+    /// Only a flat page tree (every kid a `/Page`), as this crate writes,
+    /// is supported; nested `/Pages` nodes return `MalformedPageTree`.
+    pub(crate) fn page_objects(&self) -> Result<(u32, Vec<u32>), PdfReadError> {
+        let catalog = resolve_dict(&self.data, &self.xref, self.root)?;
+        let pages: u32 = catalog
+            .get("Pages")
+            .and_then(|n| n.parse().ok())
+            .ok_or(PdfReadError::MalformedPageTree)?;
+
+        let body = self.object_body(pages)?;
+        let pos = body
+            .windows(5)
+            .position(|w| w == b"/Kids")
+            .ok_or(PdfReadError::MalformedPageTree)?;
+        let after = skip_ascii_whitespace(&body[pos + 5..]);
+        if !after.starts_with(b"[") {
+            return Err(PdfReadError::MalformedPageTree);
+        }
+        let end = after
+            .iter()
+            .position(|&b| b == b']')
+            .ok_or(PdfReadError::MalformedPageTree)?;
+        let tokens: Vec<&str> = std::str::from_utf8(&after[1..end])
+            .map_err(|_| PdfReadError::MalformedPageTree)?
+            .split_ascii_whitespace()
+            .collect();
+
+        let mut kids = Vec::new();
+        for reference in tokens.chunks(3) {
+            let num = match reference {
+                [num, _, "R"] => num.parse().map_err(|_| PdfReadError::MalformedPageTree)?,
+                _ => return Err(PdfReadError::MalformedPageTree),
+            };
+            let kid = resolve_dict(&self.data, &self.xref, num)?;
+            if kid.get("Type").map(String::as_str) != Some("/Page") {
+                return Err(PdfReadError::MalformedPageTree);
+            }
+            kids.push(num);
+        }
+        Ok((pages, kids))
+    }
+
+    /// Every object in file order as `(number, body)`, where the body is
+    /// the bytes between `N G obj` and `endobj` (stream data included).
+    ///
+    /// Each object is taken to end where the next one in the file starts,
+    /// which holds for files written front to back without incremental
+    /// updates.
+    pub(crate) fn object_bodies(&self) -> Result<Vec<(u32, &[u8])>, PdfReadError> {
+        let mut objects: Vec<(u32, usize)> = self.xref.iter().map(|(&n, &o)| (n, o)).collect();
+        objects.sort_by_key(|&(_, offset)| offset);
+        let mut bodies = Vec::with_capacity(objects.len());
+        for (i, &(num, offset)) in objects.iter().enumerate() {
+            let end = objects.get(i + 1).map_or(self.xref_offset, |&(_, o)| o);
+            bodies.push((num, self.body_between(num, offset, end)?));
+        }
+        Ok(bodies)
+    }
+
+    /// Body of a single object (see `object_bodies`).
+    fn object_body(&self, obj_num: u32) -> Result<&[u8], PdfReadError> {
+        let offset = self.xref[&obj_num];
+        let end = self
+            .xref
+            .values()
+            .copied()
+            .filter(|&o| o > offset)
+            .min()
+            .unwrap_or(self.xref_offset);
+        self.body_between(obj_num, offset, end)
+    }
+
+    fn body_between(&self, obj_num: u32, start: usize, end: usize) -> Result<&[u8], PdfReadError> {
+        let unresolvable = || PdfReadError::UnresolvableObject(obj_num);
+        if start >= end || end > self.data.len() {
+            return Err(unresolvable());
+        }
+        let body = skip_obj_header(&self.data[start..end]).ok_or_else(unresolvable)?;
+        let body = trim_ascii_end(skip_ascii_whitespace(body));
+        let body = body.strip_suffix(b"endobj").ok_or_else(unresolvable)?;
+        Ok(trim_ascii_end(body))
+    }
+}
+
+/// Copy an object body with every indirect reference `N G R` renumbered by
+/// `map`. References `map` returns `None` for become `null`. Strings and
+/// stream data are copied unchanged.
+pub(crate) fn renumber_refs(body: &[u8], map: impl Fn(u32) -> Option<u32>) -> Vec<u8> {
+    let mut out = Vec::with_capacity(body.len());
+    let mut i = 0;
+    while i < body.len() {
+        let rest = &body[i..];
+        let token_start = i == 0 || !is_regular(body[i - 1]);
+        if rest[0] == b'(' {
+            let after = skip_literal_string(rest).unwrap_or(&[]);
+            let len = rest.len() - after.len();
+            out.extend_from_slice(&rest[..len]);
+            i += len;
+        } else if rest.starts_with(b"<<") {
+            out.extend_from_slice(b"<<");
+            i += 2;
+        } else if rest[0] == b'<' {
+            let len = rest
+                .iter()
+                .position(|&b| b == b'>')
+                .map_or(rest.len(), |p| p + 1);
+            out.extend_from_slice(&rest[..len]);
+            i += len;
+        } else if token_start
+            && rest.starts_with(b"stream")
+            && matches!(rest.get(6), Some(b'\r' | b'\n'))
+        {
+            out.extend_from_slice(rest);
+            break;
+        } else if token_start && rest[0].is_ascii_digit() {
+            match parse_reference(rest) {
+                Some((num, len)) => {
+                    match map(num) {
+                        Some(new) => out.extend_from_slice(format!("{} 0 R", new).as_bytes()),
+                        None => out.extend_from_slice(b"null"),
+                    }
+                    i += len;
+                }
+                None => {
+                    let len = rest
+                        .iter()
+                        .position(|b| !b.is_ascii_digit())
+                        .unwrap_or(rest.len());
+                    out.extend_from_slice(&rest[..len]);
+                    i += len;
+                }
+            }
+        } else {
+            out.push(rest[0]);
+            i += 1;
+        }
+    }
+    out
+}
+
+/// Match `N G R` at the start of `data`, returning `N` and the match length.
+fn parse_reference(data: &[u8]) -> Option<(u32, usize)> {
+    let digits = |d: &[u8]| d.iter().take_while(|b| b.is_ascii_digit()).count();
+    let spaces = |d: &[u8]| d.iter().take_while(|b| b.is_ascii_whitespace()).count();
+    let num_len = digits(data);
+    let mut i = num_len;
+    let gap = spaces(&data[i..]);
+    i += gap;
+    let gen_len = digits(&data[i..]);
+    i += gen_len;
+    let gap2 = spaces(&data[i..]);
+    i += gap2;
+    if gap == 0 || gen_len == 0 || gap2 == 0 || data.get(i) != Some(&b'R') {
+        return None;
+    }
+    i += 1;
+    if data.get(i).is_some_and(|&b| is_regular(b)) {
+        return None;
+    }
+    let num = std::str::from_utf8(&data[..num_len]).ok()?.parse().ok()?;
+    Some((num, i))
+}
+
+/// Whether `b` is a regular character (not whitespace or a delimiter).
+fn is_regular(b: u8) -> bool {
+    !b.is_ascii_whitespace() && !b"()<>[]{}/%".contains(&b)
 }
 
 // ── Internal parsing ───────────────────────────────────────────────────────────
 
+/// A dictionary as returned by `parse_dict_bytes`: name → first value token.
+type FlatDict = HashMap<String, String>;
+
 /// Extract the PDF version from the `%PDF-x.y` header.
 fn parse_version(data: &[u8]) -> Result<String, PdfReadError> {
     if data.len() < 8 || !data.starts_with(b"%PDF-") {
@@ -160,11 +365,11 @@ fn find_startxref(data: &[u8]) -> Result<usize, PdfReadError> {
 
 /// Parse the xref table starting at `xref_offset` and the following trailer.
 ///
-/// Returns `(object_offset_map, root_object_number)`.
+/// Returns `(object_offset_map, trailer_dict)`.
 fn parse_xref_and_trailer(
     data: &[u8],
     xref_offset: usize,
-) -> Result<(HashMap<u32, usize>, u32), PdfReadError> {
+) -> Result<(HashMap<u32, usize>, FlatDict), PdfReadError> {
     if xref_offset >= data.len() {
         return Err(PdfReadError::MalformedXref);
     }
@@ -178,9 +383,9 @@ fn parse_xref_and_trailer(
     }
 
     let xref = parse_xref_table(section)?;
-    let root = parse_trailer_root(data, xref_offset)?;
+    let trailer = parse_trailer(data, xref_offset)?;
 
-    Ok((xref, root))
+    Ok((xref, trailer))
 }
 
 /// Parse the traditional xref table.
@@ -248,8 +453,8 @@ fn parse_xref_table(section: &[u8]) -> Result<HashMap<u32, usize>, PdfReadError>
     Ok(map)
 }
 
-/// Extract the `/Root` object number from the trailer dictionary.
-fn parse_trailer_root(data: &[u8], xref_offset: usize) -> Result<u32, PdfReadError> {
+/// Parse the trailer dictionary that follows the xref table.
+fn parse_trailer(data: &[u8], xref_offset: usize) -> Result<HashMap<String, String>, PdfReadError> {
     // Find "trailer" after the xref table
     let section = &data[xref_offset..];
     let pos = section
@@ -259,15 +464,7 @@ fn parse_trailer_root(data: &[u8], xref_offset: usize) -> Result<u32, PdfReadErr
 
     let after_trailer = skip_ascii_whitespace(&section[pos + 7..]);
 
-    // Parse the trailer dictionary to find /Root
-    let dict = parse_dict_bytes(after_trailer).ok_or(PdfReadError::MalformedTrailer)?;
-
-    let root_ref = dict.get("Root").ok_or(PdfReadError::MalformedTrailer)?;
-    // Root value is a reference: "N M R" — we only need N
-    let obj_num: u32 = root_ref
-        .parse()
-        .map_err(|_| PdfReadError::MalformedTrailer)?;
-    Ok(obj_num)
+    parse_dict_bytes(after_trailer).ok_or(PdfReadError::MalformedTrailer)
 }
 
 /// Follow the catalog → pages chain to read the `/Count` value.
@@ -329,7 +526,9 @@ fn resolve_dict(
 /// Parse `<<...>>` dictionary bytes into a flat `key → first-token-of-value` map.
 ///
 /// Values that are indirect references (`N G R`) are stored as just the object
-/// number string. Nested dictionaries and arrays are skipped.
+/// number string. Nested dictionaries, arrays and literal strings are
+/// skipped and recorded with an empty value, so their keys can still be
+/// tested for presence.
 fn parse_dict_bytes(data: &[u8]) -> Option<HashMap<String, String>> {
     let data = skip_ascii_whitespace(data);
     if !data.starts_with(b"<<") {
@@ -361,12 +560,15 @@ fn parse_dict_bytes(data: &[u8]) -> Option<HashMap<String, String>> {
         if cursor.starts_with(b"<<") {
             // Nested dict: skip to matching >>
             cursor = skip_nested_dict(cursor)?;
+            map.insert(key.to_string(), String::new());
         } else if cursor.starts_with(b"[") {
             // Array: skip to ]
             cursor = skip_array(cursor)?;
+            map.insert(key.to_string(), String::new());
         } else if cursor.starts_with(b"(") {
             // Literal string: skip to closing )
             cursor = skip_literal_string(cursor)?;
+            map.insert(key.to_string(), String::new());
         } else {
             let (val, rest) = next_token(cursor)?;
             cursor = skip_ascii_whitespace(rest);
@@ -471,6 +673,15 @@ fn skip_ascii_whitespace(data: &[u8]) -> &[u8] {
     &data[pos..]
 }
 
+/// Return `data` without trailing whitespace.
+fn trim_ascii_end(data: &[u8]) -> &[u8] {
+    let end = data
+        .iter()
+        .rposition(|&b| !b.is_ascii_whitespace())
+        .map_or(0, |p| p + 1);
+    &data[..end]
+}
+
 /// Skip to the end of the current line (past `\n` or `\r\n`).
 fn skip_line(data: &[u8]) -> &[u8] {
     let pos = data
@@ -1,8 +1,11 @@
+use std::borrow::Borrow;
+
 use crate::document::format_coord;
 use crate::fonts::{BuiltinFont, FontRef};
-use crate::graphics::Color;
+use crate::graphics::{self, Color};
 use crate::textflow::{
     break_word, line_height_for, measure_word, FitResult, Rect, TextStyle, UsedFonts, WordBreak,
+    ZERO_WIDTH_SPACE,
 };
 use crate::truetype::TrueTypeFont;
 use crate::writer::escape_pdf_string;
@@ -23,6 +26,30 @@ pub enum TextAlign {
     Right,
 }
 
+/// Vertical text alignment within a table cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum VerticalAlign {
+    /// Text starts at the top of the cell (default).
+    #[default]
+    Top,
+    /// The text block is centered vertically within the cell.
+    Middle,
+    /// The text block sits at the bottom of the cell.
+    Bottom,
+}
+
+/// Rules drawn under each line of cell text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum UnderlineStyle {
+    /// No underline (default).
+    #[default]
+    None,
+    /// One rule under each line.
+    Single,
+    /// Two parallel rules under each line, as used for grand totals.
+    Double,
+}
+
 /// How text that overflows the cell height is handled.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum CellOverflow {
@@ -48,11 +75,26 @@ pub struct CellStyle {
     /// Padding applied to all four sides, in points.
     pub padding: f64,
     /// How to handle text that exceeds the available cell height.
-    pub overflow: CellOverflow,
+    /// `None` inherits the table's `default_style` (`Wrap` if that is unset too).
+    pub overflow: Option<CellOverflow>,
     /// How to handle words wider than the cell's available width.
-    pub word_break: WordBreak,
+    /// `None` inherits the table's `default_style` (`BreakAll` if that is unset too).
+    pub word_break: Option<WordBreak>,
+    /// Character appended to broken pieces in `WordBreak::Hyphenate` mode.
+    /// `None` inherits the table's `default_style` (`-` if that is unset too).
+    pub hyphen_char: Option<char>,
     /// Horizontal text alignment within the cell.
     pub text_align: TextAlign,
+    /// Vertical alignment of the text block within the cell. With `Clip`
+    /// overflow, `Middle` shows the central lines of text that is too tall.
+    pub vertical_align: VerticalAlign,
+    /// Underline drawn beneath each wrapped line in the text color.
+    pub underline: UnderlineStyle,
+    /// Counter-clockwise text rotation in degrees, e.g. `90.0` for a
+    /// vertical column header. Rotated text is set as a single unwrapped
+    /// line centred in the cell; auto row heights use its rotated bounding
+    /// box. Alignment, underline and `Shrink` apply only at `0.0` (default).
+    pub rotation: f64,
 }
 
 impl Default for CellStyle {
@@ -63,18 +105,56 @@ impl Default for CellStyle {
             font: FontRef::Builtin(BuiltinFont::Helvetica),
             font_size: 10.0,
             padding: 4.0,
-            overflow: CellOverflow::Wrap,
-            word_break: WordBreak::BreakAll,
+            overflow: None,
+            word_break: None,
+            hyphen_char: None,
             text_align: TextAlign::Left,
+            vertical_align: VerticalAlign::Top,
+            underline: UnderlineStyle::None,
+            rotation: 0.0,
         }
     }
 }
 
+impl CellStyle {
+    /// Copy with font size and padding multiplied by `scale`.
+    pub(crate) fn scaled(&self, scale: f64) -> CellStyle {
+        CellStyle {
+            font_size: self.font_size * scale,
+            padding: self.padding * scale,
+            ..self.clone()
+        }
+    }
+
+    /// Effective overflow mode, falling back to `defaults` and then `Wrap`.
+    pub(crate) fn overflow_or(&self, defaults: &CellStyle) -> CellOverflow {
+        self.overflow
+            .or(defaults.overflow)
+            .unwrap_or(CellOverflow::Wrap)
+    }
+
+    /// Effective word-break mode, falling back to `defaults` and then `BreakAll`.
+    pub(crate) fn word_break_or(&self, defaults: &CellStyle) -> WordBreak {
+        self.word_break
+            .or(defaults.word_break)
+            .unwrap_or(WordBreak::BreakAll)
+    }
+
+    /// Effective hyphen character, falling back to `defaults` and then `-`.
+    pub(crate) fn hyphen_char_or(&self, defaults: &CellStyle) -> char {
+        self.hyphen_char.or(defaults.hyphen_char).unwrap_or('-')
+    }
+}
+
 /// A single table cell containing text and style.
 #[derive(Clone)]
 pub struct Cell {
     pub text: String,
     pub style: CellStyle,
+    /// Number of rows this cell covers (default 1). The cells in the same
+    /// column of the following `rowspan - 1` rows are skipped; pass blank
+    /// placeholders for them so the other columns stay aligned.
+    pub rowspan: usize,
 }
 
 impl Cell {
@@ -83,6 +163,7 @@ impl Cell {
         Cell {
             text: text.into(),
             style: CellStyle::default(),
+            rowspan: 1,
         }
     }
 
@@ -91,6 +172,7 @@ impl Cell {
         Cell {
             text: text.into(),
             style,
+            rowspan: 1,
         }
     }
 }
@@ -105,6 +187,9 @@ pub struct Row {
     /// Fixed row height in points. Required for `Clip` and `Shrink` overflow.
     /// When `None`, height is auto-calculated from cell content (`Wrap` mode).
     pub height: Option<f64>,
+    /// Marks a (repeated) header row. Header rows are never striped and do
+    /// not advance the stripe pattern.
+    pub header: bool,
 }
 
 impl Row {
@@ -114,6 +199,25 @@ impl Row {
             cells,
             background_color: None,
             height: None,
+            header: false,
+        }
+    }
+
+    /// Copy with cell font sizes, paddings and a fixed height multiplied
+    /// by `scale`.
+    pub(crate) fn scaled(&self, scale: f64) -> Row {
+        Row {
+            cells: self
+                .cells
+                .iter()
+                .map(|cell| Cell {
+                    style: cell.style.scaled(scale),
+                    ..cell.clone()
+                })
+                .collect(),
+            background_color: self.background_color,
+            height: self.height.map(|h| h * scale),
+            header: self.header,
         }
     }
 }
@@ -121,16 +225,47 @@ impl Row {
 /// Table layout configuration. Holds column widths and visual style; does not
 /// store row data. The caller supplies one `Row` at a time to `fit_row`,
 /// enabling streaming from a database cursor without buffering the full dataset.
+#[derive(Clone)]
 pub struct Table {
-    /// Column widths in points.
+    /// Column widths in points. A negative width marks a flexible column:
+    /// flexible columns share the space left after the fixed columns in
+    /// proportion to their magnitude (`-1.0` is one share, `-2.0` two).
     pub columns: Vec<f64>,
+    /// Width flexible columns are resolved against. `None` uses the width
+    /// of the cursor's rect.
+    pub total_width: Option<f64>,
     /// Reference style for constructing cells. Clone it when creating cells
-    /// to apply consistent styling across the table.
+    /// to apply consistent styling across the table. Its `overflow` and
+    /// `word_break` are also inherited by cells that leave them `None`.
     pub default_style: CellStyle,
     /// Border stroke color (default: black).
     pub border_color: Color,
     /// Border line width in points. Set to `0.0` to disable borders.
     pub border_width: f64,
+    /// Dash pattern for borders (dash and gap lengths in points), e.g.
+    /// `Some(vec![1.0, 2.0])` for a dotted rule. `None` draws solid borders.
+    pub border_dash: Option<Vec<f64>>,
+    /// Lay columns out right-to-left: the first column is drawn at the
+    /// right edge of the table. Cell text alignment is unaffected.
+    pub rtl: bool,
+    /// Zebra striping: background for every other row (the second, fourth,
+    /// ...). Header rows and rows with their own `background_color` are
+    /// skipped without breaking the alternation. `None` disables striping.
+    pub stripe_color: Option<Color>,
+    /// Restart the stripe pattern at the top of each page. When `false`
+    /// (default) the parity continues across page breaks.
+    pub reset_stripe_on_page: bool,
+    /// Lower bound for auto-height rows, in points. Rows with a fixed
+    /// `height` are not clamped.
+    pub min_row_height: Option<f64>,
+    /// Upper bound for auto-height rows, in points. Text taller than this
+    /// runs past the row bottom unless the cell uses `CellOverflow::Clip`.
+    pub max_row_height: Option<f64>,
+    /// Caption such as "(continued)" drawn across the table's width, without
+    /// borders, above the first row placed on each page after the first.
+    /// Its height is measured like an auto-height cell. `None` (default)
+    /// draws nothing.
+    pub continued_caption: Option<Cell>,
 }
 
 impl Table {
@@ -138,10 +273,70 @@ impl Table {
     pub fn new(columns: Vec<f64>) -> Self {
         Table {
             columns,
+            total_width: None,
             default_style: CellStyle::default(),
             border_color: Color::rgb(0.0, 0.0, 0.0),
             border_width: 0.5,
+            border_dash: None,
+            rtl: false,
+            stripe_color: None,
+            reset_stripe_on_page: false,
+            min_row_height: None,
+            max_row_height: None,
+            continued_caption: None,
+        }
+    }
+
+    /// Resolve flexible (negative) column widths into points.
+    ///
+    /// Fixed columns keep their width; the remaining space in
+    /// `total_width` (or `available_width` when unset) is split among the
+    /// flexible columns. If the fixed columns already exceed the total,
+    /// flexible columns collapse to zero width.
+    pub fn resolve_columns(&self, available_width: f64) -> Vec<f64> {
+        let total = self.total_width.unwrap_or(available_width);
+        let fixed: f64 = self.columns.iter().filter(|w| **w >= 0.0).sum();
+        let shares: f64 = self.columns.iter().filter(|w| **w < 0.0).map(|w| -w).sum();
+        let remaining = (total - fixed).max(0.0);
+        self.columns
+            .iter()
+            .map(|&w| if w < 0.0 { remaining * -w / shares } else { w })
+            .collect()
+    }
+
+    /// Total height `rows` take when placed top to bottom in a rect of
+    /// `width`, ignoring page breaks.
+    pub(crate) fn measure_rows(
+        &self,
+        rows: &[Row],
+        width: f64,
+        tt_fonts: &mut [TrueTypeFont],
+        precision: usize,
+    ) -> f64 {
+        let mut cursor = TableCursor::new(&Rect {
+            x: 0.0,
+            y: 0.0,
+            width,
+            height: f64::INFINITY,
+        });
+        for row in rows {
+            self.generate_row_ops(row, &mut cursor, tt_fonts, precision);
         }
+        -cursor.current_y
+    }
+
+    /// Copy of this table with every length that depends on text size
+    /// (font sizes, paddings, row height bounds) multiplied by `scale`.
+    pub(crate) fn scaled(&self, scale: f64) -> Table {
+        let mut table = self.clone();
+        table.default_style = self.default_style.scaled(scale);
+        table.min_row_height = self.min_row_height.map(|h| h * scale);
+        table.max_row_height = self.max_row_height.map(|h| h * scale);
+        table.continued_caption = self.continued_caption.as_ref().map(|cell| Cell {
+            style: cell.style.scaled(scale),
+            ..cell.clone()
+        });
+        table
     }
 
     /// Generate PDF content stream bytes for a single row.
@@ -153,11 +348,60 @@ impl Table {
         row: &Row,
         cursor: &mut TableCursor,
         tt_fonts: &mut [TrueTypeFont],
+        precision: usize,
     ) -> (Vec<u8>, FitResult, UsedFonts) {
-        let row_height = measure_row_height(row, &self.columns, &self.default_style, tt_fonts);
+        let columns = self.resolve_columns(cursor.rect.width);
+
+        // What occupies each column: a span carried over from an earlier
+        // row, or this row's own cell.
+        let covering: Vec<Option<&PendingSpan>> = (0..columns.len())
+            .map(|col_idx| cursor.spans.iter().find(|span| span.column == col_idx))
+            .collect();
+        let starts_span =
+            |col_idx: usize| row.cells.get(col_idx).is_some_and(|cell| cell.rowspan > 1);
+
+        let (mut row_height, mut wrapped) =
+            measure_row_height(row, &columns, &covering, &self.default_style, tt_fonts);
+        if row.height.is_none() {
+            if let Some(min) = self.min_row_height {
+                row_height = row_height.max(min);
+            }
+            if let Some(max) = self.max_row_height {
+                row_height = row_height.min(max);
+            }
+            // The last row of a span grows so the spanning cell fits.
+            for span in cursor.spans.iter().filter(|span| span.rows_left == 1) {
+                let (needed, _) = measure_cell(
+                    &span.cell.text,
+                    &span.cell.style,
+                    &self.default_style,
+                    columns.get(span.column).copied().unwrap_or(0.0),
+                    tt_fonts,
+                );
+                row_height = row_height.max(needed - (span.top - cursor.current_y));
+            }
+        }
         let bottom = cursor.rect.y - cursor.rect.height;
 
-        if cursor.current_y - row_height < bottom {
+        // A continuation page opens with the caption, if any.
+        let table_width: f64 = columns.iter().sum();
+        let caption = self
+            .continued_caption
+            .as_ref()
+            .filter(|_| cursor.first_row && cursor.row_index > 0)
+            .map(|cell| {
+                let (height, lines) = measure_cell(
+                    &cell.text,
+                    &cell.style,
+                    &self.default_style,
+                    table_width,
+                    tt_fonts,
+                );
+                (cell, height, lines)
+            });
+        let caption_height = caption.as_ref().map_or(0.0, |(_, height, _)| *height);
+
+        if cursor.current_y - caption_height - row_height < bottom {
             // Nothing placed yet on this page — rect is too small for this row.
             // Otherwise the page is simply full and the caller should turn it.
             let result = if cursor.first_row {
@@ -165,55 +409,239 @@ impl Table {
             } else {
                 FitResult::BoxFull
             };
-            return (Vec::new(), result, UsedFonts::default());
+            // Spans cannot cross a page break: close them at the last placed row.
+            let mut output = Vec::new();
+            let mut used = UsedFonts::default();
+            self.close_spans(
+                cursor,
+                &columns,
+                tt_fonts,
+                precision,
+                &mut output,
+                &mut used,
+            );
+            return (output, result, used);
         }
 
         let mut output: Vec<u8> = Vec::new();
         let mut used = UsedFonts::default();
 
+        if let Some((cell, height, lines)) = caption {
+            render_cell(
+                cell,
+                lines,
+                &self.default_style,
+                cursor.rect.x,
+                cursor.current_y,
+                table_width,
+                height,
+                tt_fonts,
+                precision,
+                &mut output,
+                &mut used,
+            );
+            cursor.current_y -= height;
+        }
+
+        let col_xs = self.column_xs(&columns, cursor.rect.x);
+        let occupants: Vec<Option<&Cell>> = covering
+            .iter()
+            .enumerate()
+            .map(|(col_idx, span)| match span {
+                Some(span) => Some(&span.cell),
+                None => row.cells.get(col_idx),
+            })
+            .collect();
+        // Header rows and rows with their own background sit outside the pattern.
+        let striped_row = !row.header && row.background_color.is_none();
+        let stripe_index = if self.reset_stripe_on_page {
+            cursor.page_stripe_index
+        } else {
+            cursor.stripe_index
+        };
+        let stripe = self
+            .stripe_color
+            .filter(|_| striped_row && stripe_index % 2 == 1);
         draw_row_backgrounds(
-            row,
-            &self.columns,
+            row.background_color.or(stripe),
+            &columns,
+            &occupants,
+            &col_xs,
             cursor.rect.x,
             cursor.current_y,
             row_height,
+            precision,
             &mut output,
         );
 
-        let mut col_x = cursor.rect.x;
-        for (col_idx, &col_width) in self.columns.iter().enumerate() {
-            if let Some(cell) = row.cells.get(col_idx) {
-                render_cell(
-                    cell,
-                    col_x,
-                    cursor.current_y,
-                    col_width,
-                    row_height,
-                    tt_fonts,
-                    &mut output,
-                    &mut used,
-                );
-            }
-            col_x += col_width;
+        let row_bottom = cursor.current_y - row_height;
+        for (col_idx, (&col_width, &col_x)) in columns.iter().zip(&col_xs).enumerate() {
+            let (cell, top) = match covering[col_idx] {
+                // A span's text is drawn once, over its full height, in its last row.
+                Some(span) if span.rows_left == 1 => (&span.cell, span.top),
+                Some(_) => continue,
+                None if starts_span(col_idx) => continue,
+                None => match row.cells.get(col_idx) {
+                    Some(cell) => (cell, cursor.current_y),
+                    None => continue,
+                },
+            };
+            // The measure pass wrapped the row's own cells; spans rewrap.
+            let lines = match covering[col_idx] {
+                Some(_) => None,
+                None => wrapped.get_mut(col_idx).and_then(Option::take),
+            };
+            render_cell(
+                cell,
+                lines,
+                &self.default_style,
+                col_x,
+                top,
+                col_width,
+                top - row_bottom,
+                tt_fonts,
+                precision,
+                &mut output,
+                &mut used,
+            );
         }
 
         if self.border_width > 0.0 {
+            // Dividers only depend on the widths in visual (left-to-right) order.
+            let mut visual_columns = columns.clone();
+            // Spanned cells leave out the horizontal rules inside the span.
+            let mut open_top: Vec<bool> = covering.iter().map(Option::is_some).collect();
+            let mut open_bottom: Vec<bool> = (0..columns.len())
+                .map(|col_idx| match covering[col_idx] {
+                    Some(span) => span.rows_left > 1,
+                    None => starts_span(col_idx),
+                })
+                .collect();
+            if self.rtl {
+                visual_columns.reverse();
+                open_top.reverse();
+                open_bottom.reverse();
+            }
             draw_row_borders(
-                &self.columns,
+                &visual_columns,
+                &open_top,
+                &open_bottom,
                 cursor.rect.x,
                 cursor.current_y,
                 row_height,
                 self.border_color,
                 self.border_width,
+                self.border_dash.as_deref(),
+                precision,
                 &mut output,
             );
         }
 
+        // Advance carried spans and record the ones this row starts.
+        let started: Vec<PendingSpan> = (0..columns.len())
+            .filter(|&col_idx| covering[col_idx].is_none() && starts_span(col_idx))
+            .map(|col_idx| {
+                let cell = &row.cells[col_idx];
+                PendingSpan {
+                    column: col_idx,
+                    cell: cell.clone(),
+                    top: cursor.current_y,
+                    rows_left: cell.rowspan - 1,
+                }
+            })
+            .collect();
+        cursor.spans.retain_mut(|span| {
+            span.rows_left -= 1;
+            span.rows_left > 0
+        });
+        cursor.spans.extend(started);
+
         cursor.current_y -= row_height;
         cursor.first_row = false;
+        cursor.row_index += 1;
+        if striped_row {
+            cursor.stripe_index += 1;
+            cursor.page_stripe_index += 1;
+        }
 
         (output, FitResult::Stop, used)
     }
+
+    /// Draw the text and bottom rule of spans still open when the page
+    /// fills up, ending them at the bottom of the last placed row.
+    fn close_spans(
+        &self,
+        cursor: &mut TableCursor,
+        columns: &[f64],
+        tt_fonts: &mut [TrueTypeFont],
+        precision: usize,
+        output: &mut Vec<u8>,
+        used: &mut UsedFonts,
+    ) {
+        if cursor.spans.is_empty() {
+            return;
+        }
+        let col_xs = self.column_xs(columns, cursor.rect.x);
+        let bottom = cursor.current_y;
+        let spans = std::mem::take(&mut cursor.spans);
+        for span in &spans {
+            let (Some(&col_x), Some(&col_width)) =
+                (col_xs.get(span.column), columns.get(span.column))
+            else {
+                continue;
+            };
+            render_cell(
+                &span.cell,
+                None,
+                &self.default_style,
+                col_x,
+                span.top,
+                col_width,
+                span.top - bottom,
+                tt_fonts,
+                precision,
+                output,
+                used,
+            );
+        }
+        if self.border_width > 0.0 {
+            begin_border(
+                self.border_color,
+                self.border_width,
+                self.border_dash.as_deref(),
+                precision,
+                output,
+            );
+            for span in &spans {
+                if let (Some(&col_x), Some(&col_width)) =
+                    (col_xs.get(span.column), columns.get(span.column))
+                {
+                    stroke_line(col_x, bottom, col_x + col_width, bottom, precision, output);
+                }
+            }
+            end_border(self.border_dash.as_deref(), precision, output);
+        }
+    }
+
+    /// Left x-position of each logical column. With `rtl` the first column
+    /// sits at the right edge and later columns move leftwards.
+    fn column_xs(&self, columns: &[f64], row_x: f64) -> Vec<f64> {
+        let mut xs = Vec::with_capacity(columns.len());
+        if self.rtl {
+            let mut right = row_x + columns.iter().sum::<f64>();
+            for &col_width in columns {
+                right -= col_width;
+                xs.push(right);
+            }
+        } else {
+            let mut col_x = row_x;
+            for &col_width in columns {
+                xs.push(col_x);
+                col_x += col_width;
+            }
+        }
+        xs
+    }
 }
 
 /// Tracks where the next row will be placed within a page.
@@ -255,6 +683,26 @@ pub struct TableCursor {
     pub(crate) current_y: f64,
     /// True when no rows have been placed on the current page yet.
     pub(crate) first_row: bool,
+    /// Row-spanning cells that still cover upcoming rows.
+    pub(crate) spans: Vec<PendingSpan>,
+    /// Rows placed since the cursor was created.
+    pub(crate) row_index: usize,
+    /// Stripe-eligible rows placed since the cursor was created.
+    pub(crate) stripe_index: usize,
+    /// Stripe-eligible rows placed on the current page.
+    pub(crate) page_stripe_index: usize,
+}
+
+/// A cell whose `rowspan` reaches into rows not yet placed.
+#[derive(Clone)]
+pub(crate) struct PendingSpan {
+    /// Column the spanning cell occupies.
+    pub(crate) column: usize,
+    pub(crate) cell: Cell,
+    /// Top edge of the span's first row.
+    pub(crate) top: f64,
+    /// Rows still covered, including the next one placed.
+    pub(crate) rows_left: usize,
 }
 
 impl TableCursor {
@@ -264,14 +712,24 @@ impl TableCursor {
             rect: *rect,
             current_y: rect.y,
             first_row: true,
+            spans: Vec::new(),
+            row_index: 0,
+            stripe_index: 0,
+            page_stripe_index: 0,
         }
     }
 
     /// Reset to the top of a new rect. Call this when starting a new page.
+    ///
+    /// Row spans do not continue across pages. Spans cut short by a full
+    /// page are closed by `fit_row` before it returns `BoxFull`; any span
+    /// still open here is dropped.
     pub fn reset(&mut self, rect: &Rect) {
         self.rect = *rect;
         self.current_y = rect.y;
         self.first_row = true;
+        self.spans.clear();
+        self.page_stripe_index = 0;
     }
 
     /// Returns `true` if no rows have been placed on the current page yet.
@@ -290,6 +748,23 @@ impl TableCursor {
     pub fn current_y(&self) -> f64 {
         self.current_y
     }
+
+    /// Returns the height left between the next row's top and the bottom
+    /// of the cursor's rect.
+    ///
+    /// Use it to force a page break before a group of rows that would look
+    /// bad split across pages.
+    pub fn remaining_height(&self) -> f64 {
+        self.current_y - (self.rect.y - self.rect.height)
+    }
+
+    /// Returns the number of rows placed with this cursor, across all pages.
+    ///
+    /// Header rows count too. This is the index the next row gets for
+    /// zebra striping.
+    pub fn row_index(&self) -> usize {
+        self.row_index
+    }
 }
 
 // -------------------------------------------------------
@@ -299,43 +774,126 @@ impl TableCursor {
 /// Compute the height needed for a row based on its content.
 ///
 /// Returns `row.height` directly for fixed-height rows (Clip/Shrink modes).
-/// Otherwise computes the maximum cell height across all columns.
+/// Otherwise computes the maximum cell height across all columns. Columns
+/// covered by a span and cells that start one do not size the row; the
+/// span's last row makes room for them instead. Columns without a cell only
+/// matter when the row has no cell of its own: they then give it one line
+/// of the default style.
+///
+/// Also returns the wrapped lines of each measured cell, indexed by column,
+/// so rendering does not wrap the same text again.
 fn measure_row_height(
     row: &Row,
     columns: &[f64],
+    covering: &[Option<&PendingSpan>],
     default_style: &CellStyle,
     tt_fonts: &[TrueTypeFont],
-) -> f64 {
+) -> (f64, Vec<Option<Vec<String>>>) {
     if let Some(h) = row.height {
-        return h;
-    }
-    columns
-        .iter()
-        .enumerate()
-        .map(|(col_idx, &col_width)| {
-            if let Some(cell) = row.cells.get(col_idx) {
-                measure_cell_height(&cell.text, &cell.style, col_width, tt_fonts)
-            } else {
-                // Empty column: height of one line plus padding
-                let ts = make_text_style(default_style);
-                line_height_for(&ts, tt_fonts) + 2.0 * default_style.padding
-            }
-        })
-        .fold(0.0_f64, f64::max)
+        return (h, Vec::new());
+    }
+    let mut has_cell = false;
+    let mut wrapped = Vec::with_capacity(columns.len());
+    let mut content = 0.0_f64;
+    for (col_idx, &col_width) in columns.iter().enumerate() {
+        let cell = row
+            .cells
+            .get(col_idx)
+            .filter(|_| !covering.get(col_idx).is_some_and(Option::is_some));
+        has_cell |= cell.is_some();
+        let Some(cell) = cell.filter(|cell| cell.rowspan <= 1) else {
+            wrapped.push(None);
+            continue;
+        };
+        let (height, lines) =
+            measure_cell(&cell.text, &cell.style, default_style, col_width, tt_fonts);
+        content = content.max(height);
+        wrapped.push(lines);
+    }
+    if has_cell || covering.iter().any(Option::is_some) {
+        (content, wrapped)
+    } else {
+        // No cells at all: height of one line plus padding
+        let ts = make_text_style(default_style);
+        (
+            line_height_for(&ts, tt_fonts) + 2.0 * default_style.padding,
+            wrapped,
+        )
+    }
 }
 
 /// Compute the height needed to display a cell's text content with wrapping.
-fn measure_cell_height(
+///
+/// Also returns the wrapped lines for `render_cell` to reuse; rotated cells
+/// are set as a single line and return `None`.
+fn measure_cell(
     text: &str,
     style: &CellStyle,
+    defaults: &CellStyle,
     col_width: f64,
     tt_fonts: &[TrueTypeFont],
-) -> f64 {
-    let avail_width = col_width - 2.0 * style.padding;
+) -> (f64, Option<Vec<String>>) {
     let ts = make_text_style(style);
     let lh = line_height_for(&ts, tt_fonts);
-    let lines = count_lines(text, avail_width, &ts, style.word_break, tt_fonts);
-    lines as f64 * lh + 2.0 * style.padding
+    if style.rotation != 0.0 {
+        let (sin, cos) = style.rotation.to_radians().sin_cos();
+        let width = measure_word(&rotated_line(text), &ts, tt_fonts);
+        return (
+            (width * sin).abs() + (lh * cos).abs() + 2.0 * style.padding,
+            None,
+        );
+    }
+    let avail_width = (col_width - 2.0 * style.padding).max(0.0);
+    let lines = wrap_text(
+        text,
+        avail_width,
+        &ts,
+        style.word_break_or(defaults),
+        style.hyphen_char_or(defaults),
+        tt_fonts,
+    );
+    let height = lines.len() as f64 * lh + 2.0 * style.padding;
+    (height, Some(lines))
+}
+
+/// The single line a rotated cell shows: its text with newlines as spaces.
+fn rotated_line(text: &str) -> String {
+    text.replace('\n', " ")
+}
+
+/// Widest cell per column (longest text line plus padding on both sides)
+/// over `header` and the first `sample_rows` of `rows`. Rows past the
+/// sample are never pulled from the iterator.
+pub(crate) fn natural_column_widths<I>(
+    header: &Row,
+    rows: I,
+    sample_rows: usize,
+    tt_fonts: &[TrueTypeFont],
+) -> Vec<f64>
+where
+    I: IntoIterator,
+    I::Item: Borrow<Row>,
+{
+    let mut widths: Vec<f64> = Vec::new();
+    let mut measure = |row: &Row| {
+        if widths.len() < row.cells.len() {
+            widths.resize(row.cells.len(), 0.0);
+        }
+        for (width, cell) in widths.iter_mut().zip(&row.cells) {
+            let style = make_text_style(&cell.style);
+            let text = cell
+                .text
+                .lines()
+                .map(|line| measure_word(line, &style, tt_fonts))
+                .fold(0.0, f64::max);
+            *width = width.max(text + 2.0 * cell.style.padding);
+        }
+    };
+    measure(header);
+    for row in rows.into_iter().take(sample_rows) {
+        measure(row.borrow());
+    }
+    widths
 }
 
 /// Convert a `CellStyle` to a `TextStyle` for use with measurement helpers.
@@ -343,6 +901,7 @@ fn make_text_style(style: &CellStyle) -> TextStyle {
     TextStyle {
         font: style.font,
         font_size: style.font_size,
+        ..TextStyle::default()
     }
 }
 
@@ -352,13 +911,14 @@ fn count_lines(
     avail_width: f64,
     style: &TextStyle,
     word_break: WordBreak,
+    hyphen: char,
     tt_fonts: &[TrueTypeFont],
 ) -> usize {
     if text.is_empty() {
         return 1;
     }
     text.split('\n')
-        .map(|para| count_paragraph_lines(para, avail_width, style, word_break, tt_fonts))
+        .map(|para| count_paragraph_lines(para, avail_width, style, word_break, hyphen, tt_fonts))
         .sum::<usize>()
         .max(1)
 }
@@ -369,6 +929,7 @@ fn count_paragraph_lines(
     avail_width: f64,
     style: &TextStyle,
     word_break: WordBreak,
+    hyphen: char,
     tt_fonts: &[TrueTypeFont],
 ) -> usize {
     let text = text.trim();
@@ -392,13 +953,16 @@ fn count_paragraph_lines(
             line_width = word_w;
             // If this word still overflows on its own line, count extra lines.
             if word_break != WordBreak::Normal && word_w > avail_width {
-                lines += count_break_lines(word, avail_width, style, word_break, tt_fonts) - 1;
-                line_width = trailing_piece_width(word, avail_width, style, word_break, tt_fonts);
+                lines +=
+                    count_break_lines(word, avail_width, style, word_break, hyphen, tt_fonts) - 1;
+                line_width =
+                    trailing_piece_width(word, avail_width, style, word_break, hyphen, tt_fonts);
             }
         } else if word_break != WordBreak::Normal && word_w > avail_width {
             // First word on a fresh line and it's still too wide.
-            lines += count_break_lines(word, avail_width, style, word_break, tt_fonts) - 1;
-            line_width = trailing_piece_width(word, avail_width, style, word_break, tt_fonts);
+            lines += count_break_lines(word, avail_width, style, word_break, hyphen, tt_fonts) - 1;
+            line_width =
+                trailing_piece_width(word, avail_width, style, word_break, hyphen, tt_fonts);
         } else {
             line_width = needed;
         }
@@ -412,9 +976,10 @@ fn count_break_lines(
     avail_width: f64,
     style: &TextStyle,
     word_break: WordBreak,
+    hyphen: char,
     tt_fonts: &[TrueTypeFont],
 ) -> usize {
-    break_word(word, avail_width, style, word_break, tt_fonts).len()
+    break_word(word, avail_width, style, word_break, hyphen, tt_fonts).len()
 }
 
 /// Width of the last piece when a word is broken across lines.
@@ -423,19 +988,21 @@ fn trailing_piece_width(
     avail_width: f64,
     style: &TextStyle,
     word_break: WordBreak,
+    hyphen: char,
     tt_fonts: &[TrueTypeFont],
 ) -> f64 {
-    break_word(word, avail_width, style, word_break, tt_fonts)
+    break_word(word, avail_width, style, word_break, hyphen, tt_fonts)
         .last()
         .map_or(0.0, |p| measure_word(p, style, tt_fonts))
 }
 
 /// Word-wrap `text` into lines that fit within `avail_width`.
-fn wrap_text(
+pub(crate) fn wrap_text(
     text: &str,
     avail_width: f64,
     style: &TextStyle,
     word_break: WordBreak,
+    hyphen: char,
     tt_fonts: &[TrueTypeFont],
 ) -> Vec<String> {
     let mut lines: Vec<String> = Vec::new();
@@ -445,6 +1012,7 @@ fn wrap_text(
             avail_width,
             style,
             word_break,
+            hyphen,
             tt_fonts,
             &mut lines,
         );
@@ -461,6 +1029,7 @@ fn wrap_paragraph(
     avail_width: f64,
     style: &TextStyle,
     word_break: WordBreak,
+    hyphen: char,
     tt_fonts: &[TrueTypeFont],
     out: &mut Vec<String>,
 ) {
@@ -490,6 +1059,7 @@ fn wrap_paragraph(
                 avail_width,
                 style,
                 word_break,
+                hyphen,
                 tt_fonts,
                 &mut current_line,
                 &mut line_width,
@@ -503,6 +1073,7 @@ fn wrap_paragraph(
                 avail_width,
                 style,
                 word_break,
+                hyphen,
                 tt_fonts,
                 &mut current_line,
                 &mut line_width,
@@ -526,11 +1097,13 @@ fn wrap_paragraph(
 /// All full pieces except the last are pushed to `out`. The last piece is
 /// accumulated into `current_line`/`line_width` so subsequent words can
 /// continue on the same line.
+#[allow(clippy::too_many_arguments)]
 fn place_word_on_line(
     word: &str,
     avail_width: f64,
     style: &TextStyle,
     word_break: WordBreak,
+    hyphen: char,
     tt_fonts: &[TrueTypeFont],
     current_line: &mut String,
     line_width: &mut f64,
@@ -547,7 +1120,7 @@ fn place_word_on_line(
         return;
     }
 
-    let pieces = break_word(word, avail_width, style, word_break, tt_fonts);
+    let pieces = break_word(word, avail_width, style, word_break, hyphen, tt_fonts);
     let last_idx = pieces.len() - 1;
     for (i, piece) in pieces.into_iter().enumerate() {
         if i < last_idx {
@@ -585,6 +1158,8 @@ fn record_font(font: &FontRef, used: &mut UsedFonts) {
 
 /// Emit a text string using the correct encoding for the font type.
 fn emit_cell_text(text: &str, font: FontRef, tt_fonts: &mut [TrueTypeFont], output: &mut Vec<u8>) {
+    // Zero-width spaces are break hints only; they are measured as nothing.
+    let text = &text.replace(ZERO_WIDTH_SPACE, "");
     if text.is_empty() {
         return;
     }
@@ -594,8 +1169,8 @@ fn emit_cell_text(text: &str, font: FontRef, tt_fonts: &mut [TrueTypeFont], outp
             output.extend_from_slice(format!("({}) Tj\n", escaped).as_bytes());
         }
         FontRef::TrueType(id) => {
-            let hex = tt_fonts[id.0].encode_text_hex(text);
-            output.extend_from_slice(format!("{} Tj\n", hex).as_bytes());
+            let op = tt_fonts[id.0].encode_text_op(text);
+            output.extend_from_slice(format!("{}\n", op).as_bytes());
         }
     }
 }
@@ -603,110 +1178,181 @@ fn emit_cell_text(text: &str, font: FontRef, tt_fonts: &mut [TrueTypeFont], outp
 /// Draw row and cell background fills.
 ///
 /// Row background is drawn first; per-cell backgrounds overlay on top.
+/// `occupants` holds the cell filling each column, which for spanned
+/// columns is the spanning cell from an earlier row.
+#[allow(clippy::too_many_arguments)]
 fn draw_row_backgrounds(
-    row: &Row,
+    row_background: Option<Color>,
     columns: &[f64],
+    occupants: &[Option<&Cell>],
+    col_xs: &[f64],
     row_x: f64,
     row_top: f64,
     row_height: f64,
+    precision: usize,
     output: &mut Vec<u8>,
 ) {
     let row_bottom = row_top - row_height;
 
-    if let Some(bg) = row.background_color {
+    if let Some(bg) = row_background {
         let total_width: f64 = columns.iter().sum();
         output.extend_from_slice(
             format!(
                 "{} {} {} rg\n{} {} {} {} re\nf\n",
-                format_coord(bg.r),
-                format_coord(bg.g),
-                format_coord(bg.b),
-                format_coord(row_x),
-                format_coord(row_bottom),
-                format_coord(total_width),
-                format_coord(row_height),
+                format_coord(bg.r, precision),
+                format_coord(bg.g, precision),
+                format_coord(bg.b, precision),
+                format_coord(row_x, precision),
+                format_coord(row_bottom, precision),
+                format_coord(total_width, precision),
+                format_coord(row_height, precision),
             )
             .as_bytes(),
         );
     }
 
-    let mut col_x = row_x;
-    for (col_idx, &col_width) in columns.iter().enumerate() {
-        if let Some(cell) = row.cells.get(col_idx) {
+    for ((&col_width, &col_x), cell) in columns.iter().zip(col_xs).zip(occupants) {
+        if let Some(cell) = cell {
             if let Some(bg) = cell.style.background_color {
                 output.extend_from_slice(
                     format!(
                         "{} {} {} rg\n{} {} {} {} re\nf\n",
-                        format_coord(bg.r),
-                        format_coord(bg.g),
-                        format_coord(bg.b),
-                        format_coord(col_x),
-                        format_coord(row_bottom),
-                        format_coord(col_width),
-                        format_coord(row_height),
+                        format_coord(bg.r, precision),
+                        format_coord(bg.g, precision),
+                        format_coord(bg.b, precision),
+                        format_coord(col_x, precision),
+                        format_coord(row_bottom, precision),
+                        format_coord(col_width, precision),
+                        format_coord(row_height, precision),
                     )
                     .as_bytes(),
                 );
             }
         }
-        col_x += col_width;
     }
 }
 
 /// Draw row borders: outer rectangle plus vertical column dividers.
+///
+/// `open_top` / `open_bottom` mark columns (in visual order) whose top or
+/// bottom rule is omitted because a row span continues through it. When no
+/// column is open the outline is a single rectangle.
+#[allow(clippy::too_many_arguments)]
 fn draw_row_borders(
     columns: &[f64],
+    open_top: &[bool],
+    open_bottom: &[bool],
     row_x: f64,
     row_top: f64,
     row_height: f64,
     border_color: Color,
     border_width: f64,
+    border_dash: Option<&[f64]>,
+    precision: usize,
     output: &mut Vec<u8>,
 ) {
     let row_bottom = row_top - row_height;
     let total_width: f64 = columns.iter().sum();
 
+    begin_border(border_color, border_width, border_dash, precision, output);
+
+    if open_top.iter().chain(open_bottom).any(|&open| open) {
+        // Side edges, then the top and bottom rules column by column.
+        let row_right = row_x + total_width;
+        stroke_line(row_x, row_top, row_x, row_bottom, precision, output);
+        stroke_line(row_right, row_top, row_right, row_bottom, precision, output);
+        let mut col_x = row_x;
+        for (col_idx, &col_width) in columns.iter().enumerate() {
+            if !open_top.get(col_idx).copied().unwrap_or(false) {
+                stroke_line(
+                    col_x,
+                    row_top,
+                    col_x + col_width,
+                    row_top,
+                    precision,
+                    output,
+                );
+            }
+            if !open_bottom.get(col_idx).copied().unwrap_or(false) {
+                stroke_line(
+                    col_x,
+                    row_bottom,
+                    col_x + col_width,
+                    row_bottom,
+                    precision,
+                    output,
+                );
+            }
+            col_x += col_width;
+        }
+    } else {
+        // Outer rectangle of the row
+        output.extend_from_slice(
+            format!(
+                "{} {} {} {} re\nS\n",
+                format_coord(row_x, precision),
+                format_coord(row_bottom, precision),
+                format_coord(total_width, precision),
+                format_coord(row_height, precision),
+            )
+            .as_bytes(),
+        );
+    }
+
+    // Vertical column dividers (not drawn after the last column)
+    let mut col_x = row_x;
+    for &col_width in &columns[..columns.len().saturating_sub(1)] {
+        col_x += col_width;
+        stroke_line(col_x, row_top, col_x, row_bottom, precision, output);
+    }
+
+    end_border(border_dash, precision, output);
+}
+
+/// Open a `q` block with the border stroke color, width and dash pattern.
+fn begin_border(
+    border_color: Color,
+    border_width: f64,
+    border_dash: Option<&[f64]>,
+    precision: usize,
+    output: &mut Vec<u8>,
+) {
     output.extend_from_slice(b"q\n");
     output.extend_from_slice(
         format!(
             "{} {} {} RG\n{} w\n",
-            format_coord(border_color.r),
-            format_coord(border_color.g),
-            format_coord(border_color.b),
-            format_coord(border_width),
+            format_coord(border_color.r, precision),
+            format_coord(border_color.g, precision),
+            format_coord(border_color.b, precision),
+            format_coord(border_width, precision),
         )
         .as_bytes(),
     );
+    if let Some(pattern) = border_dash {
+        output.extend_from_slice(graphics::dash_op(pattern, 0.0, precision).as_bytes());
+    }
+}
 
-    // Outer rectangle of the row
+/// Close the block opened by `begin_border`.
+fn end_border(border_dash: Option<&[f64]>, precision: usize, output: &mut Vec<u8>) {
+    if border_dash.is_some() {
+        output.extend_from_slice(graphics::dash_op(&[], 0.0, precision).as_bytes());
+    }
+    output.extend_from_slice(b"Q\n");
+}
+
+/// Stroke a single straight border segment.
+fn stroke_line(x1: f64, y1: f64, x2: f64, y2: f64, precision: usize, output: &mut Vec<u8>) {
     output.extend_from_slice(
         format!(
-            "{} {} {} {} re\nS\n",
-            format_coord(row_x),
-            format_coord(row_bottom),
-            format_coord(total_width),
-            format_coord(row_height),
+            "{} {} m\n{} {} l\nS\n",
+            format_coord(x1, precision),
+            format_coord(y1, precision),
+            format_coord(x2, precision),
+            format_coord(y2, precision),
         )
         .as_bytes(),
     );
-
-    // Vertical column dividers (not drawn after the last column)
-    let mut col_x = row_x;
-    for &col_width in &columns[..columns.len().saturating_sub(1)] {
-        col_x += col_width;
-        output.extend_from_slice(
-            format!(
-                "{} {} m\n{} {} l\nS\n",
-                format_coord(col_x),
-                format_coord(row_top),
-                format_coord(col_x),
-                format_coord(row_bottom),
-            )
-            .as_bytes(),
-        );
-    }
-
-    output.extend_from_slice(b"Q\n");
 }
 
 /// Compute the x coordinate for a line of text within a cell based on alignment.
@@ -736,30 +1382,54 @@ fn aligned_x(
 /// Render the text content of a single cell.
 ///
 /// Wraps each cell in `q/Q` to isolate graphics state. Applies clip region
-/// for `Clip` mode and reduces font size for `Shrink` mode.
+/// for `Clip` mode and reduces font size for `Shrink` mode. `wrapped` holds
+/// the lines from the measure pass, used unless `Shrink` changes the size.
+#[allow(clippy::too_many_arguments)]
 fn render_cell(
     cell: &Cell,
+    wrapped: Option<Vec<String>>,
+    defaults: &CellStyle,
     cell_x: f64,
     row_top: f64,
     col_width: f64,
     row_height: f64,
     tt_fonts: &mut [TrueTypeFont],
+    precision: usize,
     output: &mut Vec<u8>,
     used: &mut UsedFonts,
 ) {
     let style = &cell.style;
+    let overflow = style.overflow_or(defaults);
+    if style.rotation != 0.0 {
+        render_rotated_cell(
+            cell,
+            overflow == CellOverflow::Clip,
+            cell_x,
+            row_top,
+            col_width,
+            row_height,
+            tt_fonts,
+            precision,
+            output,
+            used,
+        );
+        return;
+    }
+    let word_break = style.word_break_or(defaults);
+    let hyphen = style.hyphen_char_or(defaults);
     let avail_width = (col_width - 2.0 * style.padding).max(0.0);
     let avail_height = (row_height - 2.0 * style.padding).max(0.0);
 
     // Resolve effective font size (may be reduced for Shrink mode)
-    let effective_font_size = if style.overflow == CellOverflow::Shrink {
+    let effective_font_size = if overflow == CellOverflow::Shrink {
         shrink_font_size(
             &cell.text,
             style.font,
             style.font_size,
             avail_width,
             avail_height,
-            style.word_break,
+            word_break,
+            hyphen,
             tt_fonts,
         )
     } else {
@@ -769,30 +1439,42 @@ fn render_cell(
     let ts = TextStyle {
         font: style.font,
         font_size: effective_font_size,
+        ..TextStyle::default()
     };
     let lh = line_height_for(&ts, tt_fonts);
-    let lines = wrap_text(&cell.text, avail_width, &ts, style.word_break, tt_fonts);
+    let lines = match wrapped {
+        Some(lines) if effective_font_size == style.font_size => lines,
+        _ => wrap_text(&cell.text, avail_width, &ts, word_break, hyphen, tt_fonts),
+    };
+
+    // Baseline: top of cell minus top padding minus font size (approximates ascent),
+    // shifted down by the vertical-align offset. The offset goes negative when a
+    // clipped block is taller than the cell, so the clip shows its middle lines.
+    let block_height = lines.len() as f64 * lh;
+    let v_offset = match style.vertical_align {
+        VerticalAlign::Top => 0.0,
+        VerticalAlign::Middle => (avail_height - block_height) / 2.0,
+        VerticalAlign::Bottom => avail_height - block_height,
+    };
+    let first_line_y = row_top - style.padding - v_offset - effective_font_size;
 
     output.extend_from_slice(b"q\n");
 
     // Apply clipping rectangle for Clip mode
-    if style.overflow == CellOverflow::Clip {
+    if overflow == CellOverflow::Clip {
         let clip_bottom = row_top - row_height;
         output.extend_from_slice(
             format!(
                 "{} {} {} {} re\nW\nn\n",
-                format_coord(cell_x),
-                format_coord(clip_bottom),
-                format_coord(col_width),
-                format_coord(row_height),
+                format_coord(cell_x, precision),
+                format_coord(clip_bottom, precision),
+                format_coord(col_width, precision),
+                format_coord(row_height, precision),
             )
             .as_bytes(),
         );
     }
 
-    // Baseline: top of cell minus top padding minus font size (approximates ascent)
-    let first_line_y = row_top - style.padding - effective_font_size;
-
     output.extend_from_slice(b"BT\n");
 
     // Always set an explicit fill color for text. Without this, the fill
@@ -804,37 +1486,53 @@ fn render_cell(
     output.extend_from_slice(
         format!(
             "{} {} {} rg\n",
-            format_coord(text_color.r),
-            format_coord(text_color.g),
-            format_coord(text_color.b),
+            format_coord(text_color.r, precision),
+            format_coord(text_color.g, precision),
+            format_coord(text_color.b, precision),
         )
         .as_bytes(),
     );
 
     let font_name = pdf_font_name(ts.font, tt_fonts);
     output.extend_from_slice(
-        format!("/{} {} Tf\n", font_name, format_coord(effective_font_size)).as_bytes(),
+        format!(
+            "/{} {} Tf\n",
+            font_name,
+            format_coord(effective_font_size, precision)
+        )
+        .as_bytes(),
     );
     record_font(&ts.font, used);
 
     let align = style.text_align;
-    let mut current_x = cell_x + style.padding; // placeholder; overwritten on first line
+    // Placeholder; overwritten on the first line.
+    let mut current_x = cell_x + style.padding;
+    // Start x and width of each line, for underlining after the text block.
+    let mut line_spans: Vec<(f64, f64)> = Vec::with_capacity(lines.len());
 
     for (i, line) in lines.iter().enumerate() {
         let line_x = aligned_x(line, align, cell_x, col_width, style.padding, &ts, tt_fonts);
+        if style.underline != UnderlineStyle::None {
+            line_spans.push((line_x, measure_word(line, &ts, tt_fonts)));
+        }
         if i == 0 {
             output.extend_from_slice(
                 format!(
                     "{} {} Td\n",
-                    format_coord(line_x),
-                    format_coord(first_line_y)
+                    format_coord(line_x, precision),
+                    format_coord(first_line_y, precision)
                 )
                 .as_bytes(),
             );
         } else {
             let dx = line_x - current_x;
             output.extend_from_slice(
-                format!("{} {} Td\n", format_coord(dx), format_coord(-lh)).as_bytes(),
+                format!(
+                    "{} {} Td\n",
+                    format_coord(dx, precision),
+                    format_coord(-lh, precision)
+                )
+                .as_bytes(),
             );
         }
         current_x = line_x;
@@ -842,15 +1540,138 @@ fn render_cell(
     }
 
     output.extend_from_slice(b"ET\n");
+    if !line_spans.is_empty() {
+        draw_underlines(
+            &line_spans,
+            style.underline,
+            text_color,
+            first_line_y,
+            lh,
+            effective_font_size,
+            precision,
+            output,
+        );
+    }
     output.extend_from_slice(b"Q\n");
 }
 
+/// Draw a cell's text as one line rotated by `style.rotation` about the
+/// cell centre, positioned with a `Tm` text matrix.
+#[allow(clippy::too_many_arguments)]
+fn render_rotated_cell(
+    cell: &Cell,
+    clip: bool,
+    cell_x: f64,
+    row_top: f64,
+    col_width: f64,
+    row_height: f64,
+    tt_fonts: &mut [TrueTypeFont],
+    precision: usize,
+    output: &mut Vec<u8>,
+    used: &mut UsedFonts,
+) {
+    let style = &cell.style;
+    let ts = make_text_style(style);
+    let line = rotated_line(&cell.text);
+    let width = measure_word(&line, &ts, tt_fonts);
+    let (sin, cos) = style.rotation.to_radians().sin_cos();
+    // Snap trig residue so 90 degrees is written as 0 1 -1 0, not 6e-17.
+    let snap = |v: f64| if v.abs() < 1e-9 { 0.0 } else { v };
+    let (sin, cos) = (snap(sin), snap(cos));
+
+    // Centre the line on the cell: in text space the origin sits half the
+    // width to the left and about a third of the font size below the middle
+    // of the glyphs, then that offset is rotated into page space.
+    let (cx, cy) = (cell_x + col_width / 2.0, row_top - row_height / 2.0);
+    let (dx, dy) = (-width / 2.0, -style.font_size / 3.0);
+    let origin_x = cx + dx * cos - dy * sin;
+    let origin_y = cy + dx * sin + dy * cos;
+
+    output.extend_from_slice(b"q\n");
+    if clip {
+        output.extend_from_slice(
+            format!(
+                "{} {} {} {} re\nW\nn\n",
+                format_coord(cell_x, precision),
+                format_coord(row_top - row_height, precision),
+                format_coord(col_width, precision),
+                format_coord(row_height, precision),
+            )
+            .as_bytes(),
+        );
+    }
+    let text_color = style
+        .text_color
+        .unwrap_or_else(|| Color::rgb(0.0, 0.0, 0.0));
+    output.extend_from_slice(
+        format!(
+            "BT\n{} {} {} rg\n/{} {} Tf\n{} {} {} {} {} {} Tm\n",
+            format_coord(text_color.r, precision),
+            format_coord(text_color.g, precision),
+            format_coord(text_color.b, precision),
+            pdf_font_name(ts.font, tt_fonts),
+            format_coord(style.font_size, precision),
+            format_coord(cos, precision),
+            format_coord(sin, precision),
+            format_coord(-sin, precision),
+            format_coord(cos, precision),
+            format_coord(origin_x, precision),
+            format_coord(origin_y, precision),
+        )
+        .as_bytes(),
+    );
+    record_font(&ts.font, used);
+    emit_cell_text(&line, ts.font, tt_fonts, output);
+    output.extend_from_slice(b"ET\nQ\n");
+}
+
+/// Stroke underline rules beneath each line of a cell's text.
+///
+/// Rule thickness and offsets scale with the font size: the first rule
+/// sits 0.1em below the baseline and a second rule (for `Double`) a
+/// further 0.15em down.
+#[allow(clippy::too_many_arguments)]
+fn draw_underlines(
+    line_spans: &[(f64, f64)],
+    underline: UnderlineStyle,
+    color: Color,
+    first_line_y: f64,
+    line_height: f64,
+    font_size: f64,
+    precision: usize,
+    output: &mut Vec<u8>,
+) {
+    let offsets: &[f64] = match underline {
+        UnderlineStyle::None => return,
+        UnderlineStyle::Single => &[0.1],
+        UnderlineStyle::Double => &[0.1, 0.25],
+    };
+    output.extend_from_slice(
+        format!(
+            "{} {} {} RG\n{} w\n",
+            format_coord(color.r, precision),
+            format_coord(color.g, precision),
+            format_coord(color.b, precision),
+            format_coord(font_size * 0.05, precision),
+        )
+        .as_bytes(),
+    );
+    for (i, &(x, width)) in line_spans.iter().enumerate() {
+        let baseline = first_line_y - i as f64 * line_height;
+        for offset in offsets {
+            let y = baseline - offset * font_size;
+            stroke_line(x, y, x + width, y, precision, output);
+        }
+    }
+}
+
 /// Reduce font size by 0.5pt steps until the text fits within the available
 /// dimensions, stopping at a minimum of 4pt.
 ///
 /// When `word_break` is not `Normal`, every word can be broken, so only the
 /// height constraint needs to be satisfied. When `Normal`, width must also
 /// fit (a word wider than the column can never wrap — only shrinking helps).
+#[allow(clippy::too_many_arguments)]
 fn shrink_font_size(
     text: &str,
     font: FontRef,
@@ -858,6 +1679,7 @@ fn shrink_font_size(
     avail_width: f64,
     avail_height: f64,
     word_break: WordBreak,
+    hyphen: char,
     tt_fonts: &[TrueTypeFont],
 ) -> f64 {
     const MIN_FONT_SIZE: f64 = 4.0;
@@ -865,9 +1687,13 @@ fn shrink_font_size(
 
     let mut font_size = initial_size;
     loop {
-        let ts = TextStyle { font, font_size };
+        let ts = TextStyle {
+            font,
+            font_size,
+            ..TextStyle::default()
+        };
         let lh = line_height_for(&ts, tt_fonts);
-        let lines = count_lines(text, avail_width, &ts, word_break, tt_fonts);
+        let lines = count_lines(text, avail_width, &ts, word_break, hyphen, tt_fonts);
         let fits_height = lines as f64 * lh <= avail_height;
         let fits_width = word_break != WordBreak::Normal
             || text
@@ -1,10 +1,18 @@
-use std::collections::BTreeSet;
+use std::collections::{BTreeMap, BTreeSet};
 
 use crate::document::format_coord;
 use crate::fonts::{BuiltinFont, FontMetrics, FontRef};
+use crate::images::ImageId;
 use crate::truetype::TrueTypeFont;
 use crate::writer::escape_pdf_string;
 
+/// Joins two words so they stay on one line; measured as a space.
+const NO_BREAK_SPACE: char = '\u{a0}';
+/// A line may break after an em dash even without a following space.
+const EM_DASH: char = '\u{2014}';
+/// Marks a break opportunity inside a word; never rendered or measured.
+pub(crate) const ZERO_WIDTH_SPACE: char = '\u{200b}';
+
 /// Controls how words wider than the available box width are handled.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum WordBreak {
@@ -38,11 +46,14 @@ pub struct Rect {
     pub height: f64,
 }
 
-/// Tracks which fonts were actually used during content generation.
+/// Tracks which fonts (and inline images) were actually used during
+/// content generation.
 #[derive(Debug, Default)]
 pub struct UsedFonts {
     pub builtin: BTreeSet<BuiltinFont>,
     pub truetype: BTreeSet<usize>,
+    /// Indices of images placed with `TextFlow::add_inline_image`.
+    pub images: BTreeSet<usize>,
 }
 
 /// Text styling options.
@@ -50,6 +61,10 @@ pub struct UsedFonts {
 pub struct TextStyle {
     pub font: FontRef,
     pub font_size: f64,
+    /// Multiplier for the width of inter-word spaces in a `TextFlow`
+    /// (default 1.0). Values below 1.0 tighten spacing, above loosen it;
+    /// line wrapping uses the same scaled width.
+    pub space_width_scale: f64,
 }
 
 impl Default for TextStyle {
@@ -57,6 +72,7 @@ impl Default for TextStyle {
         TextStyle {
             font: FontRef::Builtin(BuiltinFont::Helvetica),
             font_size: 12.0,
+            space_width_scale: 1.0,
         }
     }
 }
@@ -67,24 +83,37 @@ impl TextStyle {
         TextStyle {
             font: FontRef::Builtin(font),
             font_size,
+            space_width_scale: 1.0,
         }
     }
 }
 
-/// A span of text with associated style.
+/// An image that flows with the text as a word of its own.
+#[derive(Debug, Clone, Copy)]
+struct InlineImage {
+    id: ImageId,
+    width: f64,
+    height: f64,
+    /// Distance of the image's bottom edge above the baseline.
+    baseline_offset: f64,
+}
+
+/// A span of text with associated style, or an inline image.
 #[derive(Debug, Clone)]
 struct TextSpan {
     text: String,
     style: TextStyle,
+    image: Option<InlineImage>,
 }
 
 /// A word extracted from spans, carrying its style and whether
-/// it is preceded by a space.
+/// it is preceded by a space. An inline image is a word with empty text.
 #[derive(Debug, Clone)]
 struct Word {
     text: String,
     style: TextStyle,
     leading_space: bool,
+    image: Option<InlineImage>,
 }
 
 /// A TextFlow manages styled text and flows it into bounding boxes
@@ -96,6 +125,26 @@ pub struct TextFlow {
     cursor: usize,
     /// How to handle words wider than the bounding box.
     pub word_break: WordBreak,
+    /// Character appended to broken pieces in `WordBreak::Hyphenate` mode
+    /// (default `-`). Use e.g. `'\u{2010}'` for a typographic hyphen.
+    pub hyphen_char: char,
+    /// Place the first baseline one real font ascent below the rect top
+    /// (TrueType ascent, builtin cap height) instead of one font size.
+    /// Off by default.
+    pub use_real_ascent: bool,
+    /// Total line height placed by the most recent `generate_content_ops` call.
+    last_fit_height: f64,
+    /// Number of words `extract_words` yields, kept up to date by `add_text`.
+    word_count: usize,
+    /// Length of the laid-out word list (after wide-word breaking) from the
+    /// most recent `generate_content_ops` call; `None` after `add_text`.
+    laid_out_count: Option<usize>,
+}
+
+impl Default for TextFlow {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl TextFlow {
@@ -104,21 +153,82 @@ impl TextFlow {
             spans: Vec::new(),
             cursor: 0,
             word_break: WordBreak::BreakAll,
+            hyphen_char: '-',
+            use_real_ascent: false,
+            last_fit_height: 0.0,
+            word_count: 0,
+            laid_out_count: None,
         }
     }
 
     /// Add styled text to the flow.
     pub fn add_text(&mut self, text: &str, style: &TextStyle) {
+        self.word_count += count_words(text);
+        self.laid_out_count = None;
         self.spans.push(TextSpan {
             text: text.to_string(),
             style: style.clone(),
+            image: None,
         });
     }
 
+    /// Add an image that flows with the text like a word, e.g. an icon.
+    ///
+    /// The image is `width` x `height` points and counts `width` towards
+    /// line breaking. Its bottom edge sits `baseline_offset` points above
+    /// the baseline (negative to drop below it). Spaces around it follow
+    /// the neighbouring text, so `"Click "` then the image then `" to"`
+    /// puts a space on either side. Line height still comes from the text
+    /// style, so an image taller than the line overlaps the line above.
+    pub fn add_inline_image(&mut self, id: ImageId, width: f64, height: f64, baseline_offset: f64) {
+        self.word_count += 1;
+        self.laid_out_count = None;
+        let style = self
+            .spans
+            .last()
+            .map(|span| span.style.clone())
+            .unwrap_or_default();
+        self.spans.push(TextSpan {
+            text: String::new(),
+            style,
+            image: Some(InlineImage {
+                id,
+                width,
+                height,
+                baseline_offset,
+            }),
+        });
+    }
+
+    /// Images added with `add_inline_image`, in flow order.
+    pub(crate) fn inline_images(&self) -> impl Iterator<Item = ImageId> + '_ {
+        self.spans
+            .iter()
+            .filter_map(|span| span.image.map(|image| image.id))
+    }
+
     /// Returns true if all text has been consumed.
+    ///
+    /// Uses cached word counts, so it is cheap to call once per page.
     pub fn is_finished(&self) -> bool {
-        let words = self.extract_words();
-        self.cursor >= words.len()
+        self.remaining_words() == 0
+    }
+
+    /// Number of words (including forced line breaks) not yet placed.
+    ///
+    /// After a fit, words split by `word_break` count as their pieces.
+    pub fn remaining_words(&self) -> usize {
+        self.laid_out_count
+            .unwrap_or(self.word_count)
+            .saturating_sub(self.cursor)
+    }
+
+    /// Height in points consumed by the most recent fit, measured as the sum
+    /// of the placed lines' heights. Zero if nothing was placed.
+    ///
+    /// Lets callers stack further content directly below a paragraph.
+    pub fn last_fit_height(&self) -> f64 {
+        self.last_fit_height
     }
 
     /// Extract all words from spans, splitting on whitespace and
@@ -127,52 +237,84 @@ impl TextFlow {
         let mut words = Vec::new();
         let mut had_space = false;
         for span in &self.spans {
-            let mut chars = span.text.chars().peekable();
-
-            while chars.peek().is_some() {
-                // Consume leading spaces
-                while chars.peek() == Some(&' ') {
-                    had_space = true;
-                    chars.next();
-                }
-
-                if chars.peek() == Some(&'\n') {
-                    chars.next();
-                    words.push(Word {
-                        text: "\n".to_string(),
-                        style: span.style.clone(),
-                        leading_space: false,
-                    });
-                    had_space = false;
-                    continue;
-                }
-
-                // Collect word characters
-                let mut word = String::new();
-                while let Some(&ch) = chars.peek() {
-                    if ch == ' ' || ch == '\n' {
-                        break;
+            if let Some(image) = span.image {
+                words.push(Word {
+                    text: String::new(),
+                    style: span.style.clone(),
+                    leading_space: had_space && !words.is_empty(),
+                    image: Some(image),
+                });
+                had_space = false;
+                continue;
+            }
+            for token in span_tokens(&span.text) {
+                match token {
+                    SpanToken::Space => had_space = true,
+                    SpanToken::Newline => {
+                        words.push(Word {
+                            text: "\n".to_string(),
+                            style: span.style.clone(),
+                            leading_space: false,
+                            image: None,
+                        });
+                        had_space = false;
+                    }
+                    SpanToken::Word(text) => {
+                        words.push(Word {
+                            text: text.to_string(),
+                            style: span.style.clone(),
+                            leading_space: had_space && !words.is_empty(),
+                            image: None,
+                        });
+                        had_space = false;
                     }
-                    word.push(ch);
-                    chars.next();
-                }
-
-                if !word.is_empty() {
-                    words.push(Word {
-                        text: word,
-                        style: span.style.clone(),
-                        leading_space: had_space && !words.is_empty(),
-                    });
-                    had_space = false;
                 }
             }
         }
         words
     }
 
+    /// Height and line count of the remaining text when wrapped to `width`,
+    /// without moving the cursor or emitting content. Heights add up the
+    /// same way `last_fit_height` does for a box tall enough to hold
+    /// everything.
+    pub(crate) fn measure_lines(&self, width: f64, tt_fonts: &[TrueTypeFont]) -> (f64, usize) {
+        let words = self.laid_out_words(width, tt_fonts);
+        let mut height = 0.0;
+        let mut lines = 0;
+        let mut start = self.cursor;
+        while start < words.len() {
+            let (end, _) = line_break(&words, start, width, tt_fonts);
+            height += line_height_for(&words[start].style, tt_fonts);
+            lines += 1;
+            start = end;
+        }
+        (height, lines)
+    }
+
+    /// The word list as laid out at `width`: extracted words with wide
+    /// words broken according to `word_break`.
+    fn laid_out_words(&self, width: f64, tt_fonts: &[TrueTypeFont]) -> Vec<Word> {
+        let raw_words = self.extract_words();
+        if self.word_break != WordBreak::Normal {
+            break_wide_words(
+                raw_words,
+                width,
+                self.word_break,
+                self.hyphen_char,
+                tt_fonts,
+            )
+        } else {
+            raw_words
+        }
+    }
+
     /// Generate PDF content stream operations that fit within
     /// the given rectangle. Returns the content bytes, a
-    /// FitResult, and the fonts actually used.
+    /// FitResult, and the fonts and inline images actually used.
+    ///
+    /// `image_names` maps the index of each inline image to its XObject
+    /// resource name.
     ///
     /// **Multi-page stability:** when `word_break` is not `Normal`, the word
     /// list is pre-processed by `break_wide_words` before layout. That
@@ -183,14 +325,13 @@ impl TextFlow {
         &mut self,
         rect: &Rect,
         tt_fonts: &mut [TrueTypeFont],
+        image_names: &BTreeMap<usize, String>,
+        precision: usize,
     ) -> (Vec<u8>, FitResult, UsedFonts) {
+        self.last_fit_height = 0.0;
         let empty = UsedFonts::default();
-        let raw_words = self.extract_words();
-        let words = if self.word_break != WordBreak::Normal {
-            break_wide_words(raw_words, rect.width, self.word_break, tt_fonts)
-        } else {
-            raw_words
-        };
+        let words = self.laid_out_words(rect.width, tt_fonts);
+        self.laid_out_count = Some(words.len());
         if self.cursor >= words.len() {
             return (Vec::new(), FitResult::Stop, empty);
         }
@@ -208,8 +349,14 @@ impl TextFlow {
         output.extend_from_slice(b"BT\n");
 
         // First baseline: top of rect minus ascent (approximated
-        // as font_size since line_height ~ font_size * 1.2).
-        let first_baseline_y = rect.y - first_word.style.font_size;
+        // as font_size since line_height ~ font_size * 1.2, unless the
+        // real ascent was asked for).
+        let ascent = if self.use_real_ascent {
+            ascent_for(&first_word.style, tt_fonts)
+        } else {
+            first_word.style.font_size
+        };
+        let first_baseline_y = rect.y - ascent;
         let mut current_y = first_baseline_y;
         let mut is_first_line = true;
         let mut any_text_placed = false;
@@ -232,39 +379,9 @@ impl TextFlow {
 
             // Collect words that fit on this line
             let line_start = self.cursor;
-            let mut line_width: f64 = 0.0;
-            let mut line_end = self.cursor;
-
-            while line_end < words.len() {
-                let word = &words[line_end];
-
-                if word.text == "\n" {
-                    line_end += 1;
-                    break;
-                }
-
-                let word_width = measure_word(&word.text, &word.style, tt_fonts);
-                let space_width = if word.leading_space {
-                    measure_word(" ", &word.style, tt_fonts)
-                } else {
-                    0.0
-                };
-
-                let total = line_width + space_width + word_width;
-                if total > rect.width && line_end > line_start {
-                    break;
-                }
-                if total > rect.width && line_end == line_start {
-                    if !any_text_placed {
-                        output.extend_from_slice(b"ET\n");
-                        return (Vec::new(), FitResult::BoxEmpty, UsedFonts::default());
-                    }
-                    line_end += 1;
-                    break;
-                }
-
-                line_width = total;
-                line_end += 1;
+            let (line_end, overflows) = line_break(&words, line_start, rect.width, tt_fonts);
+            if overflows && !any_text_placed {
+                return (Vec::new(), FitResult::BoxEmpty, UsedFonts::default());
             }
 
             if line_end == line_start {
@@ -276,22 +393,25 @@ impl TextFlow {
                 output.extend_from_slice(
                     format!(
                         "{} {} Td\n",
-                        format_coord(rect.x),
-                        format_coord(first_baseline_y),
+                        format_coord(rect.x, precision),
+                        format_coord(first_baseline_y, precision),
                     )
                     .as_bytes(),
                 );
                 is_first_line = false;
+                self.last_fit_height += first_line_height;
             } else {
                 output.extend_from_slice(
-                    format!("0 {} Td\n", format_coord(-line_height),).as_bytes(),
+                    format!("0 {} Td\n", format_coord(-line_height, precision),).as_bytes(),
                 );
                 current_y -= line_height;
+                self.last_fit_height += line_height;
             }
 
-            // Emit words for this line
-            for i in line_start..line_end {
-                let word = &words[i];
+            // Emit words for this line, tracking the pen's distance from
+            // the line start for inline images.
+            let mut pen_x = 0.0;
+            for (offset, word) in words[line_start..line_end].iter().enumerate() {
                 if word.text == "\n" {
                     continue;
                 }
@@ -302,20 +422,70 @@ impl TextFlow {
                 if active_font != Some(font_ref) || active_size != Some(font_size) {
                     let name = pdf_font_name(font_ref, tt_fonts);
                     output.extend_from_slice(
-                        format!("/{} {} Tf\n", name, format_coord(font_size),).as_bytes(),
+                        format!("/{} {} Tf\n", name, format_coord(font_size, precision),)
+                            .as_bytes(),
                     );
                     active_font = Some(font_ref);
                     active_size = Some(font_size);
                     record_font(&font_ref, &mut used);
                 }
 
-                let is_first_on_line = i == line_start;
-                let display_text = if word.leading_space && !is_first_on_line {
+                let is_first_on_line = offset == 0;
+                let spaced = word.leading_space && !is_first_on_line;
+                if spaced {
+                    pen_x += space_width_for(&word.style, tt_fonts);
+                }
+                let display_text = if spaced {
+                    let scale = word.style.space_width_scale;
+                    if scale != 1.0 {
+                        // Shift the pen by the difference between the scaled and
+                        // natural space, in thousandths of an em (TJ units).
+                        let adjust = (1.0 - scale) * measure_word(" ", &word.style, tt_fonts)
+                            / font_size
+                            * 1000.0;
+                        output.extend_from_slice(
+                            format!("[{}] TJ\n", format_coord(adjust, precision)).as_bytes(),
+                        );
+                    }
                     format!(" {}", word.text)
                 } else {
                     word.text.clone()
                 };
 
+                if let Some(image) = word.image {
+                    if !display_text.is_empty() {
+                        emit_text(&display_text, font_ref, tt_fonts, &mut output);
+                    }
+                    // XObjects cannot be painted inside a text object: close
+                    // it, paint the image, and reopen at the line start with
+                    // the pen moved past the image.
+                    let name = &image_names[&image.id.0];
+                    output.extend_from_slice(
+                        format!(
+                            "ET\nq\n{} 0 0 {} {} {} cm\n/{} Do\nQ\nBT\n{} {} Td\n",
+                            format_coord(image.width, precision),
+                            format_coord(image.height, precision),
+                            format_coord(rect.x + pen_x, precision),
+                            format_coord(current_y + image.baseline_offset, precision),
+                            name,
+                            format_coord(rect.x, precision),
+                            format_coord(current_y, precision),
+                        )
+                        .as_bytes(),
+                    );
+                    pen_x += image.width;
+                    output.extend_from_slice(
+                        format!(
+                            "[{}] TJ\n",
+                            format_coord(-pen_x / font_size * 1000.0, precision)
+                        )
+                        .as_bytes(),
+                    );
+                    used.images.insert(image.id.0);
+                    continue;
+                }
+
+                pen_x += measure_word(&word.text, &word.style, tt_fonts);
                 emit_text(&display_text, font_ref, tt_fonts, &mut output);
             }
 
@@ -334,6 +504,61 @@ impl TextFlow {
     }
 }
 
+/// Number of entries `extract_words` produces for `text`. Words never
+/// continue across spans, so counts of separate spans add up.
+fn count_words(text: &str) -> usize {
+    span_tokens(text)
+        .filter(|token| !matches!(token, SpanToken::Space))
+        .count()
+}
+
+/// A piece of span text as `extract_words` sees it.
+enum SpanToken<'a> {
+    /// A run of one or more spaces.
+    Space,
+    /// A line break.
+    Newline,
+    /// Word characters, never empty.
+    Word(&'a str),
+}
+
+/// Split span text into spaces, newlines and words.
+///
+/// A no-break space is part of a word; an em dash ends the word it is in
+/// so the line may break after it, and a zero-width space ends a word
+/// without being kept.
+fn span_tokens(text: &str) -> impl Iterator<Item = SpanToken<'_>> {
+    let mut rest = text;
+    std::iter::from_fn(move || loop {
+        let trimmed = rest.trim_start_matches(' ');
+        if trimmed.len() != rest.len() {
+            rest = trimmed;
+            return Some(SpanToken::Space);
+        }
+        if let Some(after) = rest.strip_prefix('\n') {
+            rest = after;
+            return Some(SpanToken::Newline);
+        }
+        if rest.is_empty() {
+            return None;
+        }
+        let (end, next) = rest
+            .char_indices()
+            .find_map(|(i, ch)| match ch {
+                ' ' | '\n' => Some((i, i)),
+                ZERO_WIDTH_SPACE => Some((i, i + ch.len_utf8())),
+                EM_DASH => Some((i + ch.len_utf8(), i + ch.len_utf8())),
+                _ => None,
+            })
+            .unwrap_or((rest.len(), rest.len()));
+        let word = &rest[..end];
+        rest = &rest[next..];
+        if !word.is_empty() {
+            return Some(SpanToken::Word(word));
+        }
+    })
+}
+
 /// Split any word wider than `max_width` into character-boundary pieces.
 ///
 /// Words that fit are left unchanged. Words that exceed `max_width` are split
@@ -347,6 +572,7 @@ fn break_wide_words(
     words: Vec<Word>,
     max_width: f64,
     mode: WordBreak,
+    hyphen: char,
     tt_fonts: &[TrueTypeFont],
 ) -> Vec<Word> {
     let mut result: Vec<Word> = Vec::with_capacity(words.len());
@@ -358,16 +584,12 @@ fn break_wide_words(
         }
 
         let word_width = measure_word(&word.text, &word.style, tt_fonts);
-        if word_width <= max_width {
+        if word_width <= max_width || word.image.is_some() {
             result.push(word);
             continue;
         }
 
-        let ts = TextStyle {
-            font: word.style.font,
-            font_size: word.style.font_size,
-        };
-        let pieces = break_word(&word.text, max_width, &ts, mode, tt_fonts);
+        let pieces = break_word(&word.text, max_width, &word.style, mode, hyphen, tt_fonts);
         let leading_space = word.leading_space;
 
         for (i, piece) in pieces.into_iter().enumerate() {
@@ -375,6 +597,7 @@ fn break_wide_words(
                 text: piece,
                 style: word.style.clone(),
                 leading_space: i == 0 && leading_space,
+                image: None,
             });
         }
     }
@@ -384,19 +607,24 @@ fn break_wide_words(
 
 /// Break a single word into pieces that each fit within `avail_width`.
 ///
-/// Returns at least one piece. In `Hyphenate` mode a `-` is appended to
-/// every piece except the last. Forward progress is always guaranteed: a
+/// Returns at least one piece. In `Hyphenate` mode `hyphen` is appended to
+/// every piece except the last, and its width is reserved in each piece. Forward progress is always guaranteed: a
 /// single character is always emitted even if it exceeds the budget, so
 /// the loop cannot run forever on a pathologically narrow box.
+///
+/// Zero-width spaces (U+200B) mark preferred break points: a piece ends
+/// after the last one that fits, without a hyphen, before falling back to
+/// a character break. They are dropped from the returned pieces.
 pub(crate) fn break_word(
     word: &str,
     avail_width: f64,
     style: &TextStyle,
     mode: WordBreak,
+    hyphen: char,
     tt_fonts: &[TrueTypeFont],
 ) -> Vec<String> {
     let hyphen_w = if mode == WordBreak::Hyphenate {
-        measure_word("-", style, tt_fonts)
+        measure_word(hyphen.encode_utf8(&mut [0; 4]), style, tt_fonts)
     } else {
         0.0
     };
@@ -407,11 +635,23 @@ pub(crate) fn break_word(
         let budget = avail_width - hyphen_w;
         let mut prefix_end = 0;
         let mut prefix_width = 0.0;
+        let mut visible = String::new();
+        // End of the last zero-width space after some visible text.
+        let mut soft_break: Option<usize> = None;
 
         for ch in remaining.chars() {
             let next_end = prefix_end + ch.len_utf8();
-            let ch_w = measure_word(&remaining[..next_end], style, tt_fonts) - prefix_width;
-            if prefix_width + ch_w > budget && prefix_end > 0 {
+            if ch == ZERO_WIDTH_SPACE {
+                if !visible.is_empty() {
+                    soft_break = Some(next_end);
+                }
+                prefix_end = next_end;
+                continue;
+            }
+            let first_visible = visible.is_empty();
+            visible.push(ch);
+            let ch_w = measure_word(&visible, style, tt_fonts) - prefix_width;
+            if prefix_width + ch_w > budget && !first_visible {
                 break;
             }
             prefix_width += ch_w;
@@ -427,14 +667,26 @@ pub(crate) fn break_word(
             prefix_end = remaining.chars().next().map_or(0, |c| c.len_utf8());
         }
 
-        let is_last = prefix_end >= remaining.len();
-        let piece = if !is_last && mode == WordBreak::Hyphenate {
-            format!("{}-", &remaining[..prefix_end])
+        let mut hyphenate = mode == WordBreak::Hyphenate;
+        if let Some(end) = soft_break.filter(|_| prefix_end < remaining.len()) {
+            prefix_end = end;
+            hyphenate = false;
+        }
+        let text: String = remaining[..prefix_end]
+            .chars()
+            .filter(|&c| c != ZERO_WIDTH_SPACE)
+            .collect();
+        remaining = &remaining[prefix_end..];
+        if text.is_empty() {
+            continue;
+        }
+        let is_last = remaining.chars().all(|c| c == ZERO_WIDTH_SPACE);
+        let piece = if !is_last && hyphenate {
+            format!("{}{}", text, hyphen)
         } else {
-            remaining[..prefix_end].to_string()
+            text
         };
         pieces.push(piece);
-        remaining = &remaining[prefix_end..];
     }
     pieces
 }
@@ -447,8 +699,19 @@ pub(crate) fn line_height_for(style: &TextStyle, tt_fonts: &[TrueTypeFont]) -> f
     }
 }
 
+/// Real ascent of a style's font: TrueType ascent or builtin cap height.
+pub(crate) fn ascent_for(style: &TextStyle, tt_fonts: &[TrueTypeFont]) -> f64 {
+    match style.font {
+        FontRef::Builtin(b) => FontMetrics::cap_height(b, style.font_size),
+        FontRef::TrueType(id) => tt_fonts[id.0].scaled_ascent(style.font_size),
+    }
+}
+
 /// Measure a word's width based on font type.
 pub(crate) fn measure_word(text: &str, style: &TextStyle, tt_fonts: &[TrueTypeFont]) -> f64 {
+    if text.contains(ZERO_WIDTH_SPACE) {
+        return measure_word(&text.replace(ZERO_WIDTH_SPACE, ""), style, tt_fonts);
+    }
     match style.font {
         FontRef::Builtin(b) => FontMetrics::measure_text(text, b, style.font_size),
         FontRef::TrueType(id) => tt_fonts[id.0].measure_text(text, style.font_size),
@@ -475,17 +738,69 @@ fn record_font(font: &FontRef, used: &mut UsedFonts) {
     }
 }
 
+/// Find the end of the line starting at `start`: the index one past its
+/// last word. A line always takes at least one word; the returned flag is
+/// set when that single word is wider than `width`.
+fn line_break(
+    words: &[Word],
+    start: usize,
+    width: f64,
+    tt_fonts: &[TrueTypeFont],
+) -> (usize, bool) {
+    let mut line_width: f64 = 0.0;
+    let mut end = start;
+    while end < words.len() {
+        let word = &words[end];
+        if word.text == "\n" {
+            return (end + 1, false);
+        }
+
+        let space_width = if word.leading_space {
+            space_width_for(&word.style, tt_fonts)
+        } else {
+            0.0
+        };
+        let total = line_width + space_width + word_width(word, tt_fonts);
+        if total > width {
+            return if end > start {
+                (end, false)
+            } else {
+                (end + 1, true)
+            };
+        }
+
+        line_width = total;
+        end += 1;
+    }
+    (end, false)
+}
+
+/// Width of a word on the line: its text, or an inline image's width.
+fn word_width(word: &Word, tt_fonts: &[TrueTypeFont]) -> f64 {
+    match word.image {
+        Some(image) => image.width,
+        None => measure_word(&word.text, &word.style, tt_fonts),
+    }
+}
+
+/// Width of an inter-word space in `style`, after `space_width_scale`.
+fn space_width_for(style: &TextStyle, tt_fonts: &[TrueTypeFont]) -> f64 {
+    measure_word(" ", style, tt_fonts) * style.space_width_scale
+}
+
 /// Emit text as either literal `(text) Tj` for builtin fonts
-/// or hex `<glyph_ids> Tj` for TrueType fonts.
+/// or hex `<glyph_ids> Tj` (a kerned `TJ` array when pairs apply) for
+/// TrueType fonts.
 fn emit_text(text: &str, font: FontRef, tt_fonts: &mut [TrueTypeFont], output: &mut Vec<u8>) {
     match font {
         FontRef::Builtin(_) => {
-            let escaped = escape_pdf_string(text);
+            // Builtin fonts have no no-break space glyph; it renders as a space.
+            let escaped = escape_pdf_string(&text.replace(NO_BREAK_SPACE, " "));
             output.extend_from_slice(format!("({}) Tj\n", escaped).as_bytes());
         }
         FontRef::TrueType(id) => {
-            let hex = tt_fonts[id.0].encode_text_hex(text);
-            output.extend_from_slice(format!("{} Tj\n", hex).as_bytes());
+            let op = tt_fonts[id.0].encode_text_op(text);
+            output.extend_from_slice(format!("{}\n", op).as_bytes());
         }
     }
 }
@@ -512,7 +827,7 @@ mod break_word_tests {
     #[test]
     fn empty_word_returns_empty_vec() {
         // The outer while-loop exits immediately for an empty string.
-        let pieces = break_word("", 100.0, &hv12(), WordBreak::BreakAll, &[]);
+        let pieces = break_word("", 100.0, &hv12(), WordBreak::BreakAll, '-', &[]);
         assert!(pieces.is_empty());
     }
 
@@ -520,7 +835,7 @@ mod break_word_tests {
     fn word_that_fits_returns_single_unchanged_piece() {
         let style = hv12();
         let avail = w("hello") + 1.0; // generous budget
-        let pieces = break_word("hello", avail, &style, WordBreak::BreakAll, &[]);
+        let pieces = break_word("hello", avail, &style, WordBreak::BreakAll, '-', &[]);
         assert_eq!(pieces, vec!["hello"]);
     }
 
@@ -531,7 +846,7 @@ mod break_word_tests {
         // so it's treated as the last piece — no split.
         let style = hv12();
         let avail = w("www"); // exactly 3 w's wide
-        let pieces = break_word("www", avail, &style, WordBreak::BreakAll, &[]);
+        let pieces = break_word("www", avail, &style, WordBreak::BreakAll, '-', &[]);
         assert_eq!(pieces, vec!["www"]);
     }
 
@@ -545,7 +860,7 @@ mod break_word_tests {
         // Helvetica 'w' = 722/1000 em → at 12pt = 8.664 pt.
         let style = hv12();
         let avail = w("www"); // ~25.992 pt; "wwww" = ~34.656 pt won't fit
-        let pieces = break_word("wwwwww", avail, &style, WordBreak::BreakAll, &[]);
+        let pieces = break_word("wwwwww", avail, &style, WordBreak::BreakAll, '-', &[]);
         assert_eq!(pieces, vec!["www", "www"]);
     }
 
@@ -553,7 +868,7 @@ mod break_word_tests {
     fn break_all_produces_no_hyphens() {
         let style = hv12();
         let avail = w("ww"); // force a split
-        let pieces = break_word("wwww", avail, &style, WordBreak::BreakAll, &[]);
+        let pieces = break_word("wwww", avail, &style, WordBreak::BreakAll, '-', &[]);
         for piece in &pieces {
             assert!(
                 !piece.ends_with('-'),
@@ -569,7 +884,7 @@ mod break_word_tests {
         // Helvetica 'i' = 222/1000 em → at 12pt = 2.664 pt.
         let style = hv12();
         let avail = w("iii");
-        let pieces = break_word("iiiiiiiii", avail, &style, WordBreak::BreakAll, &[]);
+        let pieces = break_word("iiiiiiiii", avail, &style, WordBreak::BreakAll, '-', &[]);
         assert_eq!(pieces, vec!["iii", "iii", "iii"]);
     }
 
@@ -584,7 +899,7 @@ mod break_word_tests {
         // So each non-last piece holds 2 w's plus a hyphen.
         let style = hv12();
         let avail = w("www"); // ~25.992 pt
-        let pieces = break_word("wwwwww", avail, &style, WordBreak::Hyphenate, &[]);
+        let pieces = break_word("wwwwww", avail, &style, WordBreak::Hyphenate, '-', &[]);
         // Every piece except the last must end with '-'.
         let (last, rest) = pieces.split_last().unwrap();
         for piece in rest {
@@ -607,11 +922,59 @@ mod break_word_tests {
         // Use a word that requires 3 pieces so the invariant is non-trivial.
         let style = hv12();
         let avail = w("www"); // ~25.992 pt → forces multi-piece split
-        let pieces = break_word("wwwwwwww", avail, &style, WordBreak::Hyphenate, &[]);
+        let pieces = break_word("wwwwwwww", avail, &style, WordBreak::Hyphenate, '-', &[]);
         assert!(pieces.len() > 1, "expected a split");
         assert!(!pieces.last().unwrap().ends_with('-'));
     }
 
+    #[test]
+    fn hyphenate_uses_custom_hyphen_char() {
+        let style = hv12();
+        let avail = w("www");
+        let pieces = break_word("wwwwww", avail, &style, WordBreak::Hyphenate, '~', &[]);
+        let (last, rest) = pieces.split_last().unwrap();
+        assert!(!rest.is_empty());
+        for piece in rest {
+            assert!(
+                piece.ends_with('~') && !piece.contains('-'),
+                "got {:?}",
+                piece
+            );
+            // The budget reserves the custom hyphen's width.
+            assert!(w(piece) <= avail + f64::EPSILON);
+        }
+        assert!(!last.ends_with('~'));
+    }
+
+    #[test]
+    fn zero_width_space_is_preferred_break_without_hyphen() {
+        let style = hv12();
+        let avail = w("aaaabb");
+        let pieces = break_word(
+            "aaaa\u{200b}bbbb",
+            avail,
+            &style,
+            WordBreak::Hyphenate,
+            '-',
+            &[],
+        );
+        assert_eq!(pieces, vec!["aaaa", "bbbb"]);
+    }
+
+    #[test]
+    fn zero_width_space_is_dropped_from_unbroken_word() {
+        let style = hv12();
+        let pieces = break_word(
+            "ab\u{200b}cd",
+            w("abcdef"),
+            &style,
+            WordBreak::BreakAll,
+            '-',
+            &[],
+        );
+        assert_eq!(pieces, vec!["abcd"]);
+    }
+
     #[test]
     fn hyphenate_word_fitting_budget_produces_one_piece_without_hyphen() {
         // When avail is large enough that the word fits even after reserving
@@ -619,7 +982,7 @@ mod break_word_tests {
         // avail = word_width + hyphen_width + 1pt leaves the budget ≥ word_width.
         let style = hv12();
         let avail = w("hello") + w("-") + 1.0;
-        let pieces = break_word("hello", avail, &style, WordBreak::Hyphenate, &[]);
+        let pieces = break_word("hello", avail, &style, WordBreak::Hyphenate, '-', &[]);
         assert_eq!(pieces, vec!["hello"]);
     }
 
@@ -628,7 +991,7 @@ mod break_word_tests {
         // Each non-last piece (including its hyphen) must fit within avail.
         let style = hv12();
         let avail = w("www"); // ~25.992 pt
-        let pieces = break_word("wwwwwwwwww", avail, &style, WordBreak::Hyphenate, &[]);
+        let pieces = break_word("wwwwwwwwww", avail, &style, WordBreak::Hyphenate, '-', &[]);
         for piece in &pieces {
             let piece_w = measure_word(piece, &style, &[]);
             assert!(
@@ -651,7 +1014,7 @@ mod break_word_tests {
         // takes one character unconditionally so the loop always terminates.
         let style = hv12();
         let tiny = 1.0; // far smaller than any glyph
-        let pieces = break_word("iii", tiny, &style, WordBreak::BreakAll, &[]);
+        let pieces = break_word("iii", tiny, &style, WordBreak::BreakAll, '-', &[]);
         // One char per piece — forward progress guaranteed.
         assert_eq!(pieces, vec!["i", "i", "i"]);
     }
@@ -659,7 +1022,7 @@ mod break_word_tests {
     #[test]
     fn single_char_word_with_tiny_budget_returns_that_char() {
         let style = hv12();
-        let pieces = break_word("w", 1.0, &style, WordBreak::BreakAll, &[]);
+        let pieces = break_word("w", 1.0, &style, WordBreak::BreakAll, '-', &[]);
         assert_eq!(pieces, vec!["w"]);
     }
 
@@ -673,7 +1036,7 @@ mod break_word_tests {
         // Ensure break_word never produces an invalid UTF-8 slice.
         // (The font will fall back to a default width for non-ASCII, which is fine.)
         let style = hv12();
-        let pieces = break_word("éàü", 1.0, &style, WordBreak::BreakAll, &[]);
+        let pieces = break_word("éàü", 1.0, &style, WordBreak::BreakAll, '-', &[]);
         // Each piece must be valid UTF-8 (Rust strings guarantee this).
         for piece in &pieces {
             assert!(!piece.is_empty());
@@ -21,10 +21,19 @@ pub struct TrueTypeFont {
     /// Glyph ID -> advance width in font units
     pub(crate) glyph_widths: BTreeMap<u16, u16>,
     pub(crate) default_width: u16,
+    /// (left glyph, right glyph) -> kerning adjustment in font units,
+    /// from the legacy `kern` table (format 0 subtables only).
+    pub(crate) kern_pairs: BTreeMap<(u16, u16), i16>,
+    /// Whether kerning is applied when measuring and showing text (off by default).
+    pub(crate) kerning: bool,
     /// Glyph IDs that have been used (for subsetting/W array)
     pub(crate) used_glyphs: BTreeSet<u16>,
-    /// Glyph ID -> Unicode codepoint (for ToUnicode CMap)
-    pub(crate) glyph_to_unicode: BTreeMap<u16, u32>,
+    /// Glyph ID -> Unicode codepoint actually encoded with it (for the
+    /// ToUnicode CMap). Only glyphs used via `glyph_id` are recorded.
+    pub(crate) used_unicode: BTreeMap<u16, u32>,
+    /// Glyph ID -> text written to the ToUnicode CMap instead of the
+    /// recorded code point, for fonts with wrong or missing cmap entries.
+    pub(crate) unicode_overrides: BTreeMap<u16, char>,
     /// PDF resource name (e.g. "F15")
     pub(crate) pdf_name: String,
 }
@@ -51,7 +60,6 @@ impl TrueTypeFont {
 
         // Build cmap: Unicode -> GlyphID
         let mut cmap = BTreeMap::new();
-        let mut glyph_to_unicode = BTreeMap::new();
 
         let subtables = face
             .tables()
@@ -65,7 +73,6 @@ impl TrueTypeFont {
                 if let Some(gid) = subtable.glyph_index(cp) {
                     let gid_val = gid.0;
                     cmap.insert(cp, gid_val);
-                    glyph_to_unicode.entry(gid_val).or_insert(cp);
                 }
             });
         }
@@ -81,6 +88,22 @@ impl TrueTypeFont {
             glyph_widths.insert(gid, width);
         }
 
+        // Kerning pairs from the legacy `kern` table. GPOS kerning is not read.
+        let mut kern_pairs = BTreeMap::new();
+        if let Some(kern) = face.tables().kern {
+            for subtable in kern.subtables {
+                if !subtable.horizontal || subtable.variable || subtable.has_cross_stream {
+                    continue;
+                }
+                if let ttf_parser::kern::Format::Format0(table) = &subtable.format {
+                    for pair in table.pairs {
+                        let key = (pair.left().0, pair.right().0);
+                        *kern_pairs.entry(key).or_insert(0i16) += pair.value;
+                    }
+                }
+            }
+        }
+
         // Default width = width of glyph 0 (notdef)
         if let Some(&w) = glyph_widths.get(&0) {
             default_width = w;
@@ -103,8 +126,11 @@ impl TrueTypeFont {
             cmap,
             glyph_widths,
             default_width,
+            kern_pairs,
+            kerning: false,
             used_glyphs: BTreeSet::new(),
-            glyph_to_unicode,
+            used_unicode: BTreeMap::new(),
+            unicode_overrides: BTreeMap::new(),
             pdf_name,
         })
     }
@@ -130,10 +156,29 @@ impl TrueTypeFont {
         ((raw as u32 * 1000) / self.units_per_em as u32) as u16
     }
 
-    /// Measure text width in points.
+    /// Kerning adjustment between two characters in PDF units (1/1000 of
+    /// text space). Negative values pull the pair closer together. Always 0
+    /// when kerning is disabled or the font has no pair for them.
+    pub fn kerning_pdf(&self, left: char, right: char) -> i32 {
+        if !self.kerning {
+            return 0;
+        }
+        let gid = |ch: char| self.cmap.get(&(ch as u32)).copied().unwrap_or(0);
+        match self.kern_pairs.get(&(gid(left), gid(right))) {
+            Some(&value) => (value as i32 * 1000) / self.units_per_em as i32,
+            None => 0,
+        }
+    }
+
+    /// Measure text width in points, including kerning.
     pub fn measure_text(&self, text: &str, font_size: f64) -> f64 {
-        let total: u32 = text.chars().map(|ch| self.char_width_pdf(ch) as u32).sum();
-        total as f64 * font_size / 1000.0
+        let advances: i32 = text.chars().map(|ch| self.char_width_pdf(ch) as i32).sum();
+        let chars: Vec<char> = text.chars().collect();
+        let kerning: i32 = chars
+            .windows(2)
+            .map(|pair| self.kerning_pdf(pair[0], pair[1]))
+            .sum();
+        (advances + kerning) as f64 * font_size / 1000.0
     }
 
     /// Line height for a given font size using ascent - descent.
@@ -142,10 +187,25 @@ impl TrueTypeFont {
         height * font_size
     }
 
+    /// Ascent above the baseline in points at `font_size`.
+    pub fn scaled_ascent(&self, font_size: f64) -> f64 {
+        self.ascent as f64 * font_size / self.units_per_em as f64
+    }
+
+    /// Whether the font's cmap maps `ch` to a real glyph (not `.notdef`).
+    pub fn has_glyph(&self, ch: char) -> bool {
+        self.cmap.get(&(ch as u32)).is_some_and(|&gid| gid != 0)
+    }
+
     /// Look up the glyph ID for a character and record it as used.
     pub fn glyph_id(&mut self, ch: char) -> u16 {
         let gid = self.cmap.get(&(ch as u32)).copied().unwrap_or(0);
         self.used_glyphs.insert(gid);
+        // .notdef has no meaningful Unicode value; for glyphs shared by
+        // several code points, the first one encoded wins.
+        if gid != 0 {
+            self.used_unicode.entry(gid).or_insert(ch as u32);
+        }
         gid
     }
 
@@ -161,10 +221,74 @@ impl TrueTypeFont {
         hex
     }
 
+    /// Encode text as a complete text-showing operator.
+    ///
+    /// Produces `<...> Tj` when no kerning applies, otherwise a `TJ` array
+    /// such as `[<0024> 74 <0039>] TJ`. TJ numbers are subtracted from the
+    /// pen position, so they are the negated kerning values.
+    pub fn encode_text_op(&mut self, text: &str) -> String {
+        let chars: Vec<char> = text.chars().collect();
+        let adjustments: Vec<i32> = chars
+            .windows(2)
+            .map(|pair| self.kerning_pdf(pair[0], pair[1]))
+            .collect();
+        if adjustments.iter().all(|&k| k == 0) {
+            return format!("{} Tj", self.encode_text_hex(text));
+        }
+
+        let mut op = String::from("[<");
+        for (i, &ch) in chars.iter().enumerate() {
+            let gid = self.glyph_id(ch);
+            op.push_str(&format!("{:04X}", gid));
+            if let Some(&kern) = adjustments.get(i) {
+                if kern != 0 {
+                    op.push_str(&format!("> {} <", -kern));
+                }
+            }
+        }
+        op.push_str(">] TJ");
+        op
+    }
+
+    /// Width in PDF units (1/1000 em) of a glyph, falling back to the
+    /// default width for glyphs without an hmtx entry.
+    fn glyph_width_pdf(&self, gid: u16) -> i64 {
+        let raw = self
+            .glyph_widths
+            .get(&gid)
+            .copied()
+            .unwrap_or(self.default_width);
+        ((raw as u32 * 1000) / self.units_per_em as u32) as i64
+    }
+
+    /// The advance shared by every used glyph, as in a monospace font, or
+    /// `None` if the used glyphs differ (or none are used).
+    fn uniform_width_pdf(&self) -> Option<i64> {
+        let mut widths = self
+            .used_glyphs
+            .iter()
+            .map(|&gid| self.glyph_width_pdf(gid));
+        let first = widths.next()?;
+        widths.all(|w| w == first).then_some(first)
+    }
+
+    /// The CIDFont `/DW`: the common advance when all used glyphs share one,
+    /// otherwise the width of glyph 0.
+    pub(crate) fn dw_pdf(&self) -> i64 {
+        self.uniform_width_pdf()
+            .unwrap_or_else(|| self.default_width_pdf())
+    }
+
     /// Build the PDF /W array for used glyphs.
     /// Format: `[cid [w1 w2 ...] cid [w1 w2 ...] ...]`
+    ///
+    /// Empty when every used glyph has the same advance (a monospace font):
+    /// `/DW` from `dw_pdf` then covers them all.
     pub fn build_w_array(&self) -> Vec<PdfObject> {
         let mut result = Vec::new();
+        if self.uniform_width_pdf().is_some() {
+            return result;
+        }
         // BTreeSet iterates in sorted order already
         let sorted_glyphs: Vec<u16> = self.used_glyphs.iter().copied().collect();
 
@@ -177,13 +301,7 @@ impl TrueTypeFont {
             let mut j = i;
             while j < sorted_glyphs.len() && sorted_glyphs[j] == start + (j - i) as u16 {
                 let gid = sorted_glyphs[j];
-                let raw = self
-                    .glyph_widths
-                    .get(&gid)
-                    .copied()
-                    .unwrap_or(self.default_width);
-                let pdf_w = ((raw as u32 * 1000) / self.units_per_em as u32) as i64;
-                widths.push(PdfObject::Integer(pdf_w));
+                widths.push(PdfObject::Integer(self.glyph_width_pdf(gid)));
                 j += 1;
             }
 
@@ -214,18 +332,21 @@ impl TrueTypeFont {
              endcodespacerange\n",
         );
 
-        // Collect mappings for used glyphs
+        // Only glyphs actually encoded are mapped, keeping the CMap small.
         let mappings: Vec<(u16, u32)> = self
-            .used_glyphs
+            .used_unicode
             .iter()
-            .filter_map(|&gid| self.glyph_to_unicode.get(&gid).map(|&cp| (gid, cp)))
+            .map(|(&gid, &cp)| match self.unicode_overrides.get(&gid) {
+                Some(&ch) => (gid, ch as u32),
+                None => (gid, cp),
+            })
             .collect();
 
         // Write in chunks of 100 (PDF limit per beginbfchar)
         for chunk in mappings.chunks(100) {
             cmap.push_str(&format!("{} beginbfchar\n", chunk.len()));
             for &(gid, cp) in chunk {
-                cmap.push_str(&format!("<{:04X}> <{:04X}>\n", gid, cp));
+                cmap.push_str(&format!("<{:04X}> <{}>\n", gid, utf16_hex(cp)));
             }
             cmap.push_str("endbfchar\n");
         }
@@ -241,6 +362,184 @@ impl TrueTypeFont {
     }
 }
 
+/// Tables kept by `subset_font_data`, in tag order: those a PDF viewer
+/// needs to draw a simple TrueType font (ISO 32000-1, 9.9) plus `OS/2`.
+/// Layout and naming tables such as `GSUB`, `GPOS`, `name` and `post` are
+/// dropped.
+const SUBSET_TABLES: [&[u8; 4]; 11] = [
+    b"OS/2", b"cmap", b"cvt ", b"fpgm", b"glyf", b"head", b"hhea", b"hmtx", b"loca", b"maxp",
+    b"prep",
+];
+
+/// Subset TrueType font data to `glyphs` (plus `.notdef` and any
+/// components of composite glyphs), keeping glyph IDs unchanged.
+///
+/// Outlines of every other glyph are emptied in `glyf`, `loca` is rewritten
+/// in the long format and tables outside `SUBSET_TABLES` are dropped, so
+/// `cmap` and `hmtx` stay valid without renumbering. Returns `None` when
+/// the data has no `glyf` outlines (e.g. CFF-based OpenType) or is
+/// malformed; callers then embed the full font.
+pub(crate) fn subset_font_data(data: &[u8], glyphs: &BTreeSet<u16>) -> Option<Vec<u8>> {
+    let num_tables = read_u16(data, 4)? as usize;
+    let mut tables = BTreeMap::new();
+    for i in 0..num_tables {
+        let record = data.get(12 + 16 * i..28 + 16 * i)?;
+        let tag: [u8; 4] = record[..4].try_into().ok()?;
+        let offset = read_u32(record, 8)? as usize;
+        let length = read_u32(record, 12)? as usize;
+        tables.insert(tag, data.get(offset..offset.checked_add(length)?)?);
+    }
+    let (head, loca, glyf) = (
+        tables.get(b"head")?,
+        tables.get(b"loca")?,
+        tables.get(b"glyf")?,
+    );
+    let num_glyphs = read_u16(tables.get(b"maxp")?, 4)? as usize;
+    let long_loca = read_u16(head, 50)? != 0;
+    let offsets = (0..=num_glyphs)
+        .map(|i| match long_loca {
+            true => read_u32(loca, 4 * i).map(|v| v as usize),
+            false => read_u16(loca, 2 * i).map(|v| v as usize * 2),
+        })
+        .collect::<Option<Vec<usize>>>()?;
+    let outline = |gid: usize| glyf.get(offsets[gid]..offsets[gid + 1]);
+
+    let mut kept: BTreeSet<usize> = glyphs
+        .iter()
+        .map(|&g| g as usize)
+        .filter(|&g| g < num_glyphs)
+        .collect();
+    kept.insert(0);
+    let mut pending: Vec<usize> = kept.iter().copied().collect();
+    while let Some(gid) = pending.pop() {
+        for component in composite_components(outline(gid)?) {
+            if component < num_glyphs && kept.insert(component) {
+                pending.push(component);
+            }
+        }
+    }
+
+    let mut new_glyf = Vec::new();
+    let mut new_loca = Vec::with_capacity(4 * (num_glyphs + 1));
+    for gid in 0..num_glyphs {
+        new_loca.extend_from_slice(&(new_glyf.len() as u32).to_be_bytes());
+        if kept.contains(&gid) {
+            new_glyf.extend_from_slice(outline(gid)?);
+            new_glyf.resize(new_glyf.len().next_multiple_of(4), 0);
+        }
+    }
+    new_loca.extend_from_slice(&(new_glyf.len() as u32).to_be_bytes());
+    let mut new_head = head.to_vec();
+    new_head.get_mut(8..12)?.fill(0); // checkSumAdjustment, patched below
+    new_head
+        .get_mut(50..52)?
+        .copy_from_slice(&1u16.to_be_bytes()); // long loca
+
+    let out_tables: Vec<(&[u8; 4], &[u8])> = SUBSET_TABLES
+        .iter()
+        .filter_map(|&tag| match tag {
+            b"glyf" => Some((tag, new_glyf.as_slice())),
+            b"loca" => Some((tag, new_loca.as_slice())),
+            b"head" => Some((tag, new_head.as_slice())),
+            _ => tables.get(tag).map(|&t| (tag, t)),
+        })
+        .collect();
+
+    let count = out_tables.len();
+    let entry_selector = count.ilog2();
+    let search_range = (1usize << entry_selector) * 16;
+    let mut out = Vec::with_capacity(12 + 16 * count + new_glyf.len());
+    out.extend_from_slice(&data[..4]);
+    for v in [
+        count,
+        search_range,
+        entry_selector as usize,
+        count * 16 - search_range,
+    ] {
+        out.extend_from_slice(&(v as u16).to_be_bytes());
+    }
+    let mut offset = 12 + 16 * count;
+    let mut head_offset = 0;
+    for (tag, table) in &out_tables {
+        if *tag == b"head" {
+            head_offset = offset;
+        }
+        out.extend_from_slice(*tag);
+        out.extend_from_slice(&table_checksum(table).to_be_bytes());
+        out.extend_from_slice(&(offset as u32).to_be_bytes());
+        out.extend_from_slice(&(table.len() as u32).to_be_bytes());
+        offset += table.len().next_multiple_of(4);
+    }
+    for (_, table) in &out_tables {
+        out.extend_from_slice(table);
+        out.resize(out.len().next_multiple_of(4), 0);
+    }
+    let adjustment = 0xB1B0_AFBAu32.wrapping_sub(table_checksum(&out));
+    out[head_offset + 8..head_offset + 12].copy_from_slice(&adjustment.to_be_bytes());
+    Some(out)
+}
+
+/// Glyph IDs referenced by a composite glyph's components (empty for a
+/// simple or empty glyph).
+fn composite_components(glyph: &[u8]) -> Vec<usize> {
+    const ARGS_ARE_WORDS: u16 = 0x0001;
+    const HAVE_SCALE: u16 = 0x0008;
+    const MORE_COMPONENTS: u16 = 0x0020;
+    const HAVE_XY_SCALE: u16 = 0x0040;
+    const HAVE_2X2: u16 = 0x0080;
+
+    let mut components = Vec::new();
+    if read_u16(glyph, 0).is_none_or(|contours| (contours as i16) >= 0) {
+        return components;
+    }
+    let mut pos = 10;
+    while let (Some(flags), Some(gid)) = (read_u16(glyph, pos), read_u16(glyph, pos + 2)) {
+        components.push(gid as usize);
+        pos += 4 + if flags & ARGS_ARE_WORDS != 0 { 4 } else { 2 };
+        if flags & HAVE_SCALE != 0 {
+            pos += 2;
+        } else if flags & HAVE_XY_SCALE != 0 {
+            pos += 4;
+        } else if flags & HAVE_2X2 != 0 {
+            pos += 8;
+        }
+        if flags & MORE_COMPONENTS == 0 {
+            break;
+        }
+    }
+    components
+}
+
+/// Sum of a table's big-endian `u32` words, zero-padded to a multiple of 4.
+fn table_checksum(table: &[u8]) -> u32 {
+    table.chunks(4).fold(0u32, |sum, chunk| {
+        let mut word = [0u8; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(word))
+    })
+}
+
+fn read_u16(data: &[u8], pos: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(data.get(pos..pos + 2)?.try_into().ok()?))
+}
+
+fn read_u32(data: &[u8], pos: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(data.get(pos..pos + 4)?.try_into().ok()?))
+}
+
+/// Destination string of a `bfchar` entry: the code point in UTF-16BE hex,
+/// as a surrogate pair outside the Basic Multilingual Plane.
+fn utf16_hex(cp: u32) -> String {
+    let Some(ch) = char::from_u32(cp) else {
+        return format!("{:04X}", cp);
+    };
+    let mut units = [0u16; 2];
+    ch.encode_utf16(&mut units)
+        .iter()
+        .map(|unit| format!("{:04X}", unit))
+        .collect()
+}
+
 /// Extract the font family name from the name table.
 fn extract_name(face: &ttf_parser::Face) -> Option<String> {
     face.names()
@@ -0,0 +1,21 @@
+//! Conversions from common physical units to PDF points (1/72 inch).
+
+/// Points per inch.
+pub const POINTS_PER_INCH: f64 = 72.0;
+/// Points per millimeter (72 / 25.4).
+pub const POINTS_PER_MM: f64 = POINTS_PER_INCH / 25.4;
+
+/// Convert millimeters to points.
+pub fn mm(value: f64) -> f64 {
+    value * POINTS_PER_MM
+}
+
+/// Convert centimeters to points.
+pub fn cm(value: f64) -> f64 {
+    mm(value * 10.0)
+}
+
+/// Convert inches to points.
+pub fn inches(value: f64) -> f64 {
+    value * POINTS_PER_INCH
+}
@@ -0,0 +1,338 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::ops::Range;
+
+/// A structural problem found by `PdfDocument::validate`.
+///
+/// Pages are 1-indexed, matching `open_page`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationWarning {
+    /// Content uses a font name (`/Name ... Tf`) missing from the page's resources.
+    UndefinedFont { page: usize, name: String },
+    /// Content paints an XObject (`/Name Do`) missing from the page's resources.
+    UndefinedXObject { page: usize, name: String },
+    /// A page uses a TrueType font that has no PDF objects allocated.
+    UnallocatedFont { page: usize, font: usize },
+    /// A page uses an image whose XObject was never written.
+    UnwrittenImage { page: usize, image: usize },
+    /// An overlay targets a page that does not exist.
+    MissingPage { page: usize },
+}
+
+impl fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationWarning::UndefinedFont { page, name } => {
+                write!(
+                    f,
+                    "page {}: font /{} is not in the page resources",
+                    page, name
+                )
+            }
+            ValidationWarning::UndefinedXObject { page, name } => {
+                write!(
+                    f,
+                    "page {}: XObject /{} is not in the page resources",
+                    page, name
+                )
+            }
+            ValidationWarning::UnallocatedFont { page, font } => {
+                write!(
+                    f,
+                    "page {}: TrueType font {} has no font objects",
+                    page, font
+                )
+            }
+            ValidationWarning::UnwrittenImage { page, image } => {
+                write!(
+                    f,
+                    "page {}: image {} XObject was never written",
+                    page, image
+                )
+            }
+            ValidationWarning::MissingPage { page } => {
+                write!(f, "overlay targets missing page {}", page)
+            }
+        }
+    }
+}
+
+/// Kind of a lexical token in a content stream.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum TokenKind {
+    /// A literal string, parentheses included.
+    Literal,
+    /// A hex string, angle brackets included.
+    Hex,
+    /// A name, leading `/` included.
+    Name,
+    /// A comment, from `%` up to (not including) the end of the line.
+    Comment,
+    /// `[`, `]`, `{`, `}`, `<<` or `>>`.
+    Delimiter,
+    /// Inline image data after `ID`: the one whitespace byte that ends the
+    /// operator, then the samples up to the whitespace before `EI`.
+    InlineData,
+    /// An operator or a number.
+    Regular,
+}
+
+/// Split a content stream into tokens with their byte ranges, skipping
+/// the whitespace between them.
+pub(crate) fn lex_content(ops: &[u8]) -> Vec<(TokenKind, Range<usize>)> {
+    fn is_delimiter(b: u8) -> bool {
+        b.is_ascii_whitespace() || b"()<>[]{}/%".contains(&b)
+    }
+
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < ops.len() {
+        let start = i;
+        let kind = match ops[i] {
+            b if b.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            b'(' => {
+                // Take a literal string whole, honoring escapes and nested parens.
+                let mut depth = 0usize;
+                while i < ops.len() {
+                    match ops[i] {
+                        b'\\' => i += 1,
+                        b'(' => depth += 1,
+                        b')' => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        _ => {}
+                    }
+                    i += 1;
+                }
+                i = (i + 1).min(ops.len());
+                TokenKind::Literal
+            }
+            b @ (b'<' | b'>') if ops.get(i + 1) == Some(&b) => {
+                i += 2;
+                TokenKind::Delimiter
+            }
+            b'<' => {
+                while i < ops.len() && ops[i] != b'>' {
+                    i += 1;
+                }
+                i = (i + 1).min(ops.len());
+                TokenKind::Hex
+            }
+            b'%' => {
+                while i < ops.len() && ops[i] != b'\n' && ops[i] != b'\r' {
+                    i += 1;
+                }
+                TokenKind::Comment
+            }
+            b'/' => {
+                i += 1;
+                while i < ops.len() && !is_delimiter(ops[i]) {
+                    i += 1;
+                }
+                TokenKind::Name
+            }
+            b if is_delimiter(b) => {
+                i += 1;
+                TokenKind::Delimiter
+            }
+            _ => {
+                while i < ops.len() && !is_delimiter(ops[i]) {
+                    i += 1;
+                }
+                TokenKind::Regular
+            }
+        };
+        tokens.push((kind, start..i));
+
+        if kind == TokenKind::Regular && &ops[start..i] == b"ID" && i < ops.len() {
+            // Binary samples up to a whitespace-delimited `EI`.
+            let end = (i + 1..ops.len())
+                .find(|&p| {
+                    ops[p].is_ascii_whitespace()
+                        && ops[p + 1..].starts_with(b"EI")
+                        && ops.get(p + 3).is_none_or(|&c| is_delimiter(c))
+                })
+                .unwrap_or(ops.len());
+            tokens.push((TokenKind::InlineData, i..end));
+            i = end;
+        }
+    }
+    tokens
+}
+
+/// Tokens the checks below look at: operators, numbers, names (with their
+/// leading `/`) and literal strings (with their parentheses).
+fn content_tokens(ops: &[u8]) -> Vec<&[u8]> {
+    lex_content(ops)
+        .into_iter()
+        .filter(|(kind, _)| {
+            matches!(
+                kind,
+                TokenKind::Literal | TokenKind::Name | TokenKind::Regular
+            )
+        })
+        .map(|(_, range)| &ops[range])
+        .collect()
+}
+
+/// Verify that `BT`/`ET` text objects in a content stream are balanced and
+/// never nested. Text such as `(BT) Tj` is not mistaken for an operator.
+pub(crate) fn check_text_objects(ops: &[u8]) -> Result<(), String> {
+    let mut in_text = false;
+    for token in content_tokens(ops) {
+        match token {
+            b"BT" if in_text => return Err("nested BT inside text object".to_string()),
+            b"BT" => in_text = true,
+            b"ET" if !in_text => return Err("ET without matching BT".to_string()),
+            b"ET" => in_text = false,
+            _ => {}
+        }
+    }
+    if in_text {
+        return Err("BT without matching ET".to_string());
+    }
+    Ok(())
+}
+
+/// Resource names referenced by a content stream: fonts selected with `Tf`
+/// and XObjects painted with `Do` (names without the leading `/`).
+pub(crate) fn referenced_resources(ops: &[u8]) -> (BTreeSet<String>, BTreeSet<String>) {
+    let tokens = content_tokens(ops);
+    let mut fonts = BTreeSet::new();
+    let mut xobjects = BTreeSet::new();
+    for (i, token) in tokens.iter().enumerate() {
+        let name_at = |back: usize| {
+            i.checked_sub(back)
+                .map(|j| tokens[j])
+                .filter(|t| t.starts_with(b"/"))
+                .map(|t| String::from_utf8_lossy(&t[1..]).into_owned())
+        };
+        match *token {
+            b"Tf" => fonts.extend(name_at(2)),
+            b"Do" => xobjects.extend(name_at(1)),
+            _ => {}
+        }
+    }
+    (fonts, xobjects)
+}
+
+/// Byte codes shown in literal strings, keyed by the font name (without
+/// the leading `/`) selected with `Tf` when each string appears.
+pub(crate) fn shown_string_bytes(ops: &[u8]) -> BTreeMap<String, BTreeSet<u8>> {
+    let tokens = content_tokens(ops);
+    let mut shown: BTreeMap<String, BTreeSet<u8>> = BTreeMap::new();
+    let mut font: Option<String> = None;
+    for (i, token) in tokens.iter().enumerate() {
+        if *token == b"Tf" {
+            font = i
+                .checked_sub(2)
+                .map(|j| tokens[j])
+                .filter(|t| t.starts_with(b"/"))
+                .map(|t| String::from_utf8_lossy(&t[1..]).into_owned());
+        } else if let (Some(name), Some(b'(')) = (&font, token.first()) {
+            shown
+                .entry(name.clone())
+                .or_default()
+                .extend(unescape_literal(token));
+        }
+    }
+    shown
+}
+
+/// Decode a literal string token, parentheses included, to its bytes.
+fn unescape_literal(token: &[u8]) -> Vec<u8> {
+    let body = &token[1..token.len().saturating_sub(1).max(1)];
+    let mut out = Vec::with_capacity(body.len());
+    let mut i = 0;
+    while i < body.len() {
+        if body[i] != b'\\' || i + 1 == body.len() {
+            out.push(body[i]);
+            i += 1;
+            continue;
+        }
+        i += 1;
+        match body[i] {
+            b'n' => out.push(b'\n'),
+            b'r' => out.push(b'\r'),
+            b't' => out.push(b'\t'),
+            b'b' => out.push(0x08),
+            b'f' => out.push(0x0c),
+            b'0'..=b'7' => {
+                let digits = body[i..]
+                    .iter()
+                    .take(3)
+                    .take_while(|d| (b'0'..=b'7').contains(d))
+                    .count();
+                let value = body[i..i + digits]
+                    .iter()
+                    .fold(0u32, |v, d| v * 8 + (d - b'0') as u32);
+                out.push(value as u8);
+                i += digits - 1;
+            }
+            // An escaped line break continues the string on the next line.
+            b'\n' | b'\r' => {}
+            other => out.push(other),
+        }
+        i += 1;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn balanced_text_objects_pass() {
+        let ops = b"BT\n/F1 12 Tf\n72 720 Td\n(Hello) Tj\nET\nBT\nET\n";
+        assert!(check_text_objects(ops).is_ok());
+    }
+
+    #[test]
+    fn operators_inside_strings_are_ignored() {
+        let ops = b"BT\n(BT \\) ET) Tj\n<4254> Tj\nET\n% BT\n";
+        assert!(check_text_objects(ops).is_ok());
+    }
+
+    #[test]
+    fn missing_et_is_flagged() {
+        let err = check_text_objects(b"BT\n(Hello) Tj\n").unwrap_err();
+        assert!(err.contains("without matching ET"));
+    }
+
+    #[test]
+    fn nested_bt_is_flagged() {
+        let err = check_text_objects(b"BT\nBT\nET\nET\n").unwrap_err();
+        assert!(err.contains("nested BT"));
+    }
+
+    #[test]
+    fn stray_et_is_flagged() {
+        let err = check_text_objects(b"q\nET\nQ\n").unwrap_err();
+        assert!(err.contains("without matching BT"));
+    }
+
+    #[test]
+    fn shown_string_bytes_groups_by_font() {
+        let ops = b"BT\n/F1 12 Tf\n(ab\\(\\101) Tj\n/F9 10 Tf\n[(c) -20 (a)] TJ\nET\n";
+        let shown = shown_string_bytes(ops);
+        let f1: Vec<u8> = shown["F1"].iter().copied().collect();
+        let f9: Vec<u8> = shown["F9"].iter().copied().collect();
+        assert_eq!(f1, b"(Aab".to_vec());
+        assert_eq!(f9, b"ac".to_vec());
+    }
+
+    #[test]
+    fn referenced_resources_finds_fonts_and_xobjects() {
+        let ops = b"BT\n/F1 12 Tf\n(/F9 1 Tf) Tj\nET\nq\n/Im2 Do\nQ\n";
+        let (fonts, xobjects) = referenced_resources(ops);
+        assert_eq!(fonts.into_iter().collect::<Vec<_>>(), vec!["F1"]);
+        assert_eq!(xobjects.into_iter().collect::<Vec<_>>(), vec!["Im2"]);
+    }
+}
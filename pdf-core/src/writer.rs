@@ -1,21 +1,38 @@
 use std::io::{self, Write};
 
 use crate::objects::{ObjId, PdfObject};
+use crate::validate::{lex_content, TokenKind};
 
 /// Low-level PDF binary writer. Serializes PDF objects to any
 /// `Write` target while tracking byte offsets for the xref table.
+/// Offsets are counted rather than queried, so `W` need not be `Seek`.
 pub struct PdfWriter<W: Write> {
     writer: W,
     offset: usize,
     xref_entries: Vec<(u32, usize)>,
+    /// FNV-1a hash of every byte written so far.
+    digest: u128,
+    /// `/ID` written in the trailer, if set.
+    file_id: Option<Vec<u8>>,
+    /// Follow the version line with the binary comment (default true).
+    binary_marker: bool,
+    header_written: bool,
 }
 
+/// FNV-1a 128-bit offset basis and prime.
+const FNV_OFFSET: u128 = 0x6c62272e07bb014262b821756295c58d;
+const FNV_PRIME: u128 = 0x0000000001000000000000000000013b;
+
 impl<W: Write> PdfWriter<W> {
     pub fn new(writer: W) -> Self {
         PdfWriter {
             writer,
             offset: 0,
             xref_entries: Vec::new(),
+            digest: FNV_OFFSET,
+            file_id: None,
+            binary_marker: true,
+            header_written: false,
         }
     }
 
@@ -23,24 +40,58 @@ impl<W: Write> PdfWriter<W> {
     fn write_bytes(&mut self, data: &[u8]) -> io::Result<()> {
         self.writer.write_all(data)?;
         self.offset += data.len();
+        for &b in data {
+            self.digest = (self.digest ^ b as u128).wrapping_mul(FNV_PRIME);
+        }
         Ok(())
     }
 
+    /// 16-byte FNV-1a hash of everything written so far. Not
+    /// cryptographic; identical output always yields the same digest.
+    pub fn digest(&self) -> [u8; 16] {
+        self.digest.to_be_bytes()
+    }
+
+    /// Write `/ID [<id> <id>]` in the trailer.
+    pub fn set_file_id(&mut self, id: Vec<u8>) {
+        self.file_id = Some(id);
+    }
+
     /// Write a formatted string, tracking the byte offset.
     fn write_str(&mut self, s: &str) -> io::Result<()> {
         self.write_bytes(s.as_bytes())
     }
 
-    /// Write the PDF 1.7 header and binary comment.
+    /// Include the binary comment line in the header (default true).
+    /// Has no effect once the header has been written.
+    pub fn set_binary_marker(&mut self, enabled: bool) {
+        self.binary_marker = enabled;
+    }
+
+    /// Whether the header has been written yet.
+    pub fn header_written(&self) -> bool {
+        self.header_written
+    }
+
+    /// Write the PDF 1.7 header and binary comment. Does nothing if the
+    /// header was already written; `write_object` and
+    /// `write_xref_and_trailer` call it, so an explicit call is optional.
     pub fn write_header(&mut self) -> io::Result<()> {
+        if self.header_written {
+            return Ok(());
+        }
+        self.header_written = true;
         self.write_str("%PDF-1.7\n")?;
-        // Binary comment: 4 bytes >= 128 for binary detection.
-        self.write_bytes(b"%\xe2\xe3\xcf\xd3\n")?;
+        if self.binary_marker {
+            // Binary comment: 4 bytes >= 128 for binary detection.
+            self.write_bytes(b"%\xe2\xe3\xcf\xd3\n")?;
+        }
         Ok(())
     }
 
     /// Write an indirect object, recording its byte offset for xref.
     pub fn write_object(&mut self, id: ObjId, obj: &PdfObject) -> io::Result<()> {
+        self.write_header()?;
         self.xref_entries.push((id.0, self.offset));
         self.write_str(&format!("{} {} obj\n", id.0, id.1))?;
         self.write_pdf_object(obj)?;
@@ -73,6 +124,13 @@ impl<W: Write> PdfWriter<W> {
                 self.write_str(&escape_pdf_string(s))?;
                 self.write_str(")")
             }
+            PdfObject::HexString(bytes) => {
+                self.write_str("<")?;
+                for b in bytes {
+                    self.write_str(&format!("{:02X}", b))?;
+                }
+                self.write_str(">")
+            }
             PdfObject::Array(items) => {
                 self.write_str("[")?;
                 for (i, item) in items.iter().enumerate() {
@@ -111,6 +169,21 @@ impl<W: Write> PdfWriter<W> {
         }
     }
 
+    /// Write an indirect object whose body is already serialized, e.g.
+    /// copied from another file.
+    pub(crate) fn write_raw_object(&mut self, id: ObjId, body: &[u8]) -> io::Result<()> {
+        self.write_header()?;
+        self.xref_entries.push((id.0, self.offset));
+        self.write_str(&format!("{} {} obj\n", id.0, id.1))?;
+        self.write_bytes(body)?;
+        self.write_str("\nendobj\n")
+    }
+
+    /// Number of indirect objects written so far.
+    pub fn object_count(&self) -> usize {
+        self.xref_entries.len()
+    }
+
     /// Current byte offset in the output.
     pub fn current_offset(&self) -> usize {
         self.offset
@@ -122,6 +195,7 @@ impl<W: Write> PdfWriter<W> {
         root_id: ObjId,
         info_id: Option<ObjId>,
     ) -> io::Result<()> {
+        self.write_header()?;
         let xref_offset = self.offset;
 
         // Sort xref entries by object number.
@@ -162,6 +236,14 @@ impl<W: Write> PdfWriter<W> {
         if let Some(info) = info_id {
             self.write_str(&format!(" /Info {} {} R", info.0, info.1,))?;
         }
+        if let Some(id) = self.file_id.take() {
+            let hex = PdfObject::HexString(id);
+            self.write_str(" /ID [")?;
+            self.write_pdf_object(&hex)?;
+            self.write_str(" ")?;
+            self.write_pdf_object(&hex)?;
+            self.write_str("]")?;
+        }
         self.write_str(" >>\n")?;
 
         self.write_str("startxref\n")?;
@@ -171,6 +253,11 @@ impl<W: Write> PdfWriter<W> {
         Ok(())
     }
 
+    /// Flush the underlying writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
     /// Return the inner writer, consuming this PdfWriter.
     pub fn into_inner(self) -> W {
         self.writer
@@ -191,6 +278,31 @@ pub fn escape_pdf_string(s: &str) -> String {
     result
 }
 
+/// Rewrite a content stream with a single space between tokens instead
+/// of newlines.
+///
+/// Tokens come from the same lexer `validate` uses, so literal strings,
+/// hex strings and inline image data are copied unchanged, and a comment
+/// keeps the newline that ends it.
+pub(crate) fn compact_content(ops: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(ops.len());
+    let mut prev_end = 0;
+    for (kind, range) in lex_content(ops) {
+        // Tokens that touched in the input still touch; whitespace between
+        // them shrinks to one space, or to the newline after a comment.
+        let spaced = range.start > prev_end && !out.is_empty() && out.last() != Some(&b'\n');
+        if spaced && kind != TokenKind::InlineData {
+            out.push(b' ');
+        }
+        prev_end = range.end;
+        out.extend_from_slice(&ops[range]);
+        if kind == TokenKind::Comment {
+            out.push(b'\n');
+        }
+    }
+    out
+}
+
 /// Format a float for PDF output: no trailing zeros,
 /// no scientific notation.
 fn format_real(f: f64) -> String {
@@ -1,4 +1,6 @@
-use pdf_core::PdfDocument;
+use std::io::{self, Seek, SeekFrom, Write};
+
+use pdf_core::{ImageFit, PdfDocument, PdfReader, Rect, TextStyle};
 
 /// Helper: find a byte pattern in a buffer.
 fn find_bytes(haystack: &[u8], needle: &[u8]) -> Option<usize> {
@@ -196,3 +198,60 @@ fn empty_page_has_no_font_objects() {
     // No text placed, so no font objects should exist.
     assert!(!contains_bytes(&bytes, b"/BaseFont",));
 }
+
+/// A pipe-like writer: accepts bytes in order and fails any attempt to seek.
+struct ForwardOnly(Vec<u8>);
+
+impl Write for ForwardOnly {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for ForwardOnly {
+    fn seek(&mut self, _pos: SeekFrom) -> io::Result<u64> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "seek on a pipe"))
+    }
+}
+
+#[test]
+fn writes_to_non_seekable_writer() {
+    let mut doc = PdfDocument::new(ForwardOnly(Vec::new())).unwrap();
+    doc.set_compression(true);
+    let font = doc.load_font_file("tests/fixtures/DejaVuSans.ttf").unwrap();
+    let image = doc
+        .load_image_bytes(include_bytes!("fixtures/test.png").to_vec())
+        .unwrap();
+    let style = TextStyle {
+        font,
+        ..TextStyle::default()
+    };
+    let rect = Rect {
+        x: 72.0,
+        y: 680.0,
+        width: 100.0,
+        height: 80.0,
+    };
+    for i in 1..=3 {
+        doc.begin_page(612.0, 792.0);
+        doc.place_text(&format!("Page {}", i), 72.0, 720.0);
+        doc.place_text_styled("Unicode: \u{e9}t\u{e9}", 72.0, 700.0, &style);
+        doc.place_image(&image, &rect, ImageFit::Fit);
+        doc.end_page().unwrap();
+    }
+    // Overlays are appended after the pages they edit were written.
+    doc.open_page(1).unwrap();
+    doc.place_text("Page 1 of 3", 72.0, 40.0);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap().0;
+
+    assert!(bytes.starts_with(b"%PDF-1.7\n"));
+    assert!(bytes.ends_with(b"%%EOF\n"));
+    // The xref offsets computed while streaming must resolve.
+    let reader = PdfReader::from_bytes(bytes).unwrap();
+    assert_eq!(reader.page_count(), 3);
+}
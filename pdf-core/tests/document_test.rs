@@ -1,8 +1,12 @@
 use std::cell::RefCell;
 use std::io::{self, Write};
 use std::rc::Rc;
+use std::sync::{Arc, Mutex};
 
-use pdf_core::{PdfDocument, TextStyle};
+use pdf_core::{
+    BuiltinFont, ImageFit, ImageId, PdfDocument, PdfReader, ProgressEvent, Rect, TextStyle,
+    Transition,
+};
 
 #[test]
 fn create_empty_document() {
@@ -28,6 +32,179 @@ fn set_info_appears_in_output() {
     assert!(output.contains("(Test Doc)"));
 }
 
+#[test]
+fn non_ascii_title_is_written_as_utf16be_hex() {
+    let title = "Notes (v2) — Zürich";
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_info("Title", title);
+    doc.begin_page(612.0, 792.0);
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    let start = output.find("/Title <").expect("hex Title") + "/Title <".len();
+    let end = start + output[start..].find('>').unwrap();
+    let hex = &output[start..end];
+    assert!(hex.starts_with("FEFF"), "{}", hex);
+    let units: Vec<u16> = (4..hex.len())
+        .step_by(4)
+        .map(|i| u16::from_str_radix(&hex[i..i + 4], 16).unwrap())
+        .collect();
+    assert_eq!(String::from_utf16(&units).unwrap(), title);
+}
+
+#[test]
+fn info_strings_use_utf16be_only_when_non_ascii() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_info("Title", "Résumé — 日本語");
+    doc.set_info("Author", "Jane Doe");
+    doc.begin_page(612.0, 792.0);
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert!(output.contains("/Title <FEFF005200E900730075006D00E900202014002065E5672C8A9E>"));
+    assert!(output.contains("/Author (Jane Doe)"));
+}
+
+#[test]
+fn typed_info_setters_write_standard_keys() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_title("Draft")
+        .set_title("Annual Report")
+        .set_author("Zoë Ångström")
+        .set_subject("Finances")
+        .set_keywords("a, b, c")
+        .set_creator("ledger");
+    doc.begin_page(612.0, 792.0);
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert!(output.contains("/Keywords (a, b, c)"));
+    assert!(output.contains("/Subject (Finances)"));
+    assert!(output.contains("/Creator (ledger)"));
+    // A repeated setter replaces the earlier value.
+    assert!(output.contains("/Title (Annual Report)"));
+    assert!(!output.contains("(Draft)"));
+    assert!(output.contains("/Author <FEFF005A006F00EB002000C5006E006700730074007200F6006D>"));
+}
+
+/// Value of a literal-string Info entry, e.g. `/CreationDate (D:...)`.
+fn info_value(output: &str, key: &str) -> Option<String> {
+    let start = output.find(&format!("/{} (", key))? + key.len() + 3;
+    let end = start + output[start..].find(')')?;
+    Some(output[start..end].to_string())
+}
+
+#[test]
+fn dates_are_filled_in_when_not_set() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    let created = info_value(&output, "CreationDate").expect("CreationDate");
+    // D:YYYYMMDDHHmmSS+00'00'
+    assert_eq!(created.len(), 23, "{}", created);
+    assert!(created.starts_with("D:"));
+    assert!(created[2..16].bytes().all(|b| b.is_ascii_digit()));
+    assert!(created.ends_with("+00'00'"));
+    // Every field of the current UTC time is in its calendar range.
+    let field = |range: std::ops::Range<usize>| created[range].parse::<u32>().unwrap();
+    assert!(field(2..6) >= 2024);
+    assert!((1..=12).contains(&field(6..8)));
+    assert!((1..=31).contains(&field(8..10)));
+    assert!(field(10..12) < 24 && field(12..14) < 60 && field(14..16) < 60);
+    assert_eq!(info_value(&output, "ModDate"), Some(created));
+}
+
+#[test]
+fn explicit_dates_are_kept_and_auto_dates_can_be_disabled() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_info("CreationDate", "D:20200101000000Z");
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    assert_eq!(output.matches("/CreationDate").count(), 1);
+    assert!(output.contains("/CreationDate (D:20200101000000Z)"));
+    assert!(output.contains("/ModDate (D:"));
+
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_auto_dates(false);
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    assert!(!output.contains("/CreationDate"));
+    assert!(!output.contains("/ModDate"));
+}
+
+#[test]
+fn producer_defaults_to_crate_name_and_can_be_overridden() {
+    let doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    let producer = info_value(&output, "Producer").expect("Producer");
+    assert!(producer.starts_with("pivot-pdf "), "{}", producer);
+
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_info("Producer", "Acme Reports");
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    assert_eq!(output.matches("/Producer").count(), 1);
+    assert_eq!(
+        info_value(&output, "Producer").as_deref(),
+        Some("Acme Reports")
+    );
+
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_producer(None);
+    let bytes = doc.end_document().unwrap();
+    assert!(!String::from_utf8_lossy(&bytes).contains("/Producer"));
+}
+
+/// Integers in the `/Widths` array of the first font dictionary.
+fn font_widths(output: &str) -> Vec<u16> {
+    let start = output.find("/Widths [").expect("Widths array") + "/Widths [".len();
+    let end = start + output[start..].find(']').unwrap();
+    output[start..end]
+        .split_whitespace()
+        .map(|w| w.parse().unwrap())
+        .collect()
+}
+
+#[test]
+fn builtin_font_dictionary_has_afm_widths() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.place_text("Hello", 72.0, 720.0);
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert!(output.contains("/BaseFont /Helvetica /FirstChar 32 /LastChar 126 /Widths ["));
+    let widths = font_widths(&output);
+    assert_eq!(widths.len(), 95);
+    // Helvetica AFM: space, "A", "W", "a", "~".
+    let width = |ch: char| widths[ch as usize - 32];
+    assert_eq!(width(' '), 278);
+    assert_eq!(width('A'), 667);
+    assert_eq!(width('W'), 944);
+    assert_eq!(width('a'), 556);
+    assert_eq!(width('~'), 584);
+}
+
+#[test]
+fn symbol_font_dictionary_has_no_widths() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.place_text_styled(
+        "abc",
+        72.0,
+        720.0,
+        &TextStyle::builtin(BuiltinFont::Symbol, 12.0),
+    );
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert!(output.contains("/BaseFont /Symbol"));
+    assert!(!output.contains("/Widths"));
+}
+
 #[test]
 fn place_text_in_content_stream() {
     let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
@@ -83,6 +260,101 @@ fn end_page_flushes_to_writer() {
     assert!(*counter.borrow() > after_init);
 }
 
+/// Verifies that flush pushes completed pages through a buffering
+/// writer before end_document.
+#[test]
+fn flush_pushes_completed_pages_to_writer() {
+    struct CountingWriter {
+        byte_count: Rc<RefCell<usize>>,
+    }
+
+    impl Write for CountingWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            *self.byte_count.borrow_mut() += buf.len();
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let counter = Rc::new(RefCell::new(0usize));
+    let writer = io::BufWriter::new(CountingWriter {
+        byte_count: counter.clone(),
+    });
+
+    let mut doc = PdfDocument::new(writer).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.place_text("Hello", 20.0, 20.0);
+    doc.end_page().unwrap();
+
+    // Small page still sits in the BufWriter.
+    assert_eq!(*counter.borrow(), 0);
+
+    doc.flush().unwrap();
+    assert!(*counter.borrow() > 0);
+}
+
+#[test]
+fn second_end_page_is_a_no_op() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.place_text("Once", 72.0, 720.0);
+    doc.end_page().unwrap();
+    assert!(doc.end_page().is_ok());
+    assert_eq!(doc.page_count(), 1);
+
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    assert_eq!(output.matches("/Type /Page ").count(), 1);
+}
+
+#[test]
+fn failed_end_page_keeps_the_page_open() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.place_text("Kept", 72.0, 720.0);
+    doc.append_raw_content(b"BT\n");
+
+    let err = doc.end_page().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(doc.page_count(), 0);
+
+    doc.append_raw_content(b"ET\n");
+    doc.end_page().unwrap();
+    assert_eq!(doc.page_count(), 1);
+    let bytes = doc.end_document().unwrap();
+    assert!(String::from_utf8_lossy(&bytes).contains("(Kept) Tj"));
+}
+
+#[test]
+fn failed_auto_close_is_returned_by_next_end_page() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.append_raw_content(b"BT\n");
+    doc.begin_page(612.0, 792.0)
+        .place_text("Second", 72.0, 720.0);
+
+    let err = doc.end_page().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    // The error is reported once; the new page is still open and closes cleanly.
+    doc.end_page().unwrap();
+    assert_eq!(doc.page_count(), 1);
+    let bytes = doc.end_document().unwrap();
+    assert!(String::from_utf8_lossy(&bytes).contains("(Second) Tj"));
+}
+
+#[test]
+fn failed_auto_close_is_returned_by_end_document() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.append_raw_content(b"BT\n");
+    doc.begin_page(612.0, 792.0);
+
+    let kind = doc.end_document().err().map(|e| e.kind());
+    assert_eq!(kind, Some(io::ErrorKind::InvalidData));
+}
+
 #[test]
 fn auto_close_page_on_begin_page() {
     let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
@@ -169,6 +441,7 @@ fn compressed_truetype_font_has_filter_and_length1() {
         &TextStyle {
             font: font_ref,
             font_size: 12.0,
+            ..TextStyle::default()
         },
     );
     doc.end_page().unwrap();
@@ -185,6 +458,152 @@ fn compressed_truetype_font_has_filter_and_length1() {
     );
 }
 
+#[test]
+fn content_compression_can_be_disabled_separately_from_fonts() {
+    const DEJAVU_SANS: &[u8] = include_bytes!("fixtures/DejaVuSans.ttf");
+
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_compression(true).set_compress_content(false);
+    let font_ref = doc.load_font_bytes(DEJAVU_SANS.to_vec()).unwrap();
+
+    doc.begin_page(612.0, 792.0);
+    doc.place_text_styled(
+        "Test",
+        72.0,
+        720.0,
+        &TextStyle {
+            font: font_ref,
+            font_size: 12.0,
+            ..TextStyle::default()
+        },
+    );
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    // The page content stays readable...
+    assert!(output.contains("BT\n/F15 12 Tf\n72 720 Td\n"));
+    // ...while the FontFile2 and ToUnicode streams are compressed.
+    let font_file_dict = output
+        .split("<<")
+        .find(|dict| dict.contains("/Length1"))
+        .unwrap();
+    assert!(font_file_dict.contains("/Filter /FlateDecode"));
+    assert_eq!(output.matches("/Filter /FlateDecode").count(), 2);
+}
+
+#[test]
+fn page_uncompressed_keeps_only_that_page_readable() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_compression(true).set_page_uncompressed(2);
+    for label in ["Page one", "Page two", "Page three"] {
+        doc.begin_page(612.0, 792.0);
+        // Repeat the label so flate emits real compressed blocks.
+        for i in 0..20 {
+            doc.place_text(label, 72.0, 720.0 - 14.0 * i as f64);
+        }
+        doc.end_page().unwrap();
+    }
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert!(output.contains("(Page two) Tj"));
+    assert!(!output.contains("(Page one) Tj"));
+    assert!(!output.contains("(Page three) Tj"));
+    assert_eq!(output.matches("/Filter /FlateDecode").count(), 2);
+}
+
+#[test]
+fn uncompressed_mark_follows_moved_page() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_compression(true).set_page_uncompressed(1);
+    for _ in 0..3 {
+        doc.begin_page(612.0, 792.0);
+        doc.end_page().unwrap();
+    }
+    doc.move_page(1, 3).unwrap();
+
+    // Overlays are written with the compression of the page they extend.
+    for (page_num, label) in [(3, "Moved"), (1, "Shifted")] {
+        doc.open_page(page_num).unwrap();
+        for i in 0..20 {
+            doc.place_text(label, 72.0, 720.0 - 14.0 * i as f64);
+        }
+    }
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert!(output.contains("(Moved) Tj"));
+    assert!(!output.contains("(Shifted) Tj"));
+}
+
+fn sample_page(compact: bool) -> Vec<u8> {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_compact_content(compact);
+    doc.begin_page(612.0, 792.0);
+    doc.place_text("Total:  (42)", 72.0, 720.0);
+    doc.set_line_width(2.0);
+    doc.rect(72.0, 600.0, 100.0, 50.0).stroke();
+    doc.end_page().unwrap();
+    doc.end_document().unwrap()
+}
+
+#[test]
+fn compact_content_joins_operators_with_spaces() {
+    let plain = sample_page(false);
+    let compact = sample_page(true);
+    assert!(compact.len() < plain.len());
+
+    let output = String::from_utf8_lossy(&compact);
+    assert!(
+        output.contains(
+            "stream\nBT /F1 12 Tf 72 720 Td (Total:  \\(42\\)) Tj ET 2 w 72 600 100 50 re S\nendstream"
+        ),
+        "{}",
+        output
+    );
+    assert_eq!(PdfReader::from_bytes(compact).unwrap().page_count(), 1);
+}
+
+/// Check whether a byte pattern exists in the buffer.
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+/// Output of a one-page document whose content is `ops`, compacted.
+fn compact_raw_page(ops: &[u8]) -> Vec<u8> {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_compact_content(true);
+    doc.begin_page(612.0, 792.0);
+    doc.append_raw_content(ops);
+    doc.end_page().unwrap();
+    doc.end_document().unwrap()
+}
+
+#[test]
+fn compact_content_keeps_strings_intact() {
+    let bytes = compact_raw_page(b"BT\n/F1 12 Tf\n(a\nb  c) Tj\n<48 49> Tj\nET\n");
+    assert!(contains(
+        &bytes,
+        b"stream\nBT /F1 12 Tf (a\nb  c) Tj <48 49> Tj ET\nendstream"
+    ));
+}
+
+#[test]
+fn compact_content_ends_comments_with_newline() {
+    let bytes = compact_raw_page(b"q\n% note\n1 w\nQ\n");
+    assert!(contains(&bytes, b"stream\nq % note\n1 w Q\nendstream"));
+}
+
+#[test]
+fn compact_content_copies_inline_image_data() {
+    let bytes = compact_raw_page(b"q\nBI\n/W 2 /H 1\nID\n\n \xff\nEI\nQ\n");
+    assert!(contains(
+        &bytes,
+        b"stream\nq BI /W 2 /H 1 ID\n\n \xff EI Q\nendstream"
+    ));
+}
+
 #[test]
 fn uncompressed_pdf_has_no_flatedecode_filter() {
     let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
@@ -222,3 +641,413 @@ fn coord_formatting_in_content_stream() {
     // Fractional coord should retain precision.
     assert!(output.contains("12.5 0 Td"));
 }
+
+#[test]
+fn rotated_page_sets_rotate_and_unrotated_mediabox() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    // Landscape as displayed, stored as a portrait MediaBox rotated 90°.
+    doc.begin_page_rotated(792.0, 612.0, 90);
+    doc.place_text("Landscape", 72.0, 540.0);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert!(output.contains("/MediaBox [0 0 612.0 792.0]"));
+    assert!(output.contains("/Rotate 90"));
+}
+
+#[test]
+fn rotated_page_content_is_transformed_by_compensating_matrix() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page_rotated(792.0, 612.0, 90);
+    doc.place_text("Landscape", 72.0, 540.0);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    // (u, v) -> (612 - v, u): maps displayed coordinates onto the MediaBox.
+    assert!(output.contains("stream\nq\n0 1 -1 0 612 0 cm\nBT\n"));
+    assert!(output.contains("72 540 Td\n(Landscape) Tj\nET\nQ\n"));
+}
+
+#[test]
+fn rotated_page_180_and_negative_degrees() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page_rotated(612.0, 792.0, 180);
+    doc.end_page().unwrap();
+    doc.begin_page_rotated(792.0, 612.0, -90);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert!(output.contains("-1 0 0 -1 612 792 cm\n"));
+    assert!(output.contains("/Rotate 180"));
+    assert!(output.contains("0 -1 1 0 0 792 cm\n"));
+    assert!(output.contains("/Rotate 270"));
+}
+
+#[test]
+fn document_rotation_is_inherited_from_pages_node() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_document_rotation(-90);
+    doc.begin_page(612.0, 792.0);
+    doc.end_page().unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.end_page().unwrap();
+    // Explicit per-page rotations override, including an explicit 0.
+    doc.begin_page_rotated(612.0, 792.0, 0);
+    doc.end_page().unwrap();
+    doc.begin_page_rotated(792.0, 612.0, 270);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    let pages_node = output
+        .split("obj\n")
+        .find(|obj| obj.starts_with("<< /Type /Pages"))
+        .unwrap();
+    assert!(pages_node.contains("/Count 4 /Rotate 270"));
+    let page_dicts: Vec<&str> = output
+        .split("obj\n")
+        .filter(|obj| obj.starts_with("<< /Type /Page "))
+        .collect();
+    assert_eq!(page_dicts.len(), 4);
+    assert!(!page_dicts[0].contains("/Rotate"));
+    assert!(!page_dicts[1].contains("/Rotate"));
+    assert!(page_dicts[2].contains("/Rotate 0"));
+    assert!(!page_dicts[3].contains("/Rotate"));
+}
+
+#[test]
+fn overlay_on_rotated_page_repeats_matrix() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page_rotated(792.0, 612.0, 90);
+    doc.end_page().unwrap();
+    doc.open_page(1).unwrap();
+    doc.place_text("Page 1", 700.0, 20.0);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert_eq!(output.matches("0 1 -1 0 612 0 cm\n").count(), 2);
+}
+
+#[test]
+fn flipped_page_turns_content_without_rotate_entry() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page_flipped(612.0, 792.0);
+    doc.place_text("Back page", 72.0, 720.0);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    // The half turn maps (u, v) to (612 - u, 792 - v), keeping content on the sheet.
+    assert_eq!(output.matches(" cm\n").count(), 1);
+    assert!(output.contains("stream\nq\n-1 0 0 -1 612 792 cm\nBT\n"));
+    assert!(output.contains("72 720 Td\n(Back page) Tj\nET\nQ\n"));
+    let page_dict = output
+        .split("obj\n")
+        .find(|obj| obj.starts_with("<< /Type /Page "))
+        .unwrap();
+    assert!(page_dict.contains("/MediaBox [0 0 612.0 792.0]"));
+    assert!(!page_dict.contains("/Rotate"));
+}
+
+#[test]
+fn overlay_on_flipped_page_repeats_matrix() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page_flipped(612.0, 792.0);
+    doc.end_page().unwrap();
+    doc.open_page(1).unwrap();
+    doc.place_text("Page 1", 300.0, 20.0);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert_eq!(output.matches("-1 0 0 -1 612 792 cm\n").count(), 2);
+    assert_eq!(output.matches("ET\nQ\n").count(), 1);
+}
+
+#[test]
+#[should_panic(expected = "multiple of 90")]
+fn rotated_page_rejects_odd_angles() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page_rotated(612.0, 792.0, 45);
+}
+
+#[test]
+fn page_box_with_custom_origin() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page_box(10.0, 10.0, 622.0, 802.0);
+    doc.place_text("Offset", 20.0, 700.0);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert!(output.contains("/MediaBox [10 10 622.0 802.0]"));
+    // Content coordinates are written as given, not shifted by the origin.
+    assert!(output.contains("20 700 Td"));
+}
+
+#[test]
+fn begin_page_keeps_zero_origin() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert!(output.contains("/MediaBox [0 0 612.0 792.0]"));
+}
+
+#[test]
+fn begin_page_default_uses_configured_size() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page_default();
+    doc.set_default_page_size(595.0, 842.0);
+    doc.begin_page_default();
+    doc.begin_page_default();
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    // Letter until configured, then the configured size on every page.
+    assert_eq!(output.matches("/MediaBox [0 0 612.0 792.0]").count(), 1);
+    assert_eq!(output.matches("/MediaBox [0 0 595.0 842.0]").count(), 2);
+}
+
+#[test]
+fn print_on_open_adds_named_print_action() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_print_on_open(true);
+    doc.begin_page(612.0, 792.0);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert!(output.contains("/Type /Catalog"));
+    assert!(output.contains("/OpenAction << /S /Named /N /Print >>"));
+}
+
+#[test]
+fn print_on_open_is_off_by_default() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+
+    assert!(!String::from_utf8_lossy(&bytes).contains("/OpenAction"));
+}
+
+#[test]
+fn deterministic_documents_are_byte_identical() {
+    let build = || {
+        let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+        doc.set_deterministic(true);
+        doc.set_info("Title", "Reproducible");
+        doc.begin_page(612.0, 792.0);
+        doc.place_text("Same bytes every time", 72.0, 720.0);
+        doc.end_page().unwrap();
+        doc.end_document().unwrap()
+    };
+    let first = build();
+    let second = build();
+    assert_eq!(first, second);
+
+    let output = String::from_utf8_lossy(&first);
+    assert!(!output.contains("/CreationDate"));
+    assert!(!output.contains("/ModDate"));
+    // The /ID is a content hash, so different content gets a different one.
+    let id_of = |bytes: &[u8]| {
+        let text = String::from_utf8_lossy(bytes).into_owned();
+        let start = text.find("/ID [<").expect("trailer has /ID") + 6;
+        text[start..start + 32].to_string()
+    };
+    let mut other = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    other.set_deterministic(true);
+    other.begin_page(612.0, 792.0);
+    other.end_page().unwrap();
+    assert_ne!(id_of(&first), id_of(&other.end_document().unwrap()));
+}
+
+#[test]
+fn progress_callback_reports_each_page_and_image() {
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let sink = Arc::clone(&events);
+    doc.set_progress_callback(move |event| sink.lock().unwrap().push(event));
+    let image = doc
+        .load_image_bytes(include_bytes!("fixtures/test.jpg").to_vec())
+        .unwrap();
+
+    for page in 1..=3 {
+        doc.begin_page(612.0, 792.0);
+        if page == 2 {
+            let rect = Rect {
+                x: 72.0,
+                y: 72.0,
+                width: 100.0,
+                height: 100.0,
+            };
+            doc.place_image(&image, &rect, ImageFit::Fit);
+        }
+        doc.end_page().unwrap();
+    }
+    doc.end_document().unwrap();
+
+    let events = events.lock().unwrap();
+    let pages: Vec<usize> = events
+        .iter()
+        .filter_map(|event| match event {
+            ProgressEvent::PageEnded { page } => Some(*page),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(pages, vec![1, 2, 3]);
+    // The image is written with page 2, before that page ends.
+    assert!(matches!(
+        events[1],
+        ProgressEvent::ImageWritten { image: ImageId(0), bytes } if bytes > 633
+    ));
+    assert_eq!(events.len(), 4);
+}
+
+#[test]
+fn document_with_progress_callback_can_move_to_another_thread() {
+    let pages = Arc::new(Mutex::new(0));
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let sink = Arc::clone(&pages);
+    doc.set_progress_callback(move |_| *sink.lock().unwrap() += 1);
+
+    let worker = std::thread::spawn(move || {
+        doc.begin_page(612.0, 792.0);
+        doc.end_page().unwrap();
+        doc.end_document().unwrap()
+    });
+    let bytes = worker.join().unwrap();
+    assert!(bytes.starts_with(b"%PDF-"));
+    assert_eq!(*pages.lock().unwrap(), 1);
+}
+
+#[test]
+fn binary_marker_can_be_disabled() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_binary_marker(false);
+    doc.begin_page(612.0, 792.0);
+    doc.place_text("ASCII", 72.0, 720.0);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    // The first object follows the version line directly.
+    assert!(bytes.starts_with(b"%PDF-1.7\n"));
+    assert!(bytes[9].is_ascii_digit());
+    assert!(bytes.is_ascii());
+    // Offsets still point at the objects.
+    let reader = PdfReader::from_bytes(bytes).unwrap();
+    assert_eq!(reader.page_count(), 1);
+}
+
+#[test]
+fn binary_marker_is_written_by_default() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    assert!(bytes.starts_with(b"%PDF-1.7\n%\xe2\xe3\xcf\xd3\n"));
+}
+
+#[test]
+fn presentation_pages_get_dur_and_trans() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.set_page_duration(5.0)
+        .set_page_transition(Transition::Wipe);
+    doc.end_page().unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert!(output.contains("/Dur 5"), "{}", output);
+    assert!(output.contains("/Trans << /S /Wipe >>"), "{}", output);
+    // Only the configured page auto-advances.
+    assert_eq!(output.matches("/Dur").count(), 1);
+}
+
+#[test]
+fn concat_joins_pages_of_two_documents() {
+    let single_page = |text: &str| {
+        let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+        doc.set_info("Title", text);
+        doc.begin_page(612.0, 792.0);
+        doc.place_text(text, 72.0, 720.0);
+        doc.end_page().unwrap();
+        doc.end_document().unwrap()
+    };
+
+    let merged = PdfDocument::concat(vec![
+        single_page("First source"),
+        single_page("Second source"),
+    ])
+    .unwrap();
+    let output = String::from_utf8_lossy(&merged);
+
+    let reader = PdfReader::from_bytes(merged.clone()).unwrap();
+    assert_eq!(reader.page_count(), 2);
+    assert!(output.contains("(First source) Tj"));
+    assert!(output.contains("(Second source) Tj"));
+    // One catalog and page tree; pages point at the new tree.
+    assert_eq!(output.matches("/Type /Catalog").count(), 1);
+    assert_eq!(output.matches("/Type /Pages").count(), 1);
+    assert_eq!(output.matches("/Parent 2 0 R").count(), 2);
+    // Only the first document's Info is kept.
+    assert!(output.contains("/Title (First source)"));
+    assert!(!output.contains("/Title (Second source)"));
+}
+
+#[test]
+fn concat_copies_compressed_streams_unchanged() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_compression(true);
+    for _ in 0..2 {
+        doc.begin_page(612.0, 792.0);
+        doc.place_text("Compressed", 72.0, 720.0);
+        doc.end_page().unwrap();
+    }
+    let compressed = doc.end_document().unwrap();
+
+    let merged = PdfDocument::concat(vec![compressed.clone(), compressed]).unwrap();
+    let reader = PdfReader::from_bytes(merged).unwrap();
+    assert_eq!(reader.page_count(), 4);
+}
+
+#[test]
+fn page_byte_offset_points_at_the_pages_first_object() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    assert_eq!(doc.page_byte_offset(1), None);
+    for text in ["First page", "Second page"] {
+        doc.begin_page(612.0, 792.0);
+        doc.place_text(text, 72.0, 720.0);
+        doc.end_page().unwrap();
+    }
+    let first = doc.page_byte_offset(1).unwrap();
+    let second = doc.page_byte_offset(2).unwrap();
+    assert_eq!(doc.page_byte_offset(0), None);
+    assert_eq!(doc.page_byte_offset(3), None);
+    let bytes = doc.end_document().unwrap();
+    // Offsets are into the raw bytes; the binary header comment is not UTF-8.
+    let text_at =
+        |start: usize, end: usize| String::from_utf8_lossy(&bytes[start..end]).into_owned();
+
+    for offset in [first, second] {
+        let header = text_at(offset, offset + 20);
+        let parts: Vec<&str> = header.lines().next().unwrap().split(' ').collect();
+        assert_eq!(parts[1..], ["0", "obj"], "{:?}", header);
+        assert!(parts[0].parse::<u32>().is_ok());
+    }
+    // Page 1 writes the Helvetica font, then its content; page 2 only content.
+    let page1 = text_at(first, second);
+    assert!(page1.contains("/BaseFont /Helvetica"));
+    assert!(page1.contains("(First page) Tj"));
+    assert!(!page1.contains("(Second page) Tj"));
+    assert!(text_at(second, bytes.len()).contains("(Second page) Tj"));
+}
@@ -276,6 +276,7 @@ fn page_numbering_use_case() {
     let style = TextStyle {
         font: FontRef::Builtin(BuiltinFont::Helvetica),
         font_size: 10.0,
+        ..TextStyle::default()
     };
 
     let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
@@ -423,6 +424,7 @@ fn overlay_fonts_included_in_page_resources() {
     let courier_style = TextStyle {
         font: FontRef::Builtin(BuiltinFont::Courier),
         font_size: 10.0,
+        ..TextStyle::default()
     };
     doc.open_page(1).unwrap();
     doc.place_text_styled("Footer in Courier", 72.0, 36.0, &courier_style);
@@ -438,3 +440,108 @@ fn overlay_fonts_included_in_page_resources() {
     );
     assert!(output.contains("/Courier"), "Courier should be referenced");
 }
+
+// -------------------------------------------------------
+// Merged overlays
+// -------------------------------------------------------
+
+#[test]
+fn merged_overlay_yields_single_content_stream() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_merge_overlays(true);
+    doc.begin_page(612.0, 792.0);
+    doc.place_text("Page body", 72.0, 700.0);
+    doc.end_page().unwrap();
+
+    doc.open_page(1).unwrap();
+    doc.place_text("Footer", 72.0, 36.0);
+    doc.end_page().unwrap();
+
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert!(
+        !output.contains("/Contents ["),
+        "merged overlay should keep a single /Contents reference"
+    );
+    assert_eq!(output.matches("endstream").count(), 1);
+
+    let body = output.find("(Page body) Tj").expect("body operators");
+    let footer = output.find("(Footer) Tj").expect("overlay operators");
+    let end = output.find("endstream").unwrap();
+    assert!(body < footer && footer < end, "both in the same stream");
+}
+
+#[test]
+fn merged_overlays_compress_into_one_stream() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_compression(true).set_merge_overlays(true);
+    for _ in 0..2 {
+        doc.begin_page(612.0, 792.0);
+        doc.place_text("Body", 72.0, 700.0);
+        doc.end_page().unwrap();
+    }
+    for page in 1..=2 {
+        doc.open_page(page).unwrap();
+        doc.place_text("Footer", 72.0, 36.0);
+        doc.end_page().unwrap();
+    }
+
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert!(!output.contains("/Contents ["));
+    assert_eq!(output.matches("/Filter /FlateDecode").count(), 2);
+}
+
+// -------------------------------------------------------
+// move_page
+// -------------------------------------------------------
+
+/// Object numbers listed in the `/Kids` array, in order.
+fn kids(output: &str) -> Vec<String> {
+    let start = output.find("/Kids [").expect("Kids array") + "/Kids [".len();
+    let end = start + output[start..].find(']').unwrap();
+    output[start..end]
+        .split(" 0 R")
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn three_page_doc() -> PdfDocument<Vec<u8>> {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    for _ in 0..3 {
+        doc.begin_page(612.0, 792.0);
+        doc.end_page().unwrap();
+    }
+    doc
+}
+
+#[test]
+fn move_page_reorders_kids() {
+    let original = kids(&String::from_utf8_lossy(
+        &three_page_doc().end_document().unwrap(),
+    ));
+
+    let mut doc = three_page_doc();
+    doc.move_page(3, 1).unwrap();
+    let moved = kids(&String::from_utf8_lossy(&doc.end_document().unwrap()));
+
+    assert_eq!(original.len(), 3);
+    assert_eq!(
+        moved,
+        vec![
+            original[2].clone(),
+            original[0].clone(),
+            original[1].clone()
+        ]
+    );
+}
+
+#[test]
+fn move_page_out_of_range_returns_error() {
+    let mut doc = three_page_doc();
+    assert!(doc.move_page(0, 1).is_err());
+    assert!(doc.move_page(1, 4).is_err());
+}
@@ -0,0 +1,154 @@
+use pdf_core::{
+    BuiltinFont, Cell, FlowContext, ImageFit, PdfDocument, Rect, Row, Table, TextFlow, TextStyle,
+};
+
+const TEST_PNG: &[u8] = include_bytes!("fixtures/test.png");
+
+fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack.windows(needle.len()).any(|w| w == needle)
+}
+
+fn content_rect() -> Rect {
+    Rect {
+        x: 72.0,
+        y: 720.0,
+        width: 468.0,
+        height: 648.0,
+    }
+}
+
+fn paragraph(text: &str) -> TextFlow {
+    let mut flow = TextFlow::new();
+    flow.add_text(text, &TextStyle::default());
+    flow
+}
+
+fn numbered_rows(count: usize) -> Vec<Row> {
+    (0..count)
+        .map(|i| Row::new(vec![Cell::new(format!("Row {}", i)), Cell::new("value")]))
+        .collect()
+}
+
+#[test]
+fn no_content_produces_no_pages() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let flow = FlowContext::new(&mut doc, 612.0, 792.0, &content_rect());
+    flow.finish().unwrap();
+    assert_eq!(doc.page_count(), 0);
+}
+
+#[test]
+fn heading_advances_cursor_by_line_height() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let mut flow = FlowContext::new(&mut doc, 612.0, 792.0, &content_rect());
+    let style = TextStyle::builtin(BuiltinFont::HelveticaBold, 20.0);
+    flow.heading("Title", &style).unwrap();
+    // Builtin line height is 1.2 × font size.
+    assert!((flow.current_y() - (720.0 - 24.0)).abs() < 1e-9);
+    flow.finish().unwrap();
+    assert_eq!(doc.page_count(), 1);
+}
+
+#[test]
+fn leading_space_offsets_first_block_on_each_page() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let mut flow = FlowContext::new(&mut doc, 612.0, 792.0, &content_rect());
+    let style = TextStyle::builtin(BuiltinFont::HelveticaBold, 20.0);
+    flow.space(40.0).unwrap();
+    flow.heading("First", &style).unwrap();
+    flow.page_break().unwrap();
+    flow.space(40.0).unwrap();
+    flow.heading("Second", &style).unwrap();
+    flow.finish().unwrap();
+    let bytes = doc.end_document().unwrap();
+
+    // Baseline: top 720, minus the 40pt gap, minus one 20pt font size.
+    assert!(contains(&bytes, b"72 660 Td\n(First) Tj"));
+    assert!(contains(&bytes, b"72 660 Td\n(Second) Tj"));
+}
+
+#[test]
+fn paragraph_advances_cursor_by_placed_height() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let mut flow = FlowContext::new(&mut doc, 612.0, 792.0, &content_rect());
+    let mut body = paragraph("One line of body text.");
+    flow.paragraph(&mut body).unwrap();
+    assert!(body.is_finished());
+    // One 12pt line at 1.2 × font size.
+    assert!((flow.current_y() - (720.0 - 14.4)).abs() < 1e-9);
+    flow.finish().unwrap();
+}
+
+#[test]
+fn tall_table_and_paragraphs_span_multiple_pages() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let table = Table::new(vec![234.0, 234.0]);
+    let mut flow = FlowContext::new(&mut doc, 612.0, 792.0, &content_rect());
+
+    let mut intro = paragraph("Introduction paragraph before the table.");
+    flow.paragraph(&mut intro).unwrap();
+    // Each default row is 10pt × 1.2 + 8pt padding = 20pt; 80 rows need ~1600pt.
+    flow.table_rows(&table, &numbered_rows(80)).unwrap();
+    let mut outro = paragraph("Closing paragraph after the table.");
+    flow.paragraph(&mut outro).unwrap();
+    let final_y = flow.current_y();
+    flow.finish().unwrap();
+
+    assert_eq!(doc.page_count(), 3);
+    assert!(outro.is_finished());
+    // The closing paragraph follows the table on the last page, below its top.
+    assert!(final_y < 720.0);
+
+    let bytes = doc.end_document().unwrap();
+    assert!(contains(&bytes, b"(Introduction) Tj"));
+    assert!(contains(&bytes, b"(Row 0) Tj"));
+    assert!(contains(&bytes, b"(Row 79) Tj"));
+    assert!(contains(&bytes, b"(Closing) Tj"));
+}
+
+#[test]
+fn long_paragraph_continues_on_next_page() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let mut flow = FlowContext::new(&mut doc, 612.0, 792.0, &content_rect());
+    let mut body = paragraph(&"Lorem ipsum dolor sit amet. ".repeat(400));
+    flow.paragraph(&mut body).unwrap();
+    flow.finish().unwrap();
+    assert!(body.is_finished());
+    assert!(doc.page_count() > 1);
+}
+
+#[test]
+fn space_past_bottom_starts_new_page() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let mut flow = FlowContext::new(&mut doc, 612.0, 792.0, &content_rect());
+    flow.heading("First", &TextStyle::default()).unwrap();
+    flow.space(1000.0).unwrap();
+    assert_eq!(flow.current_y(), 720.0);
+    flow.heading("Second", &TextStyle::default()).unwrap();
+    flow.finish().unwrap();
+    assert_eq!(doc.page_count(), 2);
+}
+
+#[test]
+fn trailing_page_break_adds_no_empty_page() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let mut flow = FlowContext::new(&mut doc, 612.0, 792.0, &content_rect());
+    flow.heading("Only page", &TextStyle::default()).unwrap();
+    flow.page_break().unwrap();
+    flow.finish().unwrap();
+    assert_eq!(doc.page_count(), 1);
+}
+
+#[test]
+fn image_spans_content_width() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let img = doc.load_image_bytes(TEST_PNG.to_vec()).unwrap();
+    let (w, h) = doc.image_dimensions(&img);
+    let mut flow = FlowContext::new(&mut doc, 612.0, 792.0, &content_rect());
+    flow.image(&img, ImageFit::Fit).unwrap();
+    let expected_height = 468.0 * h as f64 / w as f64;
+    assert!((flow.current_y() - (720.0 - expected_height)).abs() < 1e-6);
+    flow.finish().unwrap();
+    let bytes = doc.end_document().unwrap();
+    assert!(contains(&bytes, b"/Im1 Do"));
+}
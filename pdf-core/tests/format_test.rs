@@ -0,0 +1,30 @@
+use pdf_core::format::{format_currency, format_number};
+
+#[test]
+fn us_number_format() {
+    assert_eq!(format_number(1234.56, 2, ",", "."), "1,234.56");
+    assert_eq!(format_number(1234567.891, 2, ",", "."), "1,234,567.89");
+    assert_eq!(format_number(999.0, 2, ",", "."), "999.00");
+}
+
+#[test]
+fn european_number_format() {
+    assert_eq!(format_number(1234.56, 2, ".", ","), "1.234,56");
+    assert_eq!(format_number(1234567.0, 0, ".", ","), "1.234.567");
+    assert_eq!(format_number(1234.5, 2, "\u{a0}", ","), "1\u{a0}234,50");
+}
+
+#[test]
+fn rounding_sign_and_grouping_edge_cases() {
+    assert_eq!(format_number(-1234.5, 1, ",", "."), "-1,234.5");
+    assert_eq!(format_number(-0.001, 2, ",", "."), "0.00");
+    assert_eq!(format_number(999.999, 2, ",", "."), "1,000.00");
+    assert_eq!(format_number(1234.0, 0, "", "."), "1234");
+}
+
+#[test]
+fn currency_format() {
+    assert_eq!(format_currency(9600.0, "$", 2), "$9,600.00");
+    assert_eq!(format_currency(-5.0, "$", 2), "-$5.00");
+    assert_eq!(format_currency(1234.5, "€ ", 2), "€ 1,234.50");
+}
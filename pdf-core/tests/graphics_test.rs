@@ -1,4 +1,7 @@
-use pdf_core::{Color, PdfDocument};
+use pdf_core::document::MAX_GRID_LINES;
+use pdf_core::{
+    BuiltinFont, Color, GraphicsDefaults, LineCap, LineJoin, PdfDocument, Rect, TextStyle,
+};
 
 #[test]
 fn stroke_line_produces_operators() {
@@ -44,6 +47,16 @@ fn set_line_width_operator() {
     assert!(output.contains("2.5 w\n"));
 }
 
+#[test]
+fn set_dash_operator() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.set_dash(&[3.0, 1.5], 0.0).set_dash(&[], 0.0);
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    assert!(output.contains("[3 1.5] 0 d\n[] 0 d\n"));
+}
+
 #[test]
 fn rect_operator() {
     let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
@@ -190,3 +203,497 @@ fn full_workflow_valid_pdf() {
     assert!(output.contains("/Count 1"));
     assert!(output.contains("(graphics-test)"));
 }
+
+/// Return the data of every stream object, in output order.
+fn stream_bodies(output: &str) -> Vec<&str> {
+    output
+        .split(">>\nstream\n")
+        .skip(1)
+        .filter_map(|chunk| chunk.split("\nendstream").next())
+        .collect()
+}
+
+#[test]
+fn default_graphics_state_starts_every_page() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_default_line_width(2.0)
+        .set_default_stroke_color(Color::rgb(0.0, 0.0, 1.0))
+        .set_default_fill_color(Color::gray(0.5));
+    doc.begin_page(612.0, 792.0);
+    doc.rect(10.0, 10.0, 50.0, 50.0).stroke();
+    doc.begin_page(612.0, 792.0);
+    doc.rect(20.0, 20.0, 50.0, 50.0).stroke();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    let streams = stream_bodies(&output);
+    assert_eq!(streams.len(), 2);
+    for body in streams {
+        assert!(
+            body.starts_with("2 w\n0 0 1 RG\n0.5 0.5 0.5 rg\n"),
+            "page content should begin with defaults, got: {:?}",
+            body
+        );
+    }
+}
+
+#[test]
+fn no_defaults_leaves_page_content_unchanged() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.rect(10.0, 10.0, 50.0, 50.0);
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    assert_eq!(stream_bodies(&output), vec!["10 10 50 50 re\n"]);
+}
+
+#[test]
+fn page_frame_is_inset_by_margin() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.draw_page_frame(36.0, 1.5, Color::rgb(1.0, 0.0, 0.0));
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    // (margin, margin, page_w - 2*margin, page_h - 2*margin)
+    assert!(output.contains("36 36 540 720 re\n"));
+    assert!(output.contains("q\n1 0 0 RG\n1.5 w\n36 36 540 720 re\nS\nQ\n"));
+}
+
+#[test]
+fn crop_marks_sit_outside_trim_corners() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(648.0, 828.0);
+    let trim = Rect {
+        x: 18.0,
+        y: 810.0,
+        width: 612.0,
+        height: 792.0,
+    };
+    doc.draw_crop_marks(&trim, 6.0, 12.0);
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    let body = stream_bodies(&output)[0];
+
+    // Trim corners: left 18, right 630, top 810, bottom 18.
+    let expected = [
+        "12 810 m\n0 810 l\nS\n",
+        "18 816 m\n18 828 l\nS\n",
+        "636 810 m\n648 810 l\nS\n",
+        "630 816 m\n630 828 l\nS\n",
+        "12 18 m\n0 18 l\nS\n",
+        "18 12 m\n18 0 l\nS\n",
+        "636 18 m\n648 18 l\nS\n",
+        "630 12 m\n630 0 l\nS\n",
+    ];
+    for segment in expected {
+        assert!(body.contains(segment), "missing crop mark {:?}", segment);
+    }
+    assert_eq!(body.matches(" m\n").count(), 8);
+    assert_eq!(body.matches("S\n").count(), 8);
+    assert!(body.starts_with("q\n0 0 0 RG\n0.25 w\n"));
+    assert!(body.ends_with("Q\n"));
+}
+
+fn box_rect() -> Rect {
+    Rect {
+        x: 72.0,
+        y: 720.0,
+        width: 100.0,
+        height: 50.0,
+    }
+}
+
+#[test]
+fn draw_box_with_fill_and_stroke_uses_b() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.draw_box(
+        &box_rect(),
+        Some(Color::rgb(1.0, 1.0, 0.0)),
+        Some((Color::rgb(0.0, 0.0, 1.0), 2.0)),
+    );
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    assert_eq!(
+        stream_bodies(&output),
+        vec!["q\n1 1 0 rg\n0 0 1 RG\n2 w\n72 670 100 50 re\nB\nQ\n"]
+    );
+}
+
+#[test]
+fn draw_box_picks_fill_or_stroke_operator() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.draw_box(&box_rect(), Some(Color::gray(0.5)), None);
+    doc.draw_box(&box_rect(), None, Some((Color::gray(0.0), 1.0)));
+    doc.draw_box(&box_rect(), None, None);
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    assert_eq!(
+        stream_bodies(&output),
+        vec![
+            "q\n0.5 0.5 0.5 rg\n72 670 100 50 re\nf\nQ\nq\n0 0 0 RG\n1 w\n72 670 100 50 re\nS\nQ\n"
+        ]
+    );
+}
+
+#[test]
+fn form_placed_on_two_pages_is_written_once() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let logo = doc.create_form_xobject(50.0, 20.0, |form| {
+        form.set_fill_color(Color::rgb(0.0, 0.4, 0.8))
+            .rect(0.0, 0.0, 50.0, 20.0)
+            .fill();
+    });
+    for _ in 0..2 {
+        doc.begin_page(612.0, 792.0);
+        doc.place_form(&logo, 72.0, 700.0, 2.0);
+        doc.end_page().unwrap();
+    }
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert_eq!(output.matches("/Subtype /Form").count(), 1);
+    assert!(output.contains("/BBox [0 0 50.0 20.0]"));
+    assert!(output.contains("0 0.4 0.8 rg\n0 0 50 20 re\nf\n"));
+    // Each page draws it with one Do and lists it in its resources.
+    assert_eq!(
+        output.matches("q\n2 0 0 2 72 700 cm\n/Fm1 Do\nQ\n").count(),
+        2
+    );
+    assert_eq!(output.matches("/XObject << /Fm1 ").count(), 2);
+}
+
+#[test]
+fn placed_form_passes_strict_validation() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_strict(true);
+    let mark = doc.create_form_xobject(10.0, 10.0, |form| {
+        form.move_to(0.0, 0.0).line_to(10.0, 10.0).stroke();
+    });
+    doc.begin_page(612.0, 792.0);
+    doc.place_form(&mark, 0.0, 0.0, 1.0);
+    assert!(doc.validate().is_empty());
+    doc.end_page().unwrap();
+}
+
+#[test]
+fn transparency_group_becomes_form_with_group_dict() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.begin_transparency_group(true, false);
+    doc.rect(10.0, 10.0, 50.0, 50.0).fill();
+    doc.end_transparency_group().unwrap();
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert!(output.contains("/Subtype /Form"));
+    assert!(output.contains("/Group << /S /Transparency /I true /K false >>"));
+    assert!(output.contains("/BBox [0 0 612.0 792.0]"));
+    // The page paints the group; the group holds the drawing.
+    let bodies = stream_bodies(&output);
+    assert!(bodies.contains(&"/Fm1 Do\n"));
+    assert!(bodies.contains(&"10 10 50 50 re\nf\n"));
+    assert!(output.contains("/XObject << /Fm1 "));
+}
+
+#[test]
+fn unclosed_transparency_group_fails_end_page() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.begin_transparency_group(false, false);
+    doc.rect(10.0, 10.0, 50.0, 50.0).fill();
+    assert!(doc.end_page().is_err());
+
+    // The failed end_page kept the page, so the group can still be closed.
+    doc.end_transparency_group().unwrap();
+    doc.end_page().unwrap();
+    assert_eq!(doc.page_count(), 1);
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    assert!(stream_bodies(&output).contains(&"10 10 50 50 re\nf\n"));
+}
+
+#[test]
+fn end_transparency_group_without_open_group_is_an_error() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    let kind = doc.end_transparency_group().err().map(|e| e.kind());
+    assert_eq!(kind, Some(std::io::ErrorKind::InvalidInput));
+    doc.end_page().unwrap();
+}
+
+#[test]
+fn raw_content_is_appended_verbatim() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.rect(10.0, 10.0, 50.0, 50.0)
+        .append_raw_content(b"1 J\n2 j\n")
+        .stroke();
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert_eq!(
+        stream_bodies(&output),
+        vec!["10 10 50 50 re\n1 J\n2 j\nS\n"]
+    );
+}
+
+#[test]
+fn coordinate_precision_controls_decimal_places() {
+    let render = |digits: u8| {
+        let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+        doc.set_coordinate_precision(digits);
+        doc.begin_page(612.0, 792.0);
+        doc.move_to(12.34567, 100.0);
+        let bytes = doc.end_document().unwrap();
+        String::from_utf8_lossy(&bytes).into_owned()
+    };
+
+    assert!(render(2).contains("\n12.35 100 m\n"));
+    assert!(render(6).contains("\n12.34567 100 m\n"));
+    // Default keeps four places.
+    assert!(render(4).contains("\n12.3457 100 m\n"));
+}
+
+#[test]
+fn closed_polyline_emits_one_move_three_lines_and_close() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.polyline(
+        &[(10.0, 10.0), (60.0, 10.0), (60.0, 40.0), (10.0, 40.0)],
+        true,
+    )
+    .stroke();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert_eq!(
+        stream_bodies(&output),
+        vec!["10 10 m\n60 10 l\n60 40 l\n10 40 l\nh\nS\n"]
+    );
+}
+
+#[test]
+fn grid_strokes_lines_at_each_interval() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    let rect = Rect {
+        x: 100.0,
+        y: 300.0,
+        width: 100.0,
+        height: 100.0,
+    };
+    doc.draw_grid(&rect, 25.0, 25.0, Color::gray(0.8), 0.5);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    let body = stream_bodies(&output)[0];
+
+    // Five vertical and five horizontal lines, edges included, one stroke.
+    assert_eq!(body.matches(" m\n").count(), 10);
+    assert_eq!(body.matches(" l\n").count(), 10);
+    assert_eq!(body.matches("S\n").count(), 1);
+    assert!(body.contains("125 200 m\n125 300 l\n"));
+    assert!(body.contains("100 275 m\n200 275 l\n"));
+    assert!(body.starts_with("q\n") && body.ends_with("Q\n"));
+}
+
+#[test]
+fn grid_with_tiny_spacing_is_clamped_to_max_lines() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    let rect = Rect {
+        x: 100.0,
+        y: 300.0,
+        width: 100.0,
+        height: 100.0,
+    };
+    doc.draw_grid(&rect, 1e-9, 50.0, Color::gray(0.8), 0.5);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    let body = stream_bodies(&output)[0];
+
+    // The vertical lines are capped and still span the rect edge to edge.
+    assert_eq!(body.matches(" m\n").count(), MAX_GRID_LINES + 3);
+    assert!(body.contains("100 200 m\n100 300 l\n"));
+    assert!(body.contains("200 200 m\n200 300 l\n"));
+    assert!(body.contains("100 250 m\n200 250 l\n"));
+}
+
+#[test]
+fn grid_without_lines_emits_nothing() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    let rect = Rect {
+        x: 100.0,
+        y: 300.0,
+        width: 100.0,
+        height: 100.0,
+    };
+    doc.draw_grid(&rect, 0.0, f64::NAN, Color::gray(0.8), 0.5);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert!(stream_bodies(&output)
+        .iter()
+        .all(|body| !body.contains(" RG")));
+    assert!(!output.contains("\nS\n"));
+}
+
+#[test]
+fn bar_chart_bar_widths_are_proportional_to_values() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    let rect = Rect {
+        x: 72.0,
+        y: 700.0,
+        width: 400.0,
+        height: 90.0,
+    };
+    doc.draw_bar_chart(
+        &rect,
+        &[
+            ("North".to_string(), 10.0, Color::rgb(1.0, 0.0, 0.0)),
+            ("South".to_string(), 20.0, Color::rgb(0.0, 1.0, 0.0)),
+            ("East".to_string(), 40.0, Color::rgb(0.0, 0.0, 1.0)),
+        ],
+        None,
+    );
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    let body = stream_bodies(&output)[0];
+
+    let widths: Vec<f64> = body
+        .lines()
+        .filter(|line| line.ends_with(" re"))
+        .map(|line| line.split(' ').nth(2).unwrap().parse().unwrap())
+        .collect();
+    assert_eq!(widths.len(), 3);
+    assert!((widths[1] - 2.0 * widths[0]).abs() < 1e-3, "{:?}", widths);
+    assert!((widths[2] - 4.0 * widths[0]).abs() < 1e-3, "{:?}", widths);
+    assert_eq!(body.matches("\nf\n").count(), 3);
+    for color in ["1 0 0 rg", "0 1 0 rg", "0 0 1 rg"] {
+        assert!(body.contains(color), "{}", color);
+    }
+    assert!(body.contains("(North) Tj"));
+    assert!(body.contains("(40) Tj"));
+}
+
+#[test]
+fn with_clip_brackets_drawing_in_clipped_state() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    let rect = Rect {
+        x: 72.0,
+        y: 700.0,
+        width: 100.0,
+        height: 50.0,
+    };
+    doc.with_clip(&rect, |doc| {
+        doc.set_fill_color(Color::rgb(1.0, 0.0, 0.0))
+            .rect(50.0, 600.0, 200.0, 200.0)
+            .fill();
+    })
+    .rect(300.0, 300.0, 10.0, 10.0)
+    .fill();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    let body = stream_bodies(&output)[0];
+
+    assert_eq!(
+        body,
+        "q\n72 650 100 50 re\nW\nn\n\
+         1 0 0 rg\n50 600 200 200 re\nf\n\
+         Q\n\
+         300 300 10 10 re\nf\n"
+    );
+}
+
+#[test]
+fn two_equal_slice_pie_fills_two_half_circles() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.draw_pie(
+        200.0,
+        400.0,
+        100.0,
+        &[
+            (1.0, Color::rgb(1.0, 0.0, 0.0)),
+            (1.0, Color::rgb(0.0, 0.0, 1.0)),
+        ],
+    );
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    let body = stream_bodies(&output)[0];
+
+    // Right half from 12 to 6 o'clock, then the left half back to 12, each
+    // as two quarter-circle curves with control points 0.5523·r out.
+    assert_eq!(
+        body,
+        "q\n\
+         1 0 0 rg\n200 400 m\n200 500 l\n\
+         255.2285 500 300 455.2285 300 400 c\n\
+         300 344.7715 255.2285 300 200 300 c\nh\nf\n\
+         0 0 1 rg\n200 400 m\n200 300 l\n\
+         144.7715 300 100 344.7715 100 400 c\n\
+         100 455.2285 144.7715 500 200 500 c\nh\nf\n\
+         Q\n"
+    );
+}
+
+#[test]
+fn graphics_defaults_start_every_page() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_graphics_defaults(GraphicsDefaults {
+        fill_color: Some(Color::gray(0.9)),
+        stroke_color: Some(Color::rgb(1.0, 0.0, 0.0)),
+        line_width: Some(0.5),
+        line_cap: Some(LineCap::Round),
+        line_join: Some(LineJoin::Bevel),
+        dash: Some((vec![3.0, 2.0], 0.0)),
+    });
+    doc.begin_page(612.0, 792.0);
+    doc.move_to(10.0, 10.0).line_to(100.0, 10.0).stroke();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    let streams = stream_bodies(&output);
+    assert!(
+        streams[0].starts_with("0.5 w\n1 0 0 RG\n0.9 0.9 0.9 rg\n1 J\n2 j\n[3 2] 0 d\n"),
+        "page content should begin with defaults, got: {:?}",
+        streams[0]
+    );
+}
+
+#[test]
+fn chip_fills_rounded_rect_and_centres_text() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    let style = TextStyle::builtin(BuiltinFont::Helvetica, 10.0);
+    let rect = Rect {
+        x: 100.0,
+        y: 700.0,
+        width: 80.0,
+        height: 20.0,
+    };
+    doc.draw_chip(&rect, 10.0, Color::rgb(0.0, 0.6, 0.0), "OK", &style);
+    let text_x = 100.0 + (80.0 - doc.measure_text("OK", &style)) / 2.0;
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert!(
+        output.contains("q\n0 0.6 0 rg\n110 680 m\n170 680 l\n"),
+        "{}",
+        output
+    );
+    assert_eq!(output.matches(" c\n").count(), 4, "one curve per corner");
+    assert!(output.contains("h\nf\nQ\n"));
+    // Baseline sits half the cap height (7.18pt) below the chip's middle.
+    let expected = format!("{} 686.41 Td\n(OK) Tj", text_x);
+    assert!(output.contains(&expected), "{}", output);
+}
@@ -1,4 +1,5 @@
-use pdf_core::{ImageFit, PdfDocument, Rect};
+use pdf_core::images::calculate_anchored_placement;
+use pdf_core::{ImageAnchor, ImageFit, PdfDocument, Rect};
 
 const TEST_JPEG: &[u8] = include_bytes!("fixtures/test.jpg");
 const TEST_PNG: &[u8] = include_bytes!("fixtures/test.png");
@@ -153,6 +154,31 @@ fn content_stream_has_image_operators() {
     assert!(output.contains("Q\n"), "Should have restore state (Q)");
 }
 
+#[test]
+fn tile_image_covers_rect_and_clips_to_it() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let img = doc.load_image_bytes(TEST_PNG.to_vec()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    // 100 x 50 at 30 x 20 tiles: 4 columns by 3 rows, partial at the edges.
+    let rect = Rect {
+        x: 72.0,
+        y: 72.0,
+        width: 100.0,
+        height: 50.0,
+    };
+    doc.tile_image(&img, &rect, 30.0, 20.0, ImageFit::Stretch);
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert_eq!(output.matches("/Im1 Do\n").count(), 12);
+    assert!(output.contains("q\n72 670 100 50 re W n\n"));
+    // First tile at the top-left, last one overhanging the bottom-right.
+    assert!(output.contains("30 0 0 20 72 700 cm\n"));
+    assert!(output.contains("30 0 0 20 162 660 cm\n"));
+    // The image XObject itself is written once.
+    assert_eq!(output.matches("/Subtype /Image").count(), 1);
+}
+
 // -------------------------------------------------------
 // Fit modes
 // -------------------------------------------------------
@@ -179,6 +205,45 @@ fn fit_mode_preserves_aspect_ratio() {
     );
 }
 
+#[test]
+fn fit_with_center_anchor_splits_leftover_space() {
+    // 100x80 image in a 200x150 rect scales to 187.5x150: 12.5pt spare
+    // width, none spare height. Rect bottom in PDF space: 792 - 222 = 570.
+    let place =
+        |anchor| calculate_anchored_placement(100, 80, &make_rect(), ImageFit::Fit, anchor, 792.0);
+    let center = place(ImageAnchor::Center);
+    assert_eq!((center.x, center.y), (72.0 + 6.25, 570.0));
+    let left = place(ImageAnchor::TopLeft);
+    assert_eq!((left.x, left.y), (72.0, 570.0));
+    let right = place(ImageAnchor::BottomRight);
+    assert_eq!((right.x, right.y), (72.0 + 12.5, 570.0));
+}
+
+#[test]
+fn natural_size_anchor_offsets_both_axes() {
+    // Unscaled 100x80 leaves 100pt spare width and 70pt spare height.
+    let place =
+        |anchor| calculate_anchored_placement(100, 80, &make_rect(), ImageFit::None, anchor, 792.0);
+    let center = place(ImageAnchor::Center);
+    assert_eq!((center.x, center.y), (72.0 + 50.0, 570.0 + 35.0));
+    let bottom_right = place(ImageAnchor::BottomRight);
+    assert_eq!((bottom_right.x, bottom_right.y), (172.0, 570.0));
+    // place_image keeps natural-size images at the top-left.
+    let top_left = place(ImageAnchor::TopLeft);
+    assert_eq!((top_left.x, top_left.y), (72.0, 640.0));
+}
+
+#[test]
+fn place_image_anchored_emits_anchored_matrix() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let img = doc.load_image_bytes(TEST_PNG.to_vec()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.place_image_anchored(&img, &make_rect(), ImageFit::Fit, ImageAnchor::Right);
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    assert!(output.contains("187.5 0 0 150 84.5 570 cm\n"));
+}
+
 #[test]
 fn fill_mode_has_clipping() {
     let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
@@ -274,6 +339,33 @@ fn jpeg_keeps_only_dctdecode() {
     assert_eq!(dct_count, 1, "Only one DCTDecode filter expected");
 }
 
+// -------------------------------------------------------
+// Interpolation
+// -------------------------------------------------------
+
+#[test]
+fn interpolation_off_by_default() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let img = doc.load_image_bytes(TEST_PNG.to_vec()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.place_image(&img, &make_rect(), ImageFit::Fit);
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    assert!(!output.contains("/Interpolate"));
+}
+
+#[test]
+fn interpolation_enabled_emits_flag() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let img = doc.load_image_bytes(TEST_PNG.to_vec()).unwrap();
+    doc.set_image_interpolation(&img, true);
+    doc.begin_page(612.0, 792.0);
+    doc.place_image(&img, &make_rect(), ImageFit::Fit);
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    assert!(output.contains("/Interpolate true"));
+}
+
 // -------------------------------------------------------
 // Multi-page / deduplication
 // -------------------------------------------------------
@@ -423,3 +515,267 @@ fn full_workflow_produces_valid_pdf() {
     assert!(output.contains("/Count 1"), "Has one page");
     assert!(output.contains("(images-test)"), "Has info");
 }
+
+// -------------------------------------------------------
+// Page thumbnails
+// -------------------------------------------------------
+
+#[test]
+fn page_thumbnail_references_image_xobject() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let img = doc.load_image_bytes(TEST_PNG.to_vec()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.set_page_thumbnail(&img).unwrap();
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    // Find the image XObject's object number and check /Thumb points at it.
+    let img_pos = output.find("/Subtype /Image").unwrap();
+    let obj_start = output[..img_pos].rfind(" 0 obj").unwrap();
+    let num_start = output[..obj_start].rfind('\n').unwrap() + 1;
+    let obj_num = &output[num_start..obj_start];
+    assert!(output.contains(&format!("/Thumb {} 0 R", obj_num)));
+}
+
+#[test]
+fn place_image_exact_uses_given_matrix() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let img = doc.load_image_bytes(TEST_JPEG.to_vec()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.place_image_exact(&img, 50.0, 600.0, 120.0, 45.5);
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    assert!(output.contains("120 0 0 45.5 50 600 cm"), "{}", output);
+    assert!(
+        !output.contains("re W n"),
+        "exact placement should not clip"
+    );
+}
+
+#[test]
+fn place_image_full_width_returns_next_y() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let img = doc.load_image_bytes(blank_png(20, 10)).unwrap();
+    doc.begin_page(612.0, 792.0);
+    let next_y = doc.place_image_full_width(&img, 72.0, 720.0, 400.0);
+    assert_eq!(next_y, 520.0);
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    // 2:1 image at width 400 is 200pt tall, bottom edge at 520.
+    assert!(output.contains("400 0 0 200 72 520 cm"), "{}", output);
+}
+
+/// Encode a blank grayscale PNG of the given size.
+fn blank_png(width: u32, height: u32) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, width, height);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().unwrap();
+    writer
+        .write_image_data(&vec![255; (width * height) as usize])
+        .unwrap();
+    drop(writer);
+    out
+}
+
+#[test]
+fn oversized_thumbnail_is_rejected() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let img = doc.load_image_bytes(blank_png(300, 10)).unwrap();
+    doc.begin_page(612.0, 792.0);
+    let err = doc
+        .set_page_thumbnail(&img)
+        .err()
+        .expect("oversized thumbnail should be rejected");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+/// Encode a noisy RGB gradient PNG that resembles photographic content.
+fn photo_png(width: u32, height: u32) -> Vec<u8> {
+    let mut seed: u32 = 12345;
+    let mut pixels = Vec::with_capacity((width * height * 3) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            let noise = (seed >> 16) % 24;
+            pixels.push(((x * 255 / width) + noise).min(255) as u8);
+            pixels.push(((y * 255 / height) + noise).min(255) as u8);
+            pixels.push((((x + y) * 127 / (width + height)) + noise).min(255) as u8);
+        }
+    }
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, width, height);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().unwrap();
+    writer.write_image_data(&pixels).unwrap();
+    drop(writer);
+    out
+}
+
+fn render_single_image(
+    load: impl FnOnce(&mut PdfDocument<Vec<u8>>) -> pdf_core::ImageId,
+) -> Vec<u8> {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let img = load(&mut doc);
+    doc.begin_page(612.0, 792.0);
+    doc.place_image(&img, &make_rect(), ImageFit::Fit);
+    doc.end_document().unwrap()
+}
+
+#[test]
+fn png_converted_to_jpeg_is_smaller_and_uses_dctdecode() {
+    let png = photo_png(400, 300);
+    let flate = render_single_image(|doc| doc.load_image_bytes(png.clone()).unwrap());
+    let jpeg = render_single_image(|doc| doc.load_image_bytes_as_jpeg(png.clone(), 60).unwrap());
+
+    let output = String::from_utf8_lossy(&jpeg);
+    assert!(output.contains("/Filter /DCTDecode"));
+    assert!(output.contains("/ColorSpace /DeviceRGB"));
+    assert!(
+        jpeg.len() * 2 < flate.len(),
+        "JPEG output ({} bytes) should be well under Flate output ({} bytes)",
+        jpeg.len(),
+        flate.len()
+    );
+}
+
+#[test]
+fn jpeg_input_is_stored_unchanged() {
+    let output = render_single_image(|doc| {
+        doc.load_image_bytes_as_jpeg(TEST_JPEG.to_vec(), 60)
+            .unwrap()
+    });
+    assert!(output.windows(TEST_JPEG.len()).any(|w| w == TEST_JPEG));
+}
+
+#[test]
+fn background_image_precedes_earlier_text() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let img = doc.load_image_bytes(TEST_PNG.to_vec()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.place_text("On top", 72.0, 700.0);
+    doc.place_image_background(&img, &make_rect(), ImageFit::Stretch);
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    let image = output.find("/Im1 Do").expect("image operators");
+    let text = output.find("(On top) Tj").expect("text operators");
+    assert!(image < text, "background image should be painted first");
+    assert!(output.contains("/XObject"));
+}
+
+#[test]
+fn background_image_follows_rotation_preamble() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let img = doc.load_image_bytes(TEST_PNG.to_vec()).unwrap();
+    doc.begin_page_rotated(792.0, 612.0, 90);
+    doc.place_text("Label", 72.0, 500.0);
+    doc.place_image_background(&img, &make_rect(), ImageFit::Stretch);
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    let matrix = output.find(" cm\nq\n").expect("rotation matrix then image");
+    let image = output.find("/Im1 Do").unwrap();
+    let text = output.find("(Label) Tj").unwrap();
+    assert!(matrix < image && image < text);
+}
+
+#[test]
+fn separate_mask_image_becomes_smask() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let color = doc.load_image_bytes(photo_png(16, 16)).unwrap();
+    let mask = doc.load_image_bytes(blank_png(16, 16)).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.place_image_with_mask(&color, &mask, &make_rect(), ImageFit::Fit)
+        .unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    // The mask XObject is written first; find its object number.
+    let mask_pos = output.find("/ColorSpace /DeviceGray").unwrap();
+    let obj_start = output[..mask_pos].rfind(" 0 obj").unwrap();
+    let num_start = output[..obj_start].rfind('\n').unwrap() + 1;
+    let mask_num = &output[num_start..obj_start];
+
+    let color_pos = output.find("/ColorSpace /DeviceRGB").unwrap();
+    let color_dict_end = color_pos + output[color_pos..].find(">>").unwrap();
+    assert!(output[color_pos..color_dict_end].contains(&format!("/SMask {} 0 R", mask_num)));
+    // Only the color image is drawn; the mask is not a page resource.
+    assert_eq!(output.matches(" Do\n").count(), 1);
+}
+
+#[test]
+fn color_mask_image_is_rejected() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let color = doc.load_image_bytes(photo_png(16, 16)).unwrap();
+    let not_gray = doc.load_image_bytes(photo_png(16, 16)).unwrap();
+    doc.begin_page(612.0, 792.0);
+    let err = doc
+        .place_image_with_mask(&color, &not_gray, &make_rect(), ImageFit::Fit)
+        .err()
+        .expect("RGB mask should be rejected");
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn inverted_images_emit_decode_for_color_space() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let gray = doc.load_image_bytes(blank_png(8, 8)).unwrap();
+    let rgb = doc.load_image_bytes(photo_png(8, 8)).unwrap();
+    doc.set_image_invert(&gray, true)
+        .set_image_invert(&rgb, true);
+    doc.begin_page(612.0, 792.0);
+    doc.place_image(&gray, &make_rect(), ImageFit::Fit);
+    doc.place_image(&rgb, &make_rect(), ImageFit::Fit);
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert!(output.contains("/ColorSpace /DeviceGray /BitsPerComponent 8 /Decode [1 0]"));
+    assert!(output.contains("/ColorSpace /DeviceRGB /BitsPerComponent 8 /Decode [1 0 1 0 1 0]"));
+}
+
+#[test]
+fn images_are_not_inverted_by_default() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let img = doc.load_image_bytes(blank_png(8, 8)).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.place_image(&img, &make_rect(), ImageFit::Fit);
+    let bytes = doc.end_document().unwrap();
+    assert!(!String::from_utf8_lossy(&bytes).contains("/Decode"));
+}
+
+#[test]
+fn page_background_image_covers_the_page() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let img = doc.load_image_bytes(blank_png(20, 10)).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.set_page_background_image(&img, ImageFit::Stretch);
+    doc.place_text("Cover title", 72.0, 700.0);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    let image = output
+        .find("612 0 0 792 0 0 cm\n/Im1 Do")
+        .expect("page-sized image");
+    let text = output.find("(Cover title) Tj").unwrap();
+    assert!(image < text);
+}
+
+#[test]
+fn page_background_image_fill_crops_to_the_media_box() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let img = doc.load_image_bytes(blank_png(20, 10)).unwrap();
+    doc.begin_page_box(10.0, 20.0, 110.0, 220.0);
+    doc.set_page_background_image(&img, ImageFit::Fill);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    // A 2:1 image filling a 100x200 box is 400 wide, centered and clipped.
+    assert!(output.contains("10 20 100 200 re W n"), "{}", output);
+    assert!(output.contains("400 0 0 200 -140 20 cm"), "{}", output);
+}
@@ -27,6 +27,30 @@ fn literal_string_constructor() {
     }
 }
 
+#[test]
+fn text_string_keeps_printable_ascii_literal() {
+    match PdfObject::text_string("Report (draft)") {
+        PdfObject::LiteralString(s) => assert_eq!(s, "Report (draft)"),
+        _ => panic!("expected LiteralString"),
+    }
+}
+
+#[test]
+fn text_string_uses_hex_for_control_bytes() {
+    match PdfObject::text_string("a\rb") {
+        PdfObject::HexString(bytes) => assert_eq!(bytes, b"a\rb"),
+        _ => panic!("expected HexString"),
+    }
+}
+
+#[test]
+fn text_string_encodes_non_ascii_as_utf16be() {
+    match PdfObject::text_string("é") {
+        PdfObject::HexString(bytes) => assert_eq!(bytes, vec![0xFE, 0xFF, 0x00, 0xE9]),
+        _ => panic!("expected HexString"),
+    }
+}
+
 #[test]
 fn reference_constructor() {
     let obj = PdfObject::reference(5, 0);
@@ -58,6 +58,22 @@ fn reader_pdf_version() {
     assert_eq!(reader.pdf_version(), "1.7");
 }
 
+// --- Encryption ---
+
+#[test]
+fn reader_detects_encrypted_fixture() {
+    let bytes = include_bytes!("fixtures/encrypted.pdf").to_vec();
+    let reader = PdfReader::from_bytes(bytes).unwrap();
+    assert!(reader.is_encrypted());
+    assert_eq!(reader.page_count(), 1);
+}
+
+#[test]
+fn reader_plain_pdf_is_not_encrypted() {
+    let reader = PdfReader::from_bytes(make_pdf(1)).unwrap();
+    assert!(!reader.is_encrypted());
+}
+
 // --- Task 5: open() ---
 
 #[test]
@@ -0,0 +1,67 @@
+use pdf_core::document::SIGNATURE_CONTENTS_LEN;
+use pdf_core::{PdfDocument, Rect};
+
+fn doc_with_signature() -> String {
+    doc_with_signature_named("Approver")
+}
+
+fn doc_with_signature_named(name: &str) -> String {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.add_signature_field(
+        name,
+        &Rect {
+            x: 72.0,
+            y: 144.0,
+            width: 200.0,
+            height: 50.0,
+        },
+    )
+    .unwrap();
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[test]
+fn signature_field_widget_is_written() {
+    let output = doc_with_signature();
+    assert!(output.contains("/Subtype /Widget"));
+    assert!(output.contains("/FT /Sig"));
+    assert!(output.contains("/T (Approver)"));
+    assert!(output.contains("/Rect [72.0 94.0 272.0 144.0]"));
+    assert!(output.contains("/Annots ["));
+}
+
+#[test]
+fn non_ascii_field_name_is_utf16be_hex() {
+    let output = doc_with_signature_named("Prüfer");
+    assert!(output.contains("/T <FEFF0050007200FC006600650072>"));
+}
+
+#[test]
+fn signature_dictionary_has_placeholders() {
+    let output = doc_with_signature();
+    assert!(output.contains("/Type /Sig"));
+    assert!(output.contains("/ByteRange [0 9999999999 9999999999 9999999999]"));
+    let zeros = "0".repeat(SIGNATURE_CONTENTS_LEN * 2);
+    assert!(output.contains(&format!("/Contents <{}>", zeros)));
+}
+
+#[test]
+fn acroform_sets_sig_flags() {
+    let output = doc_with_signature();
+    assert!(output.contains("/AcroForm <<"));
+    assert!(output.contains("/SigFlags 3"));
+}
+
+#[test]
+fn no_acroform_without_fields() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    assert!(!output.contains("/AcroForm"));
+    assert!(!output.contains("/Annots"));
+}
@@ -0,0 +1,43 @@
+use pdf_core::{ImageFit, PdfDocument, Rect};
+
+const TEST_PNG: &[u8] = include_bytes!("fixtures/test.png");
+
+#[test]
+fn new_document_has_empty_stats() {
+    let doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let stats = doc.stats();
+    assert_eq!(stats.page_count, 0);
+    assert_eq!(stats.content_bytes, 0);
+    assert_eq!(stats.image_bytes, 0);
+    assert_eq!(stats.object_count, 0);
+}
+
+#[test]
+fn stats_report_image_and_content_bytes() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let img = doc.load_image_bytes(TEST_PNG.to_vec()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.place_image(
+        &img,
+        &Rect {
+            x: 72.0,
+            y: 72.0,
+            width: 200.0,
+            height: 150.0,
+        },
+        ImageFit::Fit,
+    );
+    doc.place_text("Caption", 72.0, 500.0);
+    doc.end_page().unwrap();
+
+    let stats = doc.stats();
+    assert_eq!(stats.page_count, 1);
+    assert!(stats.image_bytes > 0);
+    assert!(stats.content_bytes > 0);
+    assert!(
+        stats.font_bytes > 0,
+        "builtin font dict is written at end_page"
+    );
+    // Font dict, image XObject and content stream.
+    assert_eq!(stats.object_count, 3);
+}
@@ -1,6 +1,6 @@
 use pdf_core::{
     BuiltinFont, Cell, CellOverflow, CellStyle, Color, FitResult, FontRef, PdfDocument, Rect, Row,
-    Table, TableCursor, TextAlign, WordBreak,
+    Table, TableCursor, TextAlign, TextStyle, UnderlineStyle, VerticalAlign, WordBreak,
 };
 
 /// Check whether a byte pattern exists in the buffer.
@@ -294,6 +294,79 @@ fn header_repeated_on_each_page_via_is_first_row() {
     assert_eq!(count, pages, "header should appear on every page");
 }
 
+#[test]
+fn continued_caption_marks_pages_after_the_first() {
+    let small_rect = Rect {
+        x: 72.0,
+        y: 720.0,
+        width: 468.0,
+        height: 60.0,
+    };
+    let mut table = two_col_table();
+    table.continued_caption = Some(Cell::new("continued"));
+    let header = data_row("Name", "Value");
+    let data: Vec<Row> = (0..12)
+        .map(|i| data_row(&format!("Item {}", i), &format!("{}", i * 100)))
+        .collect();
+
+    let mut doc = make_doc();
+    let mut cursor = TableCursor::new(&small_rect);
+    let mut iter = data.iter().peekable();
+    while iter.peek().is_some() {
+        doc.begin_page(612.0, 792.0);
+        doc.fit_row(&table, &header, &mut cursor).unwrap();
+        while let Some(row) = iter.peek() {
+            match doc.fit_row(&table, row, &mut cursor).unwrap() {
+                FitResult::Stop => {
+                    iter.next();
+                }
+                _ => break,
+            }
+        }
+        doc.end_page().unwrap();
+        cursor.reset(&small_rect);
+    }
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    let pages: Vec<&str> = output.split(">>\nstream\n").skip(1).collect();
+    assert!(pages.len() >= 2);
+    assert!(!pages[0].contains("(continued) Tj"));
+    for page in &pages[1..] {
+        // The caption comes before the repeated header.
+        let caption = page.find("(continued) Tj").expect("caption on page");
+        assert!(caption < page.find("(Name) Tj").unwrap());
+    }
+}
+
+#[test]
+fn rotated_header_cell_uses_text_matrix_and_grows_row() {
+    let mut doc = make_doc();
+    let style = CellStyle {
+        rotation: 90.0,
+        ..CellStyle::default()
+    };
+    let table = Table::new(vec![60.0, 60.0]);
+    let header = Row::new(vec![Cell::styled("Quantity", style), Cell::new("Price")]);
+    let mut cursor = TableCursor::new(&full_rect());
+    doc.begin_page(612.0, 792.0);
+    doc.fit_row(&table, &header, &mut cursor).unwrap();
+
+    // The row is as tall as the vertical text is long, plus padding.
+    let text_len = doc.measure_text(
+        "Quantity",
+        &pdf_core::TextStyle::builtin(BuiltinFont::Helvetica, 10.0),
+    );
+    assert!((cursor.current_y() - (720.0 - text_len - 8.0)).abs() < 1e-9);
+
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    // Rotated 90 degrees about the cell centre.
+    assert!(output.contains("0 1 -1 0 105.3333 678.76 Tm\n(Quantity) Tj\n"));
+    assert_eq!(output.matches(" Tm\n").count(), 1);
+}
+
 // -------------------------------------------------------
 // Borders
 // -------------------------------------------------------
@@ -344,6 +417,39 @@ fn custom_border_color_is_emitted() {
     assert!(contains(&bytes, b"1 0 0 RG\n"));
 }
 
+#[test]
+fn dashed_border_wraps_strokes_and_resets() {
+    let mut table = two_col_table();
+    table.border_dash = Some(vec![1.0, 2.0]);
+    let mut doc = make_doc();
+    doc.begin_page(612.0, 792.0);
+    let mut cursor = TableCursor::new(&full_rect());
+    doc.fit_row(&table, &data_row("A", "B"), &mut cursor)
+        .unwrap();
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    let dash = output.find("[1 2] 0 d\n").expect("dash pattern set");
+    let stroke = output.find("re\nS\n").expect("border stroke");
+    let reset = output.find("[] 0 d\nQ\n").expect("dash reset before Q");
+    assert!(dash < stroke && stroke < reset);
+}
+
+#[test]
+fn solid_border_emits_no_dash() {
+    let table = two_col_table();
+    let mut doc = make_doc();
+    doc.begin_page(612.0, 792.0);
+    let mut cursor = TableCursor::new(&full_rect());
+    doc.fit_row(&table, &data_row("A", "B"), &mut cursor)
+        .unwrap();
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+
+    assert!(!contains(&bytes, b" d\n"));
+}
+
 // -------------------------------------------------------
 // Background colors
 // -------------------------------------------------------
@@ -459,7 +565,7 @@ fn wrap_mode_multi_line_content_fits() {
 #[test]
 fn clip_mode_with_fixed_row_height() {
     let style = CellStyle {
-        overflow: CellOverflow::Clip,
+        overflow: Some(CellOverflow::Clip),
         ..CellStyle::default()
     };
     let long_text = "word ".repeat(40);
@@ -478,10 +584,50 @@ fn clip_mode_with_fixed_row_height() {
     assert!(contains(&bytes, b"re\nW\nn\n"));
 }
 
+#[test]
+fn cell_without_overflow_inherits_table_default() {
+    let long_text = "word ".repeat(40);
+    let mut row = Row::new(vec![Cell::new(long_text.trim())]);
+    row.height = Some(25.0);
+
+    let mut table = Table::new(vec![234.0]);
+    table.default_style.overflow = Some(CellOverflow::Clip);
+    let mut doc = make_doc();
+    doc.begin_page(612.0, 792.0);
+    let mut cursor = TableCursor::new(&full_rect());
+    doc.fit_row(&table, &row, &mut cursor).unwrap();
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+
+    assert!(contains(&bytes, b"re\nW\nn\n"));
+}
+
+#[test]
+fn cell_overflow_overrides_table_default() {
+    let long_text = "word ".repeat(40);
+    let style = CellStyle {
+        overflow: Some(CellOverflow::Wrap),
+        ..CellStyle::default()
+    };
+    let mut row = Row::new(vec![Cell::styled(long_text.trim(), style)]);
+    row.height = Some(25.0);
+
+    let mut table = Table::new(vec![234.0]);
+    table.default_style.overflow = Some(CellOverflow::Clip);
+    let mut doc = make_doc();
+    doc.begin_page(612.0, 792.0);
+    let mut cursor = TableCursor::new(&full_rect());
+    doc.fit_row(&table, &row, &mut cursor).unwrap();
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+
+    assert!(!contains(&bytes, b"re\nW\nn\n"));
+}
+
 #[test]
 fn shrink_mode_with_fixed_row_height() {
     let style = CellStyle {
-        overflow: CellOverflow::Shrink,
+        overflow: Some(CellOverflow::Shrink),
         font_size: 20.0,
         ..CellStyle::default()
     };
@@ -508,9 +654,9 @@ fn shrink_mode_shrinks_wide_single_word_when_word_break_is_normal() {
     // (col_width 52 - 2×4 padding). With word_break=Normal, word-breaking is
     // disabled so only font shrinking can make it fit.
     let style = CellStyle {
-        overflow: CellOverflow::Shrink,
+        overflow: Some(CellOverflow::Shrink),
         font_size: 14.0,
-        word_break: WordBreak::Normal,
+        word_break: Some(WordBreak::Normal),
         ..CellStyle::default()
     };
     let row = Row::new(vec![Cell::styled("WWWWWW", style)]);
@@ -652,7 +798,7 @@ fn normal_word_break_does_not_split_wide_word_in_cell() {
     // The row height is based on 1 line (no extra lines counted).
     let style = CellStyle {
         font_size: 10.0,
-        word_break: WordBreak::Normal,
+        word_break: Some(WordBreak::Normal),
         ..CellStyle::default()
     };
     let narrow_col = 40.0;
@@ -682,7 +828,7 @@ fn normal_word_break_does_not_split_wide_word_in_cell() {
 fn hyphenate_mode_emits_hyphen_in_cell() {
     let style = CellStyle {
         font_size: 10.0,
-        word_break: WordBreak::Hyphenate,
+        word_break: Some(WordBreak::Hyphenate),
         ..CellStyle::default()
     };
     let narrow_col = 40.0;
@@ -704,6 +850,27 @@ fn hyphenate_mode_emits_hyphen_in_cell() {
     );
 }
 
+#[test]
+fn cell_hyphen_char_inherits_table_default() {
+    let mut table = Table::new(vec![40.0]);
+    table.default_style.hyphen_char = Some('~');
+    let style = CellStyle {
+        word_break: Some(WordBreak::Hyphenate),
+        ..CellStyle::default()
+    };
+    let row = Row::new(vec![Cell::styled("WWWWWWWW", style)]);
+
+    let mut doc = make_doc();
+    doc.begin_page(612.0, 792.0);
+    let mut cursor = TableCursor::new(&full_rect());
+    doc.fit_row(&table, &row, &mut cursor).unwrap();
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+
+    assert!(contains(&bytes, b"~) Tj"));
+    assert!(!contains(&bytes, b"-) Tj"));
+}
+
 #[test]
 fn word_break_increases_cell_height_to_fit_all_pieces() {
     // Verify the cursor advances by more than one line-height,
@@ -955,3 +1122,596 @@ fn right_aligned_multi_line_produces_valid_pdf() {
         "multi-line right-aligned cell should have >=2 Td operators"
     );
 }
+
+// -------------------------------------------------------
+// Flexible columns
+// -------------------------------------------------------
+
+#[test]
+fn flexible_columns_share_remaining_width() {
+    let mut table = Table::new(vec![100.0, -1.0, -1.0]);
+    table.total_width = Some(400.0);
+    assert_eq!(table.resolve_columns(468.0), vec![100.0, 150.0, 150.0]);
+}
+
+#[test]
+fn flexible_columns_default_to_cursor_width() {
+    let table = Table::new(vec![68.0, -1.0, -3.0]);
+    assert_eq!(table.resolve_columns(468.0), vec![68.0, 100.0, 300.0]);
+
+    let mut doc = make_doc();
+    doc.begin_page(612.0, 792.0);
+    let mut cursor = TableCursor::new(&full_rect());
+    doc.fit_row(
+        &table,
+        &Row::new(vec![Cell::new("A"), Cell::new("B")]),
+        &mut cursor,
+    )
+    .unwrap();
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    // Outer border spans the whole resolved width.
+    assert!(contains(&bytes, b" 468 "));
+}
+
+#[test]
+fn flexible_columns_collapse_when_fixed_exceed_total() {
+    let mut table = Table::new(vec![300.0, -1.0]);
+    table.total_width = Some(200.0);
+    assert_eq!(table.resolve_columns(468.0), vec![300.0, 0.0]);
+}
+
+// -------------------------------------------------------
+// Right-to-left column order
+// -------------------------------------------------------
+
+#[test]
+fn rtl_table_places_first_cell_in_rightmost_column() {
+    // Columns 100/150/200 from x=72: the logical first column occupies
+    // [422, 522] in RTL, so its text starts at 422 + padding 4.
+    let mut table = Table::new(vec![100.0, 150.0, 200.0]);
+    table.rtl = true;
+    table.border_width = 0.0;
+    let mut doc = make_doc();
+    doc.begin_page(612.0, 792.0);
+    let mut cursor = TableCursor::new(&full_rect());
+    doc.fit_row(
+        &table,
+        &Row::new(vec![Cell::new("A"), Cell::new("B"), Cell::new("C")]),
+        &mut cursor,
+    )
+    .unwrap();
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+
+    let x = first_td_x(&bytes).expect("should have a Td operator");
+    assert!(
+        (x - 426.0).abs() < 0.01,
+        "first logical cell should start at 426, got {}",
+        x
+    );
+}
+
+#[test]
+fn rtl_table_keeps_cell_alignment() {
+    let style = CellStyle {
+        text_align: TextAlign::Right,
+        ..CellStyle::default()
+    };
+    let mut table = Table::new(vec![100.0, 200.0]);
+    table.rtl = true;
+    let mut doc = make_doc();
+    doc.begin_page(612.0, 792.0);
+    let mut cursor = TableCursor::new(&full_rect());
+    doc.fit_row(
+        &table,
+        &Row::new(vec![Cell::styled("42", style), Cell::new("Label")]),
+        &mut cursor,
+    )
+    .unwrap();
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+
+    // The right-aligned number ends at the table's right edge minus padding.
+    let x = first_td_x(&bytes).expect("should have a Td operator");
+    assert!(x > 322.0 && x < 368.0, "got {}", x);
+}
+
+// -------------------------------------------------------
+// Row spans
+// -------------------------------------------------------
+
+#[test]
+fn rowspan_cell_covers_two_rows_and_blanks_the_cell_below() {
+    let table = Table::new(vec![100.0, 100.0]);
+    let mut spanning = Cell::new("Spanning");
+    spanning.rowspan = 2;
+    let mut doc = make_doc();
+    doc.begin_page(612.0, 792.0);
+    let mut cursor = TableCursor::new(&full_rect());
+
+    let first = Row::new(vec![spanning, Cell::new("B1")]);
+    assert_eq!(
+        doc.fit_row(&table, &first, &mut cursor).unwrap(),
+        FitResult::Stop
+    );
+    let middle = cursor.current_y();
+    let second = Row::new(vec![Cell::new("Hidden"), Cell::new("B2")]);
+    assert_eq!(
+        doc.fit_row(&table, &second, &mut cursor).unwrap(),
+        FitResult::Stop
+    );
+    let bottom = cursor.current_y();
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+
+    assert!(contains(&bytes, b"(Spanning) Tj"));
+    assert!(contains(&bytes, b"(B2) Tj"));
+    assert!(
+        !contains(&bytes, b"(Hidden) Tj"),
+        "covered cell in the second row must be left blank"
+    );
+    // No rule across the spanned column between the two rows, but the
+    // span's bottom edge and the second column's row divider are drawn.
+    let middle_rule = format!("72 {0} m\n172 {0} l\nS\n", middle);
+    let bottom_rule = format!("72 {0} m\n172 {0} l\nS\n", bottom);
+    let other_middle_rule = format!("172 {0} m\n272 {0} l\nS\n", middle);
+    assert!(!contains(&bytes, middle_rule.as_bytes()));
+    assert!(contains(&bytes, bottom_rule.as_bytes()));
+    assert!(contains(&bytes, other_middle_rule.as_bytes()));
+}
+
+#[test]
+fn rowspan_last_row_grows_to_fit_spanning_text() {
+    let table = Table::new(vec![60.0, 100.0]);
+    let mut tall = Cell::new("one two three four five six seven eight");
+    tall.rowspan = 2;
+    let mut doc = make_doc();
+    doc.begin_page(612.0, 792.0);
+    let mut cursor = TableCursor::new(&full_rect());
+    doc.fit_row(&table, &Row::new(vec![tall, Cell::new("a")]), &mut cursor)
+        .unwrap();
+    let first_height = 720.0 - cursor.current_y();
+    doc.fit_row(
+        &table,
+        &Row::new(vec![Cell::new(""), Cell::new("b")]),
+        &mut cursor,
+    )
+    .unwrap();
+    let second_height = 720.0 - first_height - cursor.current_y();
+    doc.end_page().unwrap();
+
+    assert!(
+        second_height > first_height,
+        "last spanned row should grow ({} vs {})",
+        second_height,
+        first_height
+    );
+}
+
+#[test]
+fn rowspan_cut_by_full_page_is_closed_on_that_page() {
+    let table = Table::new(vec![100.0, 100.0]);
+    let rect = Rect {
+        x: 72.0,
+        y: 720.0,
+        width: 200.0,
+        height: 30.0,
+    };
+    let mut spanning = Cell::new("Spanning");
+    spanning.rowspan = 3;
+    let mut doc = make_doc();
+    doc.begin_page(612.0, 792.0);
+    let mut cursor = TableCursor::new(&rect);
+    doc.fit_row(
+        &table,
+        &Row::new(vec![spanning, Cell::new("a")]),
+        &mut cursor,
+    )
+    .unwrap();
+    let result = doc
+        .fit_row(
+            &table,
+            &Row::new(vec![Cell::new(""), Cell::new("b")]),
+            &mut cursor,
+        )
+        .unwrap();
+    assert_eq!(result, FitResult::BoxFull);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    assert!(contains(&bytes, b"(Spanning) Tj"));
+}
+
+/// Collect the operands of every `Td` operator in the content stream.
+fn td_operands(bytes: &[u8]) -> Vec<(f64, f64)> {
+    String::from_utf8_lossy(bytes)
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split(' ').collect();
+            match parts.as_slice() {
+                [x, y, "Td"] => Some((x.parse().ok()?, y.parse().ok()?)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+#[test]
+fn default_cell_style_is_top_aligned() {
+    assert_eq!(CellStyle::default().vertical_align, VerticalAlign::Top);
+}
+
+#[test]
+fn clip_with_middle_alignment_centers_text_block() {
+    // 25pt fixed row at y=720 → cell center at 707.5. The wrapped text is
+    // much taller than the row, so its first line starts above the cell.
+    let render = |vertical_align| {
+        let style = CellStyle {
+            overflow: Some(CellOverflow::Clip),
+            vertical_align,
+            ..CellStyle::default()
+        };
+        let long_text = "word ".repeat(40);
+        let mut row = Row::new(vec![Cell::styled(long_text.trim(), style)]);
+        row.height = Some(25.0);
+
+        let table = Table::new(vec![234.0]);
+        let mut doc = make_doc();
+        doc.begin_page(612.0, 792.0);
+        let mut cursor = TableCursor::new(&full_rect());
+        doc.fit_row(&table, &row, &mut cursor).unwrap();
+        doc.end_page().unwrap();
+        doc.end_document().unwrap()
+    };
+
+    let top = td_operands(&render(VerticalAlign::Top));
+    let middle = td_operands(&render(VerticalAlign::Middle));
+    assert!(middle.len() > 2, "text should wrap onto several lines");
+
+    let lh = -middle[1].1;
+    let block_top = middle[0].1 + 10.0;
+    let block_center = block_top - middle.len() as f64 * lh / 2.0;
+    assert!(
+        (block_center - 707.5).abs() < 0.01,
+        "text block should be centered on the cell, got {}",
+        block_center
+    );
+    assert!(
+        middle[0].1 > top[0].1 + lh,
+        "first lines should be clipped away"
+    );
+}
+
+#[test]
+fn remaining_height_shrinks_by_placed_rows() {
+    let table = two_col_table();
+    let mut doc = make_doc();
+    doc.begin_page(612.0, 792.0);
+    let mut cursor = TableCursor::new(&full_rect());
+    assert_eq!(cursor.remaining_height(), 648.0);
+
+    for i in 0..3 {
+        doc.fit_row(&table, &data_row(&format!("Row {}", i), "x"), &mut cursor)
+            .unwrap();
+    }
+    // Rect bottom is 720 - 648 = 72.
+    let expected = cursor.current_y() - 72.0;
+    assert!(expected < 648.0);
+    assert!((cursor.remaining_height() - expected).abs() < 1e-9);
+    assert_eq!(doc.space_remaining(&cursor), cursor.remaining_height());
+    doc.end_page().unwrap();
+}
+
+// -------------------------------------------------------
+// Zebra striping
+// -------------------------------------------------------
+
+/// Place a header and four 20pt data rows into a rect that holds four
+/// rows, repeating the header after the page turn, and return the
+/// document bytes.
+fn striped_two_pages(reset_stripe_on_page: bool) -> Vec<u8> {
+    let rect = Rect {
+        x: 72.0,
+        y: 720.0,
+        width: 468.0,
+        height: 80.0,
+    };
+    let mut table = two_col_table();
+    table.stripe_color = Some(Color::rgb(0.9, 0.9, 0.9));
+    table.reset_stripe_on_page = reset_stripe_on_page;
+    let mut header = data_row("Name", "Value");
+    header.height = Some(20.0);
+    header.header = true;
+
+    let mut doc = make_doc();
+    let mut cursor = TableCursor::new(&rect);
+    doc.begin_page(612.0, 792.0);
+    let mut i = 0;
+    while i < 4 {
+        if cursor.is_first_row() {
+            doc.fit_row(&table, &header, &mut cursor).unwrap();
+        }
+        let mut row = data_row(&format!("Row {}", i), "x");
+        row.height = Some(20.0);
+        match doc.fit_row(&table, &row, &mut cursor).unwrap() {
+            FitResult::Stop => i += 1,
+            FitResult::BoxFull => {
+                doc.end_page().unwrap();
+                doc.begin_page(612.0, 792.0);
+                cursor.reset(&rect);
+            }
+            FitResult::BoxEmpty => panic!("row should fit an empty page"),
+        }
+    }
+    doc.end_page().unwrap();
+    assert_eq!(cursor.row_index(), 6);
+    doc.end_document().unwrap()
+}
+
+fn count(haystack: &[u8], needle: &[u8]) -> usize {
+    haystack
+        .windows(needle.len())
+        .filter(|w| *w == needle)
+        .count()
+}
+
+#[test]
+fn stripe_parity_continues_across_page_break() {
+    let bytes = striped_two_pages(false);
+    // Page one holds the header and rows 0-2, page two the header and row 3.
+    // Rows 1 and 3 are striped; the headers are not, and do not shift the parity.
+    assert_eq!(count(&bytes, b"0.9 0.9 0.9 rg\n"), 2);
+    assert_eq!(count(&bytes, b"0.9 0.9 0.9 rg\n72 660 468 20 re\n"), 1);
+    assert_eq!(count(&bytes, b"0.9 0.9 0.9 rg\n72 680 468 20 re\n"), 1);
+}
+
+#[test]
+fn stripe_parity_restarts_when_reset_on_page() {
+    let bytes = striped_two_pages(true);
+    // Only row 1 is striped; page two starts over with an unstriped row.
+    assert_eq!(count(&bytes, b"0.9 0.9 0.9 rg\n"), 1);
+    assert_eq!(count(&bytes, b"0.9 0.9 0.9 rg\n72 660 468 20 re\n"), 1);
+}
+
+#[test]
+fn row_background_overrides_stripe() {
+    let mut table = two_col_table();
+    table.stripe_color = Some(Color::rgb(0.9, 0.9, 0.9));
+    let mut doc = make_doc();
+    doc.begin_page(612.0, 792.0);
+    let mut cursor = TableCursor::new(&full_rect());
+    doc.fit_row(&table, &data_row("a", "b"), &mut cursor)
+        .unwrap();
+    let mut row = data_row("c", "d");
+    row.background_color = Some(Color::rgb(1.0, 0.0, 0.0));
+    doc.fit_row(&table, &row, &mut cursor).unwrap();
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+
+    assert!(contains(&bytes, b"1 0 0 rg\n"));
+    assert!(!contains(&bytes, b"0.9 0.9 0.9 rg\n"));
+}
+
+#[test]
+fn row_with_own_background_does_not_advance_stripe() {
+    let mut table = two_col_table();
+    table.stripe_color = Some(Color::rgb(0.9, 0.9, 0.9));
+    let mut doc = make_doc();
+    doc.begin_page(612.0, 792.0);
+    let mut cursor = TableCursor::new(&full_rect());
+    doc.fit_row(&table, &data_row("a", "b"), &mut cursor)
+        .unwrap();
+    let mut highlighted = data_row("c", "d");
+    highlighted.background_color = Some(Color::rgb(1.0, 0.0, 0.0));
+    doc.fit_row(&table, &highlighted, &mut cursor).unwrap();
+    doc.fit_row(&table, &data_row("e", "f"), &mut cursor)
+        .unwrap();
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+
+    // The third row is only the second stripe-eligible one, so it is striped.
+    assert_eq!(count(&bytes, b"0.9 0.9 0.9 rg\n"), 1);
+}
+
+// -------------------------------------------------------
+// Underlines
+// -------------------------------------------------------
+
+/// Start points of `m` (move-to) operators, in order.
+fn move_to_operands(bytes: &[u8]) -> Vec<(f64, f64)> {
+    String::from_utf8_lossy(bytes)
+        .lines()
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split(' ').collect();
+            match parts.as_slice() {
+                [x, y, "m"] => Some((x.parse().ok()?, y.parse().ok()?)),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn render_underlined(underline: UnderlineStyle) -> Vec<u8> {
+    let mut table = Table::new(vec![200.0]);
+    table.border_width = 0.0;
+    let style = CellStyle {
+        underline,
+        ..CellStyle::default()
+    };
+    let row = Row::new(vec![Cell::styled("1,234.00", style)]);
+    let mut doc = make_doc();
+    doc.begin_page(612.0, 792.0);
+    let mut cursor = TableCursor::new(&full_rect());
+    doc.fit_row(&table, &row, &mut cursor).unwrap();
+    doc.end_page().unwrap();
+    doc.end_document().unwrap()
+}
+
+#[test]
+fn default_cell_style_has_no_underline() {
+    assert_eq!(CellStyle::default().underline, UnderlineStyle::None);
+    assert!(move_to_operands(&render_underlined(UnderlineStyle::None)).is_empty());
+}
+
+#[test]
+fn double_underline_emits_two_strokes_below_baseline() {
+    let bytes = render_underlined(UnderlineStyle::Double);
+    let (text_x, baseline) = td_operands(&bytes)[0];
+    let rules = move_to_operands(&bytes);
+    assert_eq!(rules.len(), 2);
+    for (x, y) in &rules {
+        assert_eq!(*x, text_x);
+        assert!(
+            *y < baseline,
+            "rule at {} not below baseline {}",
+            y,
+            baseline
+        );
+    }
+    assert!(rules[1].1 < rules[0].1);
+    assert_eq!(count(&bytes, b" l\nS\n"), 2);
+}
+
+// -------------------------------------------------------
+// Row height measurement
+// -------------------------------------------------------
+
+/// Height taken by `row` when placed at the top of a fresh page.
+fn placed_height(table: &Table, row: &Row) -> f64 {
+    let mut doc = make_doc();
+    doc.begin_page(612.0, 792.0);
+    let mut cursor = TableCursor::new(&full_rect());
+    doc.fit_row(table, row, &mut cursor).unwrap();
+    720.0 - cursor.current_y()
+}
+
+#[test]
+fn short_row_height_is_one_line_plus_padding() {
+    // Helvetica 10pt: 12pt line, 4pt padding top and bottom.
+    let height = placed_height(&two_col_table(), &data_row("Qty", "1"));
+    assert_eq!(height, 12.0 + 2.0 * 4.0);
+}
+
+#[test]
+fn missing_cells_do_not_inflate_row_height() {
+    let mut table = two_col_table();
+    table.default_style.font_size = 20.0;
+    let small = CellStyle {
+        font_size: 8.0,
+        ..CellStyle::default()
+    };
+    // Only the first of two columns has a cell.
+    let row = Row::new(vec![Cell::styled("Note", small)]);
+    assert!((placed_height(&table, &row) - (9.6 + 8.0)).abs() < 1e-9);
+}
+
+#[test]
+fn auto_row_height_is_clamped_by_table_bounds() {
+    let mut table = two_col_table();
+    table.min_row_height = Some(30.0);
+    assert_eq!(placed_height(&table, &data_row("a", "b")), 30.0);
+
+    let mut table = two_col_table();
+    table.max_row_height = Some(25.0);
+    let tall = data_row("one\ntwo\nthree", "b");
+    assert_eq!(placed_height(&table, &tall), 25.0);
+
+    // Fixed-height rows ignore the bounds.
+    let mut fixed = data_row("a", "b");
+    fixed.height = Some(50.0);
+    assert_eq!(placed_height(&table, &fixed), 50.0);
+}
+
+#[test]
+fn wrapped_cells_render_the_measured_lines() {
+    let table = Table::new(vec![60.0, 60.0]);
+    let row = data_row("alpha beta gamma delta", "one\ntwo");
+    let mut doc = make_doc();
+    doc.begin_page(612.0, 792.0);
+    let mut cursor = TableCursor::new(&full_rect());
+    doc.fit_row(&table, &row, &mut cursor).unwrap();
+    let height = 720.0 - cursor.current_y();
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    // The tallest cell wraps to three lines, and exactly those lines are drawn.
+    assert_eq!(height, 3.0 * 12.0 + 2.0 * 4.0);
+    let shown: Vec<&str> = output
+        .lines()
+        .filter_map(|line| line.strip_suffix(") Tj")?.strip_prefix('('))
+        .collect();
+    assert_eq!(shown, ["alpha beta", "gamma", "delta", "one", "two"]);
+}
+
+// -------------------------------------------------------
+// Fit to rect
+// -------------------------------------------------------
+
+#[test]
+fn fit_table_to_rect_scales_overflowing_rows_onto_one_page() {
+    let table = two_col_table();
+    // 30 rows of 20pt need 600pt; the rect holds 300pt.
+    let rows: Vec<Row> = (0..30)
+        .map(|i| data_row(&format!("Row {}", i), "data"))
+        .collect();
+    let rect = Rect {
+        x: 72.0,
+        y: 720.0,
+        width: 468.0,
+        height: 300.0,
+    };
+    let mut doc = make_doc();
+    doc.begin_page(612.0, 792.0);
+    let scale = doc.fit_table_to_rect(&table, &rows, &rect).unwrap();
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+
+    assert!(scale < 1.0 && scale > 0.4, "scale {}", scale);
+    // Every row was placed.
+    assert!(contains(&bytes, b"(Row 0) Tj"));
+    assert!(contains(&bytes, b"(Row 29) Tj"));
+    // Placed at the scaled size: 10pt * scale.
+    let font_op = format!("/F1 {} Tf", (10.0 * scale * 10_000.0).round() / 10_000.0);
+    assert!(contains(&bytes, font_op.as_bytes()), "missing {}", font_op);
+}
+
+#[test]
+fn fit_table_to_rect_leaves_fitting_table_unscaled() {
+    let table = two_col_table();
+    let rows = vec![data_row("a", "b"), data_row("c", "d")];
+    let mut doc = make_doc();
+    doc.begin_page(612.0, 792.0);
+    let scale = doc.fit_table_to_rect(&table, &rows, &full_rect()).unwrap();
+    assert_eq!(scale, 1.0);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    assert!(contains(&bytes, b"/F1 10 Tf"));
+}
+
+#[test]
+fn auto_columns_measures_only_the_sampled_rows() {
+    let doc = make_doc();
+    let style = CellStyle::default();
+    let text_style = TextStyle::builtin(BuiltinFont::Helvetica, style.font_size);
+    let natural = |text: &str| doc.measure_text(text, &text_style) + 2.0 * style.padding;
+
+    let header = Row::new(vec![Cell::new("ID"), Cell::new("Name")]);
+    // An endless source: only the sample may be pulled from it.
+    let pulled = std::cell::Cell::new(0);
+    let mut rows = std::iter::repeat_with(|| {
+        pulled.set(pulled.get() + 1);
+        let name = if pulled.get() <= 2 {
+            "Ada Lovelace".to_string()
+        } else {
+            "A much longer name past the sample".to_string()
+        };
+        Row::new(vec![Cell::new(pulled.get().to_string()), Cell::new(name)])
+    });
+
+    let widths = doc.auto_columns(&header, rows.by_ref(), 2);
+    assert_eq!(widths, vec![natural("ID"), natural("Ada Lovelace")]);
+    assert_eq!(pulled.get(), 2);
+    // The next row is still available for placing.
+    assert_eq!(rows.next().unwrap().cells[0].text, "3");
+}
@@ -1,4 +1,7 @@
-use pdf_core::{BuiltinFont, FitResult, PdfDocument, Rect, TextFlow, TextStyle, WordBreak};
+use pdf_core::{
+    BuiltinFont, FitResult, PdfDocument, Rect, TextAlign, TextFlow, TextStyle, VerticalAlign,
+    WordBreak,
+};
 
 /// Helper: check that a byte pattern exists in the buffer.
 fn contains(haystack: &[u8], needle: &[u8]) -> bool {
@@ -381,6 +384,112 @@ fn place_text_styled_uses_correct_font() {
     assert!(contains(&bytes, b"(Styled) Tj"));
 }
 
+#[test]
+fn place_text_aligned_right_ends_at_x() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    // Courier glyphs are 600 units wide: 5 chars at 10pt = 30pt.
+    let style = TextStyle::builtin(BuiltinFont::Courier, 10.0);
+    assert_eq!(doc.measure_text("Total", &style), 30.0);
+
+    doc.begin_page(612.0, 792.0);
+    doc.place_text_aligned("Total", 540.0, 100.0, &style, TextAlign::Right);
+    doc.place_text_aligned("Total", 300.0, 80.0, &style, TextAlign::Center);
+    doc.place_text_aligned("Total", 72.0, 60.0, &style, TextAlign::Left);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+
+    assert!(contains(&bytes, b"510 100 Td"));
+    assert!(contains(&bytes, b"285 80 Td"));
+    assert!(contains(&bytes, b"72 60 Td"));
+}
+
+#[test]
+fn place_text_block_centers_lines_in_rect() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    // Courier 10pt: 6pt per glyph, 12pt line height.
+    let style = TextStyle::builtin(BuiltinFont::Courier, 10.0);
+    let rect = Rect {
+        x: 100.0,
+        y: 500.0,
+        width: 200.0,
+        height: 100.0,
+    };
+
+    doc.begin_page(612.0, 792.0);
+    let lines = doc.place_text_block(
+        "Total\nSum",
+        &rect,
+        &style,
+        TextAlign::Center,
+        VerticalAlign::Middle,
+    );
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+
+    assert_eq!(lines, 2);
+    // Block is 24pt tall, leaving 38pt above it: first baseline at
+    // 500 - 38 - 10 = 452, each line centred on x = 200.
+    assert!(contains(&bytes, b"185 452 Td"));
+    assert!(contains(&bytes, b"191 440 Td"));
+}
+
+#[test]
+fn fit_text_ellipsized_truncates_long_text_only() {
+    let doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    // Courier glyphs are 6pt wide at 10pt: 60pt holds ten characters.
+    let style = TextStyle::builtin(BuiltinFont::Courier, 10.0);
+
+    assert_eq!(doc.fit_text_ellipsized("Short", &style, 60.0), "Short");
+    assert_eq!(
+        doc.fit_text_ellipsized("Exactly 10", &style, 60.0),
+        "Exactly 10"
+    );
+    let fitted = doc.fit_text_ellipsized("Quarterly revenue summary", &style, 60.0);
+    assert_eq!(fitted, "Quarter...");
+    assert!(doc.measure_text(&fitted, &style) <= 60.0);
+    // Whitespace before the ellipsis is trimmed.
+    assert_eq!(
+        doc.fit_text_ellipsized("Net of tax", &style, 42.0),
+        "Net..."
+    );
+    assert_eq!(doc.fit_text_ellipsized("Revenue", &style, 10.0), "");
+}
+
+#[test]
+fn leader_fills_gap_with_dots_ending_at_to_x() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    // Courier: ". " is 12pt at 10pt, so 100pt holds eight dots.
+    let style = TextStyle::builtin(BuiltinFont::Courier, 10.0);
+    doc.begin_page(612.0, 792.0);
+    doc.draw_leader(100.0, 200.0, 500.0, &style);
+    // Too narrow for a single dot.
+    doc.draw_leader(300.0, 310.0, 500.0, &style);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    // Fifteen 6pt glyphs end at 200, so the run starts at 110.
+    assert!(output.contains("/F9 10 Tf\n110 500 Td\n(. . . . . . . .) Tj\n"));
+    assert_eq!(output.matches(" Tj\n").count(), 1);
+}
+
+#[test]
+fn place_text_on_arc_positions_each_glyph() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let style = TextStyle::builtin(BuiltinFont::Courier, 10.0);
+    doc.begin_page(612.0, 792.0);
+    doc.place_text_on_arc("SEAL", 300.0, 400.0, 100.0, 90.0, &style);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert_eq!(output.matches(" Tm\n").count(), 4);
+    // At 90 degrees the first glyph is upright at the top of the circle.
+    assert!(output.contains("BT\n/F9 10 Tf\n1 0 0 1 300 500 Tm\n(S) Tj\n"));
+    // The next glyph has moved clockwise by 6pt of arc (0.06 rad).
+    assert!(output.contains("0.9982 -0.06 0.06 0.9982 305.9964 499.8201 Tm\n(E) Tj\n"));
+}
+
 // -------------------------------------------------------
 // Word-break tests
 // -------------------------------------------------------
@@ -557,3 +666,265 @@ fn break_all_multi_page_cursor_is_consistent() {
     let finished = r2 == FitResult::Stop || r3 == FitResult::Stop;
     assert!(finished, "text should eventually be fully placed");
 }
+
+// -------------------------------------------------------
+// Space width scaling
+// -------------------------------------------------------
+
+/// Number of lines a flow of eight short words takes in a 62pt-wide box.
+fn lines_with_space_scale(scale: f64) -> (usize, String) {
+    let style = TextStyle {
+        space_width_scale: scale,
+        ..TextStyle::default()
+    };
+    let mut tf = TextFlow::new();
+    tf.add_text("aa aa aa aa aa aa aa aa", &style);
+    let rect = Rect {
+        x: 72.0,
+        y: 720.0,
+        width: 62.0,
+        height: 648.0,
+    };
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    assert_eq!(doc.fit_textflow(&mut tf, &rect).unwrap(), FitResult::Stop);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes).into_owned();
+    (output.matches(" Td\n").count(), output)
+}
+
+#[test]
+fn half_space_width_fits_more_words_per_line() {
+    let (normal_lines, normal) = lines_with_space_scale(1.0);
+    let (tight_lines, tight) = lines_with_space_scale(0.5);
+    assert_eq!(normal_lines, 3);
+    assert_eq!(tight_lines, 2);
+    assert!(!normal.contains(" TJ\n"));
+    // Half of Helvetica's 278-unit space is pulled back before each gap.
+    assert!(tight.contains("[139] TJ\n"));
+}
+
+// -------------------------------------------------------
+// Progress queries
+// -------------------------------------------------------
+
+#[test]
+fn is_finished_tracks_progress_across_pages() {
+    let mut tf = TextFlow::new();
+    tf.add_text(&"word ".repeat(60), &TextStyle::default());
+    assert_eq!(tf.remaining_words(), 60);
+
+    let rect = Rect {
+        x: 72.0,
+        y: 720.0,
+        width: 200.0,
+        height: 50.0,
+    };
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let mut remaining = tf.remaining_words();
+    while !tf.is_finished() {
+        doc.begin_page(612.0, 792.0);
+        let result = doc.fit_textflow(&mut tf, &rect).unwrap();
+        doc.end_page().unwrap();
+        assert!(tf.remaining_words() < remaining);
+        remaining = tf.remaining_words();
+        assert_eq!(tf.is_finished(), result == FitResult::Stop);
+    }
+    assert_eq!(tf.remaining_words(), 0);
+
+    tf.add_text("more", &TextStyle::default());
+    assert!(!tf.is_finished());
+}
+
+#[test]
+fn remaining_words_counts_like_layout_splits() {
+    let mut tf = TextFlow::new();
+    let style = TextStyle::default();
+    tf.add_text("  Hello  world\n", &style);
+    tf.add_text("next\n\nline ", &style);
+    tf.add_text("", &style);
+    // The em dash ends its word, the zero-width spaces split without being
+    // kept, and the no-break space joins its word.
+    tf.add_text("em\u{2014}dash\u{200B}zero\u{200B} a\u{00A0}b", &style);
+    assert_eq!(tf.remaining_words(), 11);
+
+    // A fit that places nothing switches the count to the laid-out words.
+    let rect = Rect {
+        x: 72.0,
+        y: 720.0,
+        width: 400.0,
+        height: 1.0,
+    };
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    assert_eq!(
+        doc.fit_textflow(&mut tf, &rect).unwrap(),
+        FitResult::BoxEmpty
+    );
+    assert_eq!(tf.remaining_words(), 11);
+}
+
+// -------------------------------------------------------
+// No-break space and em dash
+// -------------------------------------------------------
+
+/// Lay out `text` in default 12pt Helvetica and return the output.
+fn flow_in_width(text: &str, width: f64) -> String {
+    let mut tf = TextFlow::new();
+    tf.add_text(text, &TextStyle::default());
+    let rect = Rect {
+        x: 72.0,
+        y: 720.0,
+        width,
+        height: 648.0,
+    };
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    assert_eq!(doc.fit_textflow(&mut tf, &rect).unwrap(), FitResult::Stop);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[test]
+fn no_break_space_keeps_tokens_together_with_space_width() {
+    let doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let style = TextStyle::default();
+    assert_eq!(
+        doc.measure_text("10\u{a0}000", &style),
+        doc.measure_text("10 000", &style)
+    );
+
+    // Wide enough for "10 000" but not for "total 10 000".
+    let width = doc.measure_text("10 000", &style) + 1.0;
+    let output = flow_in_width("total 10\u{a0}000", width);
+    assert_eq!(output.matches(" Td\n").count(), 2);
+    assert!(output.contains("(total) Tj"));
+    assert!(output.contains("(10 000) Tj"), "rendered as a plain space");
+}
+
+#[test]
+fn em_dash_is_a_break_opportunity() {
+    let doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let style = TextStyle::default();
+    let width = doc.measure_text("known\u{2014}", &style) + 1.0;
+
+    let narrow = flow_in_width("known\u{2014}well", width);
+    assert_eq!(narrow.matches(" Td\n").count(), 2);
+    assert!(narrow.contains("(well) Tj"));
+
+    // With room for both parts they share a line, with no space inserted.
+    let wide = flow_in_width("known\u{2014}well", 468.0);
+    assert_eq!(wide.matches(" Td\n").count(), 1);
+    assert!(wide.contains("(well) Tj"));
+}
+
+#[test]
+fn em_dash_words_are_counted_like_extracted_words() {
+    let mut tf = TextFlow::new();
+    tf.add_text(
+        "well\u{2014}known \u{2014} dash\u{2014}",
+        &TextStyle::default(),
+    );
+    assert_eq!(tf.remaining_words(), 4);
+}
+
+#[test]
+fn zero_width_space_breaks_camel_case_without_a_glyph() {
+    let doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let style = TextStyle::default();
+    let width = doc.measure_text("getUserAccount", &style) + 1.0;
+    assert_eq!(
+        doc.measure_text("get\u{200b}User", &style),
+        doc.measure_text("getUser", &style)
+    );
+
+    let output = flow_in_width("get\u{200b}User\u{200b}Account\u{200b}Settings", width);
+    // Breaks at the marked point instead of splitting "Settings".
+    assert_eq!(output.matches(" Td\n").count(), 2);
+    assert!(output.contains("(get) Tj\n(User) Tj\n(Account) Tj\n0 -"));
+    assert!(output.contains("(Settings) Tj\nET"));
+    assert!(!output.contains('\u{200b}'));
+}
+
+#[test]
+fn inline_image_advances_the_line_between_words() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let icon = doc.load_image_bytes(blank_png(4, 4)).unwrap();
+    let style = TextStyle::default();
+    let mut tf = TextFlow::new();
+    tf.add_text("Click ", &style);
+    tf.add_inline_image(icon, 12.0, 12.0, -2.0);
+    tf.add_text(" to save", &style);
+    assert_eq!(tf.remaining_words(), 4);
+
+    let rect = Rect {
+        x: 72.0,
+        y: 720.0,
+        width: 468.0,
+        height: 648.0,
+    };
+    doc.begin_page(612.0, 792.0);
+    assert_eq!(doc.fit_textflow(&mut tf, &rect).unwrap(), FitResult::Stop);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    // "Click" plus a space is 29.328pt wide in Helvetica 12.
+    let click = output.find("(Click) Tj").unwrap();
+    let draw = output.find("12 0 0 12 101.328 ").unwrap();
+    let word = output.find("( to) Tj").unwrap();
+    assert!(click < draw && draw < word, "{}", output);
+    assert!(output[draw..word].contains("/Im1 Do\nQ\nBT\n"));
+    // Text resumes past the image.
+    assert!(output[draw..word].contains("[-3444] TJ\n"), "{}", output);
+    assert!(output.contains("/XObject"));
+}
+
+#[test]
+fn measure_flow_matches_the_height_fit_textflow_consumes() {
+    let mut tf = TextFlow::new();
+    tf.add_text(
+        "The quick brown fox jumps over the lazy dog. ",
+        &TextStyle::default(),
+    );
+    tf.add_text(
+        "Pack my box with five dozen liquor jugs.\nSphinx of black quartz, judge my vow.",
+        &TextStyle::builtin(BuiltinFont::TimesRoman, 16.0),
+    );
+    let rect = Rect {
+        x: 72.0,
+        y: 720.0,
+        width: 150.0,
+        height: 600.0,
+    };
+
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let words = tf.remaining_words();
+    let (height, lines) = doc.measure_flow(&tf, rect.width);
+    assert_eq!(tf.remaining_words(), words);
+
+    doc.begin_page(612.0, 792.0);
+    assert_eq!(doc.fit_textflow(&mut tf, &rect).unwrap(), FitResult::Stop);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert!(lines > 3);
+    assert_eq!(output.matches(" Td\n").count(), lines);
+    assert!((height - tf.last_fit_height()).abs() < 1e-9);
+}
+
+fn blank_png(width: u32, height: u32) -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut encoder = png::Encoder::new(&mut out, width, height);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().unwrap();
+    writer
+        .write_image_data(&vec![255; (width * height) as usize])
+        .unwrap();
+    drop(writer);
+    out
+}
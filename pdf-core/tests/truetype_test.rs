@@ -1,6 +1,10 @@
-use pdf_core::{BuiltinFont, FitResult, FontRef, PdfDocument, Rect, TextFlow, TextStyle};
+use pdf_core::{
+    BuiltinFont, Cell, CellStyle, FitResult, FontRef, PdfDocument, Rect, Row, Table, TableCursor,
+    TextFlow, TextStyle, WordBreak,
+};
 
 const DEJAVU_SANS: &[u8] = include_bytes!("fixtures/DejaVuSans.ttf");
+const DEJAVU_SANS_MONO: &[u8] = include_bytes!("fixtures/DejaVuSansMono.ttf");
 
 /// Helper: check that a byte pattern exists in the buffer.
 fn contains(haystack: &[u8], needle: &[u8]) -> bool {
@@ -33,6 +37,7 @@ fn truetype_font_produces_valid_pdf() {
         &TextStyle {
             font: font_ref,
             font_size: 14.0,
+            ..TextStyle::default()
         },
     );
     doc.end_page().unwrap();
@@ -85,6 +90,7 @@ fn hex_encoding_format() {
         &TextStyle {
             font: font_ref,
             font_size: 12.0,
+            ..TextStyle::default()
         },
     );
     doc.end_page().unwrap();
@@ -124,6 +130,7 @@ fn mixed_builtin_and_truetype_on_same_page() {
         &TextStyle {
             font: tt_font,
             font_size: 12.0,
+            ..TextStyle::default()
         },
     );
 
@@ -147,6 +154,41 @@ fn mixed_builtin_and_truetype_on_same_page() {
     assert!(output.contains("/Subtype /Type0"));
 }
 
+#[test]
+fn table_cells_mix_builtin_and_truetype() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let tt_font = doc.load_font_bytes(DEJAVU_SANS.to_vec()).unwrap();
+    let tt_style = CellStyle {
+        font: tt_font,
+        ..CellStyle::default()
+    };
+    let row = Row::new(vec![
+        Cell::new("Builtin"),
+        Cell::styled("TrueType", tt_style),
+    ]);
+
+    doc.begin_page(612.0, 792.0);
+    let mut cursor = TableCursor::new(&Rect {
+        x: 72.0,
+        y: 720.0,
+        width: 468.0,
+        height: 648.0,
+    });
+    let result = doc
+        .fit_row(&Table::new(vec![234.0, 234.0]), &row, &mut cursor)
+        .unwrap();
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert_eq!(result, FitResult::Stop);
+    assert!(output.contains("(Builtin) Tj"), "Builtin cell uses literal");
+    assert!(output.contains("/F15 10 Tf"), "TT cell selects /F15");
+    assert!(output.contains("> Tj"), "TT cell uses hex glyphs");
+    assert!(!output.contains("(TrueType) Tj"));
+    assert!(output.contains("/Subtype /Type0"));
+}
+
 #[test]
 fn textflow_with_truetype() {
     let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
@@ -155,6 +197,7 @@ fn textflow_with_truetype() {
     let style = TextStyle {
         font: tt_font,
         font_size: 12.0,
+        ..TextStyle::default()
     };
 
     let mut tf = TextFlow::new();
@@ -184,6 +227,36 @@ fn textflow_with_truetype() {
     assert!(output.contains("/Subtype /Type0"));
 }
 
+#[test]
+fn textflow_custom_hyphen_char_is_encoded_at_breaks() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let font = doc.load_font_bytes(DEJAVU_SANS.to_vec()).unwrap();
+    let style = TextStyle {
+        font,
+        font_size: 12.0,
+        ..TextStyle::default()
+    };
+    let mut tf = TextFlow::new();
+    tf.word_break = WordBreak::Hyphenate;
+    tf.hyphen_char = '\u{2010}';
+    tf.add_text("Donaudampfschifffahrtsgesellschaft", &style);
+    let rect = Rect {
+        x: 72.0,
+        y: 720.0,
+        width: 60.0,
+        height: 648.0,
+    };
+
+    doc.begin_page(612.0, 792.0);
+    doc.fit_textflow(&mut tf, &rect).unwrap();
+    doc.end_page().unwrap();
+    let output = String::from_utf8_lossy(&doc.end_document().unwrap()).into_owned();
+
+    // The break glyph is read back as U+2010, and no ASCII hyphen was used.
+    assert!(output.contains("> <2010>\n"));
+    assert!(!output.contains("> <002D>\n"));
+}
+
 #[test]
 fn textflow_mixed_builtin_and_truetype() {
     let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
@@ -193,6 +266,7 @@ fn textflow_mixed_builtin_and_truetype() {
     let tt_style = TextStyle {
         font: tt_font,
         font_size: 12.0,
+        ..TextStyle::default()
     };
 
     let mut tf = TextFlow::new();
@@ -230,6 +304,7 @@ fn truetype_multi_page_textflow() {
     let style = TextStyle {
         font: tt_font,
         font_size: 12.0,
+        ..TextStyle::default()
     };
 
     let mut tf = TextFlow::new();
@@ -281,6 +356,7 @@ fn font_descriptor_has_required_fields() {
         &TextStyle {
             font: font_ref,
             font_size: 12.0,
+            ..TextStyle::default()
         },
     );
     doc.end_page().unwrap();
@@ -311,6 +387,7 @@ fn tounicode_cmap_present() {
         &TextStyle {
             font: font_ref,
             font_size: 12.0,
+            ..TextStyle::default()
         },
     );
     doc.end_page().unwrap();
@@ -325,6 +402,68 @@ fn tounicode_cmap_present() {
     assert!(output.contains("/CMapName /Adobe-Identity-UCS"));
 }
 
+#[test]
+fn tounicode_cmap_maps_only_used_characters() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let font_ref = doc.load_font_bytes(DEJAVU_SANS.to_vec()).unwrap();
+
+    doc.begin_page(612.0, 792.0);
+    doc.place_text_styled(
+        "abcabc",
+        72.0,
+        720.0,
+        &TextStyle {
+            font: font_ref,
+            font_size: 12.0,
+            ..TextStyle::default()
+        },
+    );
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    let start = output.find("beginbfchar\n").unwrap() + "beginbfchar\n".len();
+    let end = output.find("endbfchar").unwrap();
+    let entries: Vec<&str> = output[start..end].lines().collect();
+    assert!(output.contains("3 beginbfchar"));
+    assert_eq!(entries.len(), 3);
+    assert!(entries.iter().any(|e| e.ends_with("<0061>")));
+    assert!(entries.iter().any(|e| e.ends_with("<0062>")));
+    assert!(entries.iter().any(|e| e.ends_with("<0063>")));
+}
+
+#[test]
+fn glyph_unicode_override_replaces_bfchar_mapping() {
+    let render = |override_glyph: bool| {
+        let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+        let font = doc.load_font_bytes(DEJAVU_SANS.to_vec()).unwrap();
+        if override_glyph {
+            // DejaVu Sans draws "f" with glyph 0x0049.
+            doc.set_glyph_unicode(&font, 0x0049, '\u{1d453}');
+        }
+        doc.begin_page(612.0, 792.0);
+        doc.place_text_styled(
+            "fa",
+            72.0,
+            720.0,
+            &TextStyle {
+                font,
+                font_size: 12.0,
+                ..TextStyle::default()
+            },
+        );
+        doc.end_page().unwrap();
+        String::from_utf8_lossy(&doc.end_document().unwrap()).into_owned()
+    };
+
+    assert!(render(false).contains("<0049> <0066>\n"));
+    let output = render(true);
+    // Outside the BMP, so written as a UTF-16 surrogate pair.
+    assert!(output.contains("<0049> <D835DC53>\n"));
+    assert!(!output.contains("<0049> <0066>"));
+    assert!(output.contains("<0044> <0061>\n"));
+}
+
 #[test]
 fn w_array_present() {
     let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
@@ -338,6 +477,7 @@ fn w_array_present() {
         &TextStyle {
             font: font_ref,
             font_size: 12.0,
+            ..TextStyle::default()
         },
     );
     doc.end_page().unwrap();
@@ -361,6 +501,7 @@ fn font_file_embedded() {
         &TextStyle {
             font: font_ref,
             font_size: 12.0,
+            ..TextStyle::default()
         },
     );
     doc.end_page().unwrap();
@@ -389,6 +530,7 @@ fn load_font_file_from_path() {
         &TextStyle {
             font: font_ref,
             font_size: 12.0,
+            ..TextStyle::default()
         },
     );
     doc.end_page().unwrap();
@@ -416,6 +558,7 @@ fn multiple_truetype_fonts() {
         &TextStyle {
             font: font1,
             font_size: 12.0,
+            ..TextStyle::default()
         },
     );
     doc.place_text_styled(
@@ -425,6 +568,7 @@ fn multiple_truetype_fonts() {
         &TextStyle {
             font: font2,
             font_size: 14.0,
+            ..TextStyle::default()
         },
     );
     doc.end_page().unwrap();
@@ -439,3 +583,267 @@ fn multiple_truetype_fonts() {
     assert!(output.contains("/F15"));
     assert!(output.contains("/F16"));
 }
+
+// ---- Kerning ----
+
+#[test]
+fn kerned_pair_measures_narrower_than_advances() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let font = doc.load_font_bytes(DEJAVU_SANS.to_vec()).unwrap();
+    doc.set_font_kerning(font, true);
+    let style = TextStyle {
+        font,
+        font_size: 12.0,
+        ..TextStyle::default()
+    };
+    let pair = doc.measure_text("AV", &style);
+    let separate = doc.measure_text("A", &style) + doc.measure_text("V", &style);
+    assert!(
+        pair < separate,
+        "AV ({}) should be kerned below {}",
+        pair,
+        separate
+    );
+
+    doc.set_font_kerning(font, false);
+    assert_eq!(doc.measure_text("AV", &style), separate);
+}
+
+#[test]
+fn kerned_pair_emits_tj_adjustment() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let font = doc.load_font_bytes(DEJAVU_SANS.to_vec()).unwrap();
+    doc.set_font_kerning(font, true);
+    doc.begin_page(612.0, 792.0);
+    doc.place_text_styled(
+        "AV",
+        72.0,
+        720.0,
+        &TextStyle {
+            font,
+            font_size: 12.0,
+            ..TextStyle::default()
+        },
+    );
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    // A tightening kern pulls the pair together, which TJ expresses as a
+    // positive number between the two glyph strings.
+    let start = output.find("[<").expect("TJ array");
+    let end = start + output[start..].find("] TJ").expect("TJ operator");
+    let parts: Vec<&str> = output[start + 1..end].split(' ').collect();
+    assert_eq!(parts.len(), 3, "glyph, adjustment, glyph: {:?}", parts);
+    let adjustment: i32 = parts[1].parse().unwrap();
+    assert!(adjustment > 0);
+}
+
+#[test]
+fn kerning_is_off_by_default() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let font = doc.load_font_bytes(DEJAVU_SANS.to_vec()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.place_text_styled(
+        "AV",
+        72.0,
+        720.0,
+        &TextStyle {
+            font,
+            font_size: 12.0,
+            ..TextStyle::default()
+        },
+    );
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+    assert!(output.contains("> Tj"));
+    assert!(!output.contains("] TJ"));
+}
+
+#[test]
+fn real_ascent_places_first_baseline_below_rect_top() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let font = doc.load_font_bytes(DEJAVU_SANS.to_vec()).unwrap();
+    // DejaVu Sans: ascent 1901 of 2048 units, i.e. 19.01pt at 20.48pt.
+    let style = TextStyle {
+        font,
+        font_size: 20.48,
+        ..TextStyle::default()
+    };
+    let rect = Rect {
+        x: 72.0,
+        y: 700.0,
+        width: 400.0,
+        height: 200.0,
+    };
+    doc.begin_page(612.0, 792.0);
+    for use_real_ascent in [false, true] {
+        let mut flow = TextFlow::new();
+        flow.use_real_ascent = use_real_ascent;
+        flow.add_text("Ascent", &style);
+        doc.fit_textflow(&mut flow, &rect).unwrap();
+    }
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert!(output.contains("72 679.52 Td"), "font-size approximation");
+    assert!(output.contains("72 680.99 Td"), "parsed ascent");
+}
+
+// ---- Embedded standard fonts ----
+
+fn helvetica_doc(embed: bool) -> String {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    // DejaVu Sans stands in for a metric-compatible Helvetica replacement.
+    doc.load_standard_font_replacement(BuiltinFont::Helvetica, DEJAVU_SANS.to_vec())
+        .unwrap();
+    doc.set_embed_standard_fonts(embed);
+    doc.begin_page(612.0, 792.0);
+    doc.place_text("Hello", 72.0, 720.0);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+#[test]
+fn embedded_standard_font_replaces_type1_reference() {
+    let output = helvetica_doc(true);
+    assert!(output.contains("/FontFile2"));
+    assert!(output.contains("/Subtype /TrueType"));
+    assert!(output.contains("/Encoding /WinAnsiEncoding"));
+    assert!(!output.contains("/Subtype /Type1"));
+    // Content keeps the builtin literal-string operators.
+    assert!(output.contains("(Hello) Tj"));
+    // Widths are Helvetica's, starting with the 278-unit space.
+    assert!(output.contains("/FirstChar 32 /LastChar 126 /Widths [278 278 355"));
+}
+
+#[test]
+fn subset_standard_font_embeds_only_shown_glyphs() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.load_standard_font_replacement(BuiltinFont::Helvetica, DEJAVU_SANS.to_vec())
+        .unwrap();
+    doc.set_embed_standard_fonts(true)
+        .set_subset_standard_fonts(true);
+    doc.begin_page(612.0, 792.0);
+    doc.place_text("Hello", 72.0, 720.0);
+    doc.end_page().unwrap();
+    // Text on later pages is part of the subset too: ten characters in all.
+    doc.begin_page(612.0, 792.0);
+    doc.place_text("Quartz 42", 72.0, 720.0);
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    // Extract the FontFile2 stream, whose dictionary is the only one with
+    // /Length1. Search the raw bytes: lossy decoding shifts offsets.
+    let find = |needle: &[u8], from: usize| {
+        from + bytes[from..]
+            .windows(needle.len())
+            .position(|w| w == needle)
+            .unwrap()
+    };
+    let dict_start = find(b"/Length1 ", 0) + 9;
+    let digits = bytes[dict_start..]
+        .iter()
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+    let length: usize = std::str::from_utf8(&bytes[dict_start..dict_start + digits])
+        .unwrap()
+        .parse()
+        .unwrap();
+    let data_start = find(b"stream\n", dict_start) + 7;
+    let font_file = &bytes[data_start..data_start + length];
+    assert!(
+        length < DEJAVU_SANS.len() / 10,
+        "subset is {} bytes, full font {}",
+        length,
+        DEJAVU_SANS.len()
+    );
+
+    // Shown glyphs keep their outlines and IDs; others are emptied.
+    let face = ttf_parser::Face::parse(font_file, 0).unwrap();
+    for ch in "HeloQuartz42".chars() {
+        let gid = face.glyph_index(ch).unwrap();
+        assert!(face.glyph_bounding_box(gid).is_some(), "{:?} lost", ch);
+    }
+    let unused = face.glyph_index('W').unwrap();
+    assert!(face.glyph_bounding_box(unused).is_none());
+    // checkSumAdjustment makes the whole file sum to the magic constant.
+    let sum = font_file.chunks(4).fold(0u32, |sum, chunk| {
+        let mut word = [0u8; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(word))
+    });
+    assert_eq!(sum, 0xB1B0_AFBA);
+
+    // The font name carries a six-letter subset tag.
+    let base_font = output.split("/BaseFont /").nth(1).unwrap();
+    assert_eq!(&base_font[6..7], "+");
+    assert!(base_font[..6].bytes().all(|b| b.is_ascii_uppercase()));
+    assert!(base_font[7..].starts_with("DejaVuSans"));
+}
+
+#[test]
+fn standard_fonts_are_referenced_unless_embedding_is_on() {
+    let output = helvetica_doc(false);
+    assert!(output.contains("/Subtype /Type1"));
+    assert!(output.contains("/BaseFont /Helvetica"));
+    assert!(!output.contains("/FontFile2"));
+}
+
+// ---- Font fallback ----
+
+#[test]
+fn fallback_font_sets_missing_glyphs() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let dejavu = doc.load_font_bytes(DEJAVU_SANS.to_vec()).unwrap();
+    let helvetica = FontRef::Builtin(BuiltinFont::Helvetica);
+    doc.set_font_fallback(helvetica, vec![dejavu]);
+    let style = TextStyle::builtin(BuiltinFont::Helvetica, 12.0);
+
+    // Omega is missing from Helvetica's printable-ASCII range.
+    let mixed = doc.measure_text("R = 5 Ω", &style);
+    let ascii_only = doc.measure_text("R = 5 ", &style);
+    let omega_style = TextStyle {
+        font: dejavu,
+        ..style.clone()
+    };
+    let omega = doc.measure_text("Ω", &omega_style);
+    assert!((mixed - (ascii_only + omega)).abs() < 1e-9);
+
+    doc.begin_page(612.0, 792.0);
+    doc.place_text_styled("R = 5 Ω", 72.0, 720.0, &style);
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    assert!(output.contains("BT\n/F1 12 Tf\n72 720 Td\n(R = 5 ) Tj\n/F15 12 Tf\n<"));
+    assert_eq!(output.matches(" Tf\n").count(), 2);
+    assert!(output.contains("/F1 "));
+    assert!(output.contains("/F15 "));
+}
+
+#[test]
+fn monospace_font_writes_dw_without_w_array() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let font_ref = doc.load_font_bytes(DEJAVU_SANS_MONO.to_vec()).unwrap();
+
+    doc.begin_page(612.0, 792.0);
+    doc.place_text_styled(
+        "Wide and narrow: Wil1",
+        72.0,
+        720.0,
+        &TextStyle {
+            font: font_ref,
+            font_size: 12.0,
+            ..TextStyle::default()
+        },
+    );
+    doc.end_page().unwrap();
+    let bytes = doc.end_document().unwrap();
+    let output = String::from_utf8_lossy(&bytes);
+
+    // Every DejaVu Sans Mono advance is 1233/2048 em.
+    assert!(output.contains("/DW 602"), "{}", output);
+    assert!(!output.contains("/W ["));
+}
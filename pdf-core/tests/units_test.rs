@@ -0,0 +1,13 @@
+use pdf_core::units;
+
+#[test]
+fn a4_width_in_points() {
+    assert!((units::mm(210.0) - 595.2756).abs() < 1e-3);
+    assert!((units::mm(297.0) - 841.8898).abs() < 1e-3);
+}
+
+#[test]
+fn cm_and_inches() {
+    assert_eq!(units::cm(2.54), units::inches(1.0));
+    assert_eq!(units::inches(8.5), 612.0);
+}
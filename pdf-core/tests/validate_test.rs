@@ -0,0 +1,104 @@
+use std::io;
+
+use pdf_core::{BuiltinFont, FontRef, ImageFit, PdfDocument, Rect, TextStyle, ValidationWarning};
+
+const TEST_PNG: &[u8] = include_bytes!("fixtures/test.png");
+
+#[test]
+fn well_formed_document_has_no_warnings() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_strict(true);
+    let img = doc.load_image_bytes(TEST_PNG.to_vec()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.place_text_styled(
+        "Hello",
+        72.0,
+        720.0,
+        &TextStyle::builtin(BuiltinFont::Courier, 12.0),
+    );
+    let rect = Rect {
+        x: 72.0,
+        y: 72.0,
+        width: 100.0,
+        height: 80.0,
+    };
+    doc.place_image(&img, &rect, ImageFit::Fit);
+    assert!(doc.validate().is_empty());
+    doc.end_page().unwrap();
+
+    doc.open_page(1).unwrap();
+    doc.place_text("Page 1", 500.0, 36.0);
+    assert!(doc.validate().is_empty());
+
+    assert!(doc.end_document().is_ok());
+}
+
+#[test]
+fn dangling_resource_references_in_content_are_reported() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.begin_page(612.0, 792.0);
+    doc.place_text("ok", 72.0, 720.0);
+    // Select a font and paint an XObject the page never registered.
+    doc.append_raw_content(b"BT\n/F99 12 Tf\n(x) Tj\nET\n/Im7 Do\n");
+
+    assert_eq!(
+        doc.validate(),
+        vec![
+            ValidationWarning::UndefinedFont {
+                page: 1,
+                name: "F99".to_string()
+            },
+            ValidationWarning::UndefinedXObject {
+                page: 1,
+                name: "Im7".to_string()
+            },
+        ]
+    );
+}
+
+#[test]
+fn strict_mode_turns_warnings_into_errors() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    doc.set_strict(true);
+    doc.begin_page(612.0, 792.0);
+    doc.append_raw_content(b"BT\n/F99 12 Tf\nET\n");
+
+    let err = doc.end_page().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(err.to_string().contains("/F99"));
+}
+
+#[test]
+fn current_page_reports_fonts_and_images_in_use() {
+    let mut doc = PdfDocument::new(Vec::<u8>::new()).unwrap();
+    let img = doc.load_image_bytes(TEST_PNG.to_vec()).unwrap();
+    assert!(doc.current_page_fonts().is_empty());
+
+    doc.begin_page(612.0, 792.0);
+    doc.place_text_styled(
+        "Hello",
+        72.0,
+        720.0,
+        &TextStyle::builtin(BuiltinFont::Courier, 12.0),
+    );
+    assert_eq!(
+        doc.current_page_fonts(),
+        vec![FontRef::Builtin(BuiltinFont::Courier)]
+    );
+    assert!(doc.current_page_images().is_empty());
+
+    let rect = Rect {
+        x: 72.0,
+        y: 72.0,
+        width: 100.0,
+        height: 80.0,
+    };
+    doc.place_image(&img, &rect, ImageFit::Fit);
+    assert_eq!(doc.current_page_images(), vec![img]);
+
+    // A new page starts with nothing in use.
+    doc.end_page().unwrap();
+    doc.begin_page(612.0, 792.0);
+    assert!(doc.current_page_fonts().is_empty());
+    assert!(doc.current_page_images().is_empty());
+}
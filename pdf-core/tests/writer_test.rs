@@ -75,6 +75,16 @@ fn write_literal_string_escaped() {
     assert!(output.contains("(a\\(b\\)c\\\\d)"));
 }
 
+#[test]
+fn write_hex_string() {
+    let mut buf = Vec::new();
+    let mut w = PdfWriter::new(&mut buf);
+    let obj = PdfObject::HexString(vec![0x00, 0xAB, 0x7F]);
+    w.write_object(ObjId(1, 0), &obj).unwrap();
+    let output = String::from_utf8_lossy(&buf);
+    assert!(output.contains("<00AB7F>"));
+}
+
 #[test]
 fn xref_entry_is_20_bytes() {
     let mut buf = Vec::new();
@@ -150,3 +160,14 @@ fn escape_special_chars() {
     assert_eq!(escape_pdf_string("a(b)c"), "a\\(b\\)c");
     assert_eq!(escape_pdf_string("back\\slash"), "back\\\\slash");
 }
+
+#[test]
+fn header_without_binary_marker() {
+    let mut buf = Vec::new();
+    let mut w = PdfWriter::new(&mut buf);
+    w.set_binary_marker(false);
+    w.write_header().unwrap();
+    // A second call (or the first object) does not repeat it.
+    w.write_header().unwrap();
+    assert_eq!(buf, b"%PDF-1.7\n");
+}
@@ -35,6 +35,8 @@ class TextStyle
     public string $fontName;
     public float $fontSize;
     public int $fontHandle;
+    /** Multiplier for inter-word space width in a TextFlow (default 1.0) */
+    public float $spaceWidthScale;
 
     /**
      * Create a TextStyle with a builtin font name.
@@ -66,6 +68,36 @@ class TextStyle
     ): self {}
 }
 
+/**
+ * Number and currency formatting for table cells and labels.
+ */
+class Format
+{
+    /**
+     * Format a number with thousands grouping and a decimal separator.
+     *
+     * Format::number(1234.56, 2, ',', '.') gives "1,234.56";
+     * Format::number(1234.56, 2, '.', ',') gives "1.234,56".
+     *
+     * @param float  $value        Number to format
+     * @param int    $decimals     Decimal places (rounded)
+     * @param string $thousandsSep Group separator, or '' for none
+     * @param string $decimalSep   Separator before the fraction
+     * @return string
+     */
+    public static function number(float $value, int $decimals, string $thousandsSep, string $decimalSep): string {}
+
+    /**
+     * Format an amount with a currency symbol, e.g. "$9,600.00" or "-$5.00".
+     *
+     * @param float  $value    Amount
+     * @param string $symbol   Prefix such as '$'
+     * @param int    $decimals Decimal places (rounded)
+     * @return string
+     */
+    public static function currency(float $value, string $symbol, int $decimals): string {}
+}
+
 class Rect
 {
     public float $x;
@@ -85,6 +117,88 @@ class Rect
         float $width,
         float $height
     ) {}
+
+    /**
+     * Create a rect from millimeter values (converted to points).
+     *
+     * @param float $x      X coordinate in mm
+     * @param float $y      Y coordinate in mm
+     * @param float $width  Width in mm
+     * @param float $height Height in mm
+     */
+    public static function mm(float $x, float $y, float $width, float $height): Rect {}
+
+    /**
+     * Create a rect from centimeter values (converted to points).
+     *
+     * @param float $x      X coordinate in cm
+     * @param float $y      Y coordinate in cm
+     * @param float $width  Width in cm
+     * @param float $height Height in cm
+     */
+    public static function cm(float $x, float $y, float $width, float $height): Rect {}
+}
+
+/**
+ * Overflow modes for CellStyle::$overflow.
+ */
+class CellOverflow
+{
+    public const WRAP = 'wrap';
+    public const CLIP = 'clip';
+    public const SHRINK = 'shrink';
+}
+
+/**
+ * Word break modes for TextFlow::$wordBreak and CellStyle::$wordBreak.
+ */
+class WordBreak
+{
+    public const BREAK = 'break';
+    public const HYPHENATE = 'hyphenate';
+    public const NORMAL = 'normal';
+}
+
+/**
+ * Horizontal alignments for CellStyle::$textAlign and
+ * PdfDocument::placeTextAligned().
+ */
+class TextAlign
+{
+    public const LEFT = 'left';
+    public const CENTER = 'center';
+    public const RIGHT = 'right';
+}
+
+/**
+ * Vertical alignments for CellStyle::$verticalAlign and
+ * PdfDocument::placeTextBlock().
+ */
+class VerticalAlign
+{
+    public const TOP = 'top';
+    public const MIDDLE = 'middle';
+    public const BOTTOM = 'bottom';
+}
+
+/**
+ * Underline styles for CellStyle::$underline.
+ */
+class Underline
+{
+    public const NONE = 'none';
+    public const SINGLE = 'single';
+    public const DOUBLE = 'double';
+}
+
+/**
+ * Results returned by PdfDocument::fitTextflow() and PdfDocument::fitRow().
+ */
+class FitResult
+{
+    public const STOP = 'stop';
+    public const BOX_FULL = 'box_full';
+    public const BOX_EMPTY = 'box_empty';
 }
 
 class TextFlow
@@ -99,6 +213,16 @@ class TextFlow
      */
     public string $wordBreak;
 
+    /** Single character appended at "hyphenate" breaks (default "-") */
+    public string $hyphenChar;
+
+    /**
+     * Place the first baseline one real font ascent (TrueType ascent,
+     * builtin cap height) below the rect top instead of one font size
+     * (default false).
+     */
+    public bool $useRealAscent;
+
     public function __construct() {}
 
     /**
@@ -109,10 +233,25 @@ class TextFlow
      */
     public function addText(string $text, TextStyle $style): void {}
 
+    /**
+     * Add an image that flows with the text like a word, e.g. an icon.
+     *
+     * @param int   $handle         Image handle from loadImageFile/loadImageBytes
+     * @param float $width          Image width in points
+     * @param float $height         Image height in points
+     * @param float $baselineOffset Bottom edge above the baseline in points (negative drops below)
+     */
+    public function addInlineImage(int $handle, float $width, float $height, float $baselineOffset): void {}
+
     /**
      * Check whether all text has been consumed.
      */
     public function isFinished(): bool {}
+
+    /**
+     * Number of words (including forced line breaks) not yet placed.
+     */
+    public function remainingWords(): int {}
 }
 
 class CellStyle
@@ -121,8 +260,8 @@ class CellStyle
     public int $fontHandle;
     public float $fontSize;
     public float $padding;
-    /** Overflow mode: "wrap", "clip", or "shrink" */
-    public string $overflow;
+    /** Overflow mode: "wrap", "clip", "shrink", or null to inherit the table default */
+    public ?string $overflow;
     /**
      * Word break mode for words wider than the cell.
      *
@@ -130,8 +269,14 @@ class CellStyle
      *   "break"     — force-break at a character boundary (default)
      *   "hyphenate" — force-break with a hyphen at the break point
      *   "normal"    — no breaking; wide words overflow the cell
+     *   null        — inherit the table's default style
      */
-    public string $wordBreak;
+    public ?string $wordBreak;
+    /**
+     * Single character appended at "hyphenate" breaks, or null to inherit
+     * the table's default style (else "-").
+     */
+    public ?string $hyphenChar;
     /**
      * Horizontal text alignment within the cell.
      *
@@ -141,12 +286,41 @@ class CellStyle
      *   "right"  — right-aligned
      */
     public string $textAlign;
+    /**
+     * Vertical alignment of the text block within the cell. With "clip"
+     * overflow, "middle" shows the central lines of text that is too tall.
+     *
+     * Allowed values:
+     *   "top"    — top-aligned (default)
+     *   "middle" — centered vertically
+     *   "bottom" — bottom-aligned
+     */
+    public string $verticalAlign;
+
+    /**
+     * Rules drawn under each line of text, in the text color.
+     *
+     * Allowed values:
+     *   "none"   — no underline (default)
+     *   "single" — one rule
+     *   "double" — two rules, e.g. for grand totals
+     */
+    public string $underline;
+    /**
+     * Counter-clockwise text rotation in degrees (default 0), e.g. 90 for a
+     * vertical column header. Rotated text is one centred line; alignment,
+     * underline and "shrink" apply only at 0.
+     */
+    public float $rotation;
 
     /**
      * Create a CellStyle with default values.
      *
      * Defaults: fontName = "Helvetica", fontSize = 10.0, padding = 4.0,
-     *           overflow = "wrap", wordBreak = "break", textAlign = "left".
+     *           overflow = null, wordBreak = null, hyphenChar = null
+     *           (inherit the table's default style, else "wrap" / "break" /
+     *           "-"), textAlign = "left",
+     *           verticalAlign = "top", underline = "none".
      */
     public function __construct() {}
 
@@ -195,12 +369,23 @@ class Cell
      * @throws \Exception if the style contains an invalid font name
      */
     public static function styled(string $text, CellStyle $style): self {}
+
+    /**
+     * Make the cell cover several rows. The cell in the same column of each
+     * of the following rowspan - 1 rows is skipped; pass blank cells there.
+     * Spans end at a page break.
+     *
+     * @param int $rowspan Number of rows covered (minimum 1)
+     */
+    public function setRowspan(int $rowspan): void {}
 }
 
 class Row
 {
     /** Optional fixed height in points. Required for "clip" and "shrink" overflow. */
     public ?float $height;
+    /** Header row: never striped, and skipped by the stripe pattern (default false). */
+    public bool $header;
 
     /**
      * Create a row with the given cells.
@@ -224,10 +409,67 @@ class Table
      *
      * Table is config-only. Pass rows to PdfDocument::fitRow().
      *
-     * @param float[] $columns Column widths in points
+     * @param float[] $columns Column widths in points; negative widths are
+     *                         flexible shares of the remaining width
      */
     public function __construct(array $columns) {}
 
+    /**
+     * Set the width flexible (negative) columns are resolved against.
+     *
+     * @param float|null $width Total table width in points; null uses the cursor rect width
+     */
+    public function setTotalWidth(?float $width): void {}
+
+    /**
+     * Lay columns out right-to-left: the first cell of each row is drawn
+     * in the rightmost column. Cell text alignment is unaffected.
+     *
+     * @param bool $rtl Whether to reverse column placement
+     */
+    public function setRtl(bool $rtl): void {}
+
+    /**
+     * Fill every other row (the second, fourth, ...) with a stripe color.
+     * Header rows and rows with their own background color are skipped
+     * without breaking the alternation.
+     *
+     * @param Color|null $color Stripe color; null disables striping
+     */
+    public function setStripeColor(?Color $color): void {}
+
+    /**
+     * Restart the stripe pattern at the top of each page. By default the
+     * parity continues across page breaks.
+     *
+     * @param bool $reset Whether to restart striping on each page
+     */
+    public function setResetStripeOnPage(bool $reset): void {}
+
+    /**
+     * Set a lower bound for the height of auto-height rows.
+     * Rows with a fixed height are not affected.
+     *
+     * @param float|null $height Minimum height in points; null removes the bound
+     */
+    public function setMinRowHeight(?float $height): void {}
+
+    /**
+     * Set an upper bound for the height of auto-height rows. Text taller
+     * than the bound runs past the row unless the cell clips.
+     *
+     * @param float|null $height Maximum height in points; null removes the bound
+     */
+    public function setMaxRowHeight(?float $height): void {}
+
+    /**
+     * Draw a caption such as "(continued)" across the table's width, without
+     * borders, above the first row placed on each page after the first.
+     *
+     * @param Cell|null $caption Caption text and style; null removes it
+     */
+    public function setContinuedCaption(?Cell $caption): void {}
+
     /**
      * Set the border stroke color.
      *
@@ -242,6 +484,13 @@ class Table
      */
     public function setBorderWidth(float $width): void {}
 
+    /**
+     * Set a dash pattern for borders, e.g. [1.0, 2.0] for dotted rules.
+     *
+     * @param float[]|null $pattern Dash and gap lengths in points; null or [] for solid
+     */
+    public function setBorderDash(?array $pattern): void {}
+
     /**
      * Set the default style used as a fallback for cells without explicit styles.
      *
@@ -287,6 +536,68 @@ class TableCursor
      * without guessing where the table ended.
      */
     public function currentY(): float {}
+
+    /**
+     * Returns the height left between the next row and the rect bottom.
+     *
+     * Use it to start a section on a new page instead of splitting it.
+     */
+    public function remainingHeight(): float {}
+
+    /**
+     * Returns the number of rows placed with this cursor, across all pages.
+     */
+    public function rowIndex(): int {}
+}
+
+/**
+ * Records vector drawing for a reusable form XObject.
+ *
+ * Coordinates are in the form's own space, with (0, 0) at its lower-left
+ * corner. Pass the builder to PdfDocument::createFormXObject().
+ */
+class FormBuilder
+{
+    public function __construct() {}
+
+    /** Set the stroke color (PDF RG operator). */
+    public function setStrokeColor(Color $color): void {}
+
+    /** Set the fill color (PDF rg operator). */
+    public function setFillColor(Color $color): void {}
+
+    /** Set the line width (PDF w operator). */
+    public function setLineWidth(float $width): void {}
+
+    /** Set the line dash pattern (PDF d operator); an empty pattern is solid. */
+    public function setDash(array $pattern, ?float $phase = null): void {}
+
+    /** Move to a point (PDF m operator). */
+    public function moveTo(float $x, float $y): void {}
+
+    /** Line to a point (PDF l operator). */
+    public function lineTo(float $x, float $y): void {}
+
+    /** Append a rectangle (PDF re operator). */
+    public function rect(float $x, float $y, float $width, float $height): void {}
+
+    /** Close the current subpath (PDF h operator). */
+    public function closePath(): void {}
+
+    /** Stroke the current path (PDF S operator). */
+    public function stroke(): void {}
+
+    /** Fill the current path (PDF f operator). */
+    public function fill(): void {}
+
+    /** Fill and stroke the current path (PDF B operator). */
+    public function fillStroke(): void {}
+
+    /** Save the graphics state (PDF q operator). */
+    public function saveState(): void {}
+
+    /** Restore the graphics state (PDF Q operator). */
+    public function restoreState(): void {}
 }
 
 class PdfDocument
@@ -319,169 +630,767 @@ class PdfDocument
     public function loadFontFile(string $path): int {}
 
     /**
-     * Set a document info entry (e.g. "Creator", "Title").
+     * Register a TrueType file embedded in place of a builtin font.
      *
-     * @param string $key   Info key
-     * @param string $value Info value
-     * @throws \Exception if the document has already ended
+     * Only used once setEmbedStandardFonts(true) is on. The replacement
+     * should be metric-compatible, e.g. Liberation Sans for Helvetica.
+     *
+     * @param string $fontName Builtin font name, e.g. "Helvetica"
+     * @param string $path     Path to the replacement .ttf file
+     * @throws \Exception if the name is unknown or the file cannot be read or parsed
      */
-    public function setInfo(string $key, string $value): void {}
+    public function loadStandardFontReplacement(string $fontName, string $path): void {}
 
     /**
-     * Enable or disable FlateDecode compression for stream objects.
+     * Embed builtin fonts instead of referencing them by name (default: off).
      *
-     * When enabled, page content, embedded fonts, and ToUnicode CMaps
-     * are compressed, typically reducing file size by 50-80%.
-     * Disabled by default.
+     * Builtin fonts without a replacement stay as plain references.
      *
-     * @param bool $enabled Whether to enable compression
+     * @param bool $enabled Whether to embed replaced builtin fonts
      * @throws \Exception if the document has already ended
      */
-    public function setCompression(bool $enabled): void {}
+    public function setEmbedStandardFonts(bool $enabled): void {}
 
     /**
-     * Begin a new page with the given dimensions in points.
+     * Embed only the glyphs shown from each standard-font replacement
+     * (default: off). Applies while setEmbedStandardFonts is on; the fonts
+     * are written at endDocument() once every page's text is known.
      *
-     * @param float $width  Page width in points
-     * @param float $height Page height in points
+     * @param bool $enabled Whether to subset embedded replacements
      * @throws \Exception if the document has already ended
      */
-    public function beginPage(float $width, float $height): void {}
+    public function setSubsetStandardFonts(bool $enabled): void {}
 
     /**
-     * Place text at (x, y) using default 12pt Helvetica.
+     * Enable or disable pair kerning (from the font's kern table) for a
+     * loaded TrueType font. Kerning is off by default.
      *
-     * @param string $text Text to place
-     * @param float  $x   X coordinate (bottom-left origin)
-     * @param float  $y   Y coordinate (bottom-left origin)
-     * @throws \Exception if the document has already ended
+     * @param int  $handle  Font handle returned by loadFontFile()
+     * @param bool $enabled Whether to apply kerning
+     * @throws \Exception on error
      */
-    public function placeText(
-        string $text,
-        float $x,
-        float $y
-    ): void {}
+    public function setFontKerning(int $handle, bool $enabled): void {}
 
     /**
-     * Place text at (x, y) using an explicit TextStyle.
+     * Override the ToUnicode CMap entry of one glyph, so copy-paste and
+     * search read it as $unicode. Use for fonts with wrong cmap entries.
      *
-     * @param string    $text  Text to place
-     * @param float     $x     X coordinate (bottom-left origin)
-     * @param float     $y     Y coordinate (bottom-left origin)
-     * @param TextStyle $style Font and size to use
-     * @throws \Exception if the document has already ended or style is invalid
+     * @param int    $handle  Font handle returned by loadFontFile()
+     * @param int    $glyph   Glyph ID (0-65535)
+     * @param string $unicode Exactly one character
+     * @throws \Exception if the document has already ended or the arguments are invalid
      */
-    public function placeTextStyled(
-        string $text,
-        float $x,
-        float $y,
-        TextStyle $style
-    ): void {}
+    public function setGlyphUnicode(int $handle, int $glyph, string $unicode): void {}
 
     /**
-     * Fit a TextFlow into a bounding rectangle on the current page.
+     * Set the fonts tried, in order, for characters the primary font lacks.
+     * Applies to placeTextStyled() and measureText(), not TextFlow or tables.
      *
-     * @param TextFlow $flow The text flow to fit
-     * @param Rect     $rect The bounding rectangle
-     * @return string "stop", "box_full", or "box_empty"
-     * @throws \Exception on error or if the document has already ended
+     * @param TextStyle $primary         Style whose font gets the fallback chain
+     * @param int[]     $fallbackHandles Font handles returned by loadFontFile()
+     * @throws \Exception if the document has already ended or the primary font is invalid
      */
-    public function fitTextflow(
-        TextFlow $flow,
-        Rect $rect
-    ): string {}
+    public function setFontFallback(TextStyle $primary, array $fallbackHandles): void {}
 
     /**
-     * Place a single row on the current page using the streaming fit-row pattern.
-     *
-     * Returns "stop" when the row was placed successfully (advance to next row).
-     * Returns "box_full" when the page is full (end page, begin new page, reset
-     * cursor, then retry the same row). Returns "box_empty" when the rect is
-     * intrinsically too small for the row.
+     * Set a document info entry (e.g. "Creator", "Title").
      *
-     * @param Table       $table  Table config (column widths, border, default style)
-     * @param Row         $row    The row to place
-     * @param TableCursor $cursor Page-level cursor tracking current Y position
-     * @return string "stop", "box_full", or "box_empty"
-     * @throws \Exception on error or if the document has already ended
+     * @param string $key   Info key
+     * @param string $value Info value
+     * @throws \Exception if the document has already ended
      */
-    public function fitRow(Table $table, Row $row, TableCursor $cursor): string {}
-
-    // -------------------------------------------------------
-    // Graphics operations
-    // -------------------------------------------------------
+    public function setInfo(string $key, string $value): void {}
 
     /**
-     * Set the stroke color.
+     * Set the /Title info entry, replacing any earlier title.
      *
-     * @param Color $color The stroke color
+     * @param string $title Document title
      * @throws \Exception if the document has already ended
      */
-    public function setStrokeColor(Color $color): void {}
+    public function setTitle(string $title): void {}
 
     /**
-     * Set the fill color.
+     * Set the /Author info entry, replacing any earlier author.
      *
-     * @param Color $color The fill color
+     * @param string $author Author name
      * @throws \Exception if the document has already ended
      */
-    public function setFillColor(Color $color): void {}
+    public function setAuthor(string $author): void {}
 
     /**
-     * Set the line width.
+     * Set the /Subject info entry, replacing any earlier subject.
      *
-     * @param float $width Line width in points
+     * @param string $subject Document subject
      * @throws \Exception if the document has already ended
      */
-    public function setLineWidth(float $width): void {}
+    public function setSubject(string $subject): void {}
 
     /**
-     * Move to a point without drawing.
+     * Set the /Keywords info entry, replacing any earlier keywords.
      *
-     * @param float $x X coordinate
-     * @param float $y Y coordinate
+     * @param string $keywords Comma-separated keywords, e.g. "invoice, 2026"
      * @throws \Exception if the document has already ended
      */
-    public function moveTo(float $x, float $y): void {}
+    public function setKeywords(string $keywords): void {}
 
     /**
-     * Draw a line from the current point.
+     * Set the /Creator info entry, replacing any earlier creator.
      *
-     * @param float $x X coordinate of the end point
-     * @param float $y Y coordinate of the end point
+     * @param string $creator Name of the application that made the content
      * @throws \Exception if the document has already ended
      */
-    public function lineTo(float $x, float $y): void {}
+    public function setCreator(string $creator): void {}
 
     /**
-     * Append a rectangle to the path.
+     * Fill in /CreationDate and /ModDate with the current UTC time at
+     * endDocument() when they were not set with setInfo(). On by default.
      *
-     * @param float $x      X coordinate of the lower-left corner
-     * @param float $y      Y coordinate of the lower-left corner
-     * @param float $width  Width of the rectangle
-     * @param float $height Height of the rectangle
+     * @param bool $enabled Whether to add missing dates
      * @throws \Exception if the document has already ended
      */
-    public function rect(
-        float $x,
-        float $y,
-        float $width,
-        float $height
-    ): void {}
+    public function setAutoDates(bool $enabled): void {}
 
     /**
-     * Close the current subpath.
+     * Set how many decimal places numbers keep in content streams.
      *
-     * @throws \Exception if the document has already ended
+     * Defaults to 4. Fewer digits give smaller files; more give exact
+     * positioning. Affects content generated after the call.
+     *
+     * @param int $digits Decimal places (0-255)
+     * @throws \Exception if digits is out of range or the document has already ended
      */
-    public function closePath(): void {}
+    public function setCoordinatePrecision(int $digits): void {}
 
     /**
-     * Stroke the current path.
+     * Set the /Producer written when none was set with setInfo().
      *
+     * Defaults to "pivot-pdf <version>".
+     *
+     * @param string|null $producer Producer name, or null to omit the entry
      * @throws \Exception if the document has already ended
      */
-    public function stroke(): void {}
+    public function setProducer(?string $producer): void {}
+
+    /**
+     * Enable or disable FlateDecode compression for stream objects.
+     *
+     * When enabled, page content, embedded fonts, and ToUnicode CMaps
+     * are compressed, typically reducing file size by 50-80%.
+     * Disabled by default.
+     *
+     * @param bool $enabled Whether to enable compression
+     * @throws \Exception if the document has already ended
+     */
+    public function setCompression(bool $enabled): void {}
+
+    /**
+     * Compress page content and form XObject streams while compression is
+     * on. Enabled by default.
+     *
+     * @param bool $enabled Whether content streams are compressed
+     * @throws \Exception if the document has already ended
+     */
+    public function setCompressContent(bool $enabled): void {}
+
+    /**
+     * Compress embedded font files and ToUnicode CMaps while compression is
+     * on. Enabled by default.
+     *
+     * @param bool $enabled Whether font streams are compressed
+     * @throws \Exception if the document has already ended
+     */
+    public function setCompressFonts(bool $enabled): void {}
+
+    /**
+     * Compress PNG image data and soft masks while compression is on. JPEG
+     * data is always written as-is. Enabled by default.
+     *
+     * @param bool $enabled Whether image streams are compressed
+     * @throws \Exception if the document has already ended
+     */
+    public function setCompressImages(bool $enabled): void {}
+
+    /**
+     * Write one page's content streams uncompressed for debugging, even
+     * while compression is on.
+     *
+     * @param int $pageNum 1-based page number
+     * @throws \Exception if the document has already ended
+     */
+    public function setPageUncompressed(int $pageNum): void {}
+
+    /**
+     * Separate content-stream operators with single spaces instead of
+     * newlines, for smaller output. Off by default.
+     *
+     * @param bool $enabled Whether to write compact content streams
+     * @throws \Exception if the document has already ended
+     */
+    public function setCompactContent(bool $enabled): void {}
+
+    /**
+     * Write the binary comment line after "%PDF-1.7" (default true).
+     *
+     * Disabling it gives a pure-ASCII header, which is technically
+     * non-conforming for files that contain binary data. Must be called
+     * before the first page is ended.
+     *
+     * @param bool $enabled Whether to write the binary comment
+     * @throws \Exception if the document has already ended
+     */
+    public function setBinaryMarker(bool $enabled): void {}
+
+    /**
+     * Make output byte-identical across identical builds.
+     *
+     * Skips the automatic /CreationDate and /ModDate and writes a trailer
+     * /ID derived only from a hash of the document's bytes. Disabled by
+     * default.
+     *
+     * @param bool $enabled Whether to enable deterministic output
+     * @throws \Exception if the document has already ended
+     */
+    public function setDeterministic(bool $enabled): void {}
+
+    /**
+     * Ask the viewer to open its print dialog when the document is opened.
+     *
+     * Writes /OpenAction << /S /Named /N /Print >> into the catalog.
+     * Disabled by default.
+     *
+     * @param bool $enabled Whether to prompt printing on open
+     * @throws \Exception if the document has already ended
+     */
+    public function setPrintOnOpen(bool $enabled): void {}
+
+    /**
+     * Turn validation warnings into errors.
+     *
+     * When enabled, endPage() fails if the page's content references a font
+     * or image missing from its resources, and endDocument() fails if
+     * validate() reports any warning. Disabled by default.
+     *
+     * @param bool $enabled Whether to enable strict mode
+     * @throws \Exception if the document has already ended
+     */
+    public function setStrict(bool $enabled): void {}
+
+    /**
+     * Cross-check pages against the objects written so far.
+     *
+     * @return string[] Human-readable warnings; empty when no problems were found
+     * @throws \Exception if the document has already ended
+     */
+    public function validate(): array {}
+
+    /**
+     * Output size statistics for everything written so far.
+     *
+     * Keys: page_count, content_bytes, font_bytes, image_bytes, object_count.
+     * TrueType font bytes are only counted once endDocument() writes them.
+     *
+     * @return array<string, int>
+     * @throws \Exception if the document has already ended
+     */
+    public function stats(): array {}
+
+    /**
+     * Merge openPage() overlays into each page's main content stream.
+     *
+     * Pages ended after this call are buffered until endDocument() so that
+     * overlays can be appended to them, producing one stream per page.
+     * Disabled by default.
+     *
+     * @param bool $enabled Whether to merge overlays
+     * @throws \Exception if the document has already ended
+     */
+    public function setMergeOverlays(bool $enabled): void {}
+
+    /**
+     * Set a line width applied at the start of every subsequent page.
+     *
+     * @param float $width Line width in points
+     * @throws \Exception if the document has already ended
+     */
+    public function setDefaultLineWidth(float $width): void {}
+
+    /**
+     * Set a stroke color applied at the start of every subsequent page.
+     *
+     * @param Color $color The stroke color
+     * @throws \Exception if the document has already ended
+     */
+    public function setDefaultStrokeColor(Color $color): void {}
+
+    /**
+     * Set a fill color applied at the start of every subsequent page.
+     *
+     * @param Color $color The fill color
+     * @throws \Exception if the document has already ended
+     */
+    public function setDefaultFillColor(Color $color): void {}
+
+    /**
+     * Set a line cap applied at the start of every subsequent page.
+     *
+     * @param string $cap "butt", "round" or "square"
+     * @throws \Exception if the document has already ended or the cap is invalid
+     */
+    public function setDefaultLineCap(string $cap): void {}
+
+    /**
+     * Set a line join applied at the start of every subsequent page.
+     *
+     * @param string $join "miter", "round" or "bevel"
+     * @throws \Exception if the document has already ended or the join is invalid
+     */
+    public function setDefaultLineJoin(string $join): void {}
+
+    /**
+     * Set a dash pattern applied at the start of every subsequent page.
+     *
+     * @param float[] $pattern Alternating dash and gap lengths in points
+     * @param float   $phase   Distance into the pattern to start at
+     * @throws \Exception if the document has already ended
+     */
+    public function setDefaultDash(array $pattern, float $phase = 0.0): void {}
+
+    /**
+     * Set the page size used by beginPageDefault(). Defaults to US Letter
+     * (612 x 792 points).
+     *
+     * @param float $width  Page width in points
+     * @param float $height Page height in points
+     * @throws \Exception if the document has already ended
+     */
+    public function setDefaultPageSize(float $width, float $height): void {}
+
+    /**
+     * Begin a new page with the given dimensions in points.
+     *
+     * @param float $width  Page width in points
+     * @param float $height Page height in points
+     * @throws \Exception if the document has already ended
+     */
+    public function beginPage(float $width, float $height): void {}
+
+    /**
+     * Begin a new page using the size set by setDefaultPageSize().
+     *
+     * @throws \Exception if the document has already ended
+     */
+    public function beginPageDefault(): void {}
+
+    /**
+     * Begin a new page with an explicit MediaBox [llx lly urx ury].
+     *
+     * Content coordinates are not translated by the box origin.
+     *
+     * @param float $llx Lower-left X
+     * @param float $lly Lower-left Y
+     * @param float $urx Upper-right X
+     * @param float $ury Upper-right Y
+     * @throws \Exception if the document has already ended
+     */
+    public function beginPageBox(float $llx, float $lly, float $urx, float $ury): void {}
+
+    /**
+     * Begin a new page displayed rotated clockwise by $degrees.
+     *
+     * $width and $height are the size as the reader sees it. Coordinates
+     * keep their usual bottom-left-origin meaning on the rotated page.
+     *
+     * @param float $width   Displayed page width in points
+     * @param float $height  Displayed page height in points
+     * @param int   $degrees Clockwise rotation, a multiple of 90
+     * @throws \Exception if the document has already ended or degrees is not a multiple of 90
+     */
+    public function beginPageRotated(float $width, float $height, int $degrees): void {}
+
+    /**
+     * Set a clockwise rotation that every page inherits from the Pages node.
+     *
+     * Pages begun with beginPageRotated() that use a different angle keep
+     * their own /Rotate.
+     *
+     * @param int $degrees Clockwise rotation, a multiple of 90
+     * @throws \Exception if the document has already ended or degrees is not a multiple of 90
+     */
+    public function setDocumentRotation(int $degrees): void {}
+
+    /**
+     * Advance past the current page automatically after $seconds when the
+     * document is shown in full-screen presentation mode (/Dur).
+     *
+     * @param float $seconds Display time in seconds
+     * @throws \Exception if the document has already ended or seconds is negative
+     */
+    public function setPageDuration(float $seconds): void {}
+
+    /**
+     * Set the effect played when a presentation moves to the current page (/Trans).
+     *
+     * Allowed values: "split", "blinds", "box", "wipe", "dissolve", "glitter",
+     * "fly", "push", "cover", "uncover", "fade".
+     *
+     * @param string $transition Transition style
+     * @throws \Exception if the document has already ended or the style is unknown
+     */
+    public function setPageTransition(string $transition): void {}
+
+    /**
+     * Begin a new page whose content is turned 180°, for duplex back pages.
+     *
+     * Coordinates are given as on an ordinary page; the page has no
+     * /Rotate, so viewers show the content upside down.
+     *
+     * @param float $width  Page width in points
+     * @param float $height Page height in points
+     * @throws \Exception if the document has already ended
+     */
+    public function beginPageFlipped(float $width, float $height): void {}
+
+    /**
+     * Begin a new page with the given dimensions in millimeters.
+     *
+     * @param float $width  Page width in mm (e.g. 210 for A4)
+     * @param float $height Page height in mm (e.g. 297 for A4)
+     * @throws \Exception if the document has already ended
+     */
+    public function beginPageMm(float $width, float $height): void {}
+
+    /**
+     * Place text at (x, y) using default 12pt Helvetica.
+     *
+     * @param string $text Text to place
+     * @param float  $x   X coordinate (bottom-left origin)
+     * @param float  $y   Y coordinate (bottom-left origin)
+     * @throws \Exception if the document has already ended
+     */
+    public function placeText(
+        string $text,
+        float $x,
+        float $y
+    ): void {}
+
+    /**
+     * Place text at (x, y) using an explicit TextStyle.
+     *
+     * @param string    $text  Text to place
+     * @param float     $x     X coordinate (bottom-left origin)
+     * @param float     $y     Y coordinate (bottom-left origin)
+     * @param TextStyle $style Font and size to use
+     * @throws \Exception if the document has already ended or style is invalid
+     */
+    public function placeTextStyled(
+        string $text,
+        float $x,
+        float $y,
+        TextStyle $style
+    ): void {}
+
+    /**
+     * Place text aligned relative to x using its measured width.
+     *
+     * "left" starts the text at x, "right" ends it at x, and "center"
+     * centers it on x.
+     *
+     * @param string    $text  Text to place
+     * @param float     $x     Anchor X coordinate (bottom-left origin)
+     * @param float     $y     Baseline Y coordinate (bottom-left origin)
+     * @param TextStyle $style Font and size to use
+     * @param string    $align "left" (default), "center", or "right"
+     * @throws \Exception if the document has already ended or style is invalid
+     */
+    public function placeTextAligned(
+        string $text,
+        float $x,
+        float $y,
+        TextStyle $style,
+        string $align = "left"
+    ): void {}
+
+    /**
+     * Place text wrapped to $rect's width and aligned within it.
+     *
+     * Lines break at spaces and newlines like table cells. The block is
+     * positioned by $valign and each line by $halign; a block taller than
+     * the rect overflows it.
+     *
+     * @param string    $text   Text to place
+     * @param Rect      $rect   Box to align within (top-left corner, bottom-left origin)
+     * @param TextStyle $style  Font and size to use
+     * @param string    $halign "left" (default), "center", or "right"
+     * @param string    $valign "top" (default), "middle", or "bottom"
+     * @return int Number of lines placed
+     * @throws \Exception if the document has already ended or style is invalid
+     */
+    public function placeTextBlock(
+        string $text,
+        Rect $rect,
+        TextStyle $style,
+        string $halign = "left",
+        string $valign = "top"
+    ): int {}
+
+    /**
+     * Set text glyph by glyph along a circle.
+     *
+     * The first glyph starts at $startAngle degrees (counter-clockwise from
+     * 3 o'clock) and each following glyph advances clockwise by its width,
+     * upright relative to the circle.
+     *
+     * @param string    $text       Text to place
+     * @param float     $cx         Circle centre X (bottom-left origin)
+     * @param float     $cy         Circle centre Y (bottom-left origin)
+     * @param float     $radius     Baseline radius in points
+     * @param float     $startAngle Angle of the first glyph in degrees
+     * @param TextStyle $style      Font and size to use
+     * @throws \Exception if the document has already ended or style is invalid
+     */
+    public function placeTextOnArc(
+        string $text,
+        float $cx,
+        float $cy,
+        float $radius,
+        float $startAngle,
+        TextStyle $style
+    ): void {}
+
+    /**
+     * Fill a horizontal gap with a dot leader, as in a table of contents.
+     *
+     * As many periods as fit (one per width of ". ") are set on the
+     * baseline, ending flush at $toX.
+     *
+     * @param float     $fromX Left end of the gap
+     * @param float     $toX   Right end of the gap
+     * @param float     $y     Baseline Y (bottom-left origin)
+     * @param TextStyle $style Font and size of the dots
+     * @throws \Exception if the document has already ended or style is invalid
+     */
+    public function drawLeader(float $fromX, float $toX, float $y, TextStyle $style): void {}
+
+    /**
+     * Measure the width of text in points when set in the given style.
+     *
+     * @param string    $text  Text to measure
+     * @param TextStyle $style Font and size to use
+     * @return float Width in points
+     * @throws \Exception if the document has already ended or style is invalid
+     */
+    public function measureText(string $text, TextStyle $style): float {}
+
+    /**
+     * Truncate text with an ellipsis so it fits within a width.
+     *
+     * Text that already fits is returned unchanged. TrueType fonts get "…",
+     * builtin fonts "..."; an empty string is returned when not even the
+     * ellipsis fits.
+     *
+     * @param string    $text     Text to fit
+     * @param TextStyle $style    Font and size to measure with
+     * @param float     $maxWidth Available width in points
+     * @return string The fitted text
+     * @throws \Exception if the document has already ended or style is invalid
+     */
+    public function fitTextEllipsized(string $text, TextStyle $style, float $maxWidth): string {}
+
+    /**
+     * Measure the rest of a TextFlow wrapped to a width without placing it.
+     *
+     * The flow's position is unchanged, so it can still be passed to
+     * fitTextflow() afterwards.
+     *
+     * @param TextFlow $flow  The text flow to measure
+     * @param float    $width Wrapping width in points
+     * @return array<string, float> Keys: height (points), lines (line count)
+     * @throws \Exception if the document has already ended
+     */
+    public function measureFlow(TextFlow $flow, float $width): array {}
+
+    /**
+     * Fit a TextFlow into a bounding rectangle on the current page.
+     *
+     * @param TextFlow $flow The text flow to fit
+     * @param Rect     $rect The bounding rectangle
+     * @return string "stop", "box_full", or "box_empty"
+     * @throws \Exception on error or if the document has already ended
+     */
+    public function fitTextflow(
+        TextFlow $flow,
+        Rect $rect
+    ): string {}
+
+    /**
+     * Place a single row on the current page using the streaming fit-row pattern.
+     *
+     * Returns "stop" when the row was placed successfully (advance to next row).
+     * Returns "box_full" when the page is full (end page, begin new page, reset
+     * cursor, then retry the same row). Returns "box_empty" when the rect is
+     * intrinsically too small for the row.
+     *
+     * @param Table       $table  Table config (column widths, border, default style)
+     * @param Row         $row    The row to place
+     * @param TableCursor $cursor Page-level cursor tracking current Y position
+     * @return string "stop", "box_full", or "box_empty"
+     * @throws \Exception on error or if the document has already ended
+     */
+    public function fitRow(Table $table, Row $row, TableCursor $cursor): string {}
+
+    /**
+     * Place all rows in one rect on the current page, scaling font sizes,
+     * paddings and fixed row heights down uniformly if the table would
+     * not fit.
+     *
+     * @param Table $table Table config
+     * @param Row[] $rows  All rows, in order
+     * @param Rect  $rect  Area the table must fit in
+     * @return float The applied scale (1.0 when no scaling was needed)
+     * @throws \Exception on error or if the document has already ended
+     */
+    public function fitTableToRect(Table $table, array $rows, Rect $rect): float {}
+
+    /**
+     * Column widths that fit the text of the header and the first
+     * $sampleRows rows without wrapping, for new Table($columns).
+     *
+     * Rows past the sample are ignored, so wider ones later on wrap.
+     *
+     * @param Row   $header     Header row
+     * @param Row[] $rows       Data rows; only the first $sampleRows are measured
+     * @param int   $sampleRows Number of data rows to measure
+     * @return float[] One width in points per column
+     * @throws \Exception if the document has already ended
+     */
+    public function autoColumns(Row $header, array $rows, int $sampleRows): array {}
+
+    // -------------------------------------------------------
+    // Graphics operations
+    // -------------------------------------------------------
+
+    /**
+     * Set the stroke color.
+     *
+     * @param Color $color The stroke color
+     * @throws \Exception if the document has already ended
+     */
+    public function setStrokeColor(Color $color): void {}
+
+    /**
+     * Set the fill color.
+     *
+     * @param Color $color The fill color
+     * @throws \Exception if the document has already ended
+     */
+    public function setFillColor(Color $color): void {}
+
+    /**
+     * Set the line width.
+     *
+     * @param float $width Line width in points
+     * @throws \Exception if the document has already ended
+     */
+    public function setLineWidth(float $width): void {}
+
+    /**
+     * Set the line dash pattern.
+     *
+     * @param float[] $pattern Alternating dash and gap lengths in points; [] for solid
+     * @param float   $phase   Distance into the pattern to start at
+     * @throws \Exception if the document has already ended
+     */
+    public function setDash(array $pattern, float $phase = 0.0): void {}
+
+    /**
+     * Move to a point without drawing.
+     *
+     * @param float $x X coordinate
+     * @param float $y Y coordinate
+     * @throws \Exception if the document has already ended
+     */
+    public function moveTo(float $x, float $y): void {}
+
+    /**
+     * Draw a line from the current point.
+     *
+     * @param float $x X coordinate of the end point
+     * @param float $y Y coordinate of the end point
+     * @throws \Exception if the document has already ended
+     */
+    public function lineTo(float $x, float $y): void {}
+
+    /**
+     * Append a cubic Bézier curve from the current point.
+     *
+     * @param float $x1 First control point X
+     * @param float $y1 First control point Y
+     * @param float $x2 Second control point X
+     * @param float $y2 Second control point Y
+     * @param float $x3 End point X
+     * @param float $y3 End point Y
+     * @throws \Exception if the document has already ended
+     */
+    public function curveTo(float $x1, float $y1, float $x2, float $y2, float $x3, float $y3): void {}
+
+    /**
+     * Append a rectangle to the path.
+     *
+     * @param float $x      X coordinate of the lower-left corner
+     * @param float $y      Y coordinate of the lower-left corner
+     * @param float $width  Width of the rectangle
+     * @param float $height Height of the rectangle
+     * @throws \Exception if the document has already ended
+     */
+    public function rect(
+        float $x,
+        float $y,
+        float $width,
+        float $height
+    ): void {}
+
+    /**
+     * Append a rectangle with quarter-circle corners to the path.
+     *
+     * @param float $x      X coordinate of the lower-left corner
+     * @param float $y      Y coordinate of the lower-left corner
+     * @param float $width  Width of the rectangle
+     * @param float $height Height of the rectangle
+     * @param float $radius Corner radius, clamped to half the shorter side
+     * @throws \Exception if the document has already ended
+     */
+    public function roundedRect(float $x, float $y, float $width, float $height, float $radius): void {}
+
+    /**
+     * Append connected line segments through a list of points.
+     *
+     * Moves to the first point and draws a line to each later one. The
+     * path is not painted; follow with stroke(), fill() or fillStroke().
+     *
+     * ```php
+     * $doc->polyline([[10, 10], [60, 10], [60, 40]], true);
+     * $doc->stroke();
+     * ```
+     *
+     * @param array $points Array of [x, y] pairs
+     * @param bool  $close  Close the subpath back to the first point
+     * @throws \Exception if a point is not an [x, y] pair or the document has already ended
+     */
+    public function polyline(array $points, bool $close = false): void {}
+
+    /**
+     * Close the current subpath.
+     *
+     * @throws \Exception if the document has already ended
+     */
+    public function closePath(): void {}
+
+    /**
+     * Stroke the current path.
+     *
+     * @throws \Exception if the document has already ended
+     */
+    public function stroke(): void {}
 
     /**
      * Fill the current path.
@@ -511,6 +1420,187 @@ class PdfDocument
      */
     public function restoreState(): void {}
 
+    /**
+     * Append raw bytes to the current page's content stream, unchanged.
+     *
+     * An escape hatch for operators the API does not expose. You are
+     * responsible for valid syntax and balanced q/Q and BT/ET.
+     *
+     * @param string $ops Content stream operators, e.g. "1 J\n"
+     * @throws \Exception if the document has already ended
+     */
+    public function appendRawContent(string $ops): void {}
+
+    /**
+     * Record the drawing in a FormBuilder as a reusable form XObject.
+     *
+     * The form is written once, the first time a page using it ends;
+     * every placeForm() afterwards is a single Do operator.
+     *
+     * @param float       $width  Bounding box width in points
+     * @param float       $height Bounding box height in points
+     * @param FormBuilder $form   Recorded drawing
+     * @return int Form handle for placeForm()
+     * @throws \Exception if the document has already ended
+     */
+    public function createFormXObject(float $width, float $height, FormBuilder $form): int {}
+
+    /**
+     * Draw a form XObject on the current page.
+     *
+     * @param int   $handle Form handle from createFormXObject()
+     * @param float $x      Lower-left x of the placed form
+     * @param float $y      Lower-left y of the placed form
+     * @param float $scale  Uniform scale factor
+     * @throws \Exception if the document has already ended
+     */
+    public function placeForm(int $handle, float $x, float $y, float $scale): void {}
+
+    /**
+     * Start collecting page content into a transparency group.
+     *
+     * Everything drawn until endTransparencyGroup() is moved into a form
+     * XObject with a /Group << /S /Transparency >> dictionary and painted
+     * in place. Groups nest.
+     *
+     * @param bool $isolated Group /I entry
+     * @param bool $knockout Group /K entry
+     * @throws \Exception if the document has already ended
+     */
+    public function beginTransparencyGroup(bool $isolated, bool $knockout): void {}
+
+    /**
+     * Close the innermost transparency group and paint it on the page.
+     *
+     * @throws \Exception if the document has already ended or no group is open
+     */
+    public function endTransparencyGroup(): void {}
+
+    /**
+     * Stroke a frame inset by $margin on all sides of the current page.
+     *
+     * The frame is drawn inside its own save/restore state, so the color
+     * and line width do not affect later drawing.
+     *
+     * @param float $margin Inset from each page edge in points
+     * @param float $width  Line width in points
+     * @param Color $color  Stroke color
+     * @throws \Exception if the document has already ended
+     */
+    public function drawPageFrame(float $margin, float $width, Color $color): void {}
+
+    /**
+     * Draw a rectangle with an optional fill and an optional outline in one
+     * call, inside its own save/restore. Draws nothing if both are null.
+     *
+     * @param Rect       $rect      Box area; (x, y) is the top-left corner
+     * @param Color|null $fill      Fill color, or null for no fill
+     * @param Color|null $stroke    Outline color, or null for no outline
+     * @param float|null $lineWidth Outline width in points (default: 1.0)
+     * @throws \Exception if the document has already ended
+     */
+    public function drawBox(Rect $rect, ?Color $fill, ?Color $stroke = null, ?float $lineWidth = null): void {}
+
+    /**
+     * Draw a status chip: a filled rounded rectangle with centred text.
+     *
+     * The text is drawn in the current fill color.
+     *
+     * @param Rect      $rect   Chip area; (x, y) is the top-left corner
+     * @param float     $radius Corner radius in points
+     * @param Color     $fill   Background color
+     * @param string    $text   Label text
+     * @param TextStyle $style  Font and size of the label
+     * @throws \Exception if the document has already ended
+     */
+    public function drawChip(Rect $rect, float $radius, Color $fill, string $text, TextStyle $style): void {}
+
+    /**
+     * Stroke the eight standard crop marks around a trim rectangle.
+     *
+     * Each mark is a 0.25 pt black hairline of $length points starting
+     * $offset points outside the trim edge, drawn in its own save/restore.
+     *
+     * @param Rect  $trim   Trim box; (x, y) is the top-left corner
+     * @param float $offset Gap between the trim edge and each mark
+     * @param float $length Length of each mark in points
+     * @throws \Exception if the document has already ended
+     */
+    public function drawCropMarks(Rect $trim, float $offset, float $length): void {}
+
+    /**
+     * Stroke evenly spaced grid lines filling a rectangle.
+     *
+     * Lines start at the left and top edges and repeat every $spacingX /
+     * $spacingY points, drawn with one stroke in their own save/restore.
+     * A spacing that is not positive draws no lines in that direction, and
+     * nothing is drawn when neither direction has lines. A spacing so fine
+     * that a direction would need more than 10000 lines is clamped to 10000
+     * lines spread evenly across the rectangle.
+     *
+     * @param Rect  $rect     Grid area; (x, y) is the top-left corner
+     * @param float $spacingX Distance between vertical lines
+     * @param float $spacingY Distance between horizontal lines
+     * @param Color $color    Line color
+     * @param float $width    Line width in points
+     * @throws \Exception if the document has already ended
+     */
+    public function drawGrid(Rect $rect, float $spacingX, float $spacingY, Color $color, float $width): void {}
+
+    /**
+     * Fill a pie chart, one wedge per value sized by its share of the total.
+     *
+     * Wedges start at 12 o'clock and run clockwise, each filled in its
+     * matching color inside one save/restore. Non-positive values are skipped.
+     *
+     * @param float   $cx     Centre X
+     * @param float   $cy     Centre Y
+     * @param float   $radius Radius in points
+     * @param float[] $values Slice values
+     * @param Color[] $colors One color per value
+     * @throws \Exception if the counts differ or the document has already ended
+     */
+    public function drawPie(float $cx, float $cy, float $radius, array $values, array $colors): void {}
+
+    /**
+     * Draw a horizontal bar chart filling $rect, one row per value.
+     *
+     * Each row shows its label right-aligned, a bar in its color and the
+     * value after the bar. Bars are scaled so $max (default: the largest
+     * value) spans the space between the labels and the value column.
+     *
+     * @param Rect       $rect   Chart area (top-left corner, bottom-left origin)
+     * @param string[]   $labels Row labels
+     * @param float[]    $values Bar values
+     * @param Color[]    $colors One color per value
+     * @param float|null $max    Value of a full-width bar, or null for the largest value
+     * @throws \Exception if the counts differ or the document has already ended
+     */
+    public function drawBarChart(
+        Rect $rect,
+        array $labels,
+        array $values,
+        array $colors,
+        ?float $max = null
+    ): void {}
+
+    // -------------------------------------------------------
+    // Signature fields
+    // -------------------------------------------------------
+
+    /**
+     * Add an unsigned signature field to the current page.
+     *
+     * Writes a signature widget whose value is a placeholder signature
+     * dictionary (dummy /ByteRange, zeroed /Contents) for an external
+     * signer to fill in, and registers it in the AcroForm.
+     *
+     * @param string $name Field name (/T)
+     * @param Rect   $rect Widget area; (x, y) is the top-left corner
+     * @throws \Exception if the document has already ended or the write fails
+     */
+    public function addSignatureField(string $name, Rect $rect): void {}
+
     // -------------------------------------------------------
     // Image operations
     // -------------------------------------------------------
@@ -537,6 +1627,50 @@ class PdfDocument
      */
     public function loadImageBytes(string $data): int {}
 
+    /**
+     * Load an image from raw bytes and store it as JPEG (DCTDecode).
+     *
+     * PNG pixel data is re-encoded at the given quality, which is usually
+     * much smaller for photographs. JPEG input is stored unchanged.
+     *
+     * @param string $data    Raw image bytes (JPEG or PNG)
+     * @param int    $quality JPEG quality, 1-100
+     * @return int Image handle
+     * @throws \Exception if the data cannot be parsed
+     */
+    public function loadImageBytesAsJpeg(string $data, int $quality): int {}
+
+    /**
+     * Invert the image's samples when rendered (/Decode [1 0 ...]).
+     *
+     * Useful for scans stored inverted. Call before the first page using
+     * the image ends.
+     *
+     * @param int  $handle Image handle from loadImageFile/loadImageBytes
+     * @param bool $invert Whether to invert the samples
+     * @throws \Exception if the document has already ended
+     */
+    public function setImageInvert(int $handle, bool $invert): void {}
+
+    /**
+     * Ask viewers to smooth the image when it is scaled up (/Interpolate).
+     *
+     * Call before the first page using the image ends. Off by default.
+     *
+     * @param int  $handle      Image handle from loadImageFile/loadImageBytes
+     * @param bool $interpolate Whether to request interpolation
+     * @throws \Exception if the document has already ended
+     */
+    public function setImageInterpolation(int $handle, bool $interpolate): void {}
+
+    /**
+     * Use a loaded image as the current page's thumbnail (/Thumb).
+     *
+     * @param int $handle Image handle; at most 256x256 pixels
+     * @throws \Exception if the document has already ended or the image is too large
+     */
+    public function setPageThumbnail(int $handle): void {}
+
     /**
      * Place an image on the current page within a bounding rectangle.
      *
@@ -551,6 +1685,115 @@ class PdfDocument
         string $fit = 'fit'
     ): void {}
 
+    /**
+     * Place an image on the current page, positioned within the rect by an
+     * anchor instead of the fit mode's default (centered, or top-left for "none").
+     *
+     * @param int    $handle Image handle from loadImageFile/loadImageBytes
+     * @param Rect   $rect   Bounding rectangle for the image
+     * @param string $fit    Fit mode: "fit", "fill", "stretch", "none"
+     * @param string $anchor "top-left", "top", "top-right", "left", "center",
+     *                       "right", "bottom-left", "bottom" or "bottom-right"
+     * @throws \Exception if the document has already ended or the fit mode or anchor is invalid
+     */
+    public function placeImageAnchored(int $handle, Rect $rect, string $fit, string $anchor): void {}
+
+    /**
+     * Place an image $width points wide with its top-left corner at
+     * ($x, $topY), its height following the aspect ratio.
+     *
+     * $topY is a PDF y coordinate, as for placeText().
+     *
+     * @param int   $handle Image handle from loadImageFile/loadImageBytes
+     * @param float $x      Left edge in points
+     * @param float $topY   Top edge in points
+     * @param float $width  Image width in points
+     * @return float The y just below the image, for following content
+     * @throws \Exception if the document has already ended
+     */
+    public function placeImageFullWidth(int $handle, float $x, float $topY, float $width): float {}
+
+    /**
+     * Place an image with its lower-left corner at ($x, $y), stretched to
+     * exactly $width x $height points.
+     *
+     * @param int   $handle Image handle from loadImageFile/loadImageBytes
+     * @param float $x      Left edge in points
+     * @param float $y      Bottom edge in points
+     * @param float $width  Image width in points
+     * @param float $height Image height in points
+     * @throws \Exception if the document has already ended
+     */
+    public function placeImageExact(int $handle, float $x, float $y, float $width, float $height): void {}
+
+    /**
+     * Repeat an image across a rect in a grid of $tileW x $tileH cells,
+     * starting at the top-left corner and clipped to the rect.
+     *
+     * @param int    $handle Image handle from loadImageFile/loadImageBytes
+     * @param Rect   $rect   Area to cover
+     * @param float  $tileW  Cell width in points
+     * @param float  $tileH  Cell height in points
+     * @param string $fit    Fit mode within each cell: "fit", "fill", "stretch", "none"
+     * @throws \Exception if the document has already ended or the fit mode is invalid
+     */
+    public function tileImage(
+        int $handle,
+        Rect $rect,
+        float $tileW,
+        float $tileH,
+        string $fit = 'fit'
+    ): void {}
+
+    /**
+     * Place a color image using a separate grayscale image as its alpha.
+     *
+     * The color image's XObject references the mask image as its /SMask.
+     * The pairing is fixed once the color image has been written.
+     *
+     * @param int    $handle     Color image handle
+     * @param int    $maskHandle Grayscale mask image handle
+     * @param Rect   $rect       Bounding rectangle for the image
+     * @param string $fit        Fit mode: "fit" (default), "fill", "stretch", "none"
+     * @throws \Exception if the mask is not grayscale, the color image has its own
+     *                    alpha, or the document has already ended
+     */
+    public function placeImageWithMask(
+        int $handle,
+        int $maskHandle,
+        Rect $rect,
+        string $fit = 'fit'
+    ): void {}
+
+    /**
+     * Place an image behind all other content on the current page.
+     *
+     * The image is painted first when the page ends, so text and graphics
+     * drawn before this call still appear on top of it.
+     *
+     * @param int    $handle Image handle from loadImageFile/loadImageBytes
+     * @param Rect   $rect   Bounding rectangle for the image
+     * @param string $fit    Fit mode: "fit" (default), "fill", "stretch", "none"
+     * @throws \Exception if the document has already ended
+     */
+    public function placeImageBackground(
+        int $handle,
+        Rect $rect,
+        string $fit = 'fit'
+    ): void {}
+
+    /**
+     * Cover the whole current page with an image, behind all other content.
+     *
+     * The image is fitted to the page's MediaBox; use "fill" for a
+     * full-bleed cover that crops the overflow.
+     *
+     * @param int    $handle Image handle from loadImageFile/loadImageBytes
+     * @param string $fit    Fit mode: "fit" (default), "fill", "stretch", "none"
+     * @throws \Exception if the document has already ended
+     */
+    public function setPageBackgroundImage(int $handle, string $fit = 'fit'): void {}
+
     /**
      * Returns the number of completed pages.
      *
@@ -563,6 +1806,18 @@ class PdfDocument
      */
     public function pageCount(): int {}
 
+    /**
+     * Byte offset in the output where a completed page's data begins: the
+     * first object endPage() wrote for it (a font or image first used on
+     * the page, otherwise its content stream).
+     *
+     * @param int $pageNum 1-based page number
+     * @return int|null Offset, or null if the page is not completed or
+     *                  endPage() wrote nothing for it
+     * @throws \Exception if the document has already ended
+     */
+    public function pageByteOffset(int $pageNum): ?int {}
+
     /**
      * Open a completed page for editing (1-indexed).
      *
@@ -576,13 +1831,51 @@ class PdfDocument
      */
     public function openPage(int $pageNum): void {}
 
+    /**
+     * Move a completed page to a new position (both 1-indexed).
+     *
+     * The pages in between shift by one place. Later `openPage()` calls use
+     * the new numbering. If a page is currently open, it is closed first.
+     *
+     * @param int $from Current page number
+     * @param int $to   Page number the page should end up at
+     * @throws \Exception if either number is out of range or document already ended
+     */
+    public function movePage(int $from, int $to): void {}
+
     /**
      * End the current page.
      *
-     * @throws \Exception if the document has already ended
+     * Does nothing if no page is open, so calling it twice is safe.
+     *
+     * @throws \Exception if the document has already ended, the page is malformed, or a
+     *                    page that beginPage() auto-closed was malformed
      */
     public function endPage(): void {}
 
+    /**
+     * Flush buffered output to the underlying file.
+     *
+     * Pages are written at endPage(); flushing afterwards makes them visible
+     * to readers of the file before endDocument(). For in-memory documents
+     * this is a no-op.
+     *
+     * @throws \Exception if the document has already ended or the write fails
+     */
+    public function flush(): void {}
+
+    /**
+     * Concatenate PDFs produced by this extension into one document.
+     *
+     * Pages keep their order: all pages of the first input, then the
+     * second, and so on. Outlines and signature fields are not carried over.
+     *
+     * @param string[] $docs Binary PDF data, e.g. from endDocument()
+     * @return string Binary PDF data
+     * @throws \Exception if an input is not a string or cannot be parsed
+     */
+    public static function concat(array $docs): string {}
+
     /**
      * End the document.
      *
@@ -637,5 +1930,12 @@ class PdfReader
      * @return string
      */
     public function pdfVersion(): string {}
+
+    /**
+     * Whether the document is encrypted (the trailer has an /Encrypt entry).
+     *
+     * @return bool
+     */
+    public function isEncrypted(): bool {}
 }
 
@@ -1,13 +1,17 @@
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufWriter, Write};
 
 use ext_php_rs::prelude::*;
-use ext_php_rs::types::Zval;
+use ext_php_rs::types::{ZendHashTable, Zval};
 
+use pdf_core::format;
+use pdf_core::units;
 use pdf_core::{
-    BuiltinFont, Cell, CellOverflow, CellStyle, Color, FitResult, FontRef, ImageFit, ImageId,
-    PdfDocument, PdfReader, Rect, Row, Table, TableCursor, TextAlign, TextFlow, TextStyle,
-    TrueTypeFontId, WordBreak,
+    BuiltinFont, Cell, CellOverflow, CellStyle, Color, FitResult, FontRef, FormBuilder, FormId,
+    ImageAnchor, ImageFit, ImageId, LineCap, LineJoin, PdfDocument, PdfReader, Rect, Row, Table,
+    TableCursor, TextAlign, TextFlow, TextStyle, Transition, TrueTypeFontId, UnderlineStyle,
+    VerticalAlign, WordBreak,
 };
 
 // ----------------------------------------------------------
@@ -78,6 +82,9 @@ pub struct PhpTextStyle {
     /// -1 means builtin (use font_name), >= 0 means TrueType
     #[php(prop)]
     pub font_handle: i64,
+    /// Multiplier for inter-word space width in a TextFlow (default 1.0)
+    #[php(prop)]
+    pub space_width_scale: f64,
 }
 
 #[php_impl]
@@ -88,6 +95,7 @@ impl PhpTextStyle {
             font_name: font.unwrap_or_else(|| "Helvetica".to_string()),
             font_size: font_size.unwrap_or(12.0),
             font_handle: -1,
+            space_width_scale: 1.0,
         }
     }
 
@@ -97,6 +105,7 @@ impl PhpTextStyle {
             font_name: String::new(),
             font_size: font_size.unwrap_or(12.0),
             font_handle: handle,
+            space_width_scale: 1.0,
         }
     }
 }
@@ -127,10 +136,38 @@ impl PhpTextStyle {
         Ok(TextStyle {
             font: font_ref,
             font_size: self.font_size,
+            space_width_scale: self.space_width_scale,
         })
     }
 }
 
+// ----------------------------------------------------------
+// Format
+// ----------------------------------------------------------
+
+/// PHP class: Format
+///
+/// ```php
+/// Format::number(1234.56, 2, '.', ',');  // "1.234,56"
+/// Format::currency(9600, '$', 2);        // "$9,600.00"
+/// ```
+#[php_class]
+#[php(name = "Format")]
+pub struct PhpFormat;
+
+#[php_impl]
+impl PhpFormat {
+    /// Round to `decimals` places with the given thousands and decimal separators.
+    pub fn number(value: f64, decimals: usize, thousands_sep: &str, decimal_sep: &str) -> String {
+        format::format_number(value, decimals, thousands_sep, decimal_sep)
+    }
+
+    /// Prefix `symbol` to a `,`/`.` formatted amount; negatives read "-$5.00".
+    pub fn currency(value: f64, symbol: &str, decimals: usize) -> String {
+        format::format_currency(value, symbol, decimals)
+    }
+}
+
 // ----------------------------------------------------------
 // Rect
 // ----------------------------------------------------------
@@ -139,6 +176,7 @@ impl PhpTextStyle {
 ///
 /// ```php
 /// $rect = new Rect(72.0, 720.0, 468.0, 648.0);
+/// $a4 = Rect::mm(0, 0, 210, 297);
 /// ```
 #[php_class]
 #[php(name = "Rect")]
@@ -163,6 +201,26 @@ impl PhpRect {
             height,
         }
     }
+
+    /// Create a rect from millimeter values.
+    pub fn mm(x: f64, y: f64, width: f64, height: f64) -> Self {
+        PhpRect {
+            x: units::mm(x),
+            y: units::mm(y),
+            width: units::mm(width),
+            height: units::mm(height),
+        }
+    }
+
+    /// Create a rect from centimeter values.
+    pub fn cm(x: f64, y: f64, width: f64, height: f64) -> Self {
+        PhpRect {
+            x: units::cm(x),
+            y: units::cm(y),
+            width: units::cm(width),
+            height: units::cm(height),
+        }
+    }
 }
 
 impl PhpRect {
@@ -176,6 +234,111 @@ impl PhpRect {
     }
 }
 
+// ----------------------------------------------------------
+// Enum constants
+// ----------------------------------------------------------
+
+/// PHP class: CellOverflow
+///
+/// String constants accepted by `CellStyle::$overflow`. The values are the
+/// same strings the API has always taken, so legacy code keeps working.
+///
+/// ```php
+/// $style->overflow = CellOverflow::CLIP;
+/// ```
+#[php_class]
+#[php(name = "CellOverflow")]
+pub struct PhpCellOverflow;
+
+#[php_impl]
+impl PhpCellOverflow {
+    pub const WRAP: &'static str = "wrap";
+    pub const CLIP: &'static str = "clip";
+    pub const SHRINK: &'static str = "shrink";
+}
+
+/// PHP class: WordBreak
+///
+/// ```php
+/// $flow->wordBreak = WordBreak::HYPHENATE;
+/// ```
+#[php_class]
+#[php(name = "WordBreak")]
+pub struct PhpWordBreak;
+
+#[php_impl]
+impl PhpWordBreak {
+    pub const BREAK: &'static str = "break";
+    pub const HYPHENATE: &'static str = "hyphenate";
+    pub const NORMAL: &'static str = "normal";
+}
+
+/// PHP class: TextAlign
+///
+/// ```php
+/// $style->textAlign = TextAlign::RIGHT;
+/// ```
+#[php_class]
+#[php(name = "TextAlign")]
+pub struct PhpTextAlign;
+
+#[php_impl]
+impl PhpTextAlign {
+    pub const LEFT: &'static str = "left";
+    pub const CENTER: &'static str = "center";
+    pub const RIGHT: &'static str = "right";
+}
+
+/// PHP class: VerticalAlign
+///
+/// ```php
+/// $style->verticalAlign = VerticalAlign::MIDDLE;
+/// ```
+#[php_class]
+#[php(name = "VerticalAlign")]
+pub struct PhpVerticalAlign;
+
+#[php_impl]
+impl PhpVerticalAlign {
+    pub const TOP: &'static str = "top";
+    pub const MIDDLE: &'static str = "middle";
+    pub const BOTTOM: &'static str = "bottom";
+}
+
+/// PHP class: Underline
+///
+/// ```php
+/// $style->underline = Underline::DOUBLE;
+/// ```
+#[php_class]
+#[php(name = "Underline")]
+pub struct PhpUnderline;
+
+#[php_impl]
+impl PhpUnderline {
+    pub const NONE: &'static str = "none";
+    pub const SINGLE: &'static str = "single";
+    pub const DOUBLE: &'static str = "double";
+}
+
+/// PHP class: FitResult
+///
+/// Values returned by `fitTextflow()` and `fitRow()`.
+///
+/// ```php
+/// if ($doc->fitRow($table, $row, $cursor) === FitResult::BOX_FULL) { ... }
+/// ```
+#[php_class]
+#[php(name = "FitResult")]
+pub struct PhpFitResult;
+
+#[php_impl]
+impl PhpFitResult {
+    pub const STOP: &'static str = "stop";
+    pub const BOX_FULL: &'static str = "box_full";
+    pub const BOX_EMPTY: &'static str = "box_empty";
+}
+
 // ----------------------------------------------------------
 // TextFlow
 // ----------------------------------------------------------
@@ -195,6 +358,12 @@ pub struct PhpTextFlow {
     /// Word break mode: "break" (default), "hyphenate", or "normal"
     #[php(prop)]
     pub word_break: String,
+    /// Character appended at `hyphenate` breaks (default "-")
+    #[php(prop)]
+    pub hyphen_char: String,
+    /// Place the first baseline by the font's real ascent (default false)
+    #[php(prop)]
+    pub use_real_ascent: bool,
 }
 
 #[php_impl]
@@ -203,6 +372,8 @@ impl PhpTextFlow {
         PhpTextFlow {
             inner: TextFlow::new(),
             word_break: "break".to_string(),
+            hyphen_char: "-".to_string(),
+            use_real_ascent: false,
         }
     }
 
@@ -212,9 +383,18 @@ impl PhpTextFlow {
         Ok(())
     }
 
+    pub fn add_inline_image(&mut self, handle: i64, width: f64, height: f64, baseline_offset: f64) {
+        self.inner
+            .add_inline_image(ImageId(handle as usize), width, height, baseline_offset);
+    }
+
     pub fn is_finished(&self) -> bool {
         self.inner.is_finished()
     }
+
+    pub fn remaining_words(&self) -> i64 {
+        self.inner.remaining_words() as i64
+    }
 }
 
 // ----------------------------------------------------------
@@ -242,15 +422,27 @@ pub struct PhpCellStyle {
     pub font_size: f64,
     #[php(prop)]
     pub padding: f64,
-    /// Overflow mode: "wrap", "clip", or "shrink"
+    /// Overflow mode: "wrap", "clip", "shrink", or null to inherit the table default
     #[php(prop)]
-    pub overflow: String,
-    /// Word break mode: "break" (default), "hyphenate", or "normal"
+    pub overflow: Option<String>,
+    /// Word break mode: "break", "hyphenate", "normal", or null to inherit the table default
     #[php(prop)]
-    pub word_break: String,
+    pub word_break: Option<String>,
+    /// Character appended at `hyphenate` breaks, or null to inherit the table default ("-")
+    #[php(prop)]
+    pub hyphen_char: Option<String>,
     /// Text alignment: "left" (default), "center", or "right"
     #[php(prop)]
     pub text_align: String,
+    /// Vertical alignment: "top" (default), "middle", or "bottom"
+    #[php(prop)]
+    pub vertical_align: String,
+    /// Underline: "none" (default), "single", or "double"
+    #[php(prop)]
+    pub underline: String,
+    /// Counter-clockwise text rotation in degrees (default 0)
+    #[php(prop)]
+    pub rotation: f64,
     /// Background color (null = none)
     pub background_color: Option<Color>,
     /// Text color (null = default black)
@@ -265,9 +457,13 @@ impl PhpCellStyle {
             font_handle: -1,
             font_size: 10.0,
             padding: 4.0,
-            overflow: "wrap".to_string(),
-            word_break: "break".to_string(),
+            overflow: None,
+            word_break: None,
+            hyphen_char: None,
             text_align: "left".to_string(),
+            vertical_align: "top".to_string(),
+            underline: "none".to_string(),
+            rotation: 0.0,
             background_color: None,
             text_color: None,
         }
@@ -301,7 +497,11 @@ impl PhpCellStyle {
             padding: self.padding,
             overflow: self.overflow.clone(),
             word_break: self.word_break.clone(),
+            hyphen_char: self.hyphen_char.clone(),
             text_align: self.text_align.clone(),
+            vertical_align: self.vertical_align.clone(),
+            underline: self.underline.clone(),
+            rotation: self.rotation,
             background_color: self.background_color,
             text_color: self.text_color,
         }
@@ -318,23 +518,16 @@ impl PhpCellStyle {
             FontRef::Builtin(builtin)
         };
 
-        let overflow = match self.overflow.as_str() {
-            "clip" => CellOverflow::Clip,
-            "shrink" => CellOverflow::Shrink,
-            _ => CellOverflow::Wrap,
-        };
-
-        let word_break = match self.word_break.as_str() {
-            "hyphenate" => WordBreak::Hyphenate,
-            "normal" => WordBreak::Normal,
-            _ => WordBreak::BreakAll,
-        };
-
-        let text_align = match self.text_align.as_str() {
-            "center" => TextAlign::Center,
-            "right" => TextAlign::Right,
-            _ => TextAlign::Left,
-        };
+        let overflow = self.overflow.as_deref().map(parse_overflow);
+        let word_break = self.word_break.as_deref().map(parse_word_break);
+        let hyphen_char = self
+            .hyphen_char
+            .as_deref()
+            .map(parse_hyphen_char)
+            .transpose()?;
+        let text_align = parse_text_align(&self.text_align);
+        let vertical_align = parse_vertical_align(&self.vertical_align);
+        let underline = parse_underline(&self.underline);
 
         Ok(CellStyle {
             background_color: self.background_color,
@@ -344,7 +537,11 @@ impl PhpCellStyle {
             padding: self.padding,
             overflow,
             word_break,
+            hyphen_char,
             text_align,
+            vertical_align,
+            underline,
+            rotation: self.rotation,
         })
     }
 }
@@ -364,6 +561,7 @@ impl PhpCellStyle {
 pub struct PhpCell {
     text: String,
     style: Option<CellStyle>,
+    rowspan: usize,
 }
 
 #[php_impl]
@@ -372,6 +570,7 @@ impl PhpCell {
         PhpCell {
             text: text.to_string(),
             style: None,
+            rowspan: 1,
         }
     }
 
@@ -380,16 +579,24 @@ impl PhpCell {
         Ok(PhpCell {
             text: text.to_string(),
             style: Some(style.to_core()?),
+            rowspan: 1,
         })
     }
+
+    /// Number of rows this cell covers; the same column of the following rows is skipped.
+    pub fn set_rowspan(&mut self, rowspan: i64) {
+        self.rowspan = rowspan.max(1) as usize;
+    }
 }
 
 impl PhpCell {
     fn to_core(self) -> Cell {
-        match self.style {
+        let mut cell = match self.style {
             Some(s) => Cell::styled(self.text, s),
             None => Cell::new(self.text),
-        }
+        };
+        cell.rowspan = self.rowspan;
+        cell
     }
 }
 
@@ -403,6 +610,7 @@ impl PhpCell {
 /// $row = new Row([$cell1, $cell2]);
 /// $row->setBackgroundColor(new Color(0.9, 0.9, 0.9));
 /// $row->height = 20.0; // optional fixed height
+/// $row->header = true;   // repeated header: never striped
 /// ```
 #[php_class]
 #[php(name = "Row")]
@@ -411,6 +619,9 @@ pub struct PhpRow {
     background_color: Option<Color>,
     #[php(prop)]
     pub height: Option<f64>,
+    /// Header row: never striped and skipped by the stripe pattern
+    #[php(prop)]
+    pub header: bool,
 }
 
 #[php_impl]
@@ -422,6 +633,7 @@ impl PhpRow {
                 let cell = PhpCell {
                     text: c.text.clone(),
                     style: c.style.clone(),
+                    rowspan: c.rowspan,
                 };
                 cell.to_core()
             })
@@ -431,6 +643,7 @@ impl PhpRow {
             cells: core_cells,
             background_color: None,
             height: None,
+            header: false,
         }
     }
 
@@ -445,6 +658,7 @@ impl PhpRow {
         let mut row = Row::new(self.cells.clone());
         row.background_color = self.background_color;
         row.height = self.height;
+        row.header = self.header;
         row
     }
 }
@@ -498,6 +712,53 @@ impl PhpTable {
         self.inner.border_width = width;
     }
 
+    /// Set a border dash pattern; pass null (or an empty array) for solid borders.
+    pub fn set_border_dash(&mut self, pattern: Option<Vec<f64>>) {
+        self.inner.border_dash = pattern.filter(|p| !p.is_empty());
+    }
+
+    /// Width that flexible (negative) columns are resolved against; null uses the cursor rect.
+    pub fn set_total_width(&mut self, width: Option<f64>) {
+        self.inner.total_width = width;
+    }
+
+    /// Draw the first column at the right edge (right-to-left column order).
+    pub fn set_rtl(&mut self, rtl: bool) {
+        self.inner.rtl = rtl;
+    }
+
+    /// Background for every other row; pass null to disable striping.
+    pub fn set_stripe_color(&mut self, color: Option<&PhpColor>) {
+        self.inner.stripe_color = color.map(PhpColor::to_core);
+    }
+
+    /// Lower bound for auto-height rows; null removes it.
+    pub fn set_min_row_height(&mut self, height: Option<f64>) {
+        self.inner.min_row_height = height;
+    }
+
+    /// Upper bound for auto-height rows; null removes it.
+    pub fn set_max_row_height(&mut self, height: Option<f64>) {
+        self.inner.max_row_height = height;
+    }
+
+    /// Caption drawn above the first row on each page after the first; null removes it.
+    pub fn set_continued_caption(&mut self, caption: Option<&PhpCell>) {
+        self.inner.continued_caption = caption.map(|cell| {
+            PhpCell {
+                text: cell.text.clone(),
+                style: cell.style.clone(),
+                rowspan: 1,
+            }
+            .to_core()
+        });
+    }
+
+    /// Restart the stripe pattern at the top of each page.
+    pub fn set_reset_stripe_on_page(&mut self, reset: bool) {
+        self.inner.reset_stripe_on_page = reset;
+    }
+
     pub fn set_default_style(&mut self, style: &PhpCellStyle) -> Result<(), String> {
         self.inner.default_style = style.to_core()?;
         Ok(())
@@ -545,6 +806,136 @@ impl PhpTableCursor {
     pub fn current_y(&self) -> f64 {
         self.inner.current_y()
     }
+
+    pub fn remaining_height(&self) -> f64 {
+        self.inner.remaining_height()
+    }
+
+    pub fn row_index(&self) -> usize {
+        self.inner.row_index()
+    }
+}
+
+// ----------------------------------------------------------
+// FormBuilder
+// ----------------------------------------------------------
+
+/// One recorded FormBuilder call.
+enum FormOp {
+    StrokeColor(Color),
+    FillColor(Color),
+    LineWidth(f64),
+    Dash(Vec<f64>, f64),
+    MoveTo(f64, f64),
+    LineTo(f64, f64),
+    Rect(f64, f64, f64, f64),
+    ClosePath,
+    Stroke,
+    Fill,
+    FillStroke,
+    SaveState,
+    RestoreState,
+}
+
+/// PHP class: FormBuilder
+///
+/// Records vector drawing for a reusable form XObject. Coordinates are in
+/// the form's own space, with (0, 0) at its lower-left corner.
+///
+/// ```php
+/// $badge = new FormBuilder();
+/// $badge->setFillColor(new Color(0.2, 0.4, 0.8));
+/// $badge->rect(0, 0, 40, 20);
+/// $badge->fill();
+/// $handle = $doc->createFormXObject(40, 20, $badge);
+/// $doc->placeForm($handle, 72, 700, 1.0);
+/// ```
+#[php_class]
+#[php(name = "FormBuilder")]
+pub struct PhpFormBuilder {
+    ops: Vec<FormOp>,
+}
+
+#[php_impl]
+impl PhpFormBuilder {
+    pub fn __construct() -> Self {
+        PhpFormBuilder { ops: Vec::new() }
+    }
+
+    pub fn set_stroke_color(&mut self, color: &PhpColor) {
+        self.ops.push(FormOp::StrokeColor(color.to_core()));
+    }
+
+    pub fn set_fill_color(&mut self, color: &PhpColor) {
+        self.ops.push(FormOp::FillColor(color.to_core()));
+    }
+
+    pub fn set_line_width(&mut self, width: f64) {
+        self.ops.push(FormOp::LineWidth(width));
+    }
+
+    pub fn set_dash(&mut self, pattern: Vec<f64>, phase: Option<f64>) {
+        self.ops.push(FormOp::Dash(pattern, phase.unwrap_or(0.0)));
+    }
+
+    pub fn move_to(&mut self, x: f64, y: f64) {
+        self.ops.push(FormOp::MoveTo(x, y));
+    }
+
+    pub fn line_to(&mut self, x: f64, y: f64) {
+        self.ops.push(FormOp::LineTo(x, y));
+    }
+
+    pub fn rect(&mut self, x: f64, y: f64, width: f64, height: f64) {
+        self.ops.push(FormOp::Rect(x, y, width, height));
+    }
+
+    pub fn close_path(&mut self) {
+        self.ops.push(FormOp::ClosePath);
+    }
+
+    pub fn stroke(&mut self) {
+        self.ops.push(FormOp::Stroke);
+    }
+
+    pub fn fill(&mut self) {
+        self.ops.push(FormOp::Fill);
+    }
+
+    pub fn fill_stroke(&mut self) {
+        self.ops.push(FormOp::FillStroke);
+    }
+
+    pub fn save_state(&mut self) {
+        self.ops.push(FormOp::SaveState);
+    }
+
+    pub fn restore_state(&mut self) {
+        self.ops.push(FormOp::RestoreState);
+    }
+}
+
+impl PhpFormBuilder {
+    /// Replay the recorded calls on a core `FormBuilder`.
+    fn draw(&self, builder: &mut FormBuilder) {
+        for op in &self.ops {
+            match op {
+                FormOp::StrokeColor(color) => builder.set_stroke_color(*color),
+                FormOp::FillColor(color) => builder.set_fill_color(*color),
+                FormOp::LineWidth(width) => builder.set_line_width(*width),
+                FormOp::Dash(pattern, phase) => builder.set_dash(pattern, *phase),
+                FormOp::MoveTo(x, y) => builder.move_to(*x, *y),
+                FormOp::LineTo(x, y) => builder.line_to(*x, *y),
+                FormOp::Rect(x, y, width, height) => builder.rect(*x, *y, *width, *height),
+                FormOp::ClosePath => builder.close_path(),
+                FormOp::Stroke => builder.stroke(),
+                FormOp::Fill => builder.fill(),
+                FormOp::FillStroke => builder.fill_stroke(),
+                FormOp::SaveState => builder.save_state(),
+                FormOp::RestoreState => builder.restore_state(),
+            };
+        }
+    }
 }
 
 // ----------------------------------------------------------
@@ -627,148 +1018,834 @@ impl PhpPdfDocument {
         })
     }
 
-    pub fn set_info(&mut self, key: &str, value: &str) -> Result<(), String> {
-        with_doc!(self, set_info, doc => {
-            doc.set_info(key, value);
+    pub fn set_font_kerning(&mut self, handle: i64, enabled: bool) -> Result<(), String> {
+        with_doc!(self, set_font_kerning, doc => {
+            doc.set_font_kerning(FontRef::TrueType(TrueTypeFontId(handle as usize)), enabled);
             Ok(())
         })
     }
 
-    pub fn set_compression(&mut self, enabled: bool) -> Result<(), String> {
-        with_doc!(self, set_compression, doc => {
-            doc.set_compression(enabled);
+    /// Set the TrueType fonts tried, in order, for characters `primary`'s font lacks.
+    pub fn set_font_fallback(
+        &mut self,
+        primary: &PhpTextStyle,
+        fallback_handles: Vec<i64>,
+    ) -> Result<(), String> {
+        let primary = primary.to_core()?.font;
+        let fallbacks = fallback_handles
+            .iter()
+            .map(|&h| FontRef::TrueType(TrueTypeFontId(h as usize)))
+            .collect();
+        with_doc!(self, set_font_fallback, doc => {
+            doc.set_font_fallback(primary, fallbacks);
             Ok(())
         })
     }
 
-    pub fn begin_page(&mut self, width: f64, height: f64) -> Result<(), String> {
-        with_doc!(self, begin_page, doc => {
-            doc.begin_page(width, height);
+    /// Map a glyph ID of a TrueType font to `unicode` (one character) in its ToUnicode CMap.
+    pub fn set_glyph_unicode(
+        &mut self,
+        handle: i64,
+        glyph: i64,
+        unicode: String,
+    ) -> Result<(), String> {
+        let mut chars = unicode.chars();
+        let (Some(ch), None) = (chars.next(), chars.next()) else {
+            return Err(format!(
+                "set_glyph_unicode: expected exactly one character, got '{}'",
+                unicode
+            ));
+        };
+        let glyph = u16::try_from(glyph)
+            .map_err(|_| format!("set_glyph_unicode: glyph {} out of range", glyph))?;
+        with_doc!(self, set_glyph_unicode, doc => {
+            doc.set_glyph_unicode(&FontRef::TrueType(TrueTypeFontId(handle as usize)), glyph, ch);
             Ok(())
         })
     }
 
-    pub fn place_text(&mut self, text: &str, x: f64, y: f64) -> Result<(), String> {
-        with_doc!(self, place_text, doc => {
-            doc.place_text(text, x, y);
+    /// Embed builtin fonts that have a replacement instead of referencing them.
+    pub fn set_embed_standard_fonts(&mut self, enabled: bool) -> Result<(), String> {
+        with_doc!(self, set_embed_standard_fonts, doc => {
+            doc.set_embed_standard_fonts(enabled);
             Ok(())
         })
     }
 
-    pub fn place_text_styled(
-        &mut self,
-        text: &str,
-        x: f64,
-        y: f64,
-        style: &PhpTextStyle,
-    ) -> Result<(), String> {
-        let core_style = style.to_core()?;
-        with_doc!(self, place_text_styled, doc => {
-            doc.place_text_styled(text, x, y, &core_style);
+    pub fn set_subset_standard_fonts(&mut self, enabled: bool) -> Result<(), String> {
+        with_doc!(self, set_subset_standard_fonts, doc => {
+            doc.set_subset_standard_fonts(enabled);
             Ok(())
         })
     }
 
-    pub fn fit_textflow(
+    /// Register the .ttf file embedded for a builtin font name.
+    pub fn load_standard_font_replacement(
         &mut self,
-        flow: &mut PhpTextFlow,
-        rect: &PhpRect,
-    ) -> Result<String, String> {
-        let core_rect = rect.to_core();
-        flow.inner.word_break = match flow.word_break.as_str() {
-            "hyphenate" => WordBreak::Hyphenate,
-            "normal" => WordBreak::Normal,
-            _ => WordBreak::BreakAll,
-        };
-        with_doc!(self, fit_textflow, doc => {
-            let result = doc
-                .fit_textflow(
-                    &mut flow.inner,
-                    &core_rect,
-                )
-                .map_err(|e| {
-                    format!(
-                        "fit_textflow failed: {}",
-                        e,
-                    )
-                })?;
-            Ok(match result {
-                FitResult::Stop => {
-                    "stop".to_string()
-                }
-                FitResult::BoxFull => {
-                    "box_full".to_string()
-                }
-                FitResult::BoxEmpty => {
-                    "box_empty".to_string()
-                }
-            })
+        font_name: &str,
+        path: &str,
+    ) -> Result<(), String> {
+        let font = BuiltinFont::from_name(font_name)
+            .ok_or_else(|| format!("Unknown font: '{}'", font_name))?;
+        let data = std::fs::read(path)
+            .map_err(|e| format!("load_standard_font_replacement failed: {}", e))?;
+        with_doc!(self, load_standard_font_replacement, doc => {
+            doc.load_standard_font_replacement(font, data)
+                .map_err(|e| format!("load_standard_font_replacement failed: {}", e))
         })
     }
 
-    /// Place a single row into the table layout on the current page.
-    ///
-    /// Returns "stop" (placed), "box_full" (page full, turn page and retry),
-    /// or "box_empty" (rect too small for this row).
-    pub fn fit_row(
-        &mut self,
-        table: &PhpTable,
-        row: &PhpRow,
-        cursor: &mut PhpTableCursor,
-    ) -> Result<String, String> {
-        let core_row = row.to_core();
-        with_doc!(self, fit_row, doc => {
-            let result = doc
-                .fit_row(&table.inner, &core_row, &mut cursor.inner)
-                .map_err(|e| format!("fit_row failed: {}", e))?;
-            Ok(match result {
-                FitResult::Stop => "stop".to_string(),
-                FitResult::BoxFull => "box_full".to_string(),
-                FitResult::BoxEmpty => "box_empty".to_string(),
-            })
+    pub fn set_info(&mut self, key: &str, value: &str) -> Result<(), String> {
+        with_doc!(self, set_info, doc => {
+            doc.set_info(key, value);
+            Ok(())
         })
     }
 
-    // -------------------------------------------------------
-    // Image operations
-    // -------------------------------------------------------
+    /// Set the /Title info entry, replacing any earlier title.
+    pub fn set_title(&mut self, title: &str) -> Result<(), String> {
+        with_doc!(self, set_title, doc => {
+            doc.set_title(title);
+            Ok(())
+        })
+    }
 
-    /// Load an image from a file path. Returns an integer handle.
-    pub fn load_image_file(&mut self, path: &str) -> Result<i64, String> {
-        with_doc!(self, load_image_file, doc => {
-            let id = doc.load_image_file(path)
-                .map_err(|e| format!("load_image_file failed: {}", e))?;
-            Ok(id.0 as i64)
+    /// Set the /Author info entry, replacing any earlier author.
+    pub fn set_author(&mut self, author: &str) -> Result<(), String> {
+        with_doc!(self, set_author, doc => {
+            doc.set_author(author);
+            Ok(())
         })
     }
 
-    /// Load an image from raw bytes. Returns an integer handle.
-    pub fn load_image_bytes(&mut self, data: &mut Zval) -> Result<i64, String> {
+    /// Set the /Subject info entry, replacing any earlier subject.
+    pub fn set_subject(&mut self, subject: &str) -> Result<(), String> {
+        with_doc!(self, set_subject, doc => {
+            doc.set_subject(subject);
+            Ok(())
+        })
+    }
+
+    /// Set the /Keywords info entry (comma-separated), replacing any earlier keywords.
+    pub fn set_keywords(&mut self, keywords: &str) -> Result<(), String> {
+        with_doc!(self, set_keywords, doc => {
+            doc.set_keywords(keywords);
+            Ok(())
+        })
+    }
+
+    /// Set the /Creator info entry, replacing any earlier creator.
+    pub fn set_creator(&mut self, creator: &str) -> Result<(), String> {
+        with_doc!(self, set_creator, doc => {
+            doc.set_creator(creator);
+            Ok(())
+        })
+    }
+
+    /// Fill in missing CreationDate/ModDate at endDocument() (default: on).
+    pub fn set_auto_dates(&mut self, enabled: bool) -> Result<(), String> {
+        with_doc!(self, set_auto_dates, doc => {
+            doc.set_auto_dates(enabled);
+            Ok(())
+        })
+    }
+
+    /// Decimal places kept for numbers in content streams (default 4).
+    pub fn set_coordinate_precision(&mut self, digits: i64) -> Result<(), String> {
+        if !(0..=u8::MAX as i64).contains(&digits) {
+            return Err(format!(
+                "set_coordinate_precision: digits must be 0..=255, got {}",
+                digits
+            ));
+        }
+        with_doc!(self, set_coordinate_precision, doc => {
+            doc.set_coordinate_precision(digits as u8);
+            Ok(())
+        })
+    }
+
+    /// Set the default /Producer, or pass null to omit it.
+    pub fn set_producer(&mut self, producer: Option<String>) -> Result<(), String> {
+        with_doc!(self, set_producer, doc => {
+            doc.set_producer(producer.as_deref());
+            Ok(())
+        })
+    }
+
+    pub fn set_compression(&mut self, enabled: bool) -> Result<(), String> {
+        with_doc!(self, set_compression, doc => {
+            doc.set_compression(enabled);
+            Ok(())
+        })
+    }
+
+    pub fn set_compress_content(&mut self, enabled: bool) -> Result<(), String> {
+        with_doc!(self, set_compress_content, doc => {
+            doc.set_compress_content(enabled);
+            Ok(())
+        })
+    }
+
+    pub fn set_compress_fonts(&mut self, enabled: bool) -> Result<(), String> {
+        with_doc!(self, set_compress_fonts, doc => {
+            doc.set_compress_fonts(enabled);
+            Ok(())
+        })
+    }
+
+    pub fn set_compress_images(&mut self, enabled: bool) -> Result<(), String> {
+        with_doc!(self, set_compress_images, doc => {
+            doc.set_compress_images(enabled);
+            Ok(())
+        })
+    }
+
+    /// Separate content-stream operators with spaces instead of newlines.
+    pub fn set_compact_content(&mut self, enabled: bool) -> Result<(), String> {
+        with_doc!(self, set_compact_content, doc => {
+            doc.set_compact_content(enabled);
+            Ok(())
+        })
+    }
+
+    /// Write the binary comment line after `%PDF-1.7` (default true).
+    pub fn set_binary_marker(&mut self, enabled: bool) -> Result<(), String> {
+        with_doc!(self, set_binary_marker, doc => {
+            doc.set_binary_marker(enabled);
+            Ok(())
+        })
+    }
+
+    pub fn set_page_uncompressed(&mut self, page_num: usize) -> Result<(), String> {
+        with_doc!(self, set_page_uncompressed, doc => {
+            doc.set_page_uncompressed(page_num);
+            Ok(())
+        })
+    }
+
+    pub fn set_deterministic(&mut self, enabled: bool) -> Result<(), String> {
+        with_doc!(self, set_deterministic, doc => {
+            doc.set_deterministic(enabled);
+            Ok(())
+        })
+    }
+
+    pub fn set_print_on_open(&mut self, enabled: bool) -> Result<(), String> {
+        with_doc!(self, set_print_on_open, doc => {
+            doc.set_print_on_open(enabled);
+            Ok(())
+        })
+    }
+
+    pub fn set_strict(&mut self, enabled: bool) -> Result<(), String> {
+        with_doc!(self, set_strict, doc => {
+            doc.set_strict(enabled);
+            Ok(())
+        })
+    }
+
+    /// Cross-check pages against written objects; returns warning messages.
+    pub fn validate(&mut self) -> Result<Vec<String>, String> {
+        with_doc!(self, validate, doc => {
+            Ok(doc.validate().iter().map(|w| w.to_string()).collect())
+        })
+    }
+
+    /// Output size statistics as an associative array.
+    pub fn stats(&mut self) -> Result<HashMap<String, i64>, String> {
+        with_doc!(self, stats, doc => {
+            let stats = doc.stats();
+            Ok(HashMap::from([
+                ("page_count".to_string(), stats.page_count as i64),
+                ("content_bytes".to_string(), stats.content_bytes as i64),
+                ("font_bytes".to_string(), stats.font_bytes as i64),
+                ("image_bytes".to_string(), stats.image_bytes as i64),
+                ("object_count".to_string(), stats.object_count as i64),
+            ]))
+        })
+    }
+
+    pub fn set_merge_overlays(&mut self, enabled: bool) -> Result<(), String> {
+        with_doc!(self, set_merge_overlays, doc => {
+            doc.set_merge_overlays(enabled);
+            Ok(())
+        })
+    }
+
+    pub fn set_default_line_width(&mut self, width: f64) -> Result<(), String> {
+        with_doc!(self, set_default_line_width, doc => {
+            doc.set_default_line_width(width);
+            Ok(())
+        })
+    }
+
+    pub fn set_default_stroke_color(&mut self, color: &PhpColor) -> Result<(), String> {
+        with_doc!(self, set_default_stroke_color, doc => {
+            doc.set_default_stroke_color(color.to_core());
+            Ok(())
+        })
+    }
+
+    pub fn set_default_fill_color(&mut self, color: &PhpColor) -> Result<(), String> {
+        with_doc!(self, set_default_fill_color, doc => {
+            doc.set_default_fill_color(color.to_core());
+            Ok(())
+        })
+    }
+
+    /// Set a line cap ("butt", "round", "square") for every subsequent page.
+    pub fn set_default_line_cap(&mut self, cap: String) -> Result<(), String> {
+        let cap = parse_line_cap(&cap)?;
+        with_doc!(self, set_default_line_cap, doc => {
+            let mut defaults = doc.graphics_defaults().clone();
+            defaults.line_cap = Some(cap);
+            doc.set_graphics_defaults(defaults);
+            Ok(())
+        })
+    }
+
+    /// Set a line join ("miter", "round", "bevel") for every subsequent page.
+    pub fn set_default_line_join(&mut self, join: String) -> Result<(), String> {
+        let join = parse_line_join(&join)?;
+        with_doc!(self, set_default_line_join, doc => {
+            let mut defaults = doc.graphics_defaults().clone();
+            defaults.line_join = Some(join);
+            doc.set_graphics_defaults(defaults);
+            Ok(())
+        })
+    }
+
+    /// Set a dash pattern for every subsequent page.
+    pub fn set_default_dash(
+        &mut self,
+        pattern: Vec<f64>,
+        phase: Option<f64>,
+    ) -> Result<(), String> {
+        with_doc!(self, set_default_dash, doc => {
+            let mut defaults = doc.graphics_defaults().clone();
+            defaults.dash = Some((pattern, phase.unwrap_or(0.0)));
+            doc.set_graphics_defaults(defaults);
+            Ok(())
+        })
+    }
+
+    /// Set the page size used by beginPageDefault() (default: US Letter).
+    pub fn set_default_page_size(&mut self, width: f64, height: f64) -> Result<(), String> {
+        with_doc!(self, set_default_page_size, doc => {
+            doc.set_default_page_size(width, height);
+            Ok(())
+        })
+    }
+
+    pub fn begin_page(&mut self, width: f64, height: f64) -> Result<(), String> {
+        with_doc!(self, begin_page, doc => {
+            doc.begin_page(width, height);
+            Ok(())
+        })
+    }
+
+    /// Begin a page using the size from setDefaultPageSize().
+    pub fn begin_page_default(&mut self) -> Result<(), String> {
+        with_doc!(self, begin_page_default, doc => {
+            doc.begin_page_default();
+            Ok(())
+        })
+    }
+
+    /// Begin a page whose MediaBox is [llx lly urx ury].
+    pub fn begin_page_box(&mut self, llx: f64, lly: f64, urx: f64, ury: f64) -> Result<(), String> {
+        with_doc!(self, begin_page_box, doc => {
+            doc.begin_page_box(llx, lly, urx, ury);
+            Ok(())
+        })
+    }
+
+    /// Begin a page displayed rotated clockwise by `degrees` (multiple of 90).
+    pub fn begin_page_rotated(
+        &mut self,
+        width: f64,
+        height: f64,
+        degrees: i64,
+    ) -> Result<(), String> {
+        if degrees % 90 != 0 {
+            return Err(format!(
+                "begin_page_rotated: degrees must be a multiple of 90, got {}",
+                degrees
+            ));
+        }
+        with_doc!(self, begin_page_rotated, doc => {
+            doc.begin_page_rotated(width, height, (degrees % 360) as i32);
+            Ok(())
+        })
+    }
+
+    /// Set the /Rotate inherited by every page from the Pages node.
+    pub fn set_document_rotation(&mut self, degrees: i64) -> Result<(), String> {
+        if degrees % 90 != 0 {
+            return Err(format!(
+                "set_document_rotation: degrees must be a multiple of 90, got {}",
+                degrees
+            ));
+        }
+        with_doc!(self, set_document_rotation, doc => {
+            doc.set_document_rotation((degrees % 360) as i32);
+            Ok(())
+        })
+    }
+
+    /// Auto-advance the current page after `seconds` in presentation mode.
+    pub fn set_page_duration(&mut self, seconds: f64) -> Result<(), String> {
+        if !seconds.is_finite() || seconds < 0.0 {
+            return Err(format!(
+                "set_page_duration: seconds must be a non-negative number, got {}",
+                seconds
+            ));
+        }
+        with_doc!(self, set_page_duration, doc => {
+            doc.set_page_duration(seconds);
+            Ok(())
+        })
+    }
+
+    /// Set the transition effect played when the current page appears.
+    /// transition: "split", "blinds", "box", "wipe", "dissolve", "glitter",
+    /// "fly", "push", "cover", "uncover", "fade"
+    pub fn set_page_transition(&mut self, transition: &str) -> Result<(), String> {
+        let transition = parse_transition(transition)?;
+        with_doc!(self, set_page_transition, doc => {
+            doc.set_page_transition(transition);
+            Ok(())
+        })
+    }
+
+    /// Begin a page whose content is turned 180° (duplex back pages).
+    pub fn begin_page_flipped(&mut self, width: f64, height: f64) -> Result<(), String> {
+        with_doc!(self, begin_page_flipped, doc => {
+            doc.begin_page_flipped(width, height);
+            Ok(())
+        })
+    }
+
+    /// Begin a page sized in millimeters.
+    pub fn begin_page_mm(&mut self, width: f64, height: f64) -> Result<(), String> {
+        with_doc!(self, begin_page_mm, doc => {
+            doc.begin_page(units::mm(width), units::mm(height));
+            Ok(())
+        })
+    }
+
+    pub fn place_text(&mut self, text: &str, x: f64, y: f64) -> Result<(), String> {
+        with_doc!(self, place_text, doc => {
+            doc.place_text(text, x, y);
+            Ok(())
+        })
+    }
+
+    pub fn place_text_styled(
+        &mut self,
+        text: &str,
+        x: f64,
+        y: f64,
+        style: &PhpTextStyle,
+    ) -> Result<(), String> {
+        let core_style = style.to_core()?;
+        with_doc!(self, place_text_styled, doc => {
+            doc.place_text_styled(text, x, y, &core_style);
+            Ok(())
+        })
+    }
+
+    /// Set text glyph by glyph along a circle, clockwise from start_angle (degrees).
+    pub fn place_text_on_arc(
+        &mut self,
+        text: &str,
+        cx: f64,
+        cy: f64,
+        radius: f64,
+        start_angle: f64,
+        style: &PhpTextStyle,
+    ) -> Result<(), String> {
+        let core_style = style.to_core()?;
+        with_doc!(self, place_text_on_arc, doc => {
+            doc.place_text_on_arc(text, cx, cy, radius, start_angle, &core_style);
+            Ok(())
+        })
+    }
+
+    /// Fill the gap from `from_x` to `to_x` on baseline `y` with a dot leader.
+    pub fn draw_leader(
+        &mut self,
+        from_x: f64,
+        to_x: f64,
+        y: f64,
+        style: &PhpTextStyle,
+    ) -> Result<(), String> {
+        let core_style = style.to_core()?;
+        with_doc!(self, draw_leader, doc => {
+            doc.draw_leader(from_x, to_x, y, &core_style);
+            Ok(())
+        })
+    }
+
+    /// Place text aligned relative to x.
+    /// align: "left" (default), "center", "right"
+    pub fn place_text_aligned(
+        &mut self,
+        text: &str,
+        x: f64,
+        y: f64,
+        style: &PhpTextStyle,
+        align: Option<String>,
+    ) -> Result<(), String> {
+        let core_style = style.to_core()?;
+        let text_align = parse_text_align(align.as_deref().unwrap_or(PhpTextAlign::LEFT));
+        with_doc!(self, place_text_aligned, doc => {
+            doc.place_text_aligned(text, x, y, &core_style, text_align);
+            Ok(())
+        })
+    }
+
+    /// Place text wrapped to the rect and aligned within it.
+    /// halign: "left" (default), "center", "right"
+    /// valign: "top" (default), "middle", "bottom"
+    pub fn place_text_block(
+        &mut self,
+        text: &str,
+        rect: &PhpRect,
+        style: &PhpTextStyle,
+        halign: Option<String>,
+        valign: Option<String>,
+    ) -> Result<usize, String> {
+        let core_style = style.to_core()?;
+        let core_rect = rect.to_core();
+        let text_align = parse_text_align(halign.as_deref().unwrap_or(PhpTextAlign::LEFT));
+        let vertical_align =
+            parse_vertical_align(valign.as_deref().unwrap_or(PhpVerticalAlign::TOP));
+        with_doc!(self, place_text_block, doc => {
+            Ok(doc.place_text_block(text, &core_rect, &core_style, text_align, vertical_align))
+        })
+    }
+
+    /// Width of text in points when set in the given style.
+    pub fn measure_text(&mut self, text: &str, style: &PhpTextStyle) -> Result<f64, String> {
+        let core_style = style.to_core()?;
+        with_doc!(self, measure_text, doc => {
+            Ok(doc.measure_text(text, &core_style))
+        })
+    }
+
+    /// Longest prefix of text plus an ellipsis that fits within `max_width`.
+    pub fn fit_text_ellipsized(
+        &mut self,
+        text: &str,
+        style: &PhpTextStyle,
+        max_width: f64,
+    ) -> Result<String, String> {
+        let core_style = style.to_core()?;
+        with_doc!(self, fit_text_ellipsized, doc => {
+            Ok(doc.fit_text_ellipsized(text, &core_style, max_width))
+        })
+    }
+
+    /// Height and line count of the rest of `flow` wrapped to `width`, as
+    /// `["height" => ..., "lines" => ...]`; the flow's cursor does not move.
+    pub fn measure_flow(
+        &mut self,
+        flow: &mut PhpTextFlow,
+        width: f64,
+    ) -> Result<HashMap<String, f64>, String> {
+        flow.inner.word_break = parse_word_break(&flow.word_break);
+        flow.inner.hyphen_char = parse_hyphen_char(&flow.hyphen_char)?;
+        with_doc!(self, measure_flow, doc => {
+            let (height, lines) = doc.measure_flow(&flow.inner, width);
+            Ok(HashMap::from([
+                ("height".to_string(), height),
+                ("lines".to_string(), lines as f64),
+            ]))
+        })
+    }
+
+    pub fn fit_textflow(
+        &mut self,
+        flow: &mut PhpTextFlow,
+        rect: &PhpRect,
+    ) -> Result<String, String> {
+        let core_rect = rect.to_core();
+        flow.inner.word_break = parse_word_break(&flow.word_break);
+        flow.inner.hyphen_char = parse_hyphen_char(&flow.hyphen_char)?;
+        flow.inner.use_real_ascent = flow.use_real_ascent;
+        with_doc!(self, fit_textflow, doc => {
+            let result = doc
+                .fit_textflow(
+                    &mut flow.inner,
+                    &core_rect,
+                )
+                .map_err(|e| {
+                    format!(
+                        "fit_textflow failed: {}",
+                        e,
+                    )
+                })?;
+            Ok(fit_result_str(result).to_string())
+        })
+    }
+
+    /// Place a single row into the table layout on the current page.
+    ///
+    /// Returns "stop" (placed), "box_full" (page full, turn page and retry),
+    /// or "box_empty" (rect too small for this row).
+    pub fn fit_row(
+        &mut self,
+        table: &PhpTable,
+        row: &PhpRow,
+        cursor: &mut PhpTableCursor,
+    ) -> Result<String, String> {
+        let core_row = row.to_core();
+        with_doc!(self, fit_row, doc => {
+            let result = doc
+                .fit_row(&table.inner, &core_row, &mut cursor.inner)
+                .map_err(|e| format!("fit_row failed: {}", e))?;
+            Ok(fit_result_str(result).to_string())
+        })
+    }
+
+    /// Place all rows in `rect`, scaling the table down if needed. Returns the scale.
+    pub fn fit_table_to_rect(
+        &mut self,
+        table: &PhpTable,
+        rows: Vec<&PhpRow>,
+        rect: &PhpRect,
+    ) -> Result<f64, String> {
+        let core_rows: Vec<Row> = rows.iter().map(|row| row.to_core()).collect();
+        let core_rect = rect.to_core();
+        with_doc!(self, fit_table_to_rect, doc => {
+            doc.fit_table_to_rect(&table.inner, &core_rows, &core_rect)
+                .map_err(|e| format!("fit_table_to_rect failed: {}", e))
+        })
+    }
+
+    /// Column widths fitting the header and the first `sample_rows` rows.
+    pub fn auto_columns(
+        &mut self,
+        header: &PhpRow,
+        rows: Vec<&PhpRow>,
+        sample_rows: i64,
+    ) -> Result<Vec<f64>, String> {
+        let core_header = header.to_core();
+        let sample = sample_rows.max(0) as usize;
+        let core_rows = rows.iter().take(sample).map(|row| row.to_core());
+        with_doc!(self, auto_columns, doc => {
+            Ok(doc.auto_columns(&core_header, core_rows, sample))
+        })
+    }
+
+    // -------------------------------------------------------
+    // Image operations
+    // -------------------------------------------------------
+
+    /// Load an image from a file path. Returns an integer handle.
+    pub fn load_image_file(&mut self, path: &str) -> Result<i64, String> {
+        with_doc!(self, load_image_file, doc => {
+            let id = doc.load_image_file(path)
+                .map_err(|e| format!("load_image_file failed: {}", e))?;
+            Ok(id.0 as i64)
+        })
+    }
+
+    /// Load an image from raw bytes. Returns an integer handle.
+    pub fn load_image_bytes(&mut self, data: &mut Zval) -> Result<i64, String> {
         let bytes = data
             .binary()
             .ok_or_else(|| "Expected binary string".to_string())?
             .to_vec();
 
-        with_doc!(self, load_image_bytes, doc => {
-            let id = doc.load_image_bytes(bytes)
-                .map_err(|e| format!("load_image_bytes failed: {}", e))?;
-            Ok(id.0 as i64)
+        with_doc!(self, load_image_bytes, doc => {
+            let id = doc.load_image_bytes(bytes)
+                .map_err(|e| format!("load_image_bytes failed: {}", e))?;
+            Ok(id.0 as i64)
+        })
+    }
+
+    /// Load an image from raw bytes, re-encoding PNG pixel data as JPEG.
+    pub fn load_image_bytes_as_jpeg(
+        &mut self,
+        data: &mut Zval,
+        quality: i64,
+    ) -> Result<i64, String> {
+        let bytes = data
+            .binary()
+            .ok_or_else(|| "Expected binary string".to_string())?
+            .to_vec();
+        let quality = quality.clamp(1, 100) as u8;
+
+        with_doc!(self, load_image_bytes_as_jpeg, doc => {
+            let id = doc.load_image_bytes_as_jpeg(bytes, quality)
+                .map_err(|e| format!("load_image_bytes_as_jpeg failed: {}", e))?;
+            Ok(id.0 as i64)
+        })
+    }
+
+    /// Invert the image's samples with a /Decode array.
+    pub fn set_image_invert(&mut self, handle: i64, invert: bool) -> Result<(), String> {
+        let image_id = ImageId(handle as usize);
+        with_doc!(self, set_image_invert, doc => {
+            doc.set_image_invert(&image_id, invert);
+            Ok(())
+        })
+    }
+
+    /// Ask viewers to smooth the image when it is scaled up.
+    pub fn set_image_interpolation(
+        &mut self,
+        handle: i64,
+        interpolate: bool,
+    ) -> Result<(), String> {
+        let image_id = ImageId(handle as usize);
+        with_doc!(self, set_image_interpolation, doc => {
+            doc.set_image_interpolation(&image_id, interpolate);
+            Ok(())
+        })
+    }
+
+    /// Use a loaded image as the current page's thumbnail.
+    pub fn set_page_thumbnail(&mut self, handle: i64) -> Result<(), String> {
+        let image_id = ImageId(handle as usize);
+        with_doc!(self, set_page_thumbnail, doc => {
+            doc.set_page_thumbnail(&image_id)
+                .map_err(|e| format!("set_page_thumbnail failed: {}", e))?;
+            Ok(())
+        })
+    }
+
+    /// Place an image on the current page.
+    /// fit: "fit" (default), "fill", "stretch", "none"
+    pub fn place_image(
+        &mut self,
+        handle: i64,
+        rect: &PhpRect,
+        fit: Option<String>,
+    ) -> Result<(), String> {
+        let image_fit = parse_image_fit(&fit.unwrap_or_else(|| "fit".to_string()))?;
+        let core_rect = rect.to_core();
+        let image_id = ImageId(handle as usize);
+        with_doc!(self, place_image, doc => {
+            doc.place_image(&image_id, &core_rect, image_fit);
+            Ok(())
+        })
+    }
+
+    /// Place a color image using a separate grayscale image as its soft mask.
+    /// Place an image within `rect`, positioned by `anchor` ("center", "top-left", ...).
+    pub fn place_image_anchored(
+        &mut self,
+        handle: i64,
+        rect: &PhpRect,
+        fit: String,
+        anchor: String,
+    ) -> Result<(), String> {
+        let image_fit = parse_image_fit(&fit)?;
+        let image_anchor = parse_image_anchor(&anchor)?;
+        let core_rect = rect.to_core();
+        let image_id = ImageId(handle as usize);
+        with_doc!(self, place_image_anchored, doc => {
+            doc.place_image_anchored(&image_id, &core_rect, image_fit, image_anchor);
+            Ok(())
+        })
+    }
+
+    /// Place an image with its lower-left corner at (x, y), exactly width x height.
+    pub fn place_image_exact(
+        &mut self,
+        handle: i64,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+    ) -> Result<(), String> {
+        let image_id = ImageId(handle as usize);
+        with_doc!(self, place_image_exact, doc => {
+            doc.place_image_exact(&image_id, x, y, width, height);
+            Ok(())
         })
     }
 
-    /// Place an image on the current page.
-    /// fit: "fit" (default), "fill", "stretch", "none"
-    pub fn place_image(
+    /// Place an image `width` points wide at (x, top_y); returns the y below it.
+    pub fn place_image_full_width(
+        &mut self,
+        handle: i64,
+        x: f64,
+        top_y: f64,
+        width: f64,
+    ) -> Result<f64, String> {
+        let image_id = ImageId(handle as usize);
+        with_doc!(self, place_image_full_width, doc => {
+            Ok(doc.place_image_full_width(&image_id, x, top_y, width))
+        })
+    }
+
+    /// Repeat an image across `rect` in a grid of `tile_w` x `tile_h` cells.
+    pub fn tile_image(
         &mut self,
         handle: i64,
         rect: &PhpRect,
+        tile_w: f64,
+        tile_h: f64,
         fit: Option<String>,
     ) -> Result<(), String> {
         let image_fit = parse_image_fit(&fit.unwrap_or_else(|| "fit".to_string()))?;
         let core_rect = rect.to_core();
         let image_id = ImageId(handle as usize);
-        with_doc!(self, place_image, doc => {
-            doc.place_image(&image_id, &core_rect, image_fit);
+        with_doc!(self, tile_image, doc => {
+            doc.tile_image(&image_id, &core_rect, tile_w, tile_h, image_fit);
+            Ok(())
+        })
+    }
+
+    pub fn place_image_with_mask(
+        &mut self,
+        handle: i64,
+        mask_handle: i64,
+        rect: &PhpRect,
+        fit: Option<String>,
+    ) -> Result<(), String> {
+        let image_fit = parse_image_fit(&fit.unwrap_or_else(|| "fit".to_string()))?;
+        let core_rect = rect.to_core();
+        let color_id = ImageId(handle as usize);
+        let mask_id = ImageId(mask_handle as usize);
+        with_doc!(self, place_image_with_mask, doc => {
+            doc.place_image_with_mask(&color_id, &mask_id, &core_rect, image_fit)
+                .map(|_| ())
+                .map_err(|e| format!("place_image_with_mask failed: {}", e))
+        })
+    }
+
+    /// Place an image behind all other content on the current page.
+    pub fn place_image_background(
+        &mut self,
+        handle: i64,
+        rect: &PhpRect,
+        fit: Option<String>,
+    ) -> Result<(), String> {
+        let image_fit = parse_image_fit(&fit.unwrap_or_else(|| "fit".to_string()))?;
+        let core_rect = rect.to_core();
+        let image_id = ImageId(handle as usize);
+        with_doc!(self, place_image_background, doc => {
+            doc.place_image_background(&image_id, &core_rect, image_fit);
+            Ok(())
+        })
+    }
+
+    /// Cover the whole current page with an image, behind all other content.
+    /// fit: "fit" (default), "fill", "stretch", "none"
+    pub fn set_page_background_image(
+        &mut self,
+        handle: i64,
+        fit: Option<String>,
+    ) -> Result<(), String> {
+        let image_fit = parse_image_fit(&fit.unwrap_or_else(|| "fit".to_string()))?;
+        let image_id = ImageId(handle as usize);
+        with_doc!(self, set_page_background_image, doc => {
+            doc.set_page_background_image(&image_id, image_fit);
             Ok(())
         })
     }
@@ -798,6 +1875,13 @@ impl PhpPdfDocument {
         })
     }
 
+    pub fn set_dash(&mut self, pattern: Vec<f64>, phase: Option<f64>) -> Result<(), String> {
+        with_doc!(self, set_dash, doc => {
+            doc.set_dash(&pattern, phase.unwrap_or(0.0));
+            Ok(())
+        })
+    }
+
     pub fn move_to(&mut self, x: f64, y: f64) -> Result<(), String> {
         with_doc!(self, move_to, doc => {
             doc.move_to(x, y);
@@ -812,6 +1896,21 @@ impl PhpPdfDocument {
         })
     }
 
+    pub fn curve_to(
+        &mut self,
+        x1: f64,
+        y1: f64,
+        x2: f64,
+        y2: f64,
+        x3: f64,
+        y3: f64,
+    ) -> Result<(), String> {
+        with_doc!(self, curve_to, doc => {
+            doc.curve_to(x1, y1, x2, y2, x3, y3);
+            Ok(())
+        })
+    }
+
     pub fn rect(&mut self, x: f64, y: f64, width: f64, height: f64) -> Result<(), String> {
         with_doc!(self, rect, doc => {
             doc.rect(x, y, width, height);
@@ -819,6 +1918,39 @@ impl PhpPdfDocument {
         })
     }
 
+    /// Append a rectangle with corners rounded to `radius`.
+    pub fn rounded_rect(
+        &mut self,
+        x: f64,
+        y: f64,
+        width: f64,
+        height: f64,
+        radius: f64,
+    ) -> Result<(), String> {
+        with_doc!(self, rounded_rect, doc => {
+            doc.rounded_rect(x, y, width, height, radius);
+            Ok(())
+        })
+    }
+
+    /// Append line segments through `[[x, y], ...]`; closes the subpath when `close` is true.
+    pub fn polyline(&mut self, points: Vec<Vec<f64>>, close: Option<bool>) -> Result<(), String> {
+        let points = points
+            .iter()
+            .map(|p| match p.as_slice() {
+                [x, y] => Ok((*x, *y)),
+                _ => Err(format!(
+                    "polyline: expected [x, y] pairs, got {} values",
+                    p.len()
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        with_doc!(self, polyline, doc => {
+            doc.polyline(&points, close.unwrap_or(false));
+            Ok(())
+        })
+    }
+
     pub fn close_path(&mut self) -> Result<(), String> {
         with_doc!(self, close_path, doc => {
             doc.close_path();
@@ -861,6 +1993,206 @@ impl PhpPdfDocument {
         })
     }
 
+    /// Append raw bytes to the current page's content stream, unchecked.
+    pub fn append_raw_content(&mut self, ops: &mut Zval) -> Result<(), String> {
+        let bytes = ops
+            .binary()
+            .ok_or_else(|| "Expected binary string".to_string())?
+            .to_vec();
+
+        with_doc!(self, append_raw_content, doc => {
+            doc.append_raw_content(&bytes);
+            Ok(())
+        })
+    }
+
+    /// Record the drawing in `form` as a reusable form XObject. Returns a form handle.
+    pub fn create_form_xobject(
+        &mut self,
+        width: f64,
+        height: f64,
+        form: &PhpFormBuilder,
+    ) -> Result<i64, String> {
+        with_doc!(self, create_form_xobject, doc => {
+            let form_id = doc.create_form_xobject(width, height, |builder| form.draw(builder));
+            Ok(form_id.0 as i64)
+        })
+    }
+
+    /// Draw a form XObject with its lower-left corner at (x, y), scaled by `scale`.
+    pub fn place_form(&mut self, handle: i64, x: f64, y: f64, scale: f64) -> Result<(), String> {
+        with_doc!(self, place_form, doc => {
+            doc.place_form(&FormId(handle as usize), x, y, scale);
+            Ok(())
+        })
+    }
+
+    /// Start collecting page content into a transparency group.
+    pub fn begin_transparency_group(
+        &mut self,
+        isolated: bool,
+        knockout: bool,
+    ) -> Result<(), String> {
+        with_doc!(self, begin_transparency_group, doc => {
+            doc.begin_transparency_group(isolated, knockout);
+            Ok(())
+        })
+    }
+
+    /// Close the innermost transparency group and paint it on the page.
+    pub fn end_transparency_group(&mut self) -> Result<(), String> {
+        with_doc!(self, end_transparency_group, doc => {
+            doc.end_transparency_group()
+                .map_err(|e| format!("end_transparency_group failed: {}", e))?;
+            Ok(())
+        })
+    }
+
+    /// Stroke a frame inset by `margin` on all sides of the current page.
+    pub fn draw_page_frame(
+        &mut self,
+        margin: f64,
+        width: f64,
+        color: &PhpColor,
+    ) -> Result<(), String> {
+        with_doc!(self, draw_page_frame, doc => {
+            doc.draw_page_frame(margin, width, color.to_core());
+            Ok(())
+        })
+    }
+
+    /// Draw a rectangle with an optional fill and an optional outline.
+    pub fn draw_box(
+        &mut self,
+        rect: &PhpRect,
+        fill: Option<&PhpColor>,
+        stroke: Option<&PhpColor>,
+        line_width: Option<f64>,
+    ) -> Result<(), String> {
+        let core_rect = rect.to_core();
+        let fill = fill.map(|c| c.to_core());
+        let stroke = stroke.map(|c| (c.to_core(), line_width.unwrap_or(1.0)));
+        with_doc!(self, draw_box, doc => {
+            doc.draw_box(&core_rect, fill, stroke);
+            Ok(())
+        })
+    }
+
+    /// Fill a rounded rectangle and centre `text` in it (status chip).
+    pub fn draw_chip(
+        &mut self,
+        rect: &PhpRect,
+        radius: f64,
+        fill: &PhpColor,
+        text: &str,
+        style: &PhpTextStyle,
+    ) -> Result<(), String> {
+        let core_rect = rect.to_core();
+        let core_style = style.to_core()?;
+        with_doc!(self, draw_chip, doc => {
+            doc.draw_chip(&core_rect, radius, fill.to_core(), text, &core_style);
+            Ok(())
+        })
+    }
+
+    /// Stroke the eight standard crop marks just outside a trim rectangle.
+    pub fn draw_crop_marks(
+        &mut self,
+        trim: &PhpRect,
+        offset: f64,
+        length: f64,
+    ) -> Result<(), String> {
+        let core_rect = trim.to_core();
+        with_doc!(self, draw_crop_marks, doc => {
+            doc.draw_crop_marks(&core_rect, offset, length);
+            Ok(())
+        })
+    }
+
+    /// Stroke evenly spaced grid lines filling `rect`.
+    pub fn draw_grid(
+        &mut self,
+        rect: &PhpRect,
+        spacing_x: f64,
+        spacing_y: f64,
+        color: &PhpColor,
+        width: f64,
+    ) -> Result<(), String> {
+        let core_rect = rect.to_core();
+        with_doc!(self, draw_grid, doc => {
+            doc.draw_grid(&core_rect, spacing_x, spacing_y, color.to_core(), width);
+            Ok(())
+        })
+    }
+
+    /// Fill a pie chart, one slice per `values[i]` in `colors[i]`, clockwise from 12 o'clock.
+    pub fn draw_pie(
+        &mut self,
+        cx: f64,
+        cy: f64,
+        radius: f64,
+        values: Vec<f64>,
+        colors: Vec<&PhpColor>,
+    ) -> Result<(), String> {
+        if values.len() != colors.len() {
+            return Err(format!(
+                "draw_pie: {} values but {} colors",
+                values.len(),
+                colors.len()
+            ));
+        }
+        let slices: Vec<(f64, Color)> = values
+            .iter()
+            .zip(&colors)
+            .map(|(v, c)| (*v, c.to_core()))
+            .collect();
+        with_doc!(self, draw_pie, doc => {
+            doc.draw_pie(cx, cy, radius, &slices);
+            Ok(())
+        })
+    }
+
+    /// Draw a horizontal bar chart in `rect`, one labelled bar per value.
+    pub fn draw_bar_chart(
+        &mut self,
+        rect: &PhpRect,
+        labels: Vec<String>,
+        values: Vec<f64>,
+        colors: Vec<&PhpColor>,
+        max: Option<f64>,
+    ) -> Result<(), String> {
+        if labels.len() != values.len() || values.len() != colors.len() {
+            return Err(format!(
+                "draw_bar_chart: {} labels, {} values and {} colors",
+                labels.len(),
+                values.len(),
+                colors.len()
+            ));
+        }
+        let core_rect = rect.to_core();
+        let bars: Vec<(String, f64, Color)> = labels
+            .into_iter()
+            .zip(values)
+            .zip(&colors)
+            .map(|((label, value), color)| (label, value, color.to_core()))
+            .collect();
+        with_doc!(self, draw_bar_chart, doc => {
+            doc.draw_bar_chart(&core_rect, &bars, max);
+            Ok(())
+        })
+    }
+
+    /// Add an unsigned signature field (widget + `/Sig` placeholder) to the
+    /// current page.
+    pub fn add_signature_field(&mut self, name: String, rect: &PhpRect) -> Result<(), String> {
+        let core_rect = rect.to_core();
+        with_doc!(self, add_signature_field, doc => {
+            doc.add_signature_field(&name, &core_rect)
+                .map_err(|e| format!("add_signature_field failed: {}", e))?;
+            Ok(())
+        })
+    }
+
     /// Returns the number of completed pages.
     pub fn page_count(&self) -> Result<i64, String> {
         match self.inner.as_ref() {
@@ -872,6 +2204,18 @@ impl PhpPdfDocument {
         }
     }
 
+    /// Byte offset where page `page_num` (1-based) begins in the output, or null.
+    pub fn page_byte_offset(&self, page_num: i64) -> Result<Option<i64>, String> {
+        let page_num = page_num.max(0) as usize;
+        match self.inner.as_ref() {
+            Some(inner) => match inner {
+                DocumentInner::File(doc) => Ok(doc.page_byte_offset(page_num).map(|o| o as i64)),
+                DocumentInner::Memory(doc) => Ok(doc.page_byte_offset(page_num).map(|o| o as i64)),
+            },
+            None => Err("page_byte_offset: document already ended".to_string()),
+        }
+    }
+
     /// Open a completed page for editing (1-indexed).
     ///
     /// Used for adding overlay content such as page numbers after all
@@ -890,6 +2234,23 @@ impl PhpPdfDocument {
         })
     }
 
+    /// Move completed page `from` so it becomes page `to` (both 1-indexed).
+    ///
+    /// Only the page order changes; later `open_page` calls use the new
+    /// numbering. If a page is currently open, it is closed first.
+    pub fn move_page(&mut self, from: i64, to: i64) -> Result<(), String> {
+        if from < 1 || to < 1 {
+            return Err(format!(
+                "move_page: page numbers must be >= 1, got {} and {}",
+                from, to
+            ));
+        }
+        with_doc!(self, move_page, doc => {
+            doc.move_page(from as usize, to as usize)
+                .map_err(|e| format!("move_page failed: {}", e))
+        })
+    }
+
     pub fn end_page(&mut self) -> Result<(), String> {
         with_doc!(self, end_page, doc => {
             doc.end_page().map_err(|e| {
@@ -898,6 +2259,29 @@ impl PhpPdfDocument {
         })
     }
 
+    /// Flush buffered output (completed pages) to the file or stream.
+    pub fn flush(&mut self) -> Result<(), String> {
+        with_doc!(self, flush, doc => {
+            doc.flush().map_err(|e| format!("flush failed: {}", e))
+        })
+    }
+
+    /// Concatenate in-memory PDFs produced by this extension into one.
+    pub fn concat(docs: &ZendHashTable) -> Result<Zval, String> {
+        let docs = docs
+            .values()
+            .map(|doc| {
+                doc.binary::<u8>()
+                    .ok_or_else(|| "PdfDocument::concat: expected binary strings".to_string())
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let bytes =
+            PdfDocument::concat(docs).map_err(|e| format!("PdfDocument::concat failed: {}", e))?;
+        let mut zval = Zval::new();
+        zval.set_binary(bytes);
+        Ok(zval)
+    }
+
     /// End the document. Returns null for file-based docs,
     /// or a binary string for in-memory docs.
     pub fn end_document(&mut self) -> Result<Zval, String> {
@@ -947,6 +2331,7 @@ impl PhpPdfDocument {
 pub struct PhpPdfReader {
     page_count: usize,
     version: String,
+    encrypted: bool,
 }
 
 #[php_impl]
@@ -957,6 +2342,7 @@ impl PhpPdfReader {
         Ok(PhpPdfReader {
             page_count: reader.page_count(),
             version: reader.pdf_version().to_string(),
+            encrypted: reader.is_encrypted(),
         })
     }
 
@@ -967,6 +2353,7 @@ impl PhpPdfReader {
         Ok(PhpPdfReader {
             page_count: reader.page_count(),
             version: reader.pdf_version().to_string(),
+            encrypted: reader.is_encrypted(),
         })
     }
 
@@ -979,6 +2366,71 @@ impl PhpPdfReader {
     pub fn pdf_version(&self) -> String {
         self.version.clone()
     }
+
+    /// Whether the trailer has an `/Encrypt` entry.
+    pub fn is_encrypted(&self) -> bool {
+        self.encrypted
+    }
+}
+
+// Unknown strings fall back to the default mode, as they always have.
+fn parse_overflow(s: &str) -> CellOverflow {
+    match s {
+        PhpCellOverflow::CLIP => CellOverflow::Clip,
+        PhpCellOverflow::SHRINK => CellOverflow::Shrink,
+        _ => CellOverflow::Wrap,
+    }
+}
+
+fn parse_hyphen_char(s: &str) -> Result<char, String> {
+    let mut chars = s.chars();
+    match (chars.next(), chars.next()) {
+        (Some(ch), None) => Ok(ch),
+        _ => Err(format!(
+            "hyphenChar must be a single character, got '{}'",
+            s
+        )),
+    }
+}
+
+fn parse_word_break(s: &str) -> WordBreak {
+    match s {
+        PhpWordBreak::HYPHENATE => WordBreak::Hyphenate,
+        PhpWordBreak::NORMAL => WordBreak::Normal,
+        _ => WordBreak::BreakAll,
+    }
+}
+
+fn parse_text_align(s: &str) -> TextAlign {
+    match s {
+        PhpTextAlign::CENTER => TextAlign::Center,
+        PhpTextAlign::RIGHT => TextAlign::Right,
+        _ => TextAlign::Left,
+    }
+}
+
+fn parse_vertical_align(s: &str) -> VerticalAlign {
+    match s {
+        PhpVerticalAlign::MIDDLE => VerticalAlign::Middle,
+        PhpVerticalAlign::BOTTOM => VerticalAlign::Bottom,
+        _ => VerticalAlign::Top,
+    }
+}
+
+fn parse_underline(s: &str) -> UnderlineStyle {
+    match s {
+        PhpUnderline::SINGLE => UnderlineStyle::Single,
+        PhpUnderline::DOUBLE => UnderlineStyle::Double,
+        _ => UnderlineStyle::None,
+    }
+}
+
+fn fit_result_str(result: FitResult) -> &'static str {
+    match result {
+        FitResult::Stop => PhpFitResult::STOP,
+        FitResult::BoxFull => PhpFitResult::BOX_FULL,
+        FitResult::BoxEmpty => PhpFitResult::BOX_EMPTY,
+    }
 }
 
 fn parse_image_fit(s: &str) -> Result<ImageFit, String> {
@@ -994,18 +2446,88 @@ fn parse_image_fit(s: &str) -> Result<ImageFit, String> {
     }
 }
 
+fn parse_transition(s: &str) -> Result<Transition, String> {
+    match s {
+        "split" => Ok(Transition::Split),
+        "blinds" => Ok(Transition::Blinds),
+        "box" => Ok(Transition::Box),
+        "wipe" => Ok(Transition::Wipe),
+        "dissolve" => Ok(Transition::Dissolve),
+        "glitter" => Ok(Transition::Glitter),
+        "fly" => Ok(Transition::Fly),
+        "push" => Ok(Transition::Push),
+        "cover" => Ok(Transition::Cover),
+        "uncover" => Ok(Transition::Uncover),
+        "fade" => Ok(Transition::Fade),
+        _ => Err(format!(
+            "Invalid transition: '{}'. Valid: split, blinds, box, wipe, dissolve, glitter, fly, push, cover, uncover, fade",
+            s
+        )),
+    }
+}
+
+fn parse_line_cap(s: &str) -> Result<LineCap, String> {
+    match s {
+        "butt" => Ok(LineCap::Butt),
+        "round" => Ok(LineCap::Round),
+        "square" => Ok(LineCap::Square),
+        _ => Err(format!(
+            "Invalid line cap: '{}'. Valid: butt, round, square",
+            s
+        )),
+    }
+}
+
+fn parse_line_join(s: &str) -> Result<LineJoin, String> {
+    match s {
+        "miter" => Ok(LineJoin::Miter),
+        "round" => Ok(LineJoin::Round),
+        "bevel" => Ok(LineJoin::Bevel),
+        _ => Err(format!(
+            "Invalid line join: '{}'. Valid: miter, round, bevel",
+            s
+        )),
+    }
+}
+
+fn parse_image_anchor(s: &str) -> Result<ImageAnchor, String> {
+    match s {
+        "top-left" => Ok(ImageAnchor::TopLeft),
+        "top" => Ok(ImageAnchor::Top),
+        "top-right" => Ok(ImageAnchor::TopRight),
+        "left" => Ok(ImageAnchor::Left),
+        "center" => Ok(ImageAnchor::Center),
+        "right" => Ok(ImageAnchor::Right),
+        "bottom-left" => Ok(ImageAnchor::BottomLeft),
+        "bottom" => Ok(ImageAnchor::Bottom),
+        "bottom-right" => Ok(ImageAnchor::BottomRight),
+        _ => Err(format!(
+            "Invalid anchor: '{}'. Valid: top-left, top, top-right, left, center, right, bottom-left, bottom, bottom-right",
+            s
+        )),
+    }
+}
+
 #[php_module]
 pub fn get_module(module: ModuleBuilder) -> ModuleBuilder {
     module
         .class::<PhpColor>()
+        .class::<PhpFormat>()
         .class::<PhpTextStyle>()
         .class::<PhpRect>()
+        .class::<PhpCellOverflow>()
+        .class::<PhpWordBreak>()
+        .class::<PhpTextAlign>()
+        .class::<PhpVerticalAlign>()
+        .class::<PhpUnderline>()
+        .class::<PhpFitResult>()
         .class::<PhpTextFlow>()
         .class::<PhpCellStyle>()
         .class::<PhpCell>()
         .class::<PhpRow>()
         .class::<PhpTable>()
         .class::<PhpTableCursor>()
+        .class::<PhpFormBuilder>()
         .class::<PhpPdfDocument>()
         .class::<PhpPdfReader>()
 }
@@ -571,6 +571,108 @@ assert_true(str_contains($bytes, '/Filter /FlateDecode'), "Tables PDF uses compr
 unlink($outFile);
 echo "Test 10 (Tables): OK\n";
 
+// ----------------------------------------------------------
+// Test 11: Metric units
+// ----------------------------------------------------------
+$a4 = Rect::mm(0, 0, 210, 297);
+assert_true(abs($a4->width - 595.2756) < 0.001, "Rect::mm A4 width is 595.28pt (got {$a4->width})");
+assert_true(abs($a4->height - 841.8898) < 0.001, "Rect::mm A4 height is 841.89pt");
+
+$cmRect = Rect::cm(1, 1, 2.54, 2.54);
+assert_true(abs($cmRect->width - 72.0) < 0.001, "Rect::cm 2.54cm is 72pt");
+
+$doc = PdfDocument::createInMemory();
+$doc->beginPageMm(210, 297);
+$doc->endPage();
+$bytes = $doc->endDocument();
+assert_true(str_contains($bytes, '/MediaBox [0 0 595.275591 841.889764]'), "beginPageMm sets A4 MediaBox");
+
+echo "Test 11 (Metric units): OK\n";
+
+// ----------------------------------------------------------
+// Test 12: Enum constants
+// ----------------------------------------------------------
+assert_true(CellOverflow::WRAP === 'wrap', "CellOverflow::WRAP matches legacy string");
+assert_true(WordBreak::HYPHENATE === 'hyphenate', "WordBreak::HYPHENATE matches legacy string");
+assert_true(TextAlign::CENTER === 'center', "TextAlign::CENTER matches legacy string");
+assert_true(FitResult::STOP === 'stop', "FitResult::STOP matches legacy string");
+
+// Returns the cell's text-positioning operator ("x y Td").
+function render_aligned_row(string $align): string {
+    $doc = PdfDocument::createInMemory();
+    $doc->setCompression(false);
+    $doc->beginPage(612.0, 792.0);
+    $style = new CellStyle();
+    $style->textAlign = $align;
+    $style->overflow = CellOverflow::CLIP;
+    $cursor = new TableCursor(new Rect(72.0, 720.0, 468.0, 648.0));
+    $result = $doc->fitRow(new Table([200.0]), new Row([Cell::styled("42", $style)]), $cursor);
+    assert_true($result === FitResult::STOP, "fitRow returns FitResult::STOP");
+    $doc->endPage();
+    preg_match('/[0-9.]+ [0-9.]+ Td/', $doc->endDocument(), $m);
+    return $m[0] ?? '';
+}
+
+assert_true(
+    render_aligned_row(TextAlign::RIGHT) === render_aligned_row('right')
+        && render_aligned_row('right') !== '',
+    "TextAlign::RIGHT renders the same as 'right'"
+);
+assert_true(
+    render_aligned_row(TextAlign::CENTER) !== render_aligned_row(TextAlign::LEFT),
+    "TextAlign constants change the rendered position"
+);
+
+echo "Test 12 (Enum constants): OK\n";
+
+// ----------------------------------------------------------
+// Test 13: TrueType font in table cells
+// ----------------------------------------------------------
+$doc = PdfDocument::createInMemory();
+$doc->setCompression(false);
+$ttHandle = $doc->loadFontFile($fontPath);
+$ttCell = new CellStyle();
+$ttCell->fontHandle = $ttHandle;
+$doc->beginPage(612.0, 792.0);
+$cursor = new TableCursor(new Rect(72.0, 720.0, 468.0, 648.0));
+$doc->fitRow(
+    new Table([234.0, 234.0]),
+    new Row([new Cell("Builtin"), Cell::styled("TrueType", $ttCell)]),
+    $cursor
+);
+$doc->endPage();
+$bytes = $doc->endDocument();
+
+assert_true(str_contains($bytes, '(Builtin) Tj'), "Builtin cell uses a literal string");
+assert_true(str_contains($bytes, '/F15 10 Tf'), "TrueType cell selects /F15");
+assert_true(str_contains($bytes, '> Tj'), "TrueType cell uses hex-encoded glyphs");
+assert_true(!str_contains($bytes, '(TrueType) Tj'), "TrueType cell text is not literal");
+
+echo "Test 13 (TrueType table cells): OK\n";
+
+// ----------------------------------------------------------
+// Test 14: Form XObjects
+// ----------------------------------------------------------
+$doc = PdfDocument::createInMemory();
+$doc->setCompression(false);
+$logo = new FormBuilder();
+$logo->setFillColor(new Color(0.0, 0.4, 0.8));
+$logo->rect(0, 0, 50, 20);
+$logo->fill();
+$handle = $doc->createFormXObject(50, 20, $logo);
+for ($i = 0; $i < 2; $i++) {
+    $doc->beginPage(612.0, 792.0);
+    $doc->placeForm($handle, 72, 740, 1.0);
+    $doc->endPage();
+}
+$bytes = $doc->endDocument();
+
+assert_true(substr_count($bytes, '/Subtype /Form') === 1, "Form XObject is written once");
+assert_true(str_contains($bytes, "0 0.4 0.8 rg\n0 0 50 20 re\nf\n"), "Form records its drawing");
+assert_true(substr_count($bytes, "1 0 0 1 72 740 cm\n/Fm1 Do") === 2, "Each page paints the form");
+
+echo "Test 14 (Form XObjects): OK\n";
+
 // ----------------------------------------------------------
 // Summary
 // ----------------------------------------------------------